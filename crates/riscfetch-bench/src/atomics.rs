@@ -0,0 +1,148 @@
+//! Atomics benchmark: contended counter and CAS loop across threads
+//!
+//! One thread per hart hammers a single shared counter. The counter uses
+//! `fetch_add` (AMOADD), the CAS loop uses `compare_exchange` (LR/SC on
+//! rv64gc) and, when Zacas is present, `amocas.d`. Contention on one cache
+//! line shows how well atomics scale across harts.
+
+use riscfetch_core as info;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Barrier;
+use std::time::Instant;
+
+/// Increments per thread and measurement
+const OPS_PER_THREAD: u64 = 200_000;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct AtomicsResults {
+    /// Threads contending for the counter (one per hart)
+    pub threads: usize,
+    /// `fetch_add` throughput (AMOADD.D)
+    pub counter_mops: f64,
+    /// Compare-and-swap loop built from LR/SC
+    pub cas_lrsc_mops: f64,
+    /// Compare-and-swap loop using `amocas.d` (only when Zacas is present)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cas_zacas_mops: Option<f64>,
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Counter,
+    CasLrSc,
+    CasZacas,
+}
+
+/// Run the atomics benchmarks with one thread per hart
+#[must_use]
+pub fn collect(isa: &str) -> AtomicsResults {
+    let threads = info::get_hart_count_num().max(1);
+    let zacas = cfg!(target_arch = "riscv64") && info::isa_supports(isa, "zacas");
+    AtomicsResults {
+        threads,
+        counter_mops: mops(Kind::Counter, threads, OPS_PER_THREAD),
+        cas_lrsc_mops: mops(Kind::CasLrSc, threads, OPS_PER_THREAD),
+        cas_zacas_mops: zacas.then(|| mops(Kind::CasZacas, threads, OPS_PER_THREAD)),
+    }
+}
+
+/// Million increments per second across all threads
+#[allow(clippy::cast_precision_loss)]
+fn mops(kind: Kind, threads: usize, ops: u64) -> f64 {
+    let counter = AtomicU64::new(0);
+    let barrier = Barrier::new(threads + 1);
+    // The clock starts once every worker is ready; the scope joins them all
+    let start = std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                barrier.wait();
+                run(kind, &counter, ops);
+            });
+        }
+        barrier.wait();
+        Instant::now()
+    });
+    let elapsed = start.elapsed();
+    debug_assert_eq!(counter.load(Ordering::Relaxed), ops * threads as u64);
+    (ops * threads as u64) as f64 / elapsed.as_secs_f64() / 1_000_000.0
+}
+
+fn run(kind: Kind, counter: &AtomicU64, ops: u64) {
+    match kind {
+        Kind::Counter => {
+            for _ in 0..ops {
+                counter.fetch_add(1, Ordering::AcqRel);
+            }
+        }
+        Kind::CasLrSc => {
+            for _ in 0..ops {
+                let mut current = counter.load(Ordering::Relaxed);
+                while let Err(actual) = counter.compare_exchange_weak(
+                    current,
+                    current + 1,
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                ) {
+                    current = actual;
+                }
+            }
+        }
+        Kind::CasZacas => {
+            for _ in 0..ops {
+                let mut current = counter.load(Ordering::Relaxed);
+                loop {
+                    let actual = amocas(counter, current, current + 1);
+                    if actual == current {
+                        break;
+                    }
+                    current = actual;
+                }
+            }
+        }
+    }
+}
+
+/// `amocas.d.aqrl`: store `new` if the value equals `expected`; returns the
+/// previous value. Emitted with `.insn` since Zacas is not a Rust target feature.
+#[cfg(target_arch = "riscv64")]
+fn amocas(atomic: &AtomicU64, expected: u64, new: u64) -> u64 {
+    let mut previous = expected;
+    // SAFETY: only called when the ISA string reports Zacas; the pointer
+    // comes from a live, aligned AtomicU64
+    unsafe {
+        std::arch::asm!(".insn r 0x2f, 3, 0x17, {rd}, {rs1}, {rs2}",
+            rd = inout(reg) previous, rs1 = in(reg) atomic.as_ptr(), rs2 = in(reg) new,
+            options(nostack));
+    }
+    previous
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+fn amocas(_atomic: &AtomicU64, _expected: u64, _new: u64) -> u64 {
+    unreachable!("Zacas is only used on riscv64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_and_cas_are_exact() {
+        for kind in [Kind::Counter, Kind::CasLrSc] {
+            let counter = AtomicU64::new(0);
+            std::thread::scope(|scope| {
+                for _ in 0..4 {
+                    scope.spawn(|| run(kind, &counter, 1000));
+                }
+            });
+            assert_eq!(counter.load(Ordering::Relaxed), 4000);
+        }
+    }
+
+    #[test]
+    fn test_mops() {
+        assert!(mops(Kind::Counter, 2, 1000) > 0.0);
+        assert!(mops(Kind::CasLrSc, 2, 1000) > 0.0);
+    }
+}
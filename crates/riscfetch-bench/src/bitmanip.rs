@@ -0,0 +1,241 @@
+//! Bit-manipulation benchmark (Zba / Zbb / Zbs)
+//!
+//! Each kernel is compiled twice: for the baseline target (rv64gc) and with
+//! the matching B extension enabled through `#[target_feature]`. The second
+//! build only runs when the ISA string reports that extension, so cores with
+//! and without Zbb can be compared from the same binary.
+
+use riscfetch_core as info;
+use serde::Serialize;
+use std::time::Instant;
+
+/// Entries in the lookup table and bitset (powers of two, so masking
+/// replaces bounds checks)
+const TABLE_LEN: usize = 4096;
+const BITSET_WORDS: usize = 1024;
+
+/// Elements per measurement
+const ELEMENTS: usize = 1 << 16;
+const REPEATS: usize = 64;
+
+/// One kernel: baseline throughput and, when the extension is present,
+/// throughput with it enabled
+#[derive(Serialize, Debug, Clone)]
+pub struct Kernel {
+    /// Extension the kernel benefits from
+    pub extension: &'static str,
+    pub base_mops: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitmanip_mops: Option<f64>,
+}
+
+impl Kernel {
+    /// How many times faster the B-enabled build is
+    #[must_use]
+    pub fn speedup(&self) -> Option<f64> {
+        self.bitmanip_mops.map(|mops| mops / self.base_mops)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BitmanipResults {
+    /// Which of Zba / Zbb / Zbs the ISA string reports
+    pub detected: Vec<&'static str>,
+    /// Indexed loads from 32-bit indices (`sh3add.uw`)
+    pub address_generation: Kernel,
+    /// Popcount, leading and trailing zeros (`cpop`, `clz`, `ctz`)
+    pub bit_count: Kernel,
+    /// Setting and testing single bits (`bset`, `bext`)
+    pub bit_set: Kernel,
+}
+
+/// Extensions from Zba / Zbb / Zbs in `isa` (B implies all three, as
+/// `isa_supports` expands it)
+#[must_use]
+pub fn detect(isa: &str) -> Vec<&'static str> {
+    ["zba", "zbb", "zbs"]
+        .into_iter()
+        .filter(|ext| info::isa_supports(isa, ext))
+        .collect()
+}
+
+/// Run all kernels for the extensions in `isa`
+#[must_use]
+pub fn collect(isa: &str) -> BitmanipResults {
+    let detected = detect(isa);
+    let enabled = |ext: &str| cfg!(target_arch = "riscv64") && detected.contains(&ext);
+    let data = Data::new(ELEMENTS);
+    let kernel = |extension: &'static str, kind: Kind| Kernel {
+        extension,
+        base_mops: data.mops(kind, false, REPEATS),
+        bitmanip_mops: enabled(extension).then(|| data.mops(kind, true, REPEATS)),
+    };
+    BitmanipResults {
+        address_generation: kernel("zba", Kind::AddressGeneration),
+        bit_count: kernel("zbb", Kind::BitCount),
+        bit_set: kernel("zbs", Kind::BitSet),
+        detected,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    AddressGeneration,
+    BitCount,
+    BitSet,
+}
+
+/// Inputs shared by all kernels
+struct Data {
+    table: Box<[u64; TABLE_LEN]>,
+    indices: Vec<u32>,
+    words: Vec<u64>,
+}
+
+impl Data {
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(elements: usize) -> Self {
+        // xorshift: cheap, deterministic and not predictable by the branch predictor
+        let mut x: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = move || {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x
+        };
+        let mut table = Box::new([0u64; TABLE_LEN]);
+        for entry in table.iter_mut() {
+            *entry = next();
+        }
+        Self {
+            table,
+            indices: (0..elements).map(|_| next() as u32).collect(),
+            words: (0..elements).map(|_| next() >> (next() % 64)).collect(),
+        }
+    }
+
+    fn run(&self, kind: Kind, bitmanip: bool) -> u64 {
+        match (kind, bitmanip) {
+            (Kind::AddressGeneration, false) => address_generation(&self.table, &self.indices),
+            (Kind::BitCount, false) => bit_count(&self.words),
+            (Kind::BitSet, false) => bit_set(&self.indices),
+            (kind, true) => self.run_bitmanip(kind),
+        }
+    }
+
+    #[cfg(target_arch = "riscv64")]
+    fn run_bitmanip(&self, kind: Kind) -> u64 {
+        // SAFETY: only called when the ISA string reports the extension
+        unsafe {
+            match kind {
+                Kind::AddressGeneration => address_generation_zba(&self.table, &self.indices),
+                Kind::BitCount => bit_count_zbb(&self.words),
+                Kind::BitSet => bit_set_zbs(&self.indices),
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "riscv64"))]
+    fn run_bitmanip(&self, _kind: Kind) -> u64 {
+        unreachable!("B extensions are only enabled on riscv64")
+    }
+
+    /// Million elements per second over `repeats` passes
+    #[allow(clippy::cast_precision_loss)]
+    fn mops(&self, kind: Kind, bitmanip: bool, repeats: usize) -> f64 {
+        let start = Instant::now();
+        let mut check = 0u64;
+        for _ in 0..repeats {
+            check = check.wrapping_add(self.run(kind, std::hint::black_box(bitmanip)));
+        }
+        let elapsed = start.elapsed();
+        std::hint::black_box(check);
+        (self.indices.len() * repeats) as f64 / elapsed.as_secs_f64() / 1_000_000.0
+    }
+}
+
+/// Sum of table entries picked by 32-bit indices (zero-extend, shift, add)
+#[inline]
+fn address_generation(table: &[u64; TABLE_LEN], indices: &[u32]) -> u64 {
+    indices.iter().fold(0u64, |sum, &i| {
+        sum.wrapping_add(table[i as usize & (TABLE_LEN - 1)])
+    })
+}
+
+/// Popcount plus leading and trailing zeros of every word
+#[inline]
+fn bit_count(words: &[u64]) -> u64 {
+    words.iter().fold(0u64, |sum, w| {
+        sum + u64::from(w.count_ones() + w.leading_zeros() + w.trailing_zeros())
+    })
+}
+
+/// Set one bit per index in a bitset, then test one bit per index
+#[inline]
+fn bit_set(indices: &[u32]) -> u64 {
+    let mut bits = [0u64; BITSET_WORDS];
+    for &i in indices {
+        bits[(i as usize >> 6) & (BITSET_WORDS - 1)] |= 1 << (i & 63);
+    }
+    indices.iter().fold(0u64, |sum, &i| {
+        let word = bits[(i.rotate_left(7) as usize >> 6) & (BITSET_WORDS - 1)];
+        sum + ((word >> (i.rotate_left(7) & 63)) & 1)
+    })
+}
+
+#[cfg(target_arch = "riscv64")]
+#[target_feature(enable = "zba")]
+fn address_generation_zba(table: &[u64; TABLE_LEN], indices: &[u32]) -> u64 {
+    address_generation(table, indices)
+}
+
+#[cfg(target_arch = "riscv64")]
+#[target_feature(enable = "zbb")]
+fn bit_count_zbb(words: &[u64]) -> u64 {
+    bit_count(words)
+}
+
+#[cfg(target_arch = "riscv64")]
+#[target_feature(enable = "zbs")]
+fn bit_set_zbs(indices: &[u32]) -> u64 {
+    bit_set(indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(detect("rv64gc_zba_zbb"), vec!["zba", "zbb"]);
+        assert_eq!(detect("rv64gcb"), vec!["zba", "zbb", "zbs"]);
+        assert!(detect("rv64gc").is_empty());
+    }
+
+    #[test]
+    fn test_kernels() {
+        let table = [1u64; TABLE_LEN];
+        assert_eq!(address_generation(&table, &[0, 5, u32::MAX]), 3);
+        assert_eq!(bit_count(&[0, u64::MAX, 1]), 128 + 64 + 64);
+        // Index 0 sets bit 0; its rotated probe also lands on 0
+        assert_eq!(bit_set(&[0]), 1);
+    }
+
+    #[test]
+    fn test_mops_without_extensions() {
+        let data = Data::new(1024);
+        assert!(data.mops(Kind::BitCount, false, 1) > 0.0);
+        assert!(data.mops(Kind::AddressGeneration, false, 1) > 0.0);
+        assert!(data.mops(Kind::BitSet, false, 1) > 0.0);
+    }
+
+    #[test]
+    fn test_speedup() {
+        let kernel = Kernel {
+            extension: "zbb",
+            base_mops: 100.0,
+            bitmanip_mops: Some(250.0),
+        };
+        assert_eq!(kernel.speedup(), Some(2.5));
+    }
+}
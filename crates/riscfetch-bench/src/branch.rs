@@ -0,0 +1,142 @@
+//! Branch predictor benchmark
+//!
+//! The same loop of data-dependent branches runs over outcomes a predictor
+//! learns (a block of taken followed by a block of not-taken) and over
+//! random ones. Both see half of the branches taken, so the work is equal
+//! and the difference is the misprediction cost: random outcomes miss about
+//! half the time, so one miss costs about twice the extra time per branch.
+//! Small in-order cores (C906) and out-of-order ones (P550) differ a lot
+//! here. On riscv64 the loop is inline assembly so that the compiler cannot
+//! turn the branch into branch-free arithmetic.
+
+use serde::Serialize;
+use std::time::Instant;
+
+/// Branch outcomes per pass; too many for a history-based predictor to
+/// memorize the random pattern, few enough to stay in L2
+const OUTCOMES: usize = 64 * 1024;
+
+const PASSES: usize = 64;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BranchResults {
+    /// Nanoseconds per branch when the outcomes are predictable
+    pub predictable_ns: f64,
+    /// Nanoseconds per branch when the outcomes are random
+    pub random_ns: f64,
+    /// Estimated cost of one mispredicted branch
+    pub mispredict_penalty_ns: f64,
+}
+
+/// Time both patterns
+#[must_use]
+pub fn collect() -> BranchResults {
+    let predictable: Vec<u8> = (0..OUTCOMES).map(|i| u8::from(i < OUTCOMES / 2)).collect();
+    let random = random_outcomes(OUTCOMES);
+    let predictable_ns = ns_per_branch(&predictable);
+    let random_ns = ns_per_branch(&random);
+    BranchResults {
+        predictable_ns,
+        random_ns,
+        mispredict_penalty_ns: penalty(predictable_ns, random_ns),
+    }
+}
+
+/// Cost of one miss, when random outcomes miss half the time
+fn penalty(predictable_ns: f64, random_ns: f64) -> f64 {
+    (2.0 * (random_ns - predictable_ns)).max(0.0)
+}
+
+/// 0 / 1 outcomes from xorshift64, with a fixed seed so runs compare
+fn random_outcomes(count: usize) -> Vec<u8> {
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            u8::from(state & 1 == 1)
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn ns_per_branch(outcomes: &[u8]) -> f64 {
+    // One untimed pass warms the cache and the predictor
+    std::hint::black_box(kernel::count(outcomes));
+    let start = Instant::now();
+    for _ in 0..PASSES {
+        std::hint::black_box(kernel::count(outcomes));
+    }
+    start.elapsed().as_secs_f64() * 1e9 / (outcomes.len() * PASSES) as f64
+}
+
+#[cfg(target_arch = "riscv64")]
+mod kernel {
+    /// Count the non-zero bytes with one conditional branch per byte
+    pub fn count(outcomes: &[u8]) -> usize {
+        if outcomes.is_empty() {
+            return 0;
+        }
+        let mut taken: usize = 0;
+        // SAFETY: reads `outcomes.len()` bytes from the slice
+        unsafe {
+            std::arch::asm!(
+                "2:",
+                "lbu {t}, 0({p})",
+                "beqz {t}, 3f",
+                "addi {c}, {c}, 1",
+                "3:",
+                "addi {p}, {p}, 1",
+                "addi {n}, {n}, -1",
+                "bnez {n}, 2b",
+                p = inout(reg) outcomes.as_ptr() => _,
+                n = inout(reg) outcomes.len() => _,
+                c = inout(reg) taken,
+                t = out(reg) _,
+                options(nostack, readonly),
+            );
+        }
+        taken
+    }
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+mod kernel {
+    /// Count the non-zero bytes
+    pub fn count(outcomes: &[u8]) -> usize {
+        let mut taken = 0;
+        for &outcome in outcomes {
+            if std::hint::black_box(outcome) != 0 {
+                taken += 1;
+            }
+        }
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns_take_half() {
+        let random = random_outcomes(OUTCOMES);
+        let taken = kernel::count(&random);
+        assert!(taken > OUTCOMES * 45 / 100 && taken < OUTCOMES * 55 / 100);
+        assert_eq!(random, random_outcomes(OUTCOMES));
+    }
+
+    #[test]
+    fn test_penalty() {
+        assert!((penalty(1.0, 3.5) - 5.0).abs() < 1e-9);
+        assert!(penalty(2.0, 1.9).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_collect() {
+        let results = collect();
+        assert!(results.predictable_ns > 0.0);
+        assert!(results.random_ns > 0.0);
+    }
+}
@@ -0,0 +1,224 @@
+//! User-mode counter CSR latency (Zicntr)
+//!
+//! `rdcycle`, `rdtime` and `rdinstret` read the `cycle`, `time` and
+//! `instret` CSRs. Since Linux 6.6, cycle and instret trap unless
+//! `kernel.perf_user_access` is 2, and some firmware never delegates them,
+//! so each CSR is first read in a forked child; only counters whose read
+//! completes are timed. `time` is usually readable, but when the firmware
+//! emulates it the latency is in microseconds rather than nanoseconds.
+
+use riscfetch_core as info;
+use serde::Serialize;
+use std::time::Instant;
+
+const READS: u32 = 100_000;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CounterLatency {
+    /// `cycle`, `time` or `instret`
+    pub csr: &'static str,
+    /// Whether a user-mode read completes instead of raising SIGILL
+    pub accessible: bool,
+    /// Nanoseconds per read (accessible counters only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ns: Option<f64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CountersResults {
+    /// Whether the ISA string lists Zicntr
+    pub zicntr: bool,
+    /// `/proc/sys/kernel/perf_user_access` (Linux 6.6+): 0 no user access,
+    /// 1 only through perf events, 2 direct reads as on older kernels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub perf_user_access: Option<u8>,
+    /// cycle, time and instret, in that order
+    pub counters: Vec<CounterLatency>,
+}
+
+impl CountersResults {
+    /// CSRs whose user-mode reads trap
+    #[must_use]
+    pub fn trapped(&self) -> Vec<&'static str> {
+        self.counters
+            .iter()
+            .filter(|c| !c.accessible)
+            .map(|c| c.csr)
+            .collect()
+    }
+}
+
+/// Probe and time the three counters; None on other targets
+#[must_use]
+pub fn collect(isa: &str) -> Option<CountersResults> {
+    if !cfg!(all(target_arch = "riscv64", target_os = "linux")) {
+        return None;
+    }
+    Some(CountersResults {
+        zicntr: info::isa_supports(isa, "zicntr"),
+        perf_user_access: perf_user_access(),
+        counters: vec![
+            counter("cycle", kernels::cycle),
+            counter("time", kernels::time),
+            counter("instret", kernels::instret),
+        ],
+    })
+}
+
+fn counter(csr: &'static str, read: impl Fn() -> u64) -> CounterLatency {
+    let accessible = probe::completes(&read);
+    CounterLatency {
+        csr,
+        accessible,
+        ns: accessible.then(|| ns_per_read(read)),
+    }
+}
+
+fn ns_per_read(read: impl Fn() -> u64) -> f64 {
+    let start = Instant::now();
+    for _ in 0..READS {
+        std::hint::black_box(read());
+    }
+    start.elapsed().as_secs_f64() * 1e9 / f64::from(READS)
+}
+
+fn perf_user_access() -> Option<u8> {
+    std::fs::read_to_string("/proc/sys/kernel/perf_user_access")
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(target_os = "linux")]
+mod probe {
+    extern "C" fn exit_on_signal(_signal: libc::c_int) {
+        // SAFETY: _exit is async-signal-safe
+        unsafe { libc::_exit(1) }
+    }
+
+    /// Whether `read` returns normally, run in a child process so that a
+    /// trapping CSR read cannot take riscfetch down
+    pub fn completes(read: &dyn Fn() -> u64) -> bool {
+        // SAFETY: the child only installs a signal handler, runs `read`
+        // (a single CSR read) and calls _exit, all async-signal-safe
+        unsafe {
+            match libc::fork() {
+                -1 => false,
+                0 => {
+                    // Exit quietly instead of dumping core
+                    let handler: extern "C" fn(libc::c_int) = exit_on_signal;
+                    libc::signal(libc::SIGILL, handler as libc::sighandler_t);
+                    std::hint::black_box(read());
+                    libc::_exit(0)
+                }
+                pid => {
+                    let mut status = 0;
+                    libc::waitpid(pid, &mut status, 0) == pid
+                        && libc::WIFEXITED(status)
+                        && libc::WEXITSTATUS(status) == 0
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod probe {
+    pub fn completes(_read: &dyn Fn() -> u64) -> bool {
+        false
+    }
+}
+
+#[cfg(target_arch = "riscv64")]
+mod kernels {
+    use std::arch::asm;
+
+    /// `rdcycle`
+    #[inline(always)]
+    pub fn cycle() -> u64 {
+        let value: u64;
+        // SAFETY: reads a CSR; a trap is caught by the forked probe first
+        unsafe { asm!("csrr {}, 0xc00", out(reg) value, options(nostack, nomem)) };
+        value
+    }
+
+    /// `rdtime`
+    #[inline(always)]
+    pub fn time() -> u64 {
+        let value: u64;
+        // SAFETY: reads a CSR; a trap is caught by the forked probe first
+        unsafe { asm!("csrr {}, 0xc01", out(reg) value, options(nostack, nomem)) };
+        value
+    }
+
+    /// `rdinstret`
+    #[inline(always)]
+    pub fn instret() -> u64 {
+        let value: u64;
+        // SAFETY: reads a CSR; a trap is caught by the forked probe first
+        unsafe { asm!("csrr {}, 0xc02", out(reg) value, options(nostack, nomem)) };
+        value
+    }
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+mod kernels {
+    pub fn cycle() -> u64 {
+        unreachable!("counter CSRs are only read on riscv64")
+    }
+
+    pub fn time() -> u64 {
+        unreachable!("counter CSRs are only read on riscv64")
+    }
+
+    pub fn instret() -> u64 {
+        unreachable!("counter CSRs are only read on riscv64")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_probe_catches_sigill() {
+        assert!(probe::completes(&|| 1));
+        assert!(!probe::completes(&|| {
+            // SAFETY: raising a signal in the forked child
+            unsafe { libc::raise(libc::SIGILL) };
+            0
+        }));
+    }
+
+    #[test]
+    fn test_ns_per_read() {
+        assert!(ns_per_read(|| 1) >= 0.0);
+    }
+
+    #[test]
+    fn test_trapped() {
+        let entry = |csr, accessible| CounterLatency {
+            csr,
+            accessible,
+            ns: None,
+        };
+        let results = CountersResults {
+            zicntr: true,
+            perf_user_access: Some(1),
+            counters: vec![
+                entry("cycle", false),
+                entry("time", true),
+                entry("instret", false),
+            ],
+        };
+        assert_eq!(results.trapped(), ["cycle", "instret"]);
+    }
+
+    #[cfg(not(target_arch = "riscv64"))]
+    #[test]
+    fn test_collect_elsewhere() {
+        assert!(collect("rv64gc_zicntr").is_none());
+    }
+}
@@ -0,0 +1,535 @@
+//! Scalar crypto benchmark (Zkne / Zknh)
+//!
+//! AES-128 encryption and SHA-256 compression are timed twice: once with a
+//! portable software implementation and once with the K-extension
+//! instructions, so the speedup of the extension can be read directly.
+//! The instructions are emitted with `.insn`, so no assembler support for
+//! Zk is needed to build this crate.
+
+use crate::mb_per_sec;
+use riscfetch_core as info;
+use serde::Serialize;
+use std::time::Instant;
+
+/// Bytes processed per measurement
+const BYTES: usize = 4 * 1024 * 1024;
+
+/// Software versus instruction throughput for one algorithm
+#[derive(Serialize, Debug, Clone)]
+pub struct Comparison {
+    pub software_mb_per_sec: f64,
+    pub hardware_mb_per_sec: f64,
+}
+
+impl Comparison {
+    /// How many times faster the instructions are
+    #[must_use]
+    pub fn speedup(&self) -> f64 {
+        self.hardware_mb_per_sec / self.software_mb_per_sec
+    }
+}
+
+/// Results for the crypto extensions present on this machine
+#[derive(Serialize, Debug, Clone)]
+pub struct CryptoResults {
+    /// AES-128 encryption (Zkne)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aes128: Option<Comparison>,
+    /// SHA-256 compression (Zknh)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<Comparison>,
+}
+
+/// Whether the ISA string has `name` directly or through the Zkn / Zk shorthands
+fn has_crypto(isa: &str, name: &str) -> bool {
+    [name, "zkn", "zk"]
+        .iter()
+        .any(|ext| info::isa_supports(isa, ext))
+}
+
+/// Run the benchmarks for the extensions in `isa`; None when neither Zkne
+/// nor Zknh is present (or the instructions cannot be used on this target)
+#[must_use]
+pub fn collect(isa: &str) -> Option<CryptoResults> {
+    let hardware = cfg!(target_arch = "riscv64");
+    let results = CryptoResults {
+        aes128: (hardware && has_crypto(isa, "zkne")).then(|| Comparison {
+            software_mb_per_sec: aes_throughput(BYTES, aes::encrypt_block_software),
+            hardware_mb_per_sec: aes_throughput(BYTES, aes::encrypt_block_hardware),
+        }),
+        sha256: (hardware && has_crypto(isa, "zknh")).then(|| Comparison {
+            software_mb_per_sec: sha256_throughput(BYTES, sha256::compress::<sha256::Software>),
+            hardware_mb_per_sec: sha256_throughput(BYTES, sha256::compress::<sha256::Hardware>),
+        }),
+    };
+    (results.aes128.is_some() || results.sha256.is_some()).then_some(results)
+}
+
+/// Buffer with a non-trivial byte pattern
+#[allow(clippy::cast_possible_truncation)]
+fn test_data(bytes: usize) -> Vec<u8> {
+    (0..bytes).map(|i| (i * 31 + 7) as u8).collect()
+}
+
+/// Encrypt `bytes` in ECB mode and return MB/s
+fn aes_throughput(bytes: usize, encrypt: fn(&aes::RoundKeys, &mut [u8; 16])) -> f64 {
+    let keys = aes::expand_key(&[0x2b; 16]);
+    let mut data = test_data(bytes);
+    let start = Instant::now();
+    for chunk in data.chunks_exact_mut(16) {
+        let block: &mut [u8; 16] = chunk.try_into().expect("16-byte chunk");
+        encrypt(&keys, block);
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(&data);
+    mb_per_sec(bytes, elapsed)
+}
+
+/// Compress `bytes` as SHA-256 blocks (no padding) and return MB/s
+fn sha256_throughput(bytes: usize, compress: fn(&mut [u32; 8], &[u8; 64])) -> f64 {
+    let data = test_data(bytes);
+    let mut state = sha256::H0;
+    let start = Instant::now();
+    for chunk in data.chunks_exact(64) {
+        compress(&mut state, chunk.try_into().expect("64-byte chunk"));
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(state);
+    mb_per_sec(bytes, elapsed)
+}
+
+mod aes {
+    /// AES S-box (FIPS-197 figure 7)
+    const SBOX: [u8; 256] = [
+        0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab,
+        0x76, 0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4,
+        0x72, 0xc0, 0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71,
+        0xd8, 0x31, 0x15, 0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2,
+        0xeb, 0x27, 0xb2, 0x75, 0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6,
+        0xb3, 0x29, 0xe3, 0x2f, 0x84, 0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb,
+        0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf, 0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45,
+        0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8, 0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5,
+        0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2, 0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44,
+        0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73, 0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a,
+        0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb, 0xe0, 0x32, 0x3a, 0x0a, 0x49,
+        0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79, 0xe7, 0xc8, 0x37, 0x6d,
+        0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08, 0xba, 0x78, 0x25,
+        0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a, 0x70, 0x3e,
+        0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e, 0xe1,
+        0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+        0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb,
+        0x16,
+    ];
+
+    /// Round keys for AES-128: the cipher key and one per round
+    pub type RoundKeys = [[u8; 16]; 11];
+
+    fn xtime(b: u8) -> u8 {
+        (b << 1) ^ if b & 0x80 == 0 { 0 } else { 0x1b }
+    }
+
+    /// FIPS-197 key expansion (used by both implementations)
+    pub fn expand_key(key: &[u8; 16]) -> RoundKeys {
+        let mut words = [[0u8; 4]; 44];
+        for (i, word) in words.iter_mut().take(4).enumerate() {
+            word.copy_from_slice(&key[4 * i..4 * i + 4]);
+        }
+        let mut rcon = 1;
+        for i in 4..44 {
+            let mut t = words[i - 1];
+            if i % 4 == 0 {
+                t.rotate_left(1);
+                for b in &mut t {
+                    *b = SBOX[usize::from(*b)];
+                }
+                t[0] ^= rcon;
+                rcon = xtime(rcon);
+            }
+            for j in 0..4 {
+                words[i][j] = words[i - 4][j] ^ t[j];
+            }
+        }
+        let mut keys = [[0u8; 16]; 11];
+        for (round, key) in keys.iter_mut().enumerate() {
+            for column in 0..4 {
+                key[4 * column..4 * column + 4].copy_from_slice(&words[4 * round + column]);
+            }
+        }
+        keys
+    }
+
+    fn add_round_key(state: &mut [u8; 16], key: &[u8; 16]) {
+        for (s, k) in state.iter_mut().zip(key) {
+            *s ^= k;
+        }
+    }
+
+    /// State byte `4 * column + row` moves `row` columns to the left
+    fn shift_rows(state: &mut [u8; 16]) {
+        let old = *state;
+        for column in 0..4 {
+            for row in 0..4 {
+                state[4 * column + row] = old[4 * ((column + row) % 4) + row];
+            }
+        }
+    }
+
+    fn mix_columns(state: &mut [u8; 16]) {
+        for column in state.chunks_exact_mut(4) {
+            let a = [column[0], column[1], column[2], column[3]];
+            let all = a[0] ^ a[1] ^ a[2] ^ a[3];
+            for i in 0..4 {
+                column[i] = a[i] ^ all ^ xtime(a[i] ^ a[(i + 1) % 4]);
+            }
+        }
+    }
+
+    /// Byte-oriented table-free AES-128, as in FIPS-197
+    pub fn encrypt_block_software(keys: &RoundKeys, block: &mut [u8; 16]) {
+        add_round_key(block, &keys[0]);
+        for (round, key) in keys.iter().enumerate().skip(1) {
+            for b in block.iter_mut() {
+                *b = SBOX[usize::from(*b)];
+            }
+            shift_rows(block);
+            if round != 10 {
+                mix_columns(block);
+            }
+            add_round_key(block, key);
+        }
+    }
+
+    /// AES-128 with `aes64es` / `aes64esm`; the state is two little-endian
+    /// 64-bit halves
+    #[cfg(target_arch = "riscv64")]
+    pub fn encrypt_block_hardware(keys: &RoundKeys, block: &mut [u8; 16]) {
+        use std::arch::asm;
+
+        fn halves(bytes: &[u8; 16]) -> (u64, u64) {
+            let (low, high) = bytes.split_at(8);
+            (
+                u64::from_le_bytes(low.try_into().expect("8 bytes")),
+                u64::from_le_bytes(high.try_into().expect("8 bytes")),
+            )
+        }
+
+        // aes64esm rd, rs1, rs2: SubBytes, ShiftRows and MixColumns
+        fn aes64esm(rs1: u64, rs2: u64) -> u64 {
+            let rd;
+            unsafe {
+                asm!(".insn r 0x33, 0, 0x1b, {rd}, {rs1}, {rs2}",
+                    rd = out(reg) rd, rs1 = in(reg) rs1, rs2 = in(reg) rs2,
+                    options(pure, nomem, nostack));
+            }
+            rd
+        }
+
+        // aes64es rd, rs1, rs2: final round without MixColumns
+        fn aes64es(rs1: u64, rs2: u64) -> u64 {
+            let rd;
+            unsafe {
+                asm!(".insn r 0x33, 0, 0x19, {rd}, {rs1}, {rs2}",
+                    rd = out(reg) rd, rs1 = in(reg) rs1, rs2 = in(reg) rs2,
+                    options(pure, nomem, nostack));
+            }
+            rd
+        }
+
+        let (k0, k1) = halves(&keys[0]);
+        let (s0, s1) = halves(block);
+        let (mut s0, mut s1) = (s0 ^ k0, s1 ^ k1);
+        for key in &keys[1..10] {
+            let (k0, k1) = halves(key);
+            (s0, s1) = (aes64esm(s0, s1) ^ k0, aes64esm(s1, s0) ^ k1);
+        }
+        let (k0, k1) = halves(&keys[10]);
+        (s0, s1) = (aes64es(s0, s1) ^ k0, aes64es(s1, s0) ^ k1);
+        block[..8].copy_from_slice(&s0.to_le_bytes());
+        block[8..].copy_from_slice(&s1.to_le_bytes());
+    }
+
+    #[cfg(not(target_arch = "riscv64"))]
+    pub fn encrypt_block_hardware(_keys: &RoundKeys, _block: &mut [u8; 16]) {
+        unreachable!("Zkne instructions are only used on riscv64");
+    }
+}
+
+mod sha256 {
+    /// Initial hash value (FIPS 180-4, 5.3.3)
+    pub const H0: [u32; 8] = [
+        0x6a09_e667,
+        0xbb67_ae85,
+        0x3c6e_f372,
+        0xa54f_f53a,
+        0x510e_527f,
+        0x9b05_688c,
+        0x1f83_d9ab,
+        0x5be0_cd19,
+    ];
+
+    /// Round constants (FIPS 180-4, 4.2.2)
+    const K: [u32; 64] = [
+        0x428a_2f98,
+        0x7137_4491,
+        0xb5c0_fbcf,
+        0xe9b5_dba5,
+        0x3956_c25b,
+        0x59f1_11f1,
+        0x923f_82a4,
+        0xab1c_5ed5,
+        0xd807_aa98,
+        0x1283_5b01,
+        0x2431_85be,
+        0x550c_7dc3,
+        0x72be_5d74,
+        0x80de_b1fe,
+        0x9bdc_06a7,
+        0xc19b_f174,
+        0xe49b_69c1,
+        0xefbe_4786,
+        0x0fc1_9dc6,
+        0x240c_a1cc,
+        0x2de9_2c6f,
+        0x4a74_84aa,
+        0x5cb0_a9dc,
+        0x76f9_88da,
+        0x983e_5152,
+        0xa831_c66d,
+        0xb003_27c8,
+        0xbf59_7fc7,
+        0xc6e0_0bf3,
+        0xd5a7_9147,
+        0x06ca_6351,
+        0x1429_2967,
+        0x27b7_0a85,
+        0x2e1b_2138,
+        0x4d2c_6dfc,
+        0x5338_0d13,
+        0x650a_7354,
+        0x766a_0abb,
+        0x81c2_c92e,
+        0x9272_2c85,
+        0xa2bf_e8a1,
+        0xa81a_664b,
+        0xc24b_8b70,
+        0xc76c_51a3,
+        0xd192_e819,
+        0xd699_0624,
+        0xf40e_3585,
+        0x106a_a070,
+        0x19a4_c116,
+        0x1e37_6c08,
+        0x2748_774c,
+        0x34b0_bcb5,
+        0x391c_0cb3,
+        0x4ed8_aa4a,
+        0x5b9c_ca4f,
+        0x682e_6ff3,
+        0x748f_82ee,
+        0x78a5_636f,
+        0x84c8_7814,
+        0x8cc7_0208,
+        0x90be_fffa,
+        0xa450_6ceb,
+        0xbef9_a3f7,
+        0xc671_78f2,
+    ];
+
+    /// The four SHA-256 sigma functions, which Zknh provides as instructions
+    pub trait Sigma {
+        fn sum0(x: u32) -> u32;
+        fn sum1(x: u32) -> u32;
+        fn sig0(x: u32) -> u32;
+        fn sig1(x: u32) -> u32;
+    }
+
+    pub struct Software;
+
+    impl Sigma for Software {
+        fn sum0(x: u32) -> u32 {
+            x.rotate_right(2) ^ x.rotate_right(13) ^ x.rotate_right(22)
+        }
+        fn sum1(x: u32) -> u32 {
+            x.rotate_right(6) ^ x.rotate_right(11) ^ x.rotate_right(25)
+        }
+        fn sig0(x: u32) -> u32 {
+            x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3)
+        }
+        fn sig1(x: u32) -> u32 {
+            x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10)
+        }
+    }
+
+    /// `sha256sum0/sum1/sig0/sig1`; on RV64 they read and sign-extend the
+    /// low 32 bits
+    pub struct Hardware;
+
+    #[cfg(target_arch = "riscv64")]
+    macro_rules! zknh {
+        ($imm:literal, $x:expr) => {{
+            let rd: u64;
+            unsafe {
+                std::arch::asm!(concat!(".insn i 0x13, 1, {rd}, {rs1}, ", $imm),
+                    rd = out(reg) rd, rs1 = in(reg) u64::from($x),
+                    options(pure, nomem, nostack));
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            let result = rd as u32;
+            result
+        }};
+    }
+
+    #[cfg(target_arch = "riscv64")]
+    impl Sigma for Hardware {
+        fn sum0(x: u32) -> u32 {
+            zknh!("0x100", x)
+        }
+        fn sum1(x: u32) -> u32 {
+            zknh!("0x101", x)
+        }
+        fn sig0(x: u32) -> u32 {
+            zknh!("0x102", x)
+        }
+        fn sig1(x: u32) -> u32 {
+            zknh!("0x103", x)
+        }
+    }
+
+    #[cfg(not(target_arch = "riscv64"))]
+    impl Sigma for Hardware {
+        fn sum0(_: u32) -> u32 {
+            unreachable!("Zknh instructions are only used on riscv64")
+        }
+        fn sum1(_: u32) -> u32 {
+            unreachable!("Zknh instructions are only used on riscv64")
+        }
+        fn sig0(_: u32) -> u32 {
+            unreachable!("Zknh instructions are only used on riscv64")
+        }
+        fn sig1(_: u32) -> u32 {
+            unreachable!("Zknh instructions are only used on riscv64")
+        }
+    }
+
+    /// Process one 64-byte block (FIPS 180-4, 6.2.2)
+    pub fn compress<S: Sigma>(state: &mut [u32; 8], block: &[u8; 64]) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes(word.try_into().expect("4 bytes"));
+        }
+        for i in 16..64 {
+            w[i] = S::sig1(w[i - 2])
+                .wrapping_add(w[i - 7])
+                .wrapping_add(S::sig0(w[i - 15]))
+                .wrapping_add(w[i - 16]);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+        for i in 0..64 {
+            let t1 = h
+                .wrapping_add(S::sum1(e))
+                .wrapping_add((e & f) ^ (!e & g))
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let t2 = S::sum0(a).wrapping_add((a & b) ^ (a & c) ^ (b & c));
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *s = s.wrapping_add(v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_aes_software_fips197_vector() {
+        let key: [u8; 16] = hex("000102030405060708090a0b0c0d0e0f").try_into().unwrap();
+        let mut block: [u8; 16] = hex("00112233445566778899aabbccddeeff").try_into().unwrap();
+        aes::encrypt_block_software(&aes::expand_key(&key), &mut block);
+        assert_eq!(block.to_vec(), hex("69c4e0d86a7b0430d8cdb78070b4c55a"));
+    }
+
+    #[test]
+    fn test_sha256_software_abc() {
+        let mut block = [0u8; 64];
+        block[..3].copy_from_slice(b"abc");
+        block[3] = 0x80;
+        block[63] = 24;
+        let mut state = sha256::H0;
+        sha256::compress::<sha256::Software>(&mut state, &block);
+        assert_eq!(
+            state,
+            [
+                0xba78_16bf,
+                0x8f01_cfea,
+                0x4141_40de,
+                0x5dae_2223,
+                0xb003_61a3,
+                0x9617_7a9c,
+                0xb410_ff61,
+                0xf200_15ad
+            ]
+        );
+    }
+
+    #[test]
+    fn test_has_crypto_shorthands() {
+        assert!(has_crypto("rv64gc_zkne", "zkne"));
+        assert!(has_crypto("rv64gc_zkn", "zknh"));
+        assert!(!has_crypto("rv64gc_zbkb", "zkne"));
+    }
+
+    #[test]
+    fn test_collect_without_crypto() {
+        assert!(collect("rv64gc_zba_zbb").is_none());
+    }
+
+    #[cfg(target_arch = "riscv64")]
+    #[test]
+    fn test_hardware_matches_software() {
+        let isa = info::get_isa_string();
+        let keys = aes::expand_key(&[0x2b; 16]);
+        let data = test_data(256);
+        if has_crypto(&isa, "zkne") {
+            for chunk in data.chunks_exact(16) {
+                let mut software: [u8; 16] = chunk.try_into().unwrap();
+                let mut hardware = software;
+                aes::encrypt_block_software(&keys, &mut software);
+                aes::encrypt_block_hardware(&keys, &mut hardware);
+                assert_eq!(software, hardware);
+            }
+        }
+        if has_crypto(&isa, "zknh") {
+            let (mut software, mut hardware) = (sha256::H0, sha256::H0);
+            for chunk in data.chunks_exact(64) {
+                let block = chunk.try_into().unwrap();
+                sha256::compress::<sha256::Software>(&mut software, block);
+                sha256::compress::<sha256::Hardware>(&mut hardware, block);
+            }
+            assert_eq!(software, hardware);
+        }
+    }
+
+    #[test]
+    fn test_software_throughput() {
+        assert!(aes_throughput(4096, aes::encrypt_block_software) > 0.0);
+        assert!(sha256_throughput(4096, sha256::compress::<sha256::Software>) > 0.0);
+    }
+}
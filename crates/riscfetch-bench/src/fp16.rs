@@ -0,0 +1,201 @@
+//! Half-precision benchmark (Zfh / Zfhmin)
+//!
+//! Zfhmin only adds conversions between f16 and f32, so every board with
+//! it gets the conversion loop; Zfh adds arithmetic, timed as an `fmadd.h`
+//! loop next to the same loop with `fmadd.s`. Half floats are not usable
+//! from stable Rust and Zfh is not a stable target feature, so the loops
+//! are inline assembly with the instructions emitted through `.insn`.
+
+use riscfetch_core as info;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Loop iterations per measurement (each runs four independent chains)
+const ITERATIONS: u64 = 5_000_000;
+const CHAINS: u64 = 4;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Fp16Results {
+    /// `zfh` or `zfhmin`, whichever the ISA string reports
+    pub extension: &'static str,
+    /// f32 -> f16 -> f32 conversions (`fcvt.h.s`, `fcvt.s.h`)
+    pub convert_mops: f64,
+    /// Half-precision FMA (`fmadd.h`, Zfh only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fma_mflops: Option<f64>,
+    /// The same FMA loop in single precision, for comparison
+    pub fp32_fma_mflops: f64,
+}
+
+/// `zfh` when the ISA has half-precision arithmetic, `zfhmin` when it has
+/// only the conversions
+#[must_use]
+pub fn detect(isa: &str) -> Option<&'static str> {
+    if info::isa_supports(isa, "zfh") {
+        Some("zfh")
+    } else if info::isa_supports(isa, "zfhmin") {
+        Some("zfhmin")
+    } else {
+        None
+    }
+}
+
+/// Run the kernels for the extension in `isa`; None without Zfh / Zfhmin
+/// (or on other targets)
+#[must_use]
+pub fn collect(isa: &str) -> Option<Fp16Results> {
+    let extension = detect(isa).filter(|_| cfg!(target_arch = "riscv64"))?;
+    Some(Fp16Results {
+        extension,
+        convert_mops: mops(2 * CHAINS, time(kernels::convert)),
+        fma_mflops: (extension == "zfh").then(|| mops(2 * CHAINS, time(kernels::fma_h))),
+        fp32_fma_mflops: mops(2 * CHAINS, time(kernels::fma_s)),
+    })
+}
+
+fn time(kernel: fn(u64)) -> Duration {
+    let start = Instant::now();
+    kernel(ITERATIONS);
+    start.elapsed()
+}
+
+/// Millions of operations per second for `per_iteration` operations in
+/// each of `ITERATIONS` loop iterations (an FMA counts as two)
+#[allow(clippy::cast_precision_loss)]
+fn mops(per_iteration: u64, elapsed: Duration) -> f64 {
+    (ITERATIONS * per_iteration) as f64 / elapsed.as_secs_f64() / 1_000_000.0
+}
+
+#[cfg(target_arch = "riscv64")]
+mod kernels {
+    use std::arch::asm;
+
+    /// Four chains of `acc = acc * 0.875 + 1.0` (`fmadd.h`); the
+    /// accumulators settle at 8.0, clear of overflow and subnormals
+    pub fn fma_h(iterations: u64) {
+        // SAFETY: only called when the ISA string reports Zfh; touches
+        // registers only
+        unsafe {
+            asm!(
+                ".insn r 0x53, 0, 0x7a, {a0}, {one}, x0", // fmv.h.x
+                ".insn r 0x53, 0, 0x7a, {a1}, {one}, x0",
+                ".insn r 0x53, 0, 0x7a, {a2}, {one}, x0",
+                ".insn r 0x53, 0, 0x7a, {a3}, {one}, x0",
+                ".insn r 0x53, 0, 0x7a, {c}, {one}, x0",
+                ".insn r 0x53, 0, 0x7a, {m}, {mul}, x0",
+                "2:",
+                ".insn r4 0x43, 7, 2, {a0}, {a0}, {m}, {c}", // fmadd.h
+                ".insn r4 0x43, 7, 2, {a1}, {a1}, {m}, {c}",
+                ".insn r4 0x43, 7, 2, {a2}, {a2}, {m}, {c}",
+                ".insn r4 0x43, 7, 2, {a3}, {a3}, {m}, {c}",
+                "addi {n}, {n}, -1",
+                "bnez {n}, 2b",
+                n = inout(reg) iterations => _,
+                one = in(reg) 0x3c00_u64,
+                mul = in(reg) 0x3b00_u64,
+                a0 = out(freg) _, a1 = out(freg) _, a2 = out(freg) _, a3 = out(freg) _,
+                m = out(freg) _, c = out(freg) _,
+                options(nostack, nomem),
+            );
+        }
+    }
+
+    /// The `fma_h` loop in single precision (`fmadd.s`)
+    pub fn fma_s(iterations: u64) {
+        // SAFETY: F is part of the rv64gc baseline; touches registers only
+        unsafe {
+            asm!(
+                "fmv.w.x {a0}, {one}",
+                "fmv.w.x {a1}, {one}",
+                "fmv.w.x {a2}, {one}",
+                "fmv.w.x {a3}, {one}",
+                "fmv.w.x {c}, {one}",
+                "fmv.w.x {m}, {mul}",
+                "2:",
+                "fmadd.s {a0}, {a0}, {m}, {c}",
+                "fmadd.s {a1}, {a1}, {m}, {c}",
+                "fmadd.s {a2}, {a2}, {m}, {c}",
+                "fmadd.s {a3}, {a3}, {m}, {c}",
+                "addi {n}, {n}, -1",
+                "bnez {n}, 2b",
+                n = inout(reg) iterations => _,
+                one = in(reg) 0x3f80_0000_u64,
+                mul = in(reg) 0x3f60_0000_u64,
+                a0 = out(freg) _, a1 = out(freg) _, a2 = out(freg) _, a3 = out(freg) _,
+                m = out(freg) _, c = out(freg) _,
+                options(nostack, nomem),
+            );
+        }
+    }
+
+    /// Four chains of f32 -> f16 -> f32 round trips
+    pub fn convert(iterations: u64) {
+        // SAFETY: only called when the ISA string reports Zfh or Zfhmin;
+        // touches registers only
+        unsafe {
+            asm!(
+                "fmv.w.x {s0}, {one}",
+                "fmv.w.x {s1}, {one}",
+                "fmv.w.x {s2}, {one}",
+                "fmv.w.x {s3}, {one}",
+                "2:",
+                ".insn r 0x53, 7, 0x22, {h0}, {s0}, f0", // fcvt.h.s
+                ".insn r 0x53, 7, 0x22, {h1}, {s1}, f0",
+                ".insn r 0x53, 7, 0x22, {h2}, {s2}, f0",
+                ".insn r 0x53, 7, 0x22, {h3}, {s3}, f0",
+                ".insn r 0x53, 0, 0x20, {s0}, {h0}, f2", // fcvt.s.h
+                ".insn r 0x53, 0, 0x20, {s1}, {h1}, f2",
+                ".insn r 0x53, 0, 0x20, {s2}, {h2}, f2",
+                ".insn r 0x53, 0, 0x20, {s3}, {h3}, f2",
+                "addi {n}, {n}, -1",
+                "bnez {n}, 2b",
+                n = inout(reg) iterations => _,
+                one = in(reg) 0x3f80_0000_u64,
+                s0 = out(freg) _, s1 = out(freg) _, s2 = out(freg) _, s3 = out(freg) _,
+                h0 = out(freg) _, h1 = out(freg) _, h2 = out(freg) _, h3 = out(freg) _,
+                options(nostack, nomem),
+            );
+        }
+    }
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+mod kernels {
+    pub fn fma_h(_iterations: u64) {
+        unreachable!("Zfh is only used on riscv64")
+    }
+
+    pub fn fma_s(_iterations: u64) {
+        unreachable!("Zfh is only used on riscv64")
+    }
+
+    pub fn convert(_iterations: u64) {
+        unreachable!("Zfhmin is only used on riscv64")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(detect("rv64gc_zfh"), Some("zfh"));
+        assert_eq!(detect("rv64gc_zfh_zfhmin"), Some("zfh"));
+        assert_eq!(detect("rv64gc_zfhmin"), Some("zfhmin"));
+        assert_eq!(detect("rv64gcv_zvfh"), None);
+        assert_eq!(detect("rv64gc"), None);
+    }
+
+    #[test]
+    fn test_mops() {
+        let mflops = mops(8, Duration::from_secs(1));
+        assert!((mflops - 40.0).abs() < 1e-9);
+    }
+
+    #[cfg(not(target_arch = "riscv64"))]
+    #[test]
+    fn test_collect_elsewhere() {
+        assert!(collect("rv64gc_zfh").is_none());
+    }
+}
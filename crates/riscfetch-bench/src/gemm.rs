@@ -0,0 +1,183 @@
+//! int8 matrix multiply (V / Zve*)
+//!
+//! C (i32) = A (i8) × B (i8) on small square matrices, the inner operation
+//! of quantized inference. The scalar version is plain Rust (the baseline
+//! target has no V, so it is not vectorized); the vector version walks each
+//! row of C in strips, widening `vwmul.vx` (i8 → i16) and `vwadd.wv`
+//! (i16 → i32) to accumulate one strip in registers. Scores are GOPS with a
+//! multiply-add counted as two operations.
+
+use serde::Serialize;
+use std::time::Instant;
+
+/// Rows and columns of every matrix
+const SIZE: usize = 128;
+
+/// Multiplications per measurement
+const REPEATS: usize = 16;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct GemmResults {
+    /// Matrix dimension (SIZE × SIZE × SIZE)
+    pub size: usize,
+    pub scalar_gops: f64,
+    pub vector_gops: f64,
+}
+
+impl GemmResults {
+    /// How many times faster the vector version is
+    #[must_use]
+    pub fn speedup(&self) -> f64 {
+        self.vector_gops / self.scalar_gops
+    }
+}
+
+/// Run both versions; None without V or Zve* (or on other targets), or
+/// when the kernel does not report V as usable or has it turned off for
+/// this process (`vector_skipped` in the results says so)
+#[must_use]
+pub fn collect(isa: &str) -> Option<GemmResults> {
+    if !crate::vector::available(isa) {
+        return None;
+    }
+    let (a, b) = matrices();
+    let mut scalar_c = vec![0i32; SIZE * SIZE];
+    let mut vector_c = vec![0i32; SIZE * SIZE];
+    let scalar_gops = gops(|| multiply_scalar(&a, &b, &mut scalar_c));
+    let vector_gops = gops(|| kernel::multiply(&a, &b, &mut vector_c));
+    debug_assert_eq!(scalar_c, vector_c);
+    Some(GemmResults {
+        size: SIZE,
+        scalar_gops,
+        vector_gops,
+    })
+}
+
+/// A and B filled with small signed values covering the i8 range
+fn matrices() -> (Vec<i8>, Vec<i8>) {
+    let fill = |seed: usize| -> Vec<i8> {
+        (0..SIZE * SIZE)
+            .map(|i| ((i * seed + 17) % 256) as u8 as i8)
+            .collect()
+    };
+    (fill(31), fill(57))
+}
+
+/// GOPS for `REPEATS` multiplications after one untimed run
+#[allow(clippy::cast_precision_loss)]
+fn gops(mut multiply: impl FnMut()) -> f64 {
+    multiply();
+    let start = Instant::now();
+    for _ in 0..REPEATS {
+        multiply();
+    }
+    (2 * SIZE * SIZE * SIZE * REPEATS) as f64 / start.elapsed().as_secs_f64() / 1e9
+}
+
+fn multiply_scalar(a: &[i8], b: &[i8], c: &mut [i32]) {
+    for i in 0..SIZE {
+        let row = &mut c[i * SIZE..(i + 1) * SIZE];
+        row.fill(0);
+        for k in 0..SIZE {
+            let scale = i32::from(a[i * SIZE + k]);
+            for (out, &value) in row.iter_mut().zip(&b[k * SIZE..(k + 1) * SIZE]) {
+                *out += scale * i32::from(value);
+            }
+        }
+    }
+    std::hint::black_box(c);
+}
+
+#[cfg(target_arch = "riscv64")]
+mod kernel {
+    use super::SIZE;
+    use std::arch::asm;
+
+    /// C = A × B, one strip of a row of C per `vsetvli`
+    pub fn multiply(a: &[i8], b: &[i8], c: &mut [i32]) {
+        assert!(a.len() == SIZE * SIZE && b.len() == SIZE * SIZE && c.len() == SIZE * SIZE);
+        for i in 0..SIZE {
+            let mut j = 0;
+            while j < SIZE {
+                let vl: usize;
+                // SAFETY: only called when the kernel reports V and has it
+                // turned on for this process; the strip
+                // covers columns j..j + vl <= SIZE of B's rows and C's row i
+                unsafe {
+                    asm!(
+                        ".option push",
+                        ".option arch, +v",
+                        "vsetvli {vl}, {n}, e32, m4, ta, ma",
+                        "vmv.v.i v8, 0",
+                        "mv {k}, {size}",
+                        "2:",
+                        "lb {s}, 0({a})",
+                        "vsetvli zero, {vl}, e8, m1, ta, ma",
+                        "vle8.v v0, ({b})",
+                        "vwmul.vx v2, v0, {s}",
+                        "vsetvli zero, {vl}, e16, m2, ta, ma",
+                        "vwadd.wv v8, v8, v2",
+                        "addi {a}, {a}, 1",
+                        "add {b}, {b}, {size}",
+                        "addi {k}, {k}, -1",
+                        "bnez {k}, 2b",
+                        "vsetvli zero, {vl}, e32, m4, ta, ma",
+                        "vse32.v v8, ({c})",
+                        ".option pop",
+                        vl = out(reg) vl,
+                        n = in(reg) SIZE - j,
+                        size = in(reg) SIZE,
+                        a = inout(reg) a.as_ptr().add(i * SIZE) => _,
+                        b = inout(reg) b.as_ptr().add(j) => _,
+                        c = in(reg) c.as_mut_ptr().add(i * SIZE + j),
+                        k = out(reg) _,
+                        s = out(reg) _,
+                        out("v0") _, out("v2") _, out("v3") _,
+                        out("v8") _, out("v9") _, out("v10") _, out("v11") _,
+                        options(nostack),
+                    );
+                }
+                j += vl;
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+mod kernel {
+    pub fn multiply(_a: &[i8], _b: &[i8], _c: &mut [i32]) {
+        unreachable!("V is only used on riscv64")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multiply_scalar() {
+        let (a, b) = matrices();
+        let mut c = vec![0i32; SIZE * SIZE];
+        multiply_scalar(&a, &b, &mut c);
+        let expected: i32 = (0..SIZE)
+            .map(|k| i32::from(a[3 * SIZE + k]) * i32::from(b[k * SIZE + 5]))
+            .sum();
+        assert_eq!(c[3 * SIZE + 5], expected);
+    }
+
+    #[test]
+    fn test_speedup() {
+        let results = GemmResults {
+            size: SIZE,
+            scalar_gops: 0.5,
+            vector_gops: 4.0,
+        };
+        assert!((results.speedup() - 8.0).abs() < 1e-9);
+    }
+
+    #[cfg(not(target_arch = "riscv64"))]
+    #[test]
+    fn test_collect_elsewhere() {
+        assert!(collect("rv64gcv").is_none());
+    }
+}
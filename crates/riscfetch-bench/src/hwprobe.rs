@@ -0,0 +1,25 @@
+//! `riscv_hwprobe` queries (Linux 6.4+)
+//!
+//! Every function returns None (or false) on other targets and on kernels
+//! without the syscall or the key. The system call itself lives in
+//! riscfetch-core.
+
+use riscfetch_core as info;
+
+/// `RISCV_HWPROBE_KEY_CPUPERF_0`, the older name of the misaligned key
+const KEY_CPUPERF_0: i64 = 5;
+/// `RISCV_HWPROBE_KEY_MISALIGNED_SCALAR_PERF` (Linux 6.11)
+const KEY_MISALIGNED_SCALAR_PERF: i64 = 9;
+
+/// Misaligned scalar access performance as the kernel reports it
+pub fn misaligned_perf() -> Option<&'static str> {
+    let value = info::riscv_hwprobe(KEY_MISALIGNED_SCALAR_PERF)
+        .or_else(|| info::riscv_hwprobe(KEY_CPUPERF_0))?;
+    Some(match value & 0x7 {
+        1 => "emulated",
+        2 => "slow",
+        3 => "fast",
+        4 => "unsupported",
+        _ => "unknown",
+    })
+}
@@ -0,0 +1,272 @@
+//! RISC-V micro-benchmarks behind `riscfetch --benchmark`
+//!
+//! Integer, float and memory throughput with repeated runs, plus kernels
+//! for individual extensions (A / Zacas, Zba / Zbb / Zbs, Zkne / Zknh,
+//! Zfh) and system behaviour (misaligned accesses, system calls, scaling
+//! across harts), and the energy used on boards that measure their power.
+//! Everything returns plain `Serialize` structs, so board test suites and
+//! TUIs can run the same benchmarks as the CLI.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use riscfetch_bench::{run, BenchOptions};
+//!
+//! let result = run(BenchOptions::default());
+//! println!("{:.2} MOPS", result.integer_mops);
+//! ```
+
+pub mod atomics;
+pub mod bitmanip;
+pub mod branch;
+pub mod counters;
+pub mod crypto;
+pub mod fp16;
+pub mod gemm;
+mod hwprobe;
+pub mod memory;
+pub mod misaligned;
+pub mod per_hart;
+pub mod power;
+pub mod scaling;
+pub mod stats;
+pub mod syscalls;
+pub mod vector;
+
+use riscfetch_core as info;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Results of a full benchmark run
+#[derive(Serialize, Debug, Clone)]
+pub struct BenchResult {
+    /// Integer multiply/add throughput (M extension), median of the runs
+    pub integer_mops: f64,
+    /// Floating-point throughput (F/D extension), median of the runs
+    pub float_mflops: f64,
+    /// Sequential write + read bandwidth, median of the runs
+    pub memory_mb_per_sec: f64,
+    /// Bandwidth with working sets sized to each cache level, then DRAM
+    pub memory_sweep: Vec<memory::SweepPoint>,
+    /// Spread and raw samples of the three scores above
+    pub stats: stats::BasicStats,
+    /// Contended counter and CAS loop across all harts
+    pub atomics: atomics::AtomicsResults,
+    /// Zba / Zbb / Zbs kernels, with the extensions enabled where detected
+    pub bitmanip: bitmanip::BitmanipResults,
+    /// Predictable versus random branches and the misprediction penalty
+    pub branch: branch::BranchResults,
+    /// AES / SHA-256 with and without the scalar crypto instructions
+    /// (only when Zkne or Zknh is present)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crypto: Option<crypto::CryptoResults>,
+    /// Half-precision conversions and FMA (only when Zfh or Zfhmin is present)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fp16: Option<fp16::Fp16Results>,
+    /// memcpy / memset with and without the vector unit (only when the
+    /// kernel reports V)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector: Option<vector::VectorResults>,
+    /// Why the vector memcpy / memset and GEMM benchmarks did not run
+    /// although the kernel reports V
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector_skipped: Option<&'static str>,
+    /// int8 matrix multiply with and without the vector unit (only when
+    /// the kernel reports V)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gemm: Option<gemm::GemmResults>,
+    /// Misaligned versus aligned load/store cost (absent when the kernel
+    /// reports misaligned accesses as unsupported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub misaligned: Option<misaligned::MisalignedResults>,
+    /// System call and pipe round-trip latency
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub syscalls: Option<syscalls::SyscallResults>,
+    /// rdcycle / rdtime / rdinstret latency and whether they trap
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counters: Option<counters::CountersResults>,
+    /// Integer / float / memory run in several threads (`BenchOptions::threads`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scaling: Option<scaling::ScalingResults>,
+    /// Integer / float pinned to each hart in turn (`BenchOptions::per_hart`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_hart: Option<Vec<per_hart::HartScore>>,
+    /// Board power and energy over the whole run (only with an hwmon power
+    /// sensor)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power: Option<power::EnergyResults>,
+}
+
+impl BenchResult {
+    /// Bandwidth of the DRAM point of the memory sweep
+    #[must_use]
+    pub fn dram_mb_per_sec(&self) -> f64 {
+        self.memory_sweep
+            .last()
+            .map_or(self.memory_mb_per_sec, |point| point.mb_per_sec)
+    }
+}
+
+/// Optional runs and repetitions
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Measured runs per benchmark, after one warm-up run
+    pub runs: usize,
+    /// Threads for the scaling run
+    pub threads: Option<usize>,
+    /// Pin integer / float to each hart in turn
+    pub per_hart: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            runs: stats::DEFAULT_RUNS,
+            threads: None,
+            per_hart: false,
+        }
+    }
+}
+
+/// Run all benchmarks and collect the scores, plus the optional runs in
+/// `options`
+#[must_use]
+pub fn run(options: BenchOptions) -> BenchResult {
+    let meter = power::Meter::start();
+    let isa = info::get_isa_string();
+    let runs = options.runs.max(1);
+    let integer = stats::Samples::measure(runs, benchmark_integer_ops);
+    let float = stats::Samples::measure(runs, benchmark_float_ops);
+    let memory = stats::Samples::measure(runs, benchmark_memory);
+    let (integer_mops, float_mflops, memory_mb_per_sec) =
+        (integer.median, float.median, memory.median);
+    BenchResult {
+        integer_mops,
+        float_mflops,
+        memory_mb_per_sec,
+        memory_sweep: memory::collect(runs),
+        stats: stats::BasicStats {
+            runs,
+            integer_mops: integer,
+            float_mflops: float,
+            memory_mb_per_sec: memory,
+        },
+        atomics: atomics::collect(&isa),
+        bitmanip: bitmanip::collect(&isa),
+        branch: branch::collect(),
+        crypto: crypto::collect(&isa),
+        fp16: fp16::collect(&isa),
+        vector: vector::collect(&isa),
+        vector_skipped: vector::skip_reason(&isa),
+        gemm: gemm::collect(&isa),
+        misaligned: misaligned::collect(),
+        syscalls: syscalls::collect(),
+        counters: counters::collect(&isa),
+        scaling: options
+            .threads
+            .map(|n| scaling::collect(n, integer_mops, float_mflops, memory_mb_per_sec)),
+        per_hart: options.per_hart.then(per_hart::collect),
+        power: meter.and_then(power::Meter::stop),
+    }
+}
+
+/// Throughput in MB/s for `bytes` processed in `elapsed`
+#[allow(clippy::cast_precision_loss)]
+fn mb_per_sec(bytes: usize, elapsed: Duration) -> f64 {
+    bytes as f64 / elapsed.as_secs_f64() / 1_000_000.0
+}
+
+#[allow(clippy::cast_precision_loss)]
+/// Dependent multiply-add chain in MOPS (M extension)
+#[must_use]
+pub fn benchmark_integer_ops() -> f64 {
+    const ITERATIONS: u64 = 10_000_000;
+    let start = Instant::now();
+
+    let mut result: u64 = 1;
+    for i in 1..ITERATIONS {
+        result = result.wrapping_mul(i).wrapping_add(i);
+    }
+
+    let elapsed = start.elapsed();
+    let ops_per_sec = (ITERATIONS as f64 / elapsed.as_secs_f64()) / 1_000_000.0;
+
+    // Use result to prevent optimization
+    std::hint::black_box(result);
+
+    ops_per_sec
+}
+
+/// Multiply, square root and sine in MFLOPS (F/D extension)
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn benchmark_float_ops() -> f64 {
+    const ITERATIONS: u64 = 5_000_000;
+    let start = Instant::now();
+
+    let mut result: f64 = 1.0;
+    for i in 1..ITERATIONS {
+        let x = i as f64;
+        result = (result * x).sqrt() + x.sin();
+    }
+
+    let elapsed = start.elapsed();
+    let ops_per_sec = (ITERATIONS as f64 * 2.0 / elapsed.as_secs_f64()) / 1_000_000.0;
+
+    // Use result to prevent optimization
+    std::hint::black_box(result);
+
+    ops_per_sec
+}
+
+/// Sequential write then read of 10 MB in MB/s
+#[must_use]
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub fn benchmark_memory() -> f64 {
+    const SIZE: usize = 10_000_000;
+    let mut data = vec![0u8; SIZE];
+
+    let start = Instant::now();
+
+    // Write benchmark
+    for (i, item) in data.iter_mut().enumerate().take(SIZE) {
+        *item = (i & 0xFF) as u8;
+    }
+
+    // Read benchmark
+    let mut sum: u64 = 0;
+    for &byte in &data {
+        sum = sum.wrapping_add(u64::from(byte));
+    }
+
+    let elapsed = start.elapsed();
+    let mb_per_sec = (SIZE as f64 * 2.0 / elapsed.as_secs_f64()) / 1_000_000.0;
+
+    // Use sum to prevent optimization
+    std::hint::black_box(sum);
+
+    mb_per_sec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmark_integer_ops() {
+        let score = benchmark_integer_ops();
+        assert!(score > 0.0);
+    }
+
+    #[test]
+    fn test_benchmark_float_ops() {
+        let score = benchmark_float_ops();
+        assert!(score > 0.0);
+    }
+
+    #[test]
+    fn test_benchmark_memory() {
+        let score = benchmark_memory();
+        assert!(score > 0.0);
+    }
+}
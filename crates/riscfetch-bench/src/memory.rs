@@ -0,0 +1,146 @@
+//! Memory bandwidth per cache level
+//!
+//! A buffer sized to fit each data cache from sysfs (half of L1D, L2, L3)
+//! is read and written word by word until the same total traffic has been
+//! moved, then a buffer well past the last level shows DRAM bandwidth.
+//! Without cache sizes a fixed 16 KiB / 256 KiB / 4 MiB sweep is used.
+
+use crate::mb_per_sec;
+use crate::stats::Samples;
+use riscfetch_core as info;
+use serde::Serialize;
+use std::time::Instant;
+
+/// Bytes moved per sweep point, whatever the working set
+const TRAFFIC_BYTES: usize = 128 << 20;
+/// Smallest working set that counts as DRAM
+const DRAM_MIN_BYTES: u64 = 64 << 20;
+/// Working sets when sysfs has no cache sizes
+const FALLBACK_BYTES: [u64; 3] = [16 << 10, 256 << 10, 4 << 20];
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SweepPoint {
+    /// Cache level the working set fits in (`L1D`, `L2`, `L3`, `DRAM`);
+    /// absent for the fixed sweep
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<&'static str>,
+    pub working_set_bytes: u64,
+    /// Median of the runs
+    pub mb_per_sec: f64,
+    pub stddev_mb_per_sec: f64,
+}
+
+/// (level, working set) for each data cache in `caches`, plus DRAM
+#[must_use]
+pub fn working_sets(caches: &[(&'static str, u64)]) -> Vec<(Option<&'static str>, u64)> {
+    let data: Vec<_> = caches
+        .iter()
+        .filter(|(level, size)| *level != "L1I" && *size > 0)
+        .collect();
+    let mut sets: Vec<_> = if data.is_empty() {
+        FALLBACK_BYTES.iter().map(|&bytes| (None, bytes)).collect()
+    } else {
+        data.iter()
+            .map(|(level, size)| (Some(*level), size / 2))
+            .collect()
+    };
+    let largest = sets.iter().map(|(_, bytes)| *bytes).max().unwrap_or(0);
+    sets.push((Some("DRAM"), (largest * 8).max(DRAM_MIN_BYTES)));
+    sets
+}
+
+/// Sweep the working sets for the caches reported by sysfs, `runs` times each
+#[must_use]
+pub fn collect(runs: usize) -> Vec<SweepPoint> {
+    working_sets(&info::get_cache_sizes())
+        .into_iter()
+        .map(|(level, working_set_bytes)| {
+            let samples = Samples::measure(runs, || bandwidth(working_set_bytes, TRAFFIC_BYTES));
+            SweepPoint {
+                level,
+                working_set_bytes,
+                mb_per_sec: samples.median,
+                stddev_mb_per_sec: samples.stddev,
+            }
+        })
+        .collect()
+}
+
+/// Read + write bandwidth over a `working_set` byte buffer, repeating
+/// passes until about `traffic` bytes have been moved
+#[allow(clippy::cast_possible_truncation)]
+fn bandwidth(working_set: u64, traffic: usize) -> f64 {
+    let words = (working_set as usize / 8).max(1);
+    let mut data: Vec<u64> = (0..words as u64).collect();
+    let passes = (traffic / (words * 16)).max(1);
+
+    // Warm the caches (and fault in the pages) before timing
+    let mut sum = data.iter().fold(0u64, |sum, &word| sum.wrapping_add(word));
+    let start = Instant::now();
+    for _ in 0..passes {
+        for word in &mut data {
+            *word = word.wrapping_add(1);
+            sum = sum.wrapping_add(*word);
+        }
+        std::hint::black_box(&mut data);
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(sum);
+    mb_per_sec(words * 16 * passes, elapsed)
+}
+
+/// `32 KiB`, `1 MiB`
+#[must_use]
+pub fn format_size(bytes: u64) -> String {
+    if bytes >= 1 << 20 && bytes.is_multiple_of(1 << 20) {
+        format!("{} MiB", bytes >> 20)
+    } else if bytes >= 1 << 10 {
+        format!("{} KiB", bytes >> 10)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_working_sets_from_caches() {
+        let caches = [("L1D", 32 << 10), ("L1I", 32 << 10), ("L2", 2 << 20)];
+        assert_eq!(
+            working_sets(&caches),
+            vec![
+                (Some("L1D"), 16 << 10),
+                (Some("L2"), 1 << 20),
+                (Some("DRAM"), 64 << 20)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_working_sets_fallback() {
+        let sets = working_sets(&[]);
+        assert_eq!(sets.len(), 4);
+        assert_eq!(sets[0], (None, 16 << 10));
+        assert_eq!(sets[3], (Some("DRAM"), 64 << 20));
+    }
+
+    #[test]
+    fn test_dram_past_large_l3() {
+        let sets = working_sets(&[("L3", 32 << 20)]);
+        assert_eq!(sets[1], (Some("DRAM"), 128 << 20));
+    }
+
+    #[test]
+    fn test_bandwidth() {
+        assert!(bandwidth(16 << 10, 1 << 20) > 0.0);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(16 << 10), "16 KiB");
+        assert_eq!(format_size(1536 << 10), "1536 KiB");
+        assert_eq!(format_size(64 << 20), "64 MiB");
+    }
+}
@@ -0,0 +1,151 @@
+//! Misaligned access benchmark
+//!
+//! Times 64-bit load + store pairs over a buffer, once at aligned addresses
+//! and once one byte off. Cores handle misaligned accesses in hardware
+//! (fast or slow) or trap into the kernel, which emulates them and can be
+//! 100x slower. The measured penalty is classified the same way as the
+//! kernel's `riscv_hwprobe` report, and both are shown side by side.
+
+use serde::Serialize;
+use std::time::Instant;
+
+/// Words in the buffer (fits in L1 so only alignment differs)
+const WORDS: usize = 2048;
+/// Passes over the buffer; fewer when misaligned, since emulation traps
+/// on every access
+const ALIGNED_PASSES: usize = 256;
+const MISALIGNED_PASSES: usize = 16;
+
+/// Penalty thresholds between fast, slow and emulated
+const SLOW_PENALTY: f64 = 1.5;
+const EMULATED_PENALTY: f64 = 10.0;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MisalignedResults {
+    /// Nanoseconds per aligned 64-bit load + store
+    pub aligned_ns: f64,
+    /// Nanoseconds per misaligned 64-bit load + store
+    pub misaligned_ns: f64,
+    /// `misaligned_ns / aligned_ns`
+    pub penalty: f64,
+    /// `fast`, `slow` or `emulated`, from the penalty
+    pub measured: &'static str,
+    /// What `riscv_hwprobe` reports (`fast`, `slow`, `emulated`, `unknown`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hwprobe: Option<&'static str>,
+}
+
+/// Classify a misaligned/aligned time ratio
+#[must_use]
+pub fn classify(penalty: f64) -> &'static str {
+    if penalty < SLOW_PENALTY {
+        "fast"
+    } else if penalty < EMULATED_PENALTY {
+        "slow"
+    } else {
+        "emulated"
+    }
+}
+
+/// Run the benchmark; None on riscv64 unless the kernel reports how
+/// misaligned accesses are handled. Without hwprobe (Linux before 6.4),
+/// with `unknown` or with `unsupported` they may raise SIGBUS.
+#[must_use]
+pub fn collect() -> Option<MisalignedResults> {
+    let hwprobe = crate::hwprobe::misaligned_perf();
+    if !safe_to_run(hwprobe) {
+        return None;
+    }
+    let mut buffer = vec![0u64; WORDS + 1];
+    let aligned_ns = ns_per_access(&mut buffer, 0, ALIGNED_PASSES);
+    let misaligned_ns = ns_per_access(&mut buffer, 1, MISALIGNED_PASSES);
+    let penalty = misaligned_ns / aligned_ns;
+    Some(MisalignedResults {
+        aligned_ns,
+        misaligned_ns,
+        penalty,
+        measured: classify(penalty),
+        hwprobe,
+    })
+}
+
+/// Whether misaligned `ld` / `sd` complete given the hwprobe class; other
+/// targets use unaligned reads and writes, which never trap
+fn safe_to_run(hwprobe: Option<&str>) -> bool {
+    !cfg!(target_arch = "riscv64") || matches!(hwprobe, Some("fast" | "slow" | "emulated"))
+}
+
+/// Nanoseconds per load + store at `offset` bytes into `buffer`
+#[allow(clippy::cast_precision_loss)]
+fn ns_per_access(buffer: &mut [u64], offset: usize, passes: usize) -> f64 {
+    let words = buffer.len() - 1;
+    // One untimed pass warms the cache
+    kernel::run(buffer.as_mut_ptr().cast::<u8>().wrapping_add(offset), words);
+    let start = Instant::now();
+    for _ in 0..passes {
+        kernel::run(buffer.as_mut_ptr().cast::<u8>().wrapping_add(offset), words);
+    }
+    start.elapsed().as_secs_f64() * 1e9 / (words * passes) as f64
+}
+
+#[cfg(target_arch = "riscv64")]
+mod kernel {
+    /// Increment `words` 64-bit values starting at `ptr` with plain `ld` /
+    /// `sd`; Rust's `read_unaligned` would split the access into bytes
+    pub fn run(ptr: *mut u8, words: usize) {
+        // SAFETY: the caller's buffer has one spare word, so `words` values
+        // from `ptr` (at most 7 bytes in) stay inside it
+        unsafe {
+            std::arch::asm!(
+                "2:",
+                "ld {t}, 0({p})",
+                "addi {t}, {t}, 1",
+                "sd {t}, 0({p})",
+                "addi {p}, {p}, 8",
+                "addi {n}, {n}, -1",
+                "bnez {n}, 2b",
+                p = inout(reg) ptr => _,
+                n = inout(reg) words => _,
+                t = out(reg) _,
+                options(nostack),
+            );
+        }
+    }
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+mod kernel {
+    /// Increment `words` 64-bit values starting at `ptr`
+    pub fn run(ptr: *mut u8, words: usize) {
+        for i in 0..words {
+            // SAFETY: as on riscv64, the buffer has one spare word
+            unsafe {
+                let word = ptr.add(i * 8).cast::<u64>();
+                word.write_unaligned(word.read_unaligned().wrapping_add(1));
+            }
+        }
+        std::hint::black_box(ptr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(1.0), "fast");
+        assert_eq!(classify(2.5), "slow");
+        assert_eq!(classify(120.0), "emulated");
+    }
+
+    #[test]
+    fn test_ns_per_access() {
+        let mut buffer = vec![0u64; 65];
+        assert!(ns_per_access(&mut buffer, 0, 2) > 0.0);
+        // Warm-up plus two passes incremented every word three times
+        assert!(buffer[..64].iter().all(|&word| word == 3));
+        assert_eq!(buffer[64], 0);
+        assert!(ns_per_access(&mut buffer, 1, 2) > 0.0);
+    }
+}
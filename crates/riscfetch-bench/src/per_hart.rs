@@ -0,0 +1,114 @@
+//! Per-hart benchmark (`riscfetch --benchmark --per-hart`)
+//!
+//! The integer and float benchmarks run once on every hart the process may
+//! use, each time in a fresh thread pinned with `sched_setaffinity`. Big and
+//! little clusters or thermally limited cores show up as harts with lower
+//! scores. Pinning needs Linux; elsewhere the list stays empty.
+
+use crate::{benchmark_float_ops, benchmark_integer_ops};
+use riscfetch_core as info;
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct HartScore {
+    /// Logical CPU number, as in /proc/cpuinfo `processor`
+    pub hart: usize,
+    pub integer_mops: f64,
+    pub float_mflops: f64,
+}
+
+/// Benchmark every allowed hart in turn; harts that cannot be pinned
+/// (offline, outside the cpuset) are skipped
+#[must_use]
+pub fn collect() -> Vec<HartScore> {
+    allowed_harts()
+        .into_iter()
+        .filter_map(|hart| {
+            std::thread::spawn(move || {
+                pin_to(hart).then(|| HartScore {
+                    hart,
+                    integer_mops: benchmark_integer_ops(),
+                    float_mflops: benchmark_float_ops(),
+                })
+            })
+            .join()
+            .ok()
+            .flatten()
+        })
+        .collect()
+}
+
+/// Integer score of each hart relative to the fastest one (1.0 = fastest)
+#[must_use]
+pub fn relative(scores: &[HartScore]) -> Vec<f64> {
+    let fastest = scores
+        .iter()
+        .map(|score| score.integer_mops)
+        .fold(0.0, f64::max);
+    scores
+        .iter()
+        .map(|score| score.integer_mops / fastest)
+        .collect()
+}
+
+/// Harts in the affinity mask of this process
+#[cfg(target_os = "linux")]
+fn allowed_harts() -> Vec<usize> {
+    // SAFETY: cpu_set_t is plain data; the kernel fills at most its size
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        if libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) != 0 {
+            return (0..info::get_hart_count_num()).collect();
+        }
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|&cpu| libc::CPU_ISSET(cpu, &set))
+            .collect()
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn allowed_harts() -> Vec<usize> {
+    (0..info::get_hart_count_num()).collect()
+}
+
+/// Restrict the calling thread to `hart`
+#[cfg(target_os = "linux")]
+fn pin_to(hart: usize) -> bool {
+    // SAFETY: the set lives on the stack for the duration of the call;
+    // pid 0 is the calling thread
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(hart, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_to(_hart: usize) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative() {
+        let score = |hart, integer_mops| HartScore {
+            hart,
+            integer_mops,
+            float_mflops: 1.0,
+        };
+        let scores = [score(0, 800.0), score(1, 800.0), score(4, 400.0)];
+        assert_eq!(relative(&scores), vec![1.0, 1.0, 0.5]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_pin_to_allowed_hart() {
+        let harts = allowed_harts();
+        assert!(!harts.is_empty());
+        let hart = harts[0];
+        assert!(std::thread::spawn(move || pin_to(hart)).join().unwrap());
+    }
+}
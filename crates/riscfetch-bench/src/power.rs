@@ -0,0 +1,116 @@
+//! Energy used during a benchmark run
+//!
+//! Boards with an INA219 / INA3221-style current monitor report their draw
+//! through hwmon. A background thread samples the board power while the
+//! benchmarks run; the energy is the area under those samples, and the
+//! average power turns scores into performance per watt.
+
+use riscfetch_core as info;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Time between power samples
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct EnergyResults {
+    /// Mean power over the run
+    pub average_watts: f64,
+    /// Highest sample
+    pub peak_watts: f64,
+    /// Energy used over the run
+    pub joules: f64,
+    pub seconds: f64,
+    pub samples: usize,
+}
+
+impl EnergyResults {
+    /// `score` per watt of average power
+    #[must_use]
+    pub fn per_watt(&self, score: f64) -> f64 {
+        score / self.average_watts
+    }
+}
+
+/// Samples the board power in the background until stopped
+pub struct Meter {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<Vec<(f64, i64)>>,
+}
+
+impl Meter {
+    /// Start sampling; None when the board has no power sensor
+    #[must_use]
+    pub fn start() -> Option<Self> {
+        let first = info::get_board_power()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stop);
+        let thread = std::thread::spawn(move || {
+            let start = Instant::now();
+            let mut samples = vec![(0.0, first)];
+            while !flag.load(Ordering::Relaxed) {
+                std::thread::sleep(SAMPLE_INTERVAL);
+                if let Some(microwatts) = info::get_board_power() {
+                    samples.push((start.elapsed().as_secs_f64(), microwatts));
+                }
+            }
+            samples
+        });
+        Some(Self { stop, thread })
+    }
+
+    /// Stop sampling and integrate; None when fewer than two samples were
+    /// taken (a run shorter than one interval)
+    #[must_use]
+    pub fn stop(self) -> Option<EnergyResults> {
+        self.stop.store(true, Ordering::Relaxed);
+        energy(&self.thread.join().ok()?)
+    }
+}
+
+/// Trapezoidal integration of `(seconds, microwatts)` samples
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn energy(samples: &[(f64, i64)]) -> Option<EnergyResults> {
+    let (first, last) = (samples.first()?.0, samples.last()?.0);
+    let seconds = last - first;
+    if samples.len() < 2 || seconds <= 0.0 {
+        return None;
+    }
+    let joules: f64 = samples
+        .windows(2)
+        .map(|pair| {
+            let ((t0, p0), (t1, p1)) = (pair[0], pair[1]);
+            (t1 - t0) * (p0 + p1) as f64 / 2.0 / 1e6
+        })
+        .sum();
+    let peak = samples.iter().map(|(_, p)| *p).max()?;
+    Some(EnergyResults {
+        average_watts: joules / seconds,
+        peak_watts: peak as f64 / 1e6,
+        joules,
+        seconds,
+        samples: samples.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_energy() {
+        let result = energy(&[(0.0, 4_000_000), (1.0, 6_000_000), (2.0, 5_000_000)]).unwrap();
+        assert!((result.joules - 10.5).abs() < 1e-9);
+        assert!((result.average_watts - 5.25).abs() < 1e-9);
+        assert!((result.peak_watts - 6.0).abs() < 1e-9);
+        assert_eq!(result.samples, 3);
+        assert!((result.per_watt(2100.0) - 400.0).abs() < 1e-9);
+
+        assert_eq!(energy(&[(0.0, 4_000_000)]), None);
+        assert_eq!(energy(&[]), None);
+    }
+}
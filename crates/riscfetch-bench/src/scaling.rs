@@ -0,0 +1,115 @@
+//! Multi-threaded scaling (`riscfetch --benchmark --threads all|N`)
+//!
+//! The integer, float and memory benchmarks run in N threads at once. The
+//! aggregate is the sum of the per-thread scores, and the efficiency compares
+//! it with N times the single-threaded score (100% = perfect scaling).
+
+use crate::{benchmark_float_ops, benchmark_integer_ops, benchmark_memory};
+use riscfetch_core as info;
+use serde::Serialize;
+use std::sync::Barrier;
+
+/// Aggregate score of one benchmark across all threads
+#[derive(Serialize, Debug, Clone)]
+pub struct Scaling {
+    pub single: f64,
+    pub aggregate: f64,
+    /// `aggregate / (threads * single)`, 1.0 for perfect scaling
+    pub efficiency: f64,
+}
+
+impl Scaling {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(single: f64, per_thread: &[f64]) -> Self {
+        let aggregate: f64 = per_thread.iter().sum();
+        Self {
+            single,
+            aggregate,
+            efficiency: aggregate / (single * per_thread.len() as f64),
+        }
+    }
+
+    /// Aggregate relative to one thread
+    #[must_use]
+    pub fn speedup(&self) -> f64 {
+        self.aggregate / self.single
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ScalingResults {
+    pub threads: usize,
+    pub integer_mops: Scaling,
+    pub float_mflops: Scaling,
+    pub memory_mb_per_sec: Scaling,
+}
+
+/// Parse a thread count: `all` (one per hart) or a positive number
+pub fn parse_threads(text: &str) -> Result<usize, String> {
+    if text.eq_ignore_ascii_case("all") {
+        return Ok(info::get_hart_count_num().max(1));
+    }
+    match text.parse::<usize>() {
+        Ok(0) | Err(_) => Err(format!(
+            "invalid thread count '{text}' (expected all or a positive number)"
+        )),
+        Ok(n) => Ok(n),
+    }
+}
+
+/// Run `bench` in `threads` threads started together; one score per thread
+fn run_parallel(threads: usize, bench: fn() -> f64) -> Vec<f64> {
+    let barrier = Barrier::new(threads);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    barrier.wait();
+                    bench()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or(0.0))
+            .collect()
+    })
+}
+
+/// Scale the benchmarks whose single-threaded scores are given
+#[must_use]
+pub fn collect(threads: usize, integer: f64, float: f64, memory: f64) -> ScalingResults {
+    ScalingResults {
+        threads,
+        integer_mops: Scaling::new(integer, &run_parallel(threads, benchmark_integer_ops)),
+        float_mflops: Scaling::new(float, &run_parallel(threads, benchmark_float_ops)),
+        memory_mb_per_sec: Scaling::new(memory, &run_parallel(threads, benchmark_memory)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_threads() {
+        assert_eq!(parse_threads("8"), Ok(8));
+        assert!(parse_threads("all").unwrap() >= 1);
+        assert!(parse_threads("0").is_err());
+        assert!(parse_threads("many").is_err());
+    }
+
+    #[test]
+    fn test_scaling_efficiency() {
+        let scaling = Scaling::new(100.0, &[90.0, 90.0, 80.0, 60.0]);
+        assert!((scaling.aggregate - 320.0).abs() < f64::EPSILON);
+        assert!((scaling.efficiency - 0.8).abs() < 1e-9);
+        assert!((scaling.speedup() - 3.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_parallel() {
+        let scores = run_parallel(3, || 1.5);
+        assert_eq!(scores, vec![1.5, 1.5, 1.5]);
+    }
+}
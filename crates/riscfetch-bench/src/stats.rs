@@ -0,0 +1,106 @@
+//! Repeated measurements: warm-up, N runs, median and standard deviation
+//!
+//! A single run of a short benchmark is at the mercy of frequency scaling,
+//! cold caches and other processes. Every score is therefore measured
+//! after an untimed warm-up run, `runs` times, and reported as the median
+//! (robust against one slow outlier) with the sample standard deviation.
+
+use serde::Serialize;
+
+/// Runs per benchmark unless `BenchOptions::runs` says otherwise
+pub const DEFAULT_RUNS: usize = 5;
+
+/// Untimed runs before measuring
+const WARMUP_RUNS: usize = 1;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Samples {
+    pub median: f64,
+    /// Sample standard deviation (0 for a single run)
+    pub stddev: f64,
+    /// Raw scores in run order
+    pub samples: Vec<f64>,
+}
+
+impl Samples {
+    /// Warm up, then run `bench` `runs` times (at least once)
+    pub fn measure(runs: usize, mut bench: impl FnMut() -> f64) -> Self {
+        for _ in 0..WARMUP_RUNS {
+            std::hint::black_box(bench());
+        }
+        Self::from_samples((0..runs.max(1)).map(|_| bench()).collect())
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: Vec<f64>) -> Self {
+        let mut sorted = samples.clone();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+        let median = match n {
+            0 => 0.0,
+            _ if n % 2 == 1 => sorted[n / 2],
+            _ => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0,
+        };
+        let stddev = if n < 2 {
+            0.0
+        } else {
+            let mean = samples.iter().sum::<f64>() / n as f64;
+            let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+            variance.sqrt()
+        };
+        Self {
+            median,
+            stddev,
+            samples,
+        }
+    }
+}
+
+/// Samples of the basic benchmarks (`stats` in JSON)
+#[derive(Serialize, Debug, Clone)]
+pub struct BasicStats {
+    pub runs: usize,
+    pub integer_mops: Samples,
+    pub float_mflops: Samples,
+    pub memory_mb_per_sec: Samples,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_samples() {
+        let stats = Samples::from_samples(vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert!((stats.median - 4.5).abs() < 1e-9);
+        assert!((stats.stddev - (32.0f64 / 7.0).sqrt()).abs() < 1e-9);
+        assert_eq!(stats.samples.len(), 8);
+    }
+
+    #[test]
+    fn test_median_ignores_outlier() {
+        let stats = Samples::from_samples(vec![100.0, 3.0, 101.0]);
+        assert!((stats.median - 100.0).abs() < f64::EPSILON);
+        assert_eq!(stats.samples, vec![100.0, 3.0, 101.0]);
+    }
+
+    #[test]
+    fn test_single_sample() {
+        let stats = Samples::from_samples(vec![42.0]);
+        assert!((stats.median - 42.0).abs() < f64::EPSILON);
+        assert!(stats.stddev.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_measure_warms_up() {
+        let mut calls = 0;
+        let stats = Samples::measure(3, || {
+            calls += 1;
+            f64::from(calls)
+        });
+        assert_eq!(calls, 4);
+        // The warm-up result (1.0) is not part of the samples
+        assert_eq!(stats.samples, vec![2.0, 3.0, 4.0]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Kernel entry and context-switch latency
+//!
+//! `getpid` is about the cheapest system call, so its cost is mostly the
+//! trap into the kernel and back. The pipe round trip sends one byte to an
+//! echo thread and waits for it to come back, which adds two wake-ups and
+//! context switches. Both vary a lot between cores and kernel configs
+//! (mitigations, preemption model) and matter for server workloads.
+
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::time::Instant;
+
+const GETPID_CALLS: u32 = 200_000;
+const ROUND_TRIPS: u32 = 10_000;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SyscallResults {
+    /// Nanoseconds per `getpid` call
+    pub getpid_ns: f64,
+    /// Microseconds per one-byte pipe round trip between two threads
+    pub pipe_round_trip_us: f64,
+}
+
+/// Run both benchmarks; None if the pipes cannot be created
+#[must_use]
+pub fn collect() -> Option<SyscallResults> {
+    Some(SyscallResults {
+        getpid_ns: getpid_ns(GETPID_CALLS),
+        pipe_round_trip_us: pipe_round_trip_us(ROUND_TRIPS).ok()?,
+    })
+}
+
+/// `std::process::id()` is a real `getpid` call (glibc stopped caching it
+/// in 2.25, musl never did)
+fn getpid_ns(calls: u32) -> f64 {
+    let start = Instant::now();
+    for _ in 0..calls {
+        std::hint::black_box(std::process::id());
+    }
+    start.elapsed().as_secs_f64() * 1e9 / f64::from(calls)
+}
+
+fn pipe_round_trip_us(round_trips: u32) -> std::io::Result<f64> {
+    let (mut ping_reader, ping_writer) = std::io::pipe()?;
+    let (pong_reader, mut pong_writer) = std::io::pipe()?;
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            let mut byte = [0u8; 1];
+            for _ in 0..round_trips {
+                if ping_reader.read_exact(&mut byte).is_err()
+                    || pong_writer.write_all(&byte).is_err()
+                {
+                    break;
+                }
+            }
+        });
+        ping(ping_writer, pong_reader, round_trips)
+    })
+}
+
+/// Send and receive `round_trips` bytes; takes the pipe ends by value so an
+/// early error closes them and ends the echo thread
+fn ping(
+    mut writer: std::io::PipeWriter,
+    mut reader: std::io::PipeReader,
+    round_trips: u32,
+) -> std::io::Result<f64> {
+    let mut byte = [0u8; 1];
+    let start = Instant::now();
+    for _ in 0..round_trips {
+        writer.write_all(&byte)?;
+        reader.read_exact(&mut byte)?;
+    }
+    Ok(start.elapsed().as_secs_f64() * 1e6 / f64::from(round_trips))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_getpid_ns() {
+        assert!(getpid_ns(100) > 0.0);
+    }
+
+    #[test]
+    fn test_pipe_round_trip_us() {
+        assert!(pipe_round_trip_us(10).unwrap() > 0.0);
+    }
+}
@@ -0,0 +1,279 @@
+//! Vector versus scalar memcpy / memset (V extension)
+//!
+//! The same copy and fill run twice over a buffer that fits in L2: as a
+//! scalar loop of 64-bit loads and stores, and with `vle8.v` / `vse8.v` at
+//! LMUL=8. Both loops are inline assembly, so the compiler can neither
+//! vectorize the scalar one nor turn it into a libc call. V is not a stable
+//! target feature; the vector loops enable it with `.option arch, +v`.
+
+use crate::mb_per_sec;
+use riscfetch_core as info;
+use serde::Serialize;
+use std::time::Instant;
+
+/// Bytes per buffer (a multiple of the 32 bytes per scalar iteration)
+const BUFFER: usize = 256 * 1024;
+
+/// Passes over the buffer per measurement (256 MiB in total)
+const PASSES: usize = 1024;
+
+/// Scalar versus vector throughput for one operation
+#[derive(Serialize, Debug, Clone)]
+pub struct Speedup {
+    pub scalar_mb_per_sec: f64,
+    pub vector_mb_per_sec: f64,
+}
+
+impl Speedup {
+    /// How many times faster the vector loop is
+    #[must_use]
+    pub fn speedup(&self) -> f64 {
+        self.vector_mb_per_sec / self.scalar_mb_per_sec
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct VectorResults {
+    pub buffer_bytes: usize,
+    pub memcpy: Speedup,
+    pub memset: Speedup,
+}
+
+/// Whether the ISA string has V or one of the embedded Zve subsets, which
+/// all support the byte loads and stores used here
+#[must_use]
+pub fn detect(isa: &str) -> bool {
+    ["v", "zve32x", "zve32f", "zve64x", "zve64f", "zve64d"]
+        .iter()
+        .any(|name| info::isa_supports(isa, name))
+}
+
+/// Whether the vector kernels (here and in `gemm`) can run: V or Zve* in
+/// the ISA string, V reported by `riscv_hwprobe`, and vector turned on for
+/// this process
+#[must_use]
+pub fn available(isa: &str) -> bool {
+    cfg!(target_arch = "riscv64") && detect(isa) && info::hwprobe_vector() && info::vector_allowed()
+}
+
+/// Why the vector kernels were skipped on a machine whose kernel reports
+/// V: vector is turned off for this process (`riscv_v_default_allow` at 0
+/// or `PR_RISCV_V_SET_CONTROL`), so the first `vsetvli` would raise SIGILL
+#[must_use]
+pub fn skip_reason(isa: &str) -> Option<&'static str> {
+    (cfg!(target_arch = "riscv64")
+        && detect(isa)
+        && info::hwprobe_vector()
+        && !info::vector_allowed())
+    .then_some("vector disabled by kernel")
+}
+
+/// Run the comparison; None without V (or on other targets), or when the
+/// kernel does not report it as usable or has it turned off for this
+/// process
+#[must_use]
+pub fn collect(isa: &str) -> Option<VectorResults> {
+    if !available(isa) {
+        return None;
+    }
+    // u64 words keep both buffers 8-byte aligned for the scalar loops
+    let source = vec![0x5a5a_5a5a_5a5a_5a5a_u64; BUFFER / 8];
+    let mut destination = vec![0u64; BUFFER / 8];
+    let mut copy = |kernel: fn(*mut u8, *const u8, usize)| {
+        time(|| {
+            kernel(
+                destination.as_mut_ptr().cast(),
+                source.as_ptr().cast(),
+                BUFFER,
+            );
+        })
+    };
+    let memcpy = Speedup {
+        scalar_mb_per_sec: copy(kernels::copy_scalar),
+        vector_mb_per_sec: copy(kernels::copy_vector),
+    };
+    let mut fill = |kernel: fn(*mut u8, u8, usize)| {
+        time(|| kernel(destination.as_mut_ptr().cast(), 0xa5, BUFFER))
+    };
+    let memset = Speedup {
+        scalar_mb_per_sec: fill(kernels::fill_scalar),
+        vector_mb_per_sec: fill(kernels::fill_vector),
+    };
+    Some(VectorResults {
+        buffer_bytes: BUFFER,
+        memcpy,
+        memset,
+    })
+}
+
+/// MB/s for `PASSES` runs of `pass` after one untimed pass
+fn time(mut pass: impl FnMut()) -> f64 {
+    pass();
+    let start = Instant::now();
+    for _ in 0..PASSES {
+        pass();
+    }
+    mb_per_sec(BUFFER * PASSES, start.elapsed())
+}
+
+#[cfg(target_arch = "riscv64")]
+mod kernels {
+    use std::arch::asm;
+
+    /// Copy `len` bytes (a multiple of 32) with 64-bit loads and stores
+    pub fn copy_scalar(dst: *mut u8, src: *const u8, len: usize) {
+        // SAFETY: both buffers hold `len` bytes and are 8-byte aligned
+        unsafe {
+            asm!(
+                "2:",
+                "ld {a}, 0({s})",
+                "ld {b}, 8({s})",
+                "ld {c}, 16({s})",
+                "ld {d}, 24({s})",
+                "sd {a}, 0({t})",
+                "sd {b}, 8({t})",
+                "sd {c}, 16({t})",
+                "sd {d}, 24({t})",
+                "addi {s}, {s}, 32",
+                "addi {t}, {t}, 32",
+                "addi {n}, {n}, -32",
+                "bnez {n}, 2b",
+                s = inout(reg) src => _,
+                t = inout(reg) dst => _,
+                n = inout(reg) len => _,
+                a = out(reg) _, b = out(reg) _, c = out(reg) _, d = out(reg) _,
+                options(nostack),
+            );
+        }
+    }
+
+    /// Copy `len` bytes with `vle8.v` / `vse8.v` on eight-register groups
+    pub fn copy_vector(dst: *mut u8, src: *const u8, len: usize) {
+        // SAFETY: only called when the kernel reports V and has it turned on
+        // for this process; both buffers hold `len` bytes and vl never
+        // exceeds the bytes left
+        unsafe {
+            asm!(
+                ".option push",
+                ".option arch, +v",
+                "2:",
+                "vsetvli {vl}, {n}, e8, m8, ta, ma",
+                "vle8.v v0, ({s})",
+                "vse8.v v0, ({t})",
+                "add {s}, {s}, {vl}",
+                "add {t}, {t}, {vl}",
+                "sub {n}, {n}, {vl}",
+                "bnez {n}, 2b",
+                ".option pop",
+                s = inout(reg) src => _,
+                t = inout(reg) dst => _,
+                n = inout(reg) len => _,
+                vl = out(reg) _,
+                out("v0") _, out("v1") _, out("v2") _, out("v3") _,
+                out("v4") _, out("v5") _, out("v6") _, out("v7") _,
+                options(nostack),
+            );
+        }
+    }
+
+    /// Fill `len` bytes (a multiple of 32) with 64-bit stores
+    pub fn fill_scalar(dst: *mut u8, value: u8, len: usize) {
+        let word = u64::from_ne_bytes([value; 8]);
+        // SAFETY: the buffer holds `len` bytes and is 8-byte aligned
+        unsafe {
+            asm!(
+                "2:",
+                "sd {w}, 0({t})",
+                "sd {w}, 8({t})",
+                "sd {w}, 16({t})",
+                "sd {w}, 24({t})",
+                "addi {t}, {t}, 32",
+                "addi {n}, {n}, -32",
+                "bnez {n}, 2b",
+                t = inout(reg) dst => _,
+                n = inout(reg) len => _,
+                w = in(reg) word,
+                options(nostack),
+            );
+        }
+    }
+
+    /// Fill `len` bytes with `vse8.v` on an eight-register group
+    pub fn fill_vector(dst: *mut u8, value: u8, len: usize) {
+        // SAFETY: only called when the kernel reports V and has it turned on
+        // for this process; the buffer holds `len` bytes and vl never
+        // exceeds the bytes left
+        unsafe {
+            asm!(
+                ".option push",
+                ".option arch, +v",
+                "vsetvli {vl}, {n}, e8, m8, ta, ma",
+                "vmv.v.x v0, {w}",
+                "2:",
+                "vsetvli {vl}, {n}, e8, m8, ta, ma",
+                "vse8.v v0, ({t})",
+                "add {t}, {t}, {vl}",
+                "sub {n}, {n}, {vl}",
+                "bnez {n}, 2b",
+                ".option pop",
+                t = inout(reg) dst => _,
+                n = inout(reg) len => _,
+                w = in(reg) u64::from(value),
+                vl = out(reg) _,
+                out("v0") _, out("v1") _, out("v2") _, out("v3") _,
+                out("v4") _, out("v5") _, out("v6") _, out("v7") _,
+                options(nostack),
+            );
+        }
+    }
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+mod kernels {
+    pub fn copy_scalar(_dst: *mut u8, _src: *const u8, _len: usize) {
+        unreachable!("V is only used on riscv64")
+    }
+
+    pub fn copy_vector(_dst: *mut u8, _src: *const u8, _len: usize) {
+        unreachable!("V is only used on riscv64")
+    }
+
+    pub fn fill_scalar(_dst: *mut u8, _value: u8, _len: usize) {
+        unreachable!("V is only used on riscv64")
+    }
+
+    pub fn fill_vector(_dst: *mut u8, _value: u8, _len: usize) {
+        unreachable!("V is only used on riscv64")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert!(detect("rv64gcv"));
+        assert!(detect("rv64imac_zve32x"));
+        assert!(detect("rv64gc_zve64d_zvl128b"));
+        assert!(!detect("rv64gc_zvbb"));
+        assert!(!detect("rv64gc"));
+    }
+
+    #[test]
+    fn test_speedup() {
+        let copy = Speedup {
+            scalar_mb_per_sec: 4000.0,
+            vector_mb_per_sec: 10_000.0,
+        };
+        assert!((copy.speedup() - 2.5).abs() < 1e-9);
+    }
+
+    #[cfg(not(target_arch = "riscv64"))]
+    #[test]
+    fn test_collect_elsewhere() {
+        assert!(!available("rv64gcv"));
+        assert_eq!(skip_reason("rv64gcv"), None);
+        assert!(collect("rv64gcv").is_none());
+    }
+}
@@ -0,0 +1,119 @@
+//! Static login banner for `/etc/issue` (`banner` subcommand)
+//!
+//! getty prints `/etc/issue` (and, with agetty, `/etc/issue.d/*.issue`)
+//! before the login prompt and expands backslash escapes in it. The plain
+//! banner has no colors and doubles every backslash so it is shown as is;
+//! the agetty banner adds the hostname, tty and IP address escapes.
+
+use crate::display::compact_isa;
+use riscfetch_core::SystemInfo;
+use std::path::Path;
+
+/// systemd unit installed by `--install-hook`
+pub const HOOK_UNIT: &str = "/etc/systemd/system/riscfetch-banner.service";
+
+/// Escape `text` so getty prints it literally
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+}
+
+/// Banner text: board, ISA, harts, memory and kernel, one per line
+#[must_use]
+pub fn render(data: &SystemInfo, agetty: bool) -> String {
+    let mut lines = vec![format!("RISC-V {}", data.board)];
+    let mut field = |label: &str, value: String| {
+        if !value.is_empty() && value != "Unknown" {
+            lines.push(format!("  {label:<8}{value}"));
+        }
+    };
+    field("ISA", compact_isa(&data.isa));
+    if data.hart_count > 0 {
+        field("Harts", data.hart_count.to_string());
+    }
+    if data.memory_total_bytes > 0 {
+        #[allow(clippy::cast_precision_loss)]
+        let total_gib = data.memory_total_bytes as f64 / 1_073_741_824.0;
+        field("Memory", format!("{total_gib:.1} GiB"));
+    }
+    field("Kernel", data.kernel.clone());
+    field("OS", data.os.clone());
+
+    let mut banner: String = lines.iter().map(|line| escape(line) + "\n").collect();
+    if agetty {
+        // \n hostname, \l tty, \4 first IPv4 address
+        banner.push_str("  Host    \\n (\\l)\n  IP      \\4\n");
+    }
+    banner.push('\n');
+    banner
+}
+
+/// systemd oneshot unit regenerating the banner at `path` before the gettys
+/// start
+#[must_use]
+pub fn hook_unit(exe: &Path, path: &Path, agetty: bool) -> String {
+    format!(
+        "[Unit]\n\
+         Description=Regenerate the riscfetch login banner\n\
+         After=local-fs.target\n\
+         Before=getty.target systemd-user-sessions.service\n\
+         \n\
+         [Service]\n\
+         Type=oneshot\n\
+         ExecStart={} banner --write {}{}\n\
+         \n\
+         [Install]\n\
+         WantedBy=multi-user.target\n",
+        exe.display(),
+        path.display(),
+        if agetty { " --agetty" } else { "" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::sample_info;
+
+    fn sample() -> SystemInfo {
+        SystemInfo {
+            isa: "rv64imafdc_zicsr_zifencei_zba_zbb".to_string(),
+            os: "Debian C:\\ GNU/Linux".to_string(),
+            ..sample_info()
+        }
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let banner = render(&sample(), false);
+        assert_eq!(
+            banner,
+            "RISC-V StarFive VisionFive 2\n  ISA     rv64gc+zba zbb\n  Harts   4\n  Memory  8.0 GiB\n  \
+             Kernel  6.6.20-starfive\n  OS      Debian C:\\\\ GNU/Linux\n\n"
+        );
+        assert!(!banner.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_render_agetty_and_unknown_fields() {
+        let data = SystemInfo {
+            kernel: "Unknown".to_string(),
+            ..sample()
+        };
+        let banner = render(&data, true);
+        assert!(!banner.contains("Kernel"));
+        assert!(banner.ends_with("  Host    \\n (\\l)\n  IP      \\4\n\n"));
+    }
+
+    #[test]
+    fn test_hook_unit() {
+        let unit = hook_unit(
+            Path::new("/usr/bin/riscfetch"),
+            Path::new("/etc/issue.d/riscfetch.issue"),
+            true,
+        );
+        assert!(unit.contains(
+            "ExecStart=/usr/bin/riscfetch banner --write /etc/issue.d/riscfetch.issue --agetty\n"
+        ));
+        assert!(unit.contains("Before=getty.target"));
+    }
+}
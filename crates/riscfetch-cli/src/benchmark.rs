@@ -1,127 +1,542 @@
+//! Pretty output of `--benchmark`, plus the CLI-only parts: run history,
+//! board baselines and comparison with saved runs. The benchmarks themselves live in `riscfetch-bench`.
+
+pub mod baselines;
+pub mod compare;
+pub mod history;
+
 use colored::Colorize;
-use std::time::Instant;
+use riscfetch_bench::{
+    bitmanip, counters, crypto, fp16, memory, per_hart, power, scaling, vector, BenchOptions,
+    BenchResult,
+};
+use riscfetch_core as info;
 
-pub fn run_benchmarks() {
-    println!("{}", "Running RISC-V Benchmarks...".bright_yellow().bold());
-    println!();
+/// `  Popcount/CLZ (Zbb): 900.00 Mops (base 120.00, 7.5x)`, or the baseline
+/// alone when the extension is not detected
+fn print_kernel(label: &str, kernel: &bitmanip::Kernel) {
+    let (mops, note) = match (kernel.bitmanip_mops, kernel.speedup()) {
+        (Some(mops), Some(speedup)) => (
+            mops,
+            format!("(base {:.2}, {speedup:.1}x)", kernel.base_mops),
+        ),
+        _ => (
+            kernel.base_mops,
+            format!("(no {})", capitalize(kernel.extension)),
+        ),
+    };
+    println!(
+        "  {} {} {} {}",
+        label.bright_blue().bold(),
+        format!("{mops:.2}").bright_white(),
+        "Mops".bright_white().dimmed(),
+        note.bright_black()
+    );
+}
 
-    // Integer multiplication benchmark (M extension)
-    let int_score = benchmark_integer_ops();
+/// `zbb` -> `Zbb`
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+/// `AES-128 (Zkne): 412.00 MB/s (software 35.20 MB/s, 11.7x)`
+fn print_comparison(label: &str, comparison: &crypto::Comparison) {
     println!(
-        "{} {} {}",
-        "Integer Ops (M):".bright_cyan().bold(),
-        format!("{int_score:.2}").bright_white(),
-        "MOPS".bright_white().dimmed()
+        "{} {} {} {}",
+        label.bright_red().bold(),
+        format!("{:.2}", comparison.hardware_mb_per_sec).bright_white(),
+        "MB/s".bright_white().dimmed(),
+        format!(
+            "(software {:.2} MB/s, {:.1}x)",
+            comparison.software_mb_per_sec,
+            comparison.speedup()
+        )
+        .bright_black()
     );
+}
 
-    // Floating-point benchmark (F/D extension)
-    let float_score = benchmark_float_ops();
+fn print_vector(label: &str, speedup: &vector::Speedup) {
     println!(
-        "{} {} {}",
-        "Float Ops (F/D):".bright_green().bold(),
-        format!("{float_score:.2}").bright_white(),
-        "MFLOPS".bright_white().dimmed()
+        "{} {} {} {}",
+        label.bright_blue().bold(),
+        format!("{:.2}", speedup.vector_mb_per_sec).bright_white(),
+        "MB/s".bright_white().dimmed(),
+        format!(
+            "(scalar {:.2} MB/s, {:.1}x)",
+            speedup.scalar_mb_per_sec,
+            speedup.speedup()
+        )
+        .bright_black()
     );
+}
+
+/// How the counter results relate to Zicntr in the ISA string, when
+/// there is something to say
+fn counters_note(results: &counters::CountersResults) -> Option<String> {
+    let trapped = results.trapped();
+    if trapped.is_empty() {
+        return (!results.zicntr).then(|| {
+            "all counters are readable although the ISA string does not list Zicntr (older kernels omit it)"
+                .to_string()
+        });
+    }
+    let sysctl = results
+        .perf_user_access
+        .filter(|&value| value != 2)
+        .map_or_else(String::new, |value| {
+            format!(" (kernel.perf_user_access = {value}; 2 allows direct reads)")
+        });
+    let csrs = trapped.join("/");
+    Some(if results.zicntr {
+        format!("Zicntr is listed, but user-mode reads of {csrs} trap{sysctl}")
+    } else {
+        format!("user-mode reads of {csrs} trap and the ISA string does not list Zicntr{sysctl}")
+    })
+}
+
+fn print_counters(results: &counters::CountersResults) {
+    for counter in &results.counters {
+        let label = format!("CSR rd{}:", counter.csr);
+        if let Some(ns) = counter.ns {
+            println!(
+                "{} {} {}",
+                label.bright_red().bold(),
+                format!("{ns:.1}").bright_white(),
+                "ns".bright_white().dimmed()
+            );
+        } else {
+            println!(
+                "{} {}",
+                label.bright_red().bold(),
+                "trapped (SIGILL)".bright_black()
+            );
+        }
+    }
+    if let Some(note) = counters_note(results) {
+        println!("  {}", note.bright_black());
+    }
+}
 
-    // Memory bandwidth benchmark
-    let mem_score = benchmark_memory();
+/// `  Integer Ops (M): 6400.00 MOPS (7.9x, 99% efficiency)`
+fn print_scaling(label: &str, scaling: &scaling::Scaling, unit: &str) {
+    println!(
+        "  {} {} {} {}",
+        label.bright_yellow().bold(),
+        format!("{:.2}", scaling.aggregate).bright_white(),
+        unit.bright_white().dimmed(),
+        format!(
+            "({:.1}x, {:.0}% efficiency)",
+            scaling.speedup(),
+            scaling.efficiency * 100.0
+        )
+        .bright_black()
+    );
+}
+
+/// `Half FMA (Zfh): 410.00 MFLOPS (fp32 405.00 MFLOPS, 1.0x)` plus the
+/// conversion rate
+fn print_fp16(fp16: &fp16::Fp16Results) {
+    if let Some(mflops) = fp16.fma_mflops {
+        println!(
+            "{} {} {} {}",
+            "Half FMA (Zfh):".bright_green().bold(),
+            format!("{mflops:.2}").bright_white(),
+            "MFLOPS".bright_white().dimmed(),
+            format!(
+                "(fp32 {:.2} MFLOPS, {:.1}x)",
+                fp16.fp32_fma_mflops,
+                mflops / fp16.fp32_fma_mflops
+            )
+            .bright_black()
+        );
+    }
     println!(
         "{} {} {}",
-        "Memory Bandwidth:".bright_magenta().bold(),
-        format!("{mem_score:.2}").bright_white(),
-        "MB/s".bright_white().dimmed()
+        format!("Half Convert ({}):", capitalize(fp16.extension))
+            .bright_green()
+            .bold(),
+        format!("{:.2}", fp16.convert_mops).bright_white(),
+        "Mops".bright_white().dimmed()
     );
+}
 
-    println!();
-    println!("{}", "Benchmarks complete!".bright_yellow().bold());
-    println!();
+/// Average power and energy, then the main scores per watt
+fn print_power(power: &power::EnergyResults, results: &BenchResult) {
+    println!(
+        "{} {} {} {}",
+        "Power:".bright_magenta().bold(),
+        format!("{:.2}", power.average_watts).bright_white(),
+        "W".bright_white().dimmed(),
+        format!(
+            "(peak {:.2} W, {:.1} J over {:.1} s)",
+            power.peak_watts, power.joules, power.seconds
+        )
+        .bright_black()
+    );
+    println!(
+        "{} {} {} {} {}",
+        "Perf/Watt:".bright_magenta().bold(),
+        format!("{:.2}", power.per_watt(results.integer_mops)).bright_white(),
+        "MOPS/W".bright_white().dimmed(),
+        format!("{:.2}", power.per_watt(results.float_mflops)).bright_white(),
+        "MFLOPS/W".bright_white().dimmed()
+    );
 }
 
-#[allow(clippy::cast_precision_loss)]
-fn benchmark_integer_ops() -> f64 {
-    const ITERATIONS: u64 = 10_000_000;
-    let start = Instant::now();
+/// `  ~1.4× VisionFive 2` for every known board, marking the one we run on
+fn print_baselines(results: &BenchResult, board: &str) {
+    let boards = baselines::baselines();
+    if boards.is_empty() {
+        return;
+    }
+    println!("{}", "Compared with known boards:".bright_yellow().bold());
+    for baseline in &boards {
+        let relative = baseline.relative(
+            results.integer_mops,
+            results.float_mflops,
+            results.dram_mb_per_sec(),
+        );
+        println!(
+            "  {} {}{}",
+            format!("~{relative:.1}×").bright_white().bold(),
+            baseline.name,
+            if baseline.matches(board) {
+                " (this board)".bright_black().to_string()
+            } else {
+                String::new()
+            }
+        );
+    }
+}
 
-    let mut result: u64 = 1;
-    for i in 1..ITERATIONS {
-        result = result.wrapping_mul(i).wrapping_add(i);
+/// Table of bandwidth per working set, one row per cache level
+fn print_memory_sweep(points: &[memory::SweepPoint], dram_delta: &str) {
+    println!("{}", "Memory Bandwidth:".bright_magenta().bold());
+    println!(
+        "  {}",
+        format!(
+            "{:<6}{:>12}{:>12}{:>10}",
+            "Level", "Working Set", "MB/s", "±"
+        )
+        .bright_white()
+        .bold()
+    );
+    for (index, point) in points.iter().enumerate() {
+        println!(
+            "  {:<6}{:>12}{:>12.2}{:>10.2}{}",
+            point.level.unwrap_or("-"),
+            memory::format_size(point.working_set_bytes),
+            point.mb_per_sec,
+            point.stddev_mb_per_sec,
+            if index + 1 == points.len() {
+                dram_delta
+            } else {
+                ""
+            }
+        );
     }
+}
 
-    let elapsed = start.elapsed();
-    let ops_per_sec = (ITERATIONS as f64 / elapsed.as_secs_f64()) / 1_000_000.0;
+/// Table of per-hart scores with each hart relative to the fastest
+fn print_per_hart(scores: &[per_hart::HartScore]) {
+    println!("{}", "Per-Hart Scores:".bright_yellow().bold());
+    if scores.is_empty() {
+        println!("  {}", "(could not pin to any hart)".bright_black());
+        return;
+    }
+    println!(
+        "  {}",
+        format!(
+            "{:<6}{:>16}{:>16}{:>12}",
+            "Hart", "Integer (MOPS)", "Float (MFLOPS)", "vs fastest"
+        )
+        .bright_white()
+        .bold()
+    );
+    for (score, relative) in scores.iter().zip(per_hart::relative(scores)) {
+        let row = format!(
+            "{:<6}{:>16.2}{:>16.2}{:>11.0}%",
+            score.hart,
+            score.integer_mops,
+            score.float_mflops,
+            relative * 100.0
+        );
+        // Harts well below the fastest are the interesting ones
+        if relative < 0.9 {
+            println!("  {}", row.bright_red());
+        } else {
+            println!("  {row}");
+        }
+    }
+}
 
-    // Use result to prevent optimization
-    std::hint::black_box(result);
+/// Run the suite, print it and return the results; `save_history` saves the
+/// run and shows deltas against the previous one
+pub fn run_benchmarks(options: BenchOptions, save_history: bool) -> BenchResult {
+    println!(
+        "{} {}",
+        "Running RISC-V Benchmarks...".bright_yellow().bold(),
+        format!("(median of {} runs)", options.runs.max(1)).bright_black()
+    );
+    println!();
 
-    ops_per_sec
-}
+    let results = riscfetch_bench::run(options);
+
+    // Deltas against the previous run on this board
+    let previous = save_history.then(|| history::record(&results)).flatten();
+    let kernel = info::get_kernel_info();
+    let delta = |current: f64, previous_score: fn(&history::Entry) -> f64| {
+        previous.as_ref().map_or_else(String::new, |entry| {
+            let previous_kernel = (entry.kernel != kernel).then_some(entry.kernel.as_str());
+            format!(
+                " {}",
+                history::format_delta(current, previous_score(entry), previous_kernel)
+            )
+        })
+    };
+
+    // Integer multiplication benchmark (M extension)
+    println!(
+        "{} {} {} {}{}",
+        "Integer Ops (M):".bright_cyan().bold(),
+        format!("{:.2}", results.integer_mops).bright_white(),
+        format!("± {:.2}", results.stats.integer_mops.stddev).bright_black(),
+        "MOPS".bright_white().dimmed(),
+        delta(results.integer_mops, |entry| entry.integer_mops)
+    );
 
-#[allow(clippy::cast_precision_loss)]
-fn benchmark_float_ops() -> f64 {
-    const ITERATIONS: u64 = 5_000_000;
-    let start = Instant::now();
+    // Floating-point benchmark (F/D extension)
+    println!(
+        "{} {} {} {}{}",
+        "Float Ops (F/D):".bright_green().bold(),
+        format!("{:.2}", results.float_mflops).bright_white(),
+        format!("± {:.2}", results.stats.float_mflops.stddev).bright_black(),
+        "MFLOPS".bright_white().dimmed(),
+        delta(results.float_mflops, |entry| entry.float_mflops)
+    );
 
-    let mut result: f64 = 1.0;
-    for i in 1..ITERATIONS {
-        let x = i as f64;
-        result = (result * x).sqrt() + x.sin();
+    // Half precision (Zfh / Zfhmin)
+    if let Some(fp16) = &results.fp16 {
+        print_fp16(fp16);
     }
 
-    let elapsed = start.elapsed();
-    let ops_per_sec = (ITERATIONS as f64 * 2.0 / elapsed.as_secs_f64()) / 1_000_000.0;
+    // Vector unit versus scalar loops (V)
+    if let Some(vector) = &results.vector {
+        print_vector("memcpy (V):", &vector.memcpy);
+        print_vector("memset (V):", &vector.memset);
+    }
+    if let Some(reason) = results.vector_skipped {
+        println!(
+            "{} {}",
+            "memcpy / memset / GEMM (V):".bright_blue().bold(),
+            format!("skipped ({reason})").bright_black()
+        );
+    }
+    if let Some(gemm) = &results.gemm {
+        println!(
+            "{} {} {} {}",
+            "int8 GEMM (V):".bright_blue().bold(),
+            format!("{:.2}", gemm.vector_gops).bright_white(),
+            "GOPS".bright_white().dimmed(),
+            format!(
+                "(scalar {:.2} GOPS, {:.1}x, {n}×{n})",
+                gemm.scalar_gops,
+                gemm.speedup(),
+                n = gemm.size
+            )
+            .bright_black()
+        );
+    }
 
-    // Use result to prevent optimization
-    std::hint::black_box(result);
+    // Memory bandwidth at each cache level; the delta is for the DRAM row
+    print_memory_sweep(
+        &results.memory_sweep,
+        &results
+            .memory_sweep
+            .last()
+            .map_or_else(String::new, |dram| {
+                delta(dram.mb_per_sec, |entry| entry.memory_mb_per_sec)
+            }),
+    );
 
-    ops_per_sec
-}
+    // Misaligned loads/stores (hardware or trapped and emulated)
+    if let Some(misaligned) = &results.misaligned {
+        println!(
+            "{} {} {}",
+            "Misaligned Access:".bright_magenta().bold(),
+            format!("{:.1}x", misaligned.penalty).bright_white(),
+            format!(
+                "slower ({:.1} vs {:.1} ns, {}{})",
+                misaligned.misaligned_ns,
+                misaligned.aligned_ns,
+                misaligned.measured,
+                misaligned
+                    .hwprobe
+                    .map_or_else(String::new, |class| format!(", hwprobe: {class}"))
+            )
+            .bright_black()
+        );
+    }
 
-#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
-fn benchmark_memory() -> f64 {
-    const SIZE: usize = 10_000_000;
-    let mut data = vec![0u8; SIZE];
+    // Kernel entry and context switches
+    if let Some(syscalls) = &results.syscalls {
+        println!(
+            "{} {} {}",
+            "Syscall (getpid):".bright_red().bold(),
+            format!("{:.1}", syscalls.getpid_ns).bright_white(),
+            "ns".bright_white().dimmed()
+        );
+        println!(
+            "{} {} {}",
+            "Pipe Round Trip:".bright_red().bold(),
+            format!("{:.2}", syscalls.pipe_round_trip_us).bright_white(),
+            "µs".bright_white().dimmed()
+        );
+    }
+
+    // User-mode counter CSRs (Zicntr)
+    if let Some(counters) = &results.counters {
+        print_counters(counters);
+    }
 
-    let start = Instant::now();
+    // Multi-threaded scaling (--threads)
+    if let Some(scaling) = &results.scaling {
+        println!(
+            "{}",
+            format!("Scaling ({} threads):", scaling.threads)
+                .bright_yellow()
+                .bold()
+        );
+        print_scaling("Integer Ops (M):", &scaling.integer_mops, "MOPS");
+        print_scaling("Float Ops (F/D):", &scaling.float_mflops, "MFLOPS");
+        print_scaling("Memory Bandwidth:", &scaling.memory_mb_per_sec, "MB/s");
+    }
+
+    // Each hart on its own (--per-hart)
+    if let Some(scores) = &results.per_hart {
+        print_per_hart(scores);
+    }
 
-    // Write benchmark
-    for (i, item) in data.iter_mut().enumerate().take(SIZE) {
-        *item = (i & 0xFF) as u8;
+    // Atomics across all harts (A extension, Zacas)
+    println!(
+        "{} {} {} {}",
+        "Atomic Counter (A):".bright_cyan().bold(),
+        format!("{:.2}", results.atomics.counter_mops).bright_white(),
+        "Mops/s".bright_white().dimmed(),
+        format!("({} threads)", results.atomics.threads).bright_black()
+    );
+    println!(
+        "{} {} {}",
+        "CAS Loop (LR/SC):".bright_cyan().bold(),
+        format!("{:.2}", results.atomics.cas_lrsc_mops).bright_white(),
+        "Mops/s".bright_white().dimmed()
+    );
+    if let Some(mops) = results.atomics.cas_zacas_mops {
+        println!(
+            "{} {} {}",
+            "CAS Loop (Zacas):".bright_cyan().bold(),
+            format!("{mops:.2}").bright_white(),
+            "Mops/s".bright_white().dimmed()
+        );
     }
 
-    // Read benchmark
-    let mut sum: u64 = 0;
-    for &byte in &data {
-        sum = sum.wrapping_add(u64::from(byte));
+    // Bit-manipulation benchmarks (B extension)
+    let detected: Vec<String> = results
+        .bitmanip
+        .detected
+        .iter()
+        .map(|ext| capitalize(ext))
+        .collect();
+    println!(
+        "{} {}",
+        "Bit Manipulation:".bright_blue().bold(),
+        if detected.is_empty() {
+            "(no Zba/Zbb/Zbs detected)".to_string()
+        } else {
+            format!("({} detected)", detected.join(" "))
+        }
+        .bright_black()
+    );
+    print_kernel("Address Gen (Zba):", &results.bitmanip.address_generation);
+    print_kernel("Popcount/CLZ (Zbb):", &results.bitmanip.bit_count);
+    print_kernel("Bit Set (Zbs):", &results.bitmanip.bit_set);
+
+    // Branch predictor: misprediction cost from random versus learnable outcomes
+    println!(
+        "{} {} {} {}",
+        "Branch Mispredict:".bright_blue().bold(),
+        format!("{:.1}", results.branch.mispredict_penalty_ns).bright_white(),
+        "ns".bright_white().dimmed(),
+        format!(
+            "(predictable {:.2} ns, random {:.2} ns per branch)",
+            results.branch.predictable_ns, results.branch.random_ns
+        )
+        .bright_black()
+    );
+
+    // Scalar crypto benchmarks (K extension)
+    if let Some(crypto) = &results.crypto {
+        if let Some(aes) = &crypto.aes128 {
+            print_comparison("AES-128 (Zkne):", aes);
+        }
+        if let Some(sha) = &crypto.sha256 {
+            print_comparison("SHA-256 (Zknh):", sha);
+        }
     }
 
-    let elapsed = start.elapsed();
-    let mb_per_sec = (SIZE as f64 * 2.0 / elapsed.as_secs_f64()) / 1_000_000.0;
+    // Energy over the whole run (hwmon power monitor)
+    if let Some(power) = &results.power {
+        print_power(power, &results);
+    }
 
-    // Use sum to prevent optimization
-    std::hint::black_box(sum);
+    // Relative to the boards in data/baselines.toml
+    print_baselines(&results, &info::get_board_info());
 
-    mb_per_sec
+    println!();
+    println!("{}", "Benchmarks complete!".bright_yellow().bold());
+    println!();
+    results
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_benchmark_integer_ops() {
-        let score = benchmark_integer_ops();
-        assert!(score > 0.0);
+    fn counters(zicntr: bool, accessible: [bool; 3]) -> counters::CountersResults {
+        counters::CountersResults {
+            zicntr,
+            perf_user_access: Some(1),
+            counters: ["cycle", "time", "instret"]
+                .into_iter()
+                .zip(accessible)
+                .map(|(csr, accessible)| counters::CounterLatency {
+                    csr,
+                    accessible,
+                    ns: accessible.then_some(5.0),
+                })
+                .collect(),
+        }
     }
 
     #[test]
-    fn test_benchmark_float_ops() {
-        let score = benchmark_float_ops();
-        assert!(score > 0.0);
+    fn test_counters_note() {
+        assert_eq!(counters_note(&counters(true, [true; 3])), None);
+        assert!(counters_note(&counters(false, [true; 3]))
+            .unwrap()
+            .contains("does not list Zicntr"));
+        assert_eq!(
+            counters_note(&counters(true, [false, true, false])).unwrap(),
+            "Zicntr is listed, but user-mode reads of cycle/instret trap \
+             (kernel.perf_user_access = 1; 2 allows direct reads)"
+        );
     }
 
     #[test]
-    fn test_benchmark_memory() {
-        let score = benchmark_memory();
-        assert!(score > 0.0);
+    fn test_capitalize() {
+        assert_eq!(capitalize("zbb"), "Zbb");
+        assert_eq!(capitalize(""), "");
     }
 }
@@ -0,0 +1,120 @@
+//! Reference scores of common boards (`data/baselines.toml`)
+//!
+//! After a benchmark run the integer, float and DRAM scores are compared
+//! with each board in the embedded file. The relative figure is the
+//! geometric mean of the three ratios, so no single benchmark dominates.
+
+use serde::Deserialize;
+
+/// Format version this build understands
+pub const VERSION: u32 = 1;
+
+const BASELINES: &str = include_str!("../../data/baselines.toml");
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct File {
+    version: u32,
+    board: Vec<Baseline>,
+}
+
+/// Reference scores of one board
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Baseline {
+    pub name: String,
+    /// Substring of the device-tree model identifying the board
+    pub model: String,
+    pub integer_mops: f64,
+    pub float_mflops: f64,
+    pub memory_mb_per_sec: f64,
+}
+
+impl Baseline {
+    /// How many times faster `scores` (integer, float, memory) are than this
+    /// board
+    #[must_use]
+    pub fn relative(&self, integer_mops: f64, float_mflops: f64, memory_mb_per_sec: f64) -> f64 {
+        let product = (integer_mops / self.integer_mops)
+            * (float_mflops / self.float_mflops)
+            * (memory_mb_per_sec / self.memory_mb_per_sec);
+        product.cbrt()
+    }
+
+    /// Whether `board` (the device-tree model) is this board
+    #[must_use]
+    pub fn matches(&self, board: &str) -> bool {
+        board.to_lowercase().contains(&self.model.to_lowercase())
+    }
+}
+
+fn parse(text: &str) -> Result<Vec<Baseline>, String> {
+    let file: File = toml::from_str(text).map_err(|e| e.to_string())?;
+    if file.version != VERSION {
+        return Err(format!(
+            "baseline data version {} (expected {VERSION})",
+            file.version
+        ));
+    }
+    Ok(file.board)
+}
+
+/// The boards shipped with this build
+#[must_use]
+pub fn baselines() -> Vec<Baseline> {
+    // The embedded file is checked by the tests below
+    parse(BASELINES).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_baselines_parse() {
+        let boards = parse(BASELINES).unwrap();
+        let names: Vec<_> = boards.iter().map(|b| b.name.as_str()).collect();
+        for name in [
+            "VisionFive 2",
+            "LicheePi 4A",
+            "Banana Pi F3",
+            "Milk-V Mars",
+            "Milk-V Jupiter",
+        ] {
+            assert!(names.contains(&name), "missing baseline for {name}");
+        }
+        assert!(boards
+            .iter()
+            .all(|b| b.integer_mops > 0.0 && b.float_mflops > 0.0 && b.memory_mb_per_sec > 0.0));
+    }
+
+    #[test]
+    fn test_parse_rejects_other_version() {
+        let err = parse("version = 2\nboard = []\n").unwrap_err();
+        assert!(err.contains("version 2"));
+    }
+
+    #[test]
+    fn test_relative() {
+        let board = Baseline {
+            name: "Board".to_string(),
+            model: "Board".to_string(),
+            integer_mops: 100.0,
+            float_mflops: 10.0,
+            memory_mb_per_sec: 1000.0,
+        };
+        assert!((board.relative(100.0, 10.0, 1000.0) - 1.0).abs() < 1e-9);
+        // 2x, 2x and 2x
+        assert!((board.relative(200.0, 20.0, 2000.0) - 2.0).abs() < 1e-9);
+        // 8x in one benchmark only
+        assert!((board.relative(800.0, 10.0, 1000.0) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_matches() {
+        let boards = baselines();
+        let vf2 = boards.iter().find(|b| b.name == "VisionFive 2").unwrap();
+        assert!(vf2.matches("StarFive VisionFive 2 v1.3B"));
+        assert!(!vf2.matches("Milk-V Mars"));
+    }
+}
@@ -0,0 +1,361 @@
+//! Side-by-side comparison with a saved run (`bench --compare other.json`)
+//!
+//! The saved file is the output of `bench --json`, `--json --benchmark` or a
+//! `bench --submit` document. Metrics are looked up by path, so a file from
+//! another board or an older riscfetch still compares on what both measured.
+
+use super::history::NOISE;
+use crate::query;
+use colored::Colorize;
+use serde_json::Value;
+
+/// A saved benchmark run
+#[derive(Debug)]
+pub struct Saved {
+    /// Board the run came from, when the file records it
+    pub board: Option<String>,
+    /// The `benchmarks` object
+    pub benchmarks: Value,
+}
+
+/// One compared metric
+struct Metric {
+    label: &'static str,
+    path: &'static str,
+    unit: &'static str,
+    lower_is_better: bool,
+}
+
+const fn higher(label: &'static str, path: &'static str, unit: &'static str) -> Metric {
+    Metric {
+        label,
+        path,
+        unit,
+        lower_is_better: false,
+    }
+}
+
+const fn lower(label: &'static str, path: &'static str, unit: &'static str) -> Metric {
+    Metric {
+        label,
+        path,
+        unit,
+        lower_is_better: true,
+    }
+}
+
+/// Metrics before the memory sweep rows
+const HEAD: &[Metric] = &[
+    higher("Integer Ops (M)", "integer_mops", "MOPS"),
+    higher("Float Ops (F/D)", "float_mflops", "MFLOPS"),
+    higher("Memory Bandwidth", "memory_mb_per_sec", "MB/s"),
+];
+
+/// Metrics after the memory sweep rows
+const TAIL: &[Metric] = &[
+    higher("Atomic Counter (A)", "atomics.counter_mops", "Mops/s"),
+    higher("CAS Loop (LR/SC)", "atomics.cas_lrsc_mops", "Mops/s"),
+    higher("CAS Loop (Zacas)", "atomics.cas_zacas_mops", "Mops/s"),
+    higher(
+        "Address Gen (Zba)",
+        "bitmanip.address_generation.bitmanip_mops",
+        "Mops",
+    ),
+    higher(
+        "Popcount/CLZ (Zbb)",
+        "bitmanip.bit_count.bitmanip_mops",
+        "Mops",
+    ),
+    higher("Bit Set (Zbs)", "bitmanip.bit_set.bitmanip_mops", "Mops"),
+    lower("Branch Mispredict", "branch.mispredict_penalty_ns", "ns"),
+    higher(
+        "AES-128 (Zkne)",
+        "crypto.aes128.hardware_mb_per_sec",
+        "MB/s",
+    ),
+    higher(
+        "SHA-256 (Zknh)",
+        "crypto.sha256.hardware_mb_per_sec",
+        "MB/s",
+    ),
+    higher("FP16 Convert", "fp16.convert_mops", "Mops"),
+    higher("FP16 FMA (Zfh)", "fp16.fma_mflops", "MFLOPS"),
+    higher("memcpy (V)", "vector.memcpy.vector_mb_per_sec", "MB/s"),
+    higher("memset (V)", "vector.memset.vector_mb_per_sec", "MB/s"),
+    higher("int8 GEMM (V)", "gemm.vector_gops", "GOPS"),
+    lower("Misaligned Access", "misaligned.penalty", "x"),
+    lower("Syscall (getpid)", "syscalls.getpid_ns", "ns"),
+    lower("Pipe Round Trip", "syscalls.pipe_round_trip_us", "µs"),
+    lower("CSR rdcycle", "counters.counters[0].ns", "ns"),
+    lower("CSR rdtime", "counters.counters[1].ns", "ns"),
+    lower("CSR rdinstret", "counters.counters[2].ns", "ns"),
+    higher(
+        "Integer Ops (scaled)",
+        "scaling.integer_mops.aggregate",
+        "MOPS",
+    ),
+    higher(
+        "Float Ops (scaled)",
+        "scaling.float_mflops.aggregate",
+        "MFLOPS",
+    ),
+];
+
+/// Memory sweep levels in display order
+const LEVELS: [&str; 4] = ["L1D", "L2", "L3", "DRAM"];
+
+/// One row of the comparison; a side is `None` when it did not measure it
+#[derive(Debug, PartialEq)]
+pub struct Row {
+    pub label: String,
+    pub unit: &'static str,
+    pub this: Option<f64>,
+    pub other: Option<f64>,
+    pub lower_is_better: bool,
+}
+
+impl Row {
+    /// Relative change of this run against the other (`0.1` = 10% higher)
+    #[must_use]
+    pub fn change(&self) -> Option<f64> {
+        match (self.this, self.other) {
+            (Some(this), Some(other)) if other > 0.0 => Some(this / other - 1.0),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a saved run
+pub fn parse(text: &str) -> Result<Saved, String> {
+    let value: Value =
+        serde_json::from_str(text).map_err(|e| format!("not a riscfetch JSON file: {e}"))?;
+    let board = value
+        .get("board")
+        .or_else(|| value.pointer("/hardware/board"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let benchmarks = match value.get("benchmarks") {
+        Some(benchmarks) => benchmarks.clone(),
+        None if value.get("integer_mops").is_some() => value,
+        None => {
+            return Err(
+                "no benchmark results (save them with `riscfetch bench --json`)".to_string(),
+            )
+        }
+    };
+    Ok(Saved { board, benchmarks })
+}
+
+fn number(benchmarks: &Value, path: &str) -> Option<f64> {
+    query::select(benchmarks, path).ok().and_then(Value::as_f64)
+}
+
+/// Bandwidth of the sweep point at `level`
+fn sweep(benchmarks: &Value, level: &str) -> Option<f64> {
+    benchmarks
+        .get("memory_sweep")?
+        .as_array()?
+        .iter()
+        .find(|point| point.get("level").and_then(Value::as_str) == Some(level))?
+        .get("mb_per_sec")?
+        .as_f64()
+}
+
+/// Rows measured by at least one side, in display order
+#[must_use]
+pub fn rows(this: &Value, other: &Value) -> Vec<Row> {
+    let metric = |m: &Metric| Row {
+        label: m.label.to_string(),
+        unit: m.unit,
+        this: number(this, m.path),
+        other: number(other, m.path),
+        lower_is_better: m.lower_is_better,
+    };
+    let levels = LEVELS.iter().map(|level| Row {
+        label: format!("Memory ({level})"),
+        unit: "MB/s",
+        this: sweep(this, level),
+        other: sweep(other, level),
+        lower_is_better: false,
+    });
+
+    HEAD.iter()
+        .map(metric)
+        .chain(levels)
+        .chain(TAIL.iter().map(metric))
+        .filter(|row| row.this.is_some() || row.other.is_some())
+        .collect()
+}
+
+/// `▲ +16.1%` in green when this run is better, `▼` in red when worse
+fn format_change(row: &Row) -> String {
+    let Some(change) = row.change() else {
+        return String::new();
+    };
+    let text = format!("{:+.1}%", change * 100.0);
+    let better = if row.lower_is_better {
+        change < -NOISE
+    } else {
+        change > NOISE
+    };
+    let worse = if row.lower_is_better {
+        change > NOISE
+    } else {
+        change < -NOISE
+    };
+    if better {
+        format!("▲ {text}").green().to_string()
+    } else if worse {
+        format!("▼ {text}").red().to_string()
+    } else {
+        format!("= {text}").bright_black().to_string()
+    }
+}
+
+fn value(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| format!("{v:.2}"))
+}
+
+/// Render the table; `this_label` / `other_label` head the columns
+#[must_use]
+pub fn render(rows: &[Row], this_label: &str, other_label: &str) -> Vec<String> {
+    let width = |header: &str, values: Vec<String>| {
+        values
+            .iter()
+            .map(|v| v.chars().count())
+            .chain(std::iter::once(header.chars().count()))
+            .max()
+            .unwrap_or(0)
+    };
+    let label_width = width("", rows.iter().map(|r| r.label.clone()).collect());
+    let this_width = width(this_label, rows.iter().map(|r| value(r.this)).collect());
+    let other_width = width(other_label, rows.iter().map(|r| value(r.other)).collect());
+    let unit_width = width("", rows.iter().map(|r| r.unit.to_string()).collect());
+
+    let mut lines = vec![format!(
+        "{:label_width$} {this_label:>this_width$}  {other_label:>other_width$}",
+        ""
+    )
+    .bright_cyan()
+    .bold()
+    .to_string()];
+    for row in rows {
+        lines.push(format!(
+            "{} {}  {:>other_width$}  {}  {}",
+            format!("{:label_width$}", row.label).bright_yellow().bold(),
+            format!("{:>this_width$}", value(row.this)).bright_white(),
+            value(row.other),
+            format!("{:unit_width$}", row.unit).bright_white().dimmed(),
+            format_change(row)
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+    use crate::report::tests::sample_bench;
+    use serde_json::json;
+
+    fn this() -> Value {
+        json!(sample_bench())
+    }
+
+    fn other() -> Value {
+        json!({
+            "integer_mops": 650.0,
+            "float_mflops": 120.5,
+            "memory_mb_per_sec": 4096.0,
+            "memory_sweep": [
+                { "level": "L2", "working_set_bytes": 262_144, "mb_per_sec": 10_000.0 },
+                { "level": "DRAM", "working_set_bytes": 67_108_864, "mb_per_sec": 3000.0 }
+            ],
+            "syscalls": { "getpid_ns": 250.0, "pipe_round_trip_us": 12.0 }
+        })
+    }
+
+    #[test]
+    fn test_parse_document_shapes() {
+        let bench = parse(r#"{"integer_mops": 1.0}"#).unwrap();
+        assert_eq!(bench.board, None);
+        assert_eq!(bench.benchmarks["integer_mops"], 1.0);
+
+        let json =
+            parse(r#"{"board": "VisionFive 2", "benchmarks": {"integer_mops": 2.0}}"#).unwrap();
+        assert_eq!(json.board.as_deref(), Some("VisionFive 2"));
+        assert_eq!(json.benchmarks["integer_mops"], 2.0);
+
+        let submitted =
+            parse(r#"{"hardware": {"board": "Mars"}, "benchmarks": {"integer_mops": 3.0}}"#)
+                .unwrap();
+        assert_eq!(submitted.board.as_deref(), Some("Mars"));
+
+        assert!(parse(r#"{"isa": "rv64gc"}"#).is_err());
+        assert!(parse("not json").is_err());
+    }
+
+    #[test]
+    fn test_rows_union_in_order() {
+        let rows = rows(&this(), &other());
+        let labels: Vec<&str> = rows.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(
+            &labels[..5],
+            [
+                "Integer Ops (M)",
+                "Float Ops (F/D)",
+                "Memory Bandwidth",
+                "Memory (L2)",
+                "Memory (DRAM)"
+            ]
+        );
+        let dram = rows.iter().find(|r| r.label == "Memory (DRAM)").unwrap();
+        assert_eq!((dram.this, dram.other), (None, Some(3000.0)));
+        // Measured by neither side
+        assert!(!labels.contains(&"AES-128 (Zkne)"));
+        assert!(labels.contains(&"Syscall (getpid)"));
+    }
+
+    #[test]
+    fn test_change_direction() {
+        let rows = rows(&this(), &other());
+        let integer = &rows[0];
+        assert!((integer.change().unwrap() - 0.25).abs() < 1e-9);
+        assert_eq!(strip_ansi(&format_change(integer)), "▲ +25.0%");
+        assert_eq!(strip_ansi(&format_change(&rows[1])), "= -0.4%");
+        assert_eq!(strip_ansi(&format_change(&rows[2])), "▼ -50.0%");
+
+        let faster = Row {
+            label: "Syscall (getpid)".to_string(),
+            unit: "ns",
+            this: Some(200.0),
+            other: Some(250.0),
+            lower_is_better: true,
+        };
+        assert_eq!(strip_ansi(&format_change(&faster)), "▲ -20.0%");
+    }
+
+    #[test]
+    fn test_render_columns_aligned() {
+        let lines: Vec<String> = render(&rows(&this(), &other()), "this board", "lpi4a.json")
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        assert!(lines[0].ends_with("this board  lpi4a.json"));
+        let integer = lines
+            .iter()
+            .find(|l| l.starts_with("Integer Ops (M)"))
+            .unwrap();
+        let dram = lines
+            .iter()
+            .find(|l| l.starts_with("Memory (DRAM)"))
+            .unwrap();
+        assert!(integer.contains("812.50      650.00  MOPS"));
+        assert_eq!(
+            integer.find("650.00").unwrap() + 6,
+            dram.find("3000.00").unwrap() + 7
+        );
+        assert!(dram.contains(" -  "));
+    }
+}
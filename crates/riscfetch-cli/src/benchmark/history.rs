@@ -0,0 +1,183 @@
+//! Benchmark history (`~/.local/share/riscfetch/benchmarks/`)
+//!
+//! Every `--benchmark` run appends its headline scores, with the kernel
+//! release, to a JSON-lines file per board. The next run prints deltas
+//! against the previous entry, so kernel upgrades and governor changes
+//! show up as "▲ +3% vs last run".
+
+use colored::Colorize;
+use riscfetch_bench::BenchResult;
+use riscfetch_core as info;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Changes within this fraction count as unchanged
+pub const NOISE: f64 = 0.01;
+
+/// One run in the history file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub kernel: String,
+    pub integer_mops: f64,
+    pub float_mflops: f64,
+    /// DRAM point of the memory sweep
+    pub memory_mb_per_sec: f64,
+}
+
+impl Entry {
+    #[must_use]
+    pub fn new(results: &BenchResult, kernel: String) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            kernel,
+            integer_mops: results.integer_mops,
+            float_mflops: results.float_mflops,
+            memory_mb_per_sec: results.dram_mb_per_sec(),
+        }
+    }
+}
+
+/// `$XDG_DATA_HOME/riscfetch/benchmarks`, then `~/.local/share/riscfetch/benchmarks`
+#[must_use]
+pub fn history_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .map(|dir| dir.join("riscfetch/benchmarks"))
+}
+
+/// `StarFive VisionFive 2 v1.3B` -> `starfive-visionfive-2-v1-3b.jsonl`
+#[must_use]
+pub fn file_name(board: &str) -> String {
+    let mut slug = String::new();
+    for ch in board.chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    format!("{}.jsonl", if slug.is_empty() { "unknown" } else { slug })
+}
+
+/// Last readable entry of a history file
+#[must_use]
+pub fn last_entry(path: &Path) -> Option<Entry> {
+    fs::read_to_string(path)
+        .ok()?
+        .lines()
+        .rev()
+        .find_map(|line| serde_json::from_str(line).ok())
+}
+
+/// Append `entry` as one JSON line, creating the directory if needed
+pub fn append(path: &Path, entry: &Entry) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Save this run and return the previous one for the board, if any.
+/// History problems only produce a warning; the benchmark output stays.
+pub fn record(results: &BenchResult) -> Option<Entry> {
+    let path = history_dir()?.join(file_name(&info::get_board_info()));
+    let previous = last_entry(&path);
+    if let Err(e) = append(&path, &Entry::new(results, info::get_kernel_info())) {
+        eprintln!(
+            "{} cannot save benchmark history to {}: {e}",
+            "Warning:".yellow().bold(),
+            path.display()
+        );
+    }
+    previous
+}
+
+/// `(▲ +3% vs last run)`, naming the previous kernel when it differs
+#[must_use]
+pub fn format_delta(current: f64, previous: f64, previous_kernel: Option<&str>) -> String {
+    let change = current / previous - 1.0;
+    let against = previous_kernel.map_or_else(
+        || "vs last run".to_string(),
+        |kernel| format!("vs last run on {kernel}"),
+    );
+    if change > NOISE {
+        format!("(▲ +{:.0}% {against})", change * 100.0)
+            .green()
+            .to_string()
+    } else if change < -NOISE {
+        format!("(▼ {:.0}% {against})", change * 100.0)
+            .red()
+            .to_string()
+    } else {
+        format!("(= {against})").bright_black().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+
+    fn entry(timestamp: u64, integer_mops: f64) -> Entry {
+        Entry {
+            timestamp,
+            kernel: "6.6.20".to_string(),
+            integer_mops,
+            float_mflops: 120.0,
+            memory_mb_per_sec: 2048.0,
+        }
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            file_name("StarFive VisionFive 2 v1.3B"),
+            "starfive-visionfive-2-v1-3b.jsonl"
+        );
+        assert_eq!(file_name("  Milk-V  Jupiter "), "milk-v-jupiter.jsonl");
+        assert_eq!(file_name(""), "unknown.jsonl");
+    }
+
+    #[test]
+    fn test_append_and_last_entry() {
+        let path = std::env::temp_dir()
+            .join(format!("riscfetch-history-{}", std::process::id()))
+            .join("board.jsonl");
+        let _ = fs::remove_file(&path);
+        assert_eq!(last_entry(&path), None);
+        append(&path, &entry(1, 800.0)).unwrap();
+        append(&path, &entry(2, 812.0)).unwrap();
+        assert_eq!(last_entry(&path), Some(entry(2, 812.0)));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_format_delta() {
+        assert_eq!(
+            strip_ansi(&format_delta(824.0, 800.0, None)),
+            "(▲ +3% vs last run)"
+        );
+        assert_eq!(
+            strip_ansi(&format_delta(760.0, 800.0, Some("6.1.0"))),
+            "(▼ -5% vs last run on 6.1.0)"
+        );
+        assert_eq!(
+            strip_ansi(&format_delta(801.0, 800.0, None)),
+            "(= vs last run)"
+        );
+    }
+}
@@ -0,0 +1,595 @@
+//! Extension browser (`riscfetch extensions`, `riscfetch explain`)
+//!
+//! Lists every known extension with its category and whether this system
+//! supports it. `--interactive` opens a full-screen list with incremental
+//! search, a category filter and a description pane (requires the `tui`
+//! cargo feature, enabled by default). `explain` shows a single extension,
+//! with `--long` its extended documentation from the core.
+
+use crate::display;
+use colored::Colorize;
+use riscfetch_core as info;
+
+/// Category id used for the single-letter extensions
+const STANDARD_CATEGORY: &str = "standard";
+
+/// Rows used by the description pane
+const DETAIL_ROWS: usize = 3;
+
+/// Rows used by the header, the two separators and the key help line
+const CHROME_ROWS: usize = 4;
+
+/// One known extension with its support status
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub name: String,
+    pub description: String,
+    /// Category id (`standard` or an id from the core Z/S category tables)
+    pub category: String,
+    pub category_name: &'static str,
+    pub supported: bool,
+}
+
+/// Every known extension (standard, Z, S) with support status for `isa`
+#[must_use]
+pub fn entries(isa: &str) -> Vec<Entry> {
+    let standard = info::get_all_standard_extensions_with_status(isa)
+        .into_iter()
+        .map(|(name, description, supported)| Entry {
+            name,
+            description,
+            category: STANDARD_CATEGORY.to_string(),
+            category_name: category_name(STANDARD_CATEGORY),
+            supported,
+        });
+    let z = info::get_all_z_extensions_with_status(isa)
+        .into_iter()
+        .map(|e| Entry {
+            category_name: info::get_z_category_name(&e.category),
+            name: e.name,
+            description: e.description,
+            category: e.category,
+            supported: e.supported,
+        });
+    let s = info::get_all_s_extensions_with_status(isa)
+        .into_iter()
+        .map(|e| Entry {
+            category_name: info::get_s_category_name(&e.category),
+            name: e.name,
+            description: e.description,
+            category: e.category,
+            supported: e.supported,
+        });
+    standard.chain(z).chain(s).collect()
+}
+
+/// Category ids accepted by `--category`, in display order
+#[must_use]
+pub fn category_ids() -> Vec<&'static str> {
+    std::iter::once(STANDARD_CATEGORY)
+        .chain(info::Z_CATEGORY_NAMES.iter().map(|(id, _)| *id))
+        .chain(info::S_CATEGORY_NAMES.iter().map(|(id, _)| *id))
+        .collect()
+}
+
+/// Display name of a category id
+fn category_name(id: &str) -> &'static str {
+    if id == STANDARD_CATEGORY {
+        "Standard"
+    } else if info::Z_CATEGORY_NAMES.iter().any(|(z, _)| *z == id) {
+        info::get_z_category_name(id)
+    } else {
+        info::get_s_category_name(id)
+    }
+}
+
+/// Entries in `category` (all when None) whose name or description contains
+/// `query`, case-insensitively
+#[must_use]
+pub fn filter<'a>(entries: &'a [Entry], query: &str, category: Option<&str>) -> Vec<&'a Entry> {
+    let query = query.to_lowercase();
+    entries
+        .iter()
+        .filter(|e| category.is_none_or(|c| e.category == c))
+        .filter(|e| {
+            query.is_empty()
+                || e.name.to_lowercase().contains(&query)
+                || e.description.to_lowercase().contains(&query)
+        })
+        .collect()
+}
+
+/// One list row: mark, name, category and description
+fn format_row(entry: &Entry, selected: bool) -> String {
+    let mark = if entry.supported {
+        "✓".bright_green().bold()
+    } else {
+        "✗".bright_black()
+    };
+    let name = format!("{:<12}", entry.name);
+    let name = match (selected, entry.supported) {
+        (true, _) => name.reversed().bold(),
+        (false, true) => name.bright_green(),
+        (false, false) => name.bright_black(),
+    };
+    let category = format!("{:<18}", entry.category_name).cyan();
+    let description = if entry.supported {
+        entry.description.normal()
+    } else {
+        entry.description.bright_black()
+    };
+    format!(" {mark} {name} {category} {description}")
+}
+
+/// Non-interactive listing
+#[must_use]
+pub fn list_lines(entries: &[Entry], query: &str, category: Option<&str>) -> Vec<String> {
+    filter(entries, query, category)
+        .into_iter()
+        .map(|e| format_row(e, false))
+        .collect()
+}
+
+/// Width `explain` wraps the extended documentation to
+pub const EXPLAIN_WIDTH: usize = 78;
+
+/// The entry named `name`, case-insensitively (`zba`, `Zba`, `v`)
+#[must_use]
+pub fn find<'a>(entries: &'a [Entry], name: &str) -> Option<&'a Entry> {
+    let name = name.trim();
+    entries.iter().find(|e| e.name.eq_ignore_ascii_case(name))
+}
+
+/// Greedy word wrap of one paragraph to `width` columns; longer words get a
+/// line of their own
+fn wrap(paragraph: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in paragraph.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// `explain` view: name, category, support status and description; with
+/// `long` the extended documentation wrapped to `width`, paragraphs
+/// separated by blank lines
+#[must_use]
+pub fn explain_lines(entry: &Entry, long: bool, width: usize) -> Vec<String> {
+    let doc = info::extension_doc(&entry.name);
+    let mut lines = vec![
+        format!(
+            "{} {} {}",
+            entry.name.bright_cyan().bold(),
+            "—".bright_black(),
+            entry.description.bold()
+        ),
+        format!("{} {}", "Category: ".bright_yellow(), entry.category_name),
+        format!(
+            "{} {}",
+            "Supported:".bright_yellow(),
+            if entry.supported {
+                "yes".bright_green()
+            } else {
+                "no".bright_black()
+            }
+        ),
+    ];
+    match (long, doc) {
+        (true, Some(doc)) => {
+            for paragraph in doc.split("\n\n") {
+                lines.push(String::new());
+                lines.extend(wrap(paragraph, width));
+            }
+        }
+        (true, None) => {
+            lines.push(String::new());
+            lines.push(
+                "No extended documentation for this extension yet"
+                    .bright_black()
+                    .to_string(),
+            );
+        }
+        (false, Some(_)) => {
+            lines.push(String::new());
+            lines.push(
+                format!(
+                    "More: riscfetch explain --long {}",
+                    entry.name.to_lowercase()
+                )
+                .bright_black()
+                .to_string(),
+            );
+        }
+        (false, None) => {}
+    }
+    lines
+}
+
+/// Input to the interactive browser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Backspace,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    /// Next category
+    Tab,
+    /// Previous category
+    BackTab,
+    /// Clear the search, or close when it is already empty
+    Esc,
+    Quit,
+}
+
+/// State of the interactive browser
+pub struct Browser {
+    entries: Vec<Entry>,
+    categories: Vec<&'static str>,
+    query: String,
+    /// Index into `categories`, None for all
+    category: Option<usize>,
+    selected: usize,
+    offset: usize,
+    /// List rows in the last render, used for PageUp / PageDown
+    page: usize,
+}
+
+impl Browser {
+    #[must_use]
+    pub fn new(entries: Vec<Entry>, query: &str, category: Option<&str>) -> Self {
+        let categories = category_ids();
+        let category = category.and_then(|c| categories.iter().position(|id| *id == c));
+        Self {
+            entries,
+            categories,
+            query: query.to_string(),
+            category,
+            selected: 0,
+            offset: 0,
+            page: 1,
+        }
+    }
+
+    fn category_id(&self) -> Option<&'static str> {
+        self.category.map(|i| self.categories[i])
+    }
+
+    #[must_use]
+    pub fn visible(&self) -> Vec<&Entry> {
+        filter(&self.entries, &self.query, self.category_id())
+    }
+
+    fn reset_selection(&mut self) {
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    /// Cycle the category filter: all, standard, Z categories, S categories
+    fn cycle_category(&mut self, forward: bool) {
+        let count = self.categories.len();
+        self.category = match (self.category, forward) {
+            (None, true) => Some(0),
+            (None, false) => Some(count - 1),
+            (Some(i), true) if i + 1 < count => Some(i + 1),
+            (Some(i), false) if i > 0 => Some(i - 1),
+            _ => None,
+        };
+        self.reset_selection();
+    }
+
+    /// Apply a key; returns false when the browser should close
+    pub fn handle(&mut self, key: Key) -> bool {
+        let last = self.visible().len().saturating_sub(1);
+        match key {
+            Key::Char(c) => {
+                self.query.push(c);
+                self.reset_selection();
+            }
+            Key::Backspace => {
+                self.query.pop();
+                self.reset_selection();
+            }
+            Key::Up => self.selected = self.selected.saturating_sub(1),
+            Key::Down => self.selected = (self.selected + 1).min(last),
+            Key::PageUp => self.selected = self.selected.saturating_sub(self.page),
+            Key::PageDown => self.selected = (self.selected + self.page).min(last),
+            Key::Home => self.selected = 0,
+            Key::End => self.selected = last,
+            Key::Tab => self.cycle_category(true),
+            Key::BackTab => self.cycle_category(false),
+            Key::Esc if !self.query.is_empty() => {
+                self.query.clear();
+                self.reset_selection();
+            }
+            Key::Esc | Key::Quit => return false,
+        }
+        true
+    }
+
+    /// Lay out the screen as `height` lines at most `width` columns wide
+    pub fn render(&mut self, width: usize, height: usize) -> Vec<String> {
+        let rows = height.saturating_sub(CHROME_ROWS + DETAIL_ROWS).max(1);
+        self.page = rows;
+
+        let visible = filter(&self.entries, &self.query, self.category_id());
+        self.selected = self.selected.min(visible.len().saturating_sub(1));
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + rows {
+            self.offset = self.selected + 1 - rows;
+        }
+
+        let category = self.category_id().map_or("All", category_name);
+        let supported = visible.iter().filter(|e| e.supported).count();
+        let rule = "─".repeat(width).bright_black().to_string();
+
+        let mut lines = vec![
+            format!(
+                "{} {}_  {} {}  {}",
+                "Search:".bright_yellow().bold(),
+                self.query,
+                "Category:".bright_yellow().bold(),
+                category.cyan(),
+                format!("{} shown, {supported} supported", visible.len()).bright_black()
+            ),
+            rule.clone(),
+        ];
+        for i in self.offset..self.offset + rows {
+            lines.push(
+                visible
+                    .get(i)
+                    .map(|e| format_row(e, i == self.selected))
+                    .unwrap_or_default(),
+            );
+        }
+        lines.push(rule);
+
+        match visible.get(self.selected) {
+            Some(e) => {
+                lines.push(format!(
+                    "{} {}",
+                    e.name.bright_cyan().bold(),
+                    format!("({})", e.category_name).bright_black()
+                ));
+                lines.push(if e.supported {
+                    "Supported on this system".bright_green().to_string()
+                } else {
+                    "Not supported on this system".bright_black().to_string()
+                });
+                lines.push(e.description.clone());
+            }
+            None => {
+                lines.push("No matching extensions".bright_black().to_string());
+                lines.extend([String::new(), String::new()]);
+            }
+        }
+        lines.push(
+            "type to search · ↑↓ PgUp PgDn move · Tab category · Esc clear/quit"
+                .bright_black()
+                .to_string(),
+        );
+
+        lines
+            .iter()
+            .take(height.max(1))
+            .map(|line| display::elide_ansi(line, width))
+            .collect()
+    }
+}
+
+/// Run the full-screen browser until Esc / Ctrl-C
+#[cfg(feature = "tui")]
+pub fn run(browser: &mut Browser) -> std::io::Result<()> {
+    use crossterm::{cursor, execute, terminal};
+
+    let mut stdout = std::io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = event_loop(browser, &mut stdout);
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+#[cfg(feature = "tui")]
+fn event_loop(browser: &mut Browser, stdout: &mut std::io::Stdout) -> std::io::Result<()> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use crossterm::{cursor, queue, terminal};
+    use std::io::Write;
+
+    loop {
+        let (width, height) = terminal::size()?;
+        let lines = browser.render(usize::from(width), usize::from(height));
+        queue!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(terminal::ClearType::All)
+        )?;
+        write!(stdout, "{}", lines.join("\r\n"))?;
+        stdout.flush()?;
+
+        let Event::Key(event) = event::read()? else {
+            continue;
+        };
+        if event.kind != KeyEventKind::Press {
+            continue;
+        }
+        let key = match event.code {
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => Key::Quit,
+            KeyCode::Char(c) => Key::Char(c),
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Up => Key::Up,
+            KeyCode::Down => Key::Down,
+            KeyCode::PageUp => Key::PageUp,
+            KeyCode::PageDown => Key::PageDown,
+            KeyCode::Home => Key::Home,
+            KeyCode::End => Key::End,
+            KeyCode::Tab => Key::Tab,
+            KeyCode::BackTab => Key::BackTab,
+            KeyCode::Esc => Key::Esc,
+            _ => continue,
+        };
+        if !browser.handle(key) {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISA: &str = "rv64imafdc_zicsr_zba_zbb_sstc";
+
+    fn names(entries: &[&Entry]) -> Vec<String> {
+        entries.iter().map(|e| e.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_entries_cover_all_tables() {
+        let all = entries(ISA);
+        assert_eq!(
+            all.len(),
+            info::STANDARD_EXTENSIONS.len() + info::Z_EXTENSIONS.len() + info::S_EXTENSIONS.len()
+        );
+        let zba = all.iter().find(|e| e.name == "Zba").unwrap();
+        assert!(zba.supported);
+        assert_eq!(zba.category, "bit");
+        assert_eq!(zba.category_name, "Bit Manipulation");
+        assert!(!all.iter().find(|e| e.name == "V").unwrap().supported);
+    }
+
+    #[test]
+    fn test_every_category_id_is_known() {
+        let ids = category_ids();
+        for entry in entries(ISA) {
+            assert!(ids.contains(&entry.category.as_str()), "{}", entry.category);
+        }
+    }
+
+    #[test]
+    fn test_filter_query_and_category() {
+        let all = entries(ISA);
+        let bit = filter(&all, "", Some("bit"));
+        assert!(names(&bit).contains(&"Zba".to_string()));
+        assert!(bit.iter().all(|e| e.category == "bit"));
+
+        // Matches the description too, case-insensitively
+        let hits = filter(&all, "HYPERVISOR", None);
+        assert!(names(&hits).contains(&"H".to_string()));
+        assert!(filter(&all, "no such extension", None).is_empty());
+    }
+
+    #[test]
+    fn test_find_and_wrap() {
+        let all = entries(ISA);
+        assert_eq!(find(&all, "ZBA").unwrap().name, "Zba");
+        assert_eq!(find(&all, " v ").unwrap().name, "V");
+        assert!(find(&all, "zbx").is_none());
+
+        assert_eq!(wrap("one two three four", 9), ["one two", "three", "four"]);
+        assert_eq!(wrap("averyverylongword x", 4), ["averyverylongword", "x"]);
+        assert!(wrap("", 10).is_empty());
+    }
+
+    #[test]
+    fn test_explain_lines() {
+        let plain = |lines: Vec<String>| -> Vec<String> {
+            lines.iter().map(|l| display::strip_ansi(l)).collect()
+        };
+        let all = entries(ISA);
+        let zba = find(&all, "zba").unwrap();
+
+        let short = plain(explain_lines(zba, false, EXPLAIN_WIDTH));
+        assert_eq!(short[0], "Zba — Address Generation");
+        assert_eq!(short[2], "Supported: yes");
+        assert_eq!(short.last().unwrap(), "More: riscfetch explain --long zba");
+
+        let long = plain(explain_lines(zba, true, EXPLAIN_WIDTH));
+        assert!(long.len() > short.len());
+        assert!(long
+            .iter()
+            .all(|line| line.chars().count() <= EXPLAIN_WIDTH));
+        assert!(long.iter().any(|line| line.contains("sh1add")));
+        assert_eq!(long.iter().filter(|line| line.is_empty()).count(), 2);
+
+        let zbs = find(&all, "zbs").unwrap();
+        assert_eq!(explain_lines(zbs, false, EXPLAIN_WIDTH).len(), 3);
+        assert!(plain(explain_lines(zbs, true, EXPLAIN_WIDTH))
+            .last()
+            .unwrap()
+            .starts_with("No extended documentation"));
+    }
+
+    #[test]
+    fn test_browser_incremental_search() {
+        let mut browser = Browser::new(entries(ISA), "", None);
+        let total = browser.visible().len();
+        for c in "zbb".chars() {
+            browser.handle(Key::Char(c));
+        }
+        assert!(names(&browser.visible()).contains(&"Zbb".to_string()));
+        assert!(browser.visible().len() < total);
+
+        browser.handle(Key::Backspace);
+        assert_eq!(browser.query, "zb");
+        // Esc clears the search first, then closes
+        assert!(browser.handle(Key::Esc));
+        assert_eq!(browser.visible().len(), total);
+        assert!(!browser.handle(Key::Esc));
+    }
+
+    #[test]
+    fn test_browser_category_cycle() {
+        let mut browser = Browser::new(entries(ISA), "", None);
+        browser.handle(Key::Tab);
+        assert!(browser.visible().iter().all(|e| e.category == "standard"));
+        browser.handle(Key::BackTab);
+        browser.handle(Key::BackTab);
+        assert_eq!(browser.category_id(), Some("user"));
+        browser.handle(Key::Tab);
+        assert_eq!(browser.category_id(), None);
+    }
+
+    #[test]
+    fn test_browser_navigation_clamped() {
+        let mut browser = Browser::new(entries(ISA), "", Some("standard"));
+        let last = browser.visible().len() - 1;
+        browser.handle(Key::Up);
+        assert_eq!(browser.selected, 0);
+        browser.handle(Key::End);
+        browser.handle(Key::Down);
+        assert_eq!(browser.selected, last);
+    }
+
+    #[test]
+    fn test_render_scrolls_to_selection() {
+        let mut browser = Browser::new(entries(ISA), "", None);
+        browser.handle(Key::End);
+        let lines: Vec<String> = browser
+            .render(80, 12)
+            .iter()
+            .map(|l| display::strip_ansi(l))
+            .collect();
+        assert_eq!(lines.len(), 12);
+        assert!(lines.iter().all(|l| display::visible_width(l) <= 80));
+        // Last entry is both in the list and in the description pane
+        let last = browser.visible().last().unwrap().name.clone();
+        assert!(lines[2..7].iter().any(|l| l.contains(&last)));
+        assert!(lines[8].starts_with(&last));
+    }
+}
@@ -0,0 +1,184 @@
+//! Recommended C/C++ build configuration (`riscfetch buildflags`)
+//!
+//! Target triple, `-march` / `-mabi` for the hardware extensions, `-mtune`
+//! for a recognized core, and the same as a CMake toolchain file. `-mtune`
+//! is used rather than `-mcpu` because `-mcpu` would replace the extension
+//! list of `-march` with the core's defaults.
+
+use crate::toolchain::hardware_extensions;
+use riscfetch_core::Core;
+use serde::Serialize;
+
+/// Build configuration for one machine
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct BuildFlags {
+    /// GNU target triple, e.g. `riscv64-linux-gnu`
+    pub triple: String,
+    /// Rust target, e.g. `riscv64gc-unknown-linux-gnu`
+    pub rust_target: String,
+    /// `-march` value with every user-level hardware extension
+    pub march: String,
+    /// `-mabi` value: the widest hardware floating point the ABI can use
+    pub mabi: String,
+    /// Recognized core
+    pub core: Option<&'static str>,
+    /// `-mtune` value for GCC
+    pub gcc_tune: Option<&'static str>,
+    /// `-mtune` value for Clang
+    pub llvm_tune: Option<&'static str>,
+}
+
+impl BuildFlags {
+    #[must_use]
+    pub fn new(isa: &str, core: Option<&'static Core>) -> Self {
+        let xlen = if isa.to_lowercase().starts_with("rv32") {
+            32
+        } else {
+            64
+        };
+        let extensions = hardware_extensions(isa);
+        let has = |ext: &str| extensions.iter().any(|e| e == ext);
+        let letters: String = extensions
+            .iter()
+            .filter(|e| e.len() == 1)
+            .map(String::as_str)
+            .collect();
+        let mut march = format!("rv{xlen}i{letters}");
+        for ext in extensions.iter().filter(|e| e.len() > 1) {
+            march.push('_');
+            march.push_str(ext);
+        }
+        let float = if has("d") {
+            "d"
+        } else if has("f") {
+            "f"
+        } else {
+            ""
+        };
+        let mabi = if xlen == 32 {
+            format!("ilp32{float}")
+        } else {
+            format!("lp64{float}")
+        };
+        Self {
+            triple: format!("riscv{xlen}-linux-gnu"),
+            rust_target: format!("riscv{xlen}gc-unknown-linux-gnu"),
+            march,
+            mabi,
+            core: core.map(|core| core.name),
+            gcc_tune: core.and_then(|core| core.gcc_cpu),
+            llvm_tune: core.and_then(|core| core.llvm_cpu),
+        }
+    }
+
+    fn flags(&self, tune: Option<&str>) -> String {
+        let mut flags = format!("-march={} -mabi={}", self.march, self.mabi);
+        if let Some(tune) = tune {
+            flags.push_str(&format!(" -mtune={tune}"));
+        }
+        flags
+    }
+
+    /// Flags for gcc
+    #[must_use]
+    pub fn gcc_flags(&self) -> String {
+        self.flags(self.gcc_tune)
+    }
+
+    /// Flags for clang, including the target
+    #[must_use]
+    pub fn clang_flags(&self) -> String {
+        format!("--target={} {}", self.triple, self.flags(self.llvm_tune))
+    }
+
+    /// CMake toolchain file using the cross gcc
+    #[must_use]
+    pub fn cmake(&self) -> String {
+        let processor = self.triple.split('-').next().unwrap_or_default();
+        let flags = self.gcc_flags();
+        format!(
+            "set(CMAKE_SYSTEM_NAME Linux)\n\
+             set(CMAKE_SYSTEM_PROCESSOR {processor})\n\
+             set(CMAKE_C_COMPILER {triple}-gcc)\n\
+             set(CMAKE_CXX_COMPILER {triple}-g++)\n\
+             set(CMAKE_C_FLAGS_INIT \"{flags}\")\n\
+             set(CMAKE_CXX_FLAGS_INIT \"{flags}\")\n",
+            triple = self.triple
+        )
+    }
+
+    /// Human-readable summary followed by the CMake snippet
+    #[must_use]
+    pub fn render(&self) -> String {
+        let tune = match (self.gcc_tune, self.llvm_tune) {
+            (Some(gcc), Some(llvm)) if gcc == llvm => gcc.to_string(),
+            (Some(gcc), Some(llvm)) => format!("{gcc} (GCC), {llvm} (LLVM)"),
+            (Some(gcc), None) => format!("{gcc} (GCC only)"),
+            (None, Some(llvm)) => format!("{llvm} (LLVM only)"),
+            (None, None) => "none".to_string(),
+        };
+        let mut lines = vec![
+            format!("Target triple:  {}", self.triple),
+            format!("Rust target:    {}", self.rust_target),
+            format!("-march:         {}", self.march),
+            format!("-mabi:          {}", self.mabi),
+            format!("Core:           {}", self.core.unwrap_or("not recognized")),
+            format!("-mtune:         {tune}"),
+            String::new(),
+            format!("GCC:    {}", self.gcc_flags()),
+            format!("Clang:  {}", self.clang_flags()),
+            String::new(),
+            "CMake toolchain file:".to_string(),
+        ];
+        lines.extend(self.cmake().lines().map(|line| format!("  {line}")));
+        lines.iter().map(|line| format!("{line}\n")).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riscfetch_core::{identify_core, HardwareIds};
+
+    const ISA: &str = "rv64imafdcv_zicsr_zifencei_zba_zbb_sstc_xtheadvector";
+
+    #[test]
+    fn test_build_flags() {
+        let u74 = identify_core(&["sifive,u74-mc"], &HardwareIds::default());
+        let flags = BuildFlags::new(ISA, u74);
+        assert_eq!(flags.triple, "riscv64-linux-gnu");
+        assert_eq!(flags.march, "rv64imafdcv_zicsr_zifencei_zba_zbb");
+        assert_eq!(flags.mabi, "lp64d");
+        assert_eq!(
+            flags.gcc_flags(),
+            "-march=rv64imafdcv_zicsr_zifencei_zba_zbb -mabi=lp64d -mtune=sifive-u74"
+        );
+        assert!(flags
+            .clang_flags()
+            .starts_with("--target=riscv64-linux-gnu -march="));
+
+        let flags = BuildFlags::new("rv32imac", None);
+        assert_eq!(flags.march, "rv32imac");
+        assert_eq!(flags.mabi, "ilp32");
+        assert_eq!(flags.rust_target, "riscv32gc-unknown-linux-gnu");
+        assert_eq!(flags.gcc_flags(), "-march=rv32imac -mabi=ilp32");
+    }
+
+    #[test]
+    fn test_cmake_and_render() {
+        let flags = BuildFlags::new("rv64gc", None);
+        let cmake = flags.cmake();
+        assert!(cmake.contains("set(CMAKE_SYSTEM_PROCESSOR riscv64)\n"));
+        assert!(cmake.contains("set(CMAKE_C_COMPILER riscv64-linux-gnu-gcc)\n"));
+        assert!(cmake.contains(
+            "set(CMAKE_C_FLAGS_INIT \"-march=rv64imafdc_zicsr_zifencei -mabi=lp64d\")\n"
+        ));
+
+        let text = flags.render();
+        assert!(text.contains("Core:           not recognized\n-mtune:         none\n"));
+        assert!(text.ends_with(&format!(
+            "  set(CMAKE_CXX_FLAGS_INIT \"{}\")\n",
+            flags.gcc_flags()
+        )));
+    }
+}
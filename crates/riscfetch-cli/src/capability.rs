@@ -0,0 +1,150 @@
+//! Capability checks for scripts and CI (`--check`, `--require`)
+//!
+//! Each name is an extension (`zvbb`, `v`) or a profile (`rva23`); a
+//! profile passes only if all of its required extensions are present.
+
+use colored::Colorize;
+use riscfetch_core as info;
+
+/// Result of checking one name against the ISA string
+#[derive(Debug, PartialEq, Eq)]
+pub struct Outcome {
+    /// Extension as given, or the canonical profile name
+    pub name: String,
+    pub profile: bool,
+    /// Required extensions that are absent (the extension itself for a plain name)
+    pub missing: Vec<String>,
+}
+
+impl Outcome {
+    #[must_use]
+    pub fn supported(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Check every name, in order
+#[must_use]
+pub fn evaluate(isa: &str, names: &[String]) -> Vec<Outcome> {
+    names
+        .iter()
+        .map(|name| match info::get_profile(name) {
+            Some((profile, required)) => Outcome {
+                name: profile.to_string(),
+                profile: true,
+                missing: info::missing_extensions(isa, required)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+            },
+            None => Outcome {
+                name: name.clone(),
+                profile: false,
+                missing: if info::isa_supports(isa, name) {
+                    Vec::new()
+                } else {
+                    vec![name.to_lowercase()]
+                },
+            },
+        })
+        .collect()
+}
+
+/// `✓ name`, `✗ name`, or `✗ profile (missing: a, b)`
+#[must_use]
+pub fn format_outcome(outcome: &Outcome) -> String {
+    if outcome.supported() {
+        format!(
+            "{} {}",
+            "✓".bright_green().bold(),
+            outcome.name.bright_green()
+        )
+    } else if outcome.profile {
+        format!(
+            "{} {} {}",
+            "✗".bright_red().bold(),
+            outcome.name.bright_red(),
+            format!("(missing: {})", outcome.missing.join(", ")).bright_black()
+        )
+    } else {
+        format!("{} {}", "✗".bright_red().bold(), outcome.name.bright_red())
+    }
+}
+
+/// Every missing extension across `outcomes`, without duplicates
+#[must_use]
+pub fn all_missing(outcomes: &[Outcome]) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for name in outcomes.iter().flat_map(|o| &o.missing) {
+        if !missing.contains(name) {
+            missing.push(name.clone());
+        }
+    }
+    missing
+}
+
+/// `--require --json` document
+#[must_use]
+pub fn require_json(outcomes: &[Outcome]) -> serde_json::Value {
+    let missing = all_missing(outcomes);
+    serde_json::json!({
+        "ok": missing.is_empty(),
+        "required": outcomes.iter().map(|o| o.name.as_str()).collect::<Vec<_>>(),
+        "missing": missing,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+
+    const ISA: &str = "rv64imafdc_zicntr_zicsr_zifencei_zihpm_zba_zbb";
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn test_evaluate_extensions() {
+        let outcomes = evaluate(ISA, &names(&["zba", "Zvbb"]));
+        assert!(outcomes[0].supported());
+        assert_eq!(outcomes[1].missing, vec!["zvbb"]);
+    }
+
+    #[test]
+    fn test_evaluate_profiles() {
+        let outcomes = evaluate(ISA, &names(&["rva20", "rva22"]));
+        assert_eq!(outcomes[0].name, "RVA20U64");
+        assert!(outcomes[0].supported());
+        assert!(outcomes[1].profile);
+        assert!(outcomes[1].missing.contains(&"zbs".to_string()));
+    }
+
+    #[test]
+    fn test_format_outcome() {
+        let outcomes = evaluate("rv64gc_zba", &names(&["zba", "zbb", "rva22"]));
+        assert_eq!(strip_ansi(&format_outcome(&outcomes[0])), "✓ zba");
+        assert_eq!(strip_ansi(&format_outcome(&outcomes[1])), "✗ zbb");
+        assert!(
+            strip_ansi(&format_outcome(&outcomes[2])).starts_with("✗ RVA22U64 (missing: zicntr")
+        );
+    }
+
+    #[test]
+    fn test_all_missing_dedup() {
+        let outcomes = evaluate(ISA, &names(&["zbs", "rva22"]));
+        let missing = all_missing(&outcomes);
+        assert_eq!(missing[0], "zbs");
+        assert_eq!(missing.iter().filter(|m| *m == "zbs").count(), 1);
+    }
+
+    #[test]
+    fn test_require_json() {
+        let doc = require_json(&evaluate(ISA, &names(&["zba", "zvbb"])));
+        assert_eq!(
+            doc,
+            serde_json::json!({"ok": false, "required": ["zba", "zvbb"], "missing": ["zvbb"]})
+        );
+    }
+}
@@ -1,11 +1,14 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(name = "riscfetch")]
 #[command(author, version, about = "RISC-V architecture information display tool", long_about = None)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct Args {
-    /// Vendor logo (default, sifive, starfive, thead, milkv, sipeed, pine64, eswin, ultrarisc, kendryte, allwinner, espressif, spacemit, sophgo, wch)
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Vendor logo (default, sifive, starfive, thead, nuclei, milkv, sipeed, pine64, deepcomputing, framework, eswin, ultrarisc, kendryte, allwinner, espressif, spacemit, sophgo, bouffalo, renesas, wch, gigadevice, ventana, tenstorrent), or os / debian / ubuntu / fedora / arch for a distro logo
     #[arg(short, long, default_value = "default")]
     pub logo: String,
 
@@ -13,18 +16,158 @@ pub struct Args {
     #[arg(long, default_value = "normal")]
     pub style: String,
 
+    /// FIGlet font for the generated vendor logos (standard, slant, small, banner)
+    #[arg(long, value_name = "NAME", value_parser = ["standard", "slant", "small", "banner"])]
+    pub figlet_font: Option<String>,
+
+    /// Logo colors: #rrggbb (solid), #rrggbb,#rrggbb (24-bit gradient) or default (rainbow / vendor color)
+    #[arg(long, value_name = "COLORS")]
+    pub logo_colors: Option<String>,
+
+    /// When to use colors (auto, always, never); auto honors NO_COLOR and disables colors when piped
+    #[arg(long, value_name = "WHEN", default_value = "auto", value_parser = ["auto", "always", "never"])]
+    pub color: String,
+
     /// Run simple benchmarks
     #[arg(short, long)]
     pub benchmark: bool,
 
+    /// With --benchmark: also run integer/float/memory in N threads (all = one per hart) and report scaling
+    #[arg(long, value_name = "N|all", requires = "benchmark")]
+    pub threads: Option<String>,
+
+    /// With --benchmark: measured runs per benchmark, after one warm-up run (median and stddev are reported)
+    #[arg(long, value_name = "N", default_value_t = 5, value_parser = clap::value_parser!(u16).range(1..), requires = "benchmark")]
+    pub runs: u16,
+
+    /// With --benchmark: also run integer/float pinned to each hart in turn
+    #[arg(long, requires = "benchmark")]
+    pub per_hart: bool,
+
+    /// With --benchmark: do not save the run or compare it with the previous one
+    #[arg(long, requires = "benchmark")]
+    pub no_history: bool,
+
     /// Show detailed explanation of each ISA extension
     #[arg(short, long)]
     pub explain: bool,
 
+    /// Annotate each field with where its value came from (file, command, fallback)
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Log every file read, command, system call and fallback to stderr
+    #[arg(long, global = true)]
+    pub debug: bool,
+
+    /// Do not run the executables in ~/.config/riscfetch/plugins/
+    #[arg(long)]
+    pub no_plugins: bool,
+
     /// Output in JSON format (machine-readable)
     #[arg(short, long)]
     pub json: bool,
 
+    /// Print one value from the JSON output (e.g. .hardware_ids.marchid, .z_extensions[0].name)
+    #[arg(short, long, value_name = "PATH")]
+    pub query: Option<String>,
+
+    /// Exit 0 if every extension or profile is supported, 1 otherwise (e.g. zvbb, rva23; comma-separated)
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub check: Vec<String>,
+
+    /// With --check: print nothing, report only through the exit code
+    #[arg(long, requires = "check")]
+    pub quiet: bool,
+
+    /// Refuse unless every extension or profile is present: list the missing ones and exit 1 (with --json: JSON report)
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub require: Vec<String>,
+
+    /// Compare this machine side by side with another machine's JSON (from --json or snapshot save)
+    #[arg(long, value_name = "FILE")]
+    pub diff: Option<std::path::PathBuf>,
+
+    /// Like --diff, with the JSON downloaded from URL (e.g. a board profile from a community database)
+    #[arg(long, value_name = "URL", conflicts_with = "diff")]
+    pub compare_url: Option<String>,
+
+    /// Output format for scripting and reports (csv, tsv, env, markdown)
+    #[arg(short, long, value_name = "FORMAT", value_parser = ["csv", "tsv", "env", "markdown"])]
+    pub output: Option<String>,
+
+    /// Print the report of the WebAssembly formatter plugin <NAME>.wasm; needs the `wasm-plugins` feature
+    #[arg(long, value_name = "NAME", conflicts_with = "output")]
+    pub wasm_output: Option<String>,
+
+    /// Show your own PNG logo via kitty/iTerm2/sixel graphics (default: <vendor>.png in ~/.local/share/riscfetch/logos)
+    #[arg(long, value_name = "FILE", num_args = 0..=1, default_missing_value = "")]
+    pub image_logo: Option<String>,
+
+    /// Hide fields of the normal display (comma-separated ids, e.g. uptime,memory)
+    #[arg(long, value_name = "FIELDS", value_delimiter = ',')]
+    pub hide: Vec<String>,
+
+    /// Show fields that are hidden by default or by the config file
+    #[arg(long, value_name = "FIELDS", value_delimiter = ',')]
+    pub show: Vec<String>,
+
+    /// Layout: top (logo above info) or side (logo left of info, neofetch-style)
+    #[arg(long, value_name = "LAYOUT", default_value = "top", value_parser = ["top", "side"])]
+    pub layout: String,
+
+    /// Refresh the display every SECONDS (default: 2) until interrupted
+    #[arg(long, value_name = "SECONDS", num_args = 0..=1, default_missing_value = "2")]
+    pub watch: Option<u64>,
+
+    /// Keep the logo, colors and symbols when stdout is not a terminal (default: plain field list when piped)
+    #[arg(long)]
+    pub force_tty: bool,
+
+    /// Print a one-line summary (for status bars and prompts)
+    #[arg(long)]
+    pub oneline: bool,
+
+    /// Print an exhaustive sectioned report (SoC, harts, caches, sensors, devices, firmware, kernel config) for bug reports
+    #[arg(long)]
+    pub full: bool,
+
+    /// Print recommended RUSTFLAGS (-C target-feature, -C target-cpu) for this hardware, limited to what the installed rustc knows
+    #[arg(long)]
+    pub rustflags: bool,
+
+    /// Print a QEMU -cpu option (rv64i,v=true,zba=true,...,vlen=256) reproducing this machine's ISA
+    #[arg(long)]
+    pub qemu_cpu: bool,
+
+    /// Print a single raw value (isa, board, hart_count, extensions.z, ...)
+    #[arg(long, value_name = "NAME")]
+    pub field: Option<String>,
+
+    /// Render a custom layout from a template file ({isa}, {board}, {extensions.z.bit}, ...)
+    #[arg(long, value_name = "FILE")]
+    pub template: Option<std::path::PathBuf>,
+
+    /// Export the logo and key fields as a shareable SVG card
+    #[arg(long, value_name = "FILE")]
+    pub export_svg: Option<std::path::PathBuf>,
+
+    /// Export the SVG card rasterized as PNG; needs the `png` feature
+    #[arg(long, value_name = "FILE")]
+    pub export_png: Option<std::path::PathBuf>,
+
+    /// Periodically publish the JSON snapshot to an MQTT broker (host[:port]); needs the `mqtt` feature
+    #[arg(long, value_name = "BROKER")]
+    pub mqtt: Option<String>,
+
+    /// MQTT topic to publish to (default: riscfetch/<hostname>)
+    #[arg(long, value_name = "TOPIC", requires = "mqtt")]
+    pub topic: Option<String>,
+
+    /// Seconds between MQTT publishes
+    #[arg(long, value_name = "SECONDS", default_value_t = 60, requires = "mqtt")]
+    pub mqtt_interval: u64,
+
     /// Show only RISC-V specific info (exclude generic system info like OS, memory, uptime)
     #[arg(short, long)]
     pub riscv_only: bool,
@@ -33,3 +176,223 @@ pub struct Args {
     #[arg(short, long)]
     pub all: bool,
 }
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Generate a self-contained report file
+    Report {
+        /// Write a single-file HTML report to FILE
+        #[arg(long, value_name = "FILE")]
+        html: std::path::PathBuf,
+
+        /// Run benchmarks and include the results
+        #[arg(short, long)]
+        benchmark: bool,
+
+        /// Vendor logo to embed (default: auto-detect)
+        #[arg(short, long, default_value = "default")]
+        logo: String,
+    },
+
+    /// Upload the JSON output to a paste service and print its URL
+    Share {
+        /// Paste endpoint that accepts a POST body and returns the URL [env: RISCFETCH_PASTE_URL] [default: https://paste.rs/]
+        #[arg(long, value_name = "URL")]
+        endpoint: Option<String>,
+
+        /// Upload without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Run the benchmarks without the system information display
+    Bench {
+        /// Measured runs per benchmark, after one warm-up run (median and stddev are reported)
+        #[arg(long, value_name = "N", default_value_t = 5, value_parser = clap::value_parser!(u16).range(1..))]
+        runs: u16,
+
+        /// Also run integer/float/memory in N threads (all = one per hart) and report scaling
+        #[arg(long, value_name = "N|all")]
+        threads: Option<String>,
+
+        /// Also run integer/float pinned to each hart in turn
+        #[arg(long)]
+        per_hart: bool,
+
+        /// Do not save the run or compare it with the previous one
+        #[arg(long)]
+        no_history: bool,
+
+        /// Print the results as JSON (the format read by --compare)
+        #[arg(long, conflicts_with_all = ["compare", "submit"])]
+        json: bool,
+
+        /// Show the results side by side with a saved run (from bench --json or --json --benchmark) and the change in percent
+        #[arg(long, value_name = "FILE")]
+        compare: Option<std::path::PathBuf>,
+
+        /// Upload the anonymized results and hardware description to a community results endpoint, after confirmation
+        #[arg(long)]
+        submit: bool,
+
+        /// Results endpoint for --submit [env: RISCFETCH_RESULTS_URL]
+        #[arg(long, value_name = "URL", requires = "submit")]
+        endpoint: Option<String>,
+
+        /// Submit without asking for confirmation
+        #[arg(short, long, requires = "submit")]
+        yes: bool,
+    },
+
+    /// List every known ISA extension with its support status
+    Extensions {
+        /// Only list extensions whose name or description contains TEXT
+        #[arg(value_name = "TEXT", default_value = "")]
+        search: String,
+
+        /// Only list one category (standard, or a Z/S category such as bit, crypto, vm)
+        #[arg(short, long)]
+        category: Option<String>,
+
+        /// Browse in a full-screen view with incremental search (Tab cycles categories, Esc quits)
+        #[arg(short, long)]
+        interactive: bool,
+    },
+
+    /// Describe one ISA extension and whether this system supports it; --long adds its extended documentation
+    Explain {
+        /// Extension name, e.g. v, zba, sstc
+        #[arg(value_name = "EXTENSION")]
+        name: String,
+
+        /// Print the multi-paragraph documentation (V, Zba, Zbb, Zicond, Sstc, H)
+        #[arg(long)]
+        long: bool,
+    },
+
+    /// Explain why fields are missing (old kernel, no device tree, vector disabled, container, permissions) with suggested fixes
+    Doctor {
+        /// Print the findings as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the device-tree nodes riscfetch reads (model, CPU ISA and cache properties, timebase, SoC caches)
+    Dt {
+        /// Print the nodes as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print per-hart frequency, governor, utilization and thermal zone
+    Harts {
+        /// Print the harts as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Monitor temperatures, per-hart frequency and load, and fan speeds until interrupted
+    Sensors {
+        /// Seconds between samples
+        #[arg(short, long, value_name = "SECONDS", default_value_t = 2)]
+        interval: u64,
+
+        /// Stop after COUNT samples
+        #[arg(short = 'n', long, value_name = "COUNT")]
+        count: Option<u64>,
+
+        /// Print each sample as one JSON object per line
+        #[arg(long)]
+        json_lines: bool,
+    },
+
+    /// Check which hardware extensions the installed gcc / clang / rustc can target
+    Toolchain {
+        /// Print the compilers and their unsupported extensions as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the recommended C/C++ build configuration (triple, -march, -mabi, -mtune, CMake toolchain file)
+    Buildflags {
+        /// Print only the CMake toolchain file
+        #[arg(long, conflicts_with = "json")]
+        cmake: bool,
+
+        /// Print the configuration as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List vendor and distro logos for --logo with their aliases and detection keywords
+    Vendors {
+        /// Print the list as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the logo selected by --logo / --style; --preview shows every vendor logo instead
+    Logo {
+        /// Show every vendor logo in the normal and small styles, one after another
+        #[arg(long)]
+        preview: bool,
+    },
+
+    /// Summary table of many boards from JSON files and/or hosts lists (hosts are queried over ssh)
+    Fleet {
+        /// `.json` files from --json / snapshot save, or hosts lists (one ssh destination or .json path per line)
+        #[arg(value_name = "FILE", required = true)]
+        files: Vec<std::path::PathBuf>,
+    },
+
+    /// Print a sanitized profile of this board (ISA, SoC, device-tree model, caches, CSR IDs) for the board database
+    Contribute {
+        /// Print a GitHub new-issue URL with the profile prefilled instead
+        #[arg(long)]
+        issue: bool,
+
+        /// Open the prefilled GitHub issue in the browser
+        #[arg(long, conflicts_with = "issue")]
+        open: bool,
+    },
+
+    /// Print a static login banner (board, ISA, harts, memory) for /etc/issue, or write it to a file
+    Banner {
+        /// Write the banner to FILE instead of printing it (e.g. /etc/issue.d/riscfetch.issue)
+        #[arg(long, value_name = "FILE")]
+        write: Option<std::path::PathBuf>,
+
+        /// Add agetty escapes for the hostname, tty and IP address
+        #[arg(long)]
+        agetty: bool,
+
+        /// Also install a systemd unit that regenerates the banner on every boot
+        #[arg(long, requires = "write")]
+        install_hook: bool,
+    },
+
+    /// Save the current system information and compare against it later
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotAction {
+    /// Write the current system information to FILE as JSON
+    Save {
+        #[arg(value_name = "FILE")]
+        file: std::path::PathBuf,
+    },
+
+    /// Show what changed since FILE was saved (extensions, kernel, memory, ...)
+    Diff {
+        #[arg(value_name = "FILE")]
+        file: std::path::PathBuf,
+
+        /// Print the changes as a JSON array
+        #[arg(long)]
+        json: bool,
+    },
+}
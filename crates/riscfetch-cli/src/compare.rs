@@ -0,0 +1,222 @@
+//! Two-column comparison with another machine (`--diff other.json`)
+//!
+//! The other machine is a file written by `--json` or `snapshot save`, or
+//! the same document downloaded from a URL (`--compare-url`).
+//! Hardware rows come first, then every extension detected on either side;
+//! rows whose values differ are highlighted.
+
+use crate::display::{elide_ansi, visible_width};
+use colored::Colorize;
+use riscfetch_core::{ExtensionEntry, SystemInfo};
+
+/// Width of the row label column
+const LABEL_WIDTH: usize = 12;
+
+/// Widest value column; longer values (the ISA string) are elided
+const MAX_COLUMN_WIDTH: usize = 36;
+
+#[allow(clippy::cast_precision_loss)]
+fn gib(bytes: u64) -> String {
+    format!("{:.2} GiB", bytes as f64 / 1_073_741_824.0)
+}
+
+fn optional(value: Option<&String>) -> String {
+    value.cloned().unwrap_or_else(|| "-".to_string())
+}
+
+fn vector(data: &SystemInfo) -> String {
+    match (data.vector.enabled, data.vector.vlen) {
+        (true, Some(vlen)) => format!("VLEN={vlen}"),
+        (true, None) => "yes".to_string(),
+        (false, _) => "no".to_string(),
+    }
+}
+
+/// Hardware and software rows: (label, this machine, other machine)
+#[must_use]
+pub fn field_rows(this: &SystemInfo, other: &SystemInfo) -> Vec<(&'static str, String, String)> {
+    let row = |label, get: &dyn Fn(&SystemInfo) -> String| (label, get(this), get(other));
+    vec![
+        row("Board", &|d| d.board.clone()),
+        row("ISA", &|d| d.isa.clone()),
+        row("Harts", &|d| d.hart_count.to_string()),
+        row("Vector", &vector),
+        row("Memory", &|d| gib(d.memory_total_bytes)),
+        row("L1D", &|d| optional(d.cache.l1d.as_ref())),
+        row("L1I", &|d| optional(d.cache.l1i.as_ref())),
+        row("L2", &|d| optional(d.cache.l2.as_ref())),
+        row("L3", &|d| optional(d.cache.l3.as_ref())),
+        row("mvendorid", &|d| d.hardware_ids.mvendorid.clone()),
+        row("marchid", &|d| d.hardware_ids.marchid.clone()),
+        row("mimpid", &|d| d.hardware_ids.mimpid.clone()),
+        row("OS", &|d| d.os.clone()),
+        row("Kernel", &|d| d.kernel.clone()),
+    ]
+}
+
+fn all_extensions(data: &SystemInfo) -> impl Iterator<Item = &ExtensionEntry> {
+    data.extensions
+        .iter()
+        .chain(&data.z_extensions)
+        .chain(&data.s_extensions)
+}
+
+/// Extensions detected on either side: (name, on this machine, on the other)
+#[must_use]
+pub fn extension_rows(this: &SystemInfo, other: &SystemInfo) -> Vec<(String, bool, bool)> {
+    let has = |data: &SystemInfo, name: &str| all_extensions(data).any(|e| e.name == name);
+    let mut rows: Vec<(String, bool, bool)> = all_extensions(this)
+        .map(|e| (e.name.clone(), true, has(other, &e.name)))
+        .collect();
+    rows.extend(
+        all_extensions(other)
+            .filter(|e| !has(this, &e.name))
+            .map(|e| (e.name.clone(), false, true)),
+    );
+    rows
+}
+
+fn pad(text: &str, width: usize) -> String {
+    let text = elide_ansi(text, width);
+    let fill = width.saturating_sub(visible_width(&text));
+    format!("{text}{}", " ".repeat(fill))
+}
+
+fn mark(supported: bool) -> String {
+    if supported {
+        "✓".bright_green().bold().to_string()
+    } else {
+        "✗".bright_red().to_string()
+    }
+}
+
+/// Column heading for a downloaded document: the last path segment of the
+/// URL (`board.json`), or its host when the path is empty
+#[must_use]
+pub fn url_label(url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let host = segments.next().unwrap_or(path);
+    segments.next_back().unwrap_or(host).to_string()
+}
+
+/// Render the comparison; `this_label` / `other_label` head the columns
+#[must_use]
+pub fn render(
+    this: &SystemInfo,
+    this_label: &str,
+    other: &SystemInfo,
+    other_label: &str,
+) -> Vec<String> {
+    let fields = field_rows(this, other);
+    let width = fields
+        .iter()
+        .map(|(_, a, _)| visible_width(a))
+        .chain(std::iter::once(visible_width(this_label)))
+        .max()
+        .unwrap_or(0)
+        .min(MAX_COLUMN_WIDTH);
+
+    let line = |label: String, a: String, b: String| {
+        format!("{} {}   {}", pad(&label, LABEL_WIDTH), pad(&a, width), b)
+    };
+
+    let mut lines = vec![line(
+        String::new(),
+        this_label.bright_cyan().bold().to_string(),
+        other_label.bright_cyan().bold().to_string(),
+    )];
+    for (label, a, b) in fields {
+        let label = label.bright_yellow().bold().to_string();
+        if a == b {
+            lines.push(line(label, a, b));
+        } else {
+            lines.push(line(
+                label,
+                a.bright_white().bold().to_string(),
+                b.bright_magenta().bold().to_string(),
+            ));
+        }
+    }
+
+    let extensions = extension_rows(this, other);
+    let differing = extensions.iter().filter(|(_, a, b)| a != b).count();
+    lines.push(String::new());
+    lines.push(format!(
+        "{} {}",
+        "Extensions".bright_yellow().bold(),
+        format!("({differing} differ)").bright_black()
+    ));
+    for (name, a, b) in extensions {
+        let name = if a == b {
+            name.normal().to_string()
+        } else {
+            name.bright_magenta().bold().to_string()
+        };
+        lines.push(line(name, mark(a), mark(b)));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+    use crate::output::tests::sample_info;
+
+    fn other() -> SystemInfo {
+        let mut data = sample_info();
+        data.board = "Sipeed Lichee Pi 4A".to_string();
+        data.z_extensions.retain(|e| e.name != "Zba");
+        data.s_extensions.push(ExtensionEntry {
+            name: "Svpbmt".to_string(),
+            description: String::new(),
+        });
+        data
+    }
+
+    #[test]
+    fn test_url_label() {
+        assert_eq!(
+            url_label("https://boards.example.org/profiles/megrez.json?raw=1"),
+            "megrez.json"
+        );
+        assert_eq!(url_label("https://example.org/lpi4a/"), "lpi4a");
+        assert_eq!(url_label("http://example.org"), "example.org");
+    }
+
+    #[test]
+    fn test_field_rows() {
+        let rows = field_rows(&sample_info(), &other());
+        let board = rows.iter().find(|(l, _, _)| *l == "Board").unwrap();
+        assert_eq!(board.1, "StarFive VisionFive 2");
+        assert_eq!(board.2, "Sipeed Lichee Pi 4A");
+        let harts = rows.iter().find(|(l, _, _)| *l == "Harts").unwrap();
+        assert_eq!(harts.1, harts.2);
+    }
+
+    #[test]
+    fn test_extension_rows_union() {
+        let rows = extension_rows(&sample_info(), &other());
+        assert!(rows.contains(&("Zba".to_string(), true, false)));
+        assert!(rows.contains(&("Zbb".to_string(), true, true)));
+        // Only on the other machine: listed last
+        assert_eq!(rows.last().unwrap(), &("Svpbmt".to_string(), false, true));
+    }
+
+    #[test]
+    fn test_render_columns_aligned() {
+        let lines: Vec<String> = render(&sample_info(), "vf2", &other(), "lpi4a.json")
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        assert!(lines[0].ends_with("lpi4a.json"));
+        let board = lines.iter().find(|l| l.starts_with("Board")).unwrap();
+        let zba = lines.iter().find(|l| l.starts_with("Zba")).unwrap();
+        // Second column starts at the same offset on every row
+        let column = |line: &str, text: &str| line[..line.rfind(text).unwrap()].chars().count();
+        assert_eq!(column(board, "Sipeed"), column(zba, "✗"));
+        assert!(lines.iter().any(|l| l == "Extensions (2 differ)"));
+    }
+}
@@ -0,0 +1,125 @@
+//! User configuration file (`~/.config/riscfetch/config.toml`)
+//!
+//! ```toml
+//! hide = ["uptime", "memory"]
+//! show = []
+//! order = ["board", "isa", "ext"]
+//! piped = "json"
+//! logo_colors = "#00c0ff,#c000ff"
+//! figlet_font = "slant"
+//! ```
+//!
+//! A missing file is the same as an empty one. Command-line flags are
+//! applied after the file and win over it.
+
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Environment variable pointing at an alternative config file
+pub const CONFIG_ENV: &str = "RISCFETCH_CONFIG";
+
+/// Output when stdout is not a terminal (and `--force-tty` is not given)
+#[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Piped {
+    /// Field lines without logo, colors or symbols
+    #[default]
+    Plain,
+    /// Same document as `--json`
+    Json,
+}
+
+#[derive(Deserialize, Default, Debug, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Field ids to hide (see `fields::FIELDS`)
+    pub hide: Vec<String>,
+    /// Field ids to show even if hidden by default
+    pub show: Vec<String>,
+    /// Field ids to display first, in this order
+    pub order: Vec<String>,
+    /// Output format when piped
+    pub piped: Piped,
+    /// Logo colors, same syntax as `--logo-colors`
+    pub logo_colors: Option<String>,
+    /// `FIGlet` font for vendor logos, same names as `--figlet-font`
+    pub figlet_font: Option<String>,
+}
+
+/// Location of the config file: `$RISCFETCH_CONFIG`, then
+/// `$XDG_CONFIG_HOME/riscfetch/config.toml`, then `~/.config/riscfetch/config.toml`
+#[must_use]
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("riscfetch/config.toml"))
+}
+
+/// Parse a config document
+pub fn parse(text: &str) -> Result<Config, String> {
+    toml::from_str(text).map_err(|e| e.message().to_string())
+}
+
+/// Load the config file, returning the defaults when it does not exist
+pub fn load() -> Result<Config, String> {
+    let Some(path) = path() else {
+        return Ok(Config::default());
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(text) => parse(&text).map_err(|e| format!("{}: {e}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(format!("{}: {e}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_parse_hide_show() {
+        let config = parse("hide = [\"uptime\", \"memory\"]\nshow = [\"cache\"]\n").unwrap();
+        assert_eq!(config.hide, vec!["uptime", "memory"]);
+        assert_eq!(config.show, vec!["cache"]);
+    }
+
+    #[test]
+    fn test_parse_order() {
+        let config = parse("order = [\"board\", \"isa\"]").unwrap();
+        assert_eq!(config.order, vec!["board", "isa"]);
+    }
+
+    #[test]
+    fn test_parse_piped() {
+        assert_eq!(parse("").unwrap().piped, Piped::Plain);
+        assert_eq!(parse("piped = \"json\"").unwrap().piped, Piped::Json);
+        assert!(parse("piped = \"yaml\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_figlet_font() {
+        let config = parse("figlet_font = \"banner\"").unwrap();
+        assert_eq!(config.figlet_font.as_deref(), Some("banner"));
+    }
+
+    #[test]
+    fn test_parse_logo_colors() {
+        let config = parse("logo_colors = \"#00c0ff,#c000ff\"").unwrap();
+        assert_eq!(config.logo_colors.as_deref(), Some("#00c0ff,#c000ff"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_keys() {
+        assert!(parse("hdie = [\"uptime\"]").is_err());
+        assert!(parse("hide = \"uptime\"").is_err());
+    }
+}
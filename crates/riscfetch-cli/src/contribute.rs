@@ -0,0 +1,212 @@
+//! Board database contributions (`riscfetch contribute`)
+//!
+//! The profile is built from an allowlist, like the benchmark submission:
+//! device-tree model and `compatible` strings, ISA, harts, caches and CSR
+//! IDs, but no hostname, user, serial numbers or usage figures. Its `board`
+//! object has the fields of a `data/boards.toml` entry, prefilled where the
+//! device tree knows them.
+
+use riscfetch_core::{Board, SystemInfo};
+use serde_json::{json, Value};
+
+/// Format version of the profile
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Where board profiles are submitted
+const ISSUES_URL: &str = concat!(env!("CARGO_PKG_REPOSITORY"), "/issues/new");
+
+/// Memory sizes boards are sold with, in MiB
+const MARKETED_MIB: [u64; 18] = [
+    64, 128, 256, 512, 1024, 2048, 3072, 4096, 6144, 8192, 12288, 16384, 24576, 32768, 49152,
+    65536, 98304, 131_072,
+];
+
+/// Memory size the board was probably sold with: `MemTotal` plus the
+/// firmware carve-outs, rounded to the nearest marketed size (`8 GB`)
+fn ram_option(info: &SystemInfo) -> Option<String> {
+    let reserved = info
+        .memory_breakdown
+        .and_then(|breakdown| breakdown.reserved_bytes)
+        .unwrap_or(0);
+    marketed_size(info.memory_total_bytes + reserved)
+}
+
+/// Nearest of `MARKETED_MIB` (`512 MB`, `8 GB`), or whole GB beyond it
+fn marketed_size(bytes: u64) -> Option<String> {
+    if bytes == 0 {
+        return None;
+    }
+    let mib = bytes / (1 << 20);
+    let largest = MARKETED_MIB[MARKETED_MIB.len() - 1];
+    let size = if mib > largest {
+        (mib + 512) / 1024 * 1024
+    } else {
+        MARKETED_MIB
+            .into_iter()
+            .min_by_key(|size| size.abs_diff(mib))?
+    };
+    Some(if size < 1024 {
+        format!("{size} MB")
+    } else {
+        format!("{} GB", size / 1024)
+    })
+}
+
+/// The sanitized profile; `compatible` as in the device tree (board first,
+/// SoC last) and `known` the catalog entry already matching the board
+#[must_use]
+pub fn profile(info: &SystemInfo, compatible: &[String], known: Option<&Board>) -> Value {
+    let names = |entries: &[riscfetch_core::ExtensionEntry]| -> Vec<String> {
+        entries.iter().map(|e| e.name.clone()).collect()
+    };
+    // A lone entry names the board; with more the last one is the SoC
+    let (soc_compatible, board_compatible) = match compatible {
+        [board] => (None, std::slice::from_ref(board)),
+        [board @ .., soc] => (Some(soc), board),
+        [] => (None, compatible),
+    };
+    json!({
+        "schema": SCHEMA_VERSION,
+        "riscfetch_version": env!("CARGO_PKG_VERSION"),
+        "known_as": known.map(|board| &board.name),
+        "board": {
+            "name": info.board,
+            "model": info.board,
+            "compatible": board_compatible,
+            "soc": known.map_or("", |board| board.soc.as_str()),
+            "soc_compatible": soc_compatible,
+            "ram": ram_option(info).into_iter().collect::<Vec<_>>(),
+            "year": known.map(|board| board.year),
+            "form_factor": known.map(|board| &board.form_factor),
+            "url": known.map(|board| &board.url),
+        },
+        "hardware": {
+            "isa": info.isa,
+            "extensions": names(&info.extensions),
+            "z_extensions": names(&info.z_extensions),
+            "s_extensions": names(&info.s_extensions),
+            "vector": info.vector,
+            "hart_count": info.hart_count,
+            "hardware_ids": info.hardware_ids,
+            "cache": info.cache,
+        },
+        "kernel": info.kernel,
+        "os": info.os,
+    })
+}
+
+/// Percent-encode everything but unreserved URL characters
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// New-issue URL with the title and the profile in the body
+#[must_use]
+pub fn issue_url(profile: &Value) -> String {
+    let model = profile["board"]["model"].as_str().unwrap_or_default();
+    let title = match profile["known_as"].as_str() {
+        Some(name) => format!("Board profile: {model} (listed as {name})"),
+        None => format!("Board profile: {model}"),
+    };
+    let body = format!(
+        "Generated by `riscfetch contribute`. Please fill in what the device tree \
+         cannot tell (SoC name, year, form factor, vendor link).\n\n```json\n{}\n```\n",
+        serde_json::to_string_pretty(profile).unwrap_or_default()
+    );
+    format!(
+        "{ISSUES_URL}?title={}&body={}",
+        percent_encode(&title),
+        percent_encode(&body)
+    )
+}
+
+/// Open `url` in the desktop's browser
+pub fn open(url: &str) -> std::io::Result<()> {
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let status = std::process::Command::new(program).arg(url).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "{program} exited with {status}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::sample_info;
+
+    fn compatible() -> Vec<String> {
+        ["starfive,visionfive-2-v1.3b", "starfive,jh7110"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    #[test]
+    fn test_profile_is_sanitized() {
+        let mut info = sample_info();
+        info.memory_used_bytes = 123_456_789;
+        info.uptime_seconds = 987_654;
+        let text = profile(&info, &compatible(), None).to_string();
+        assert!(!text.contains("123456789"));
+        assert!(!text.contains("987654"));
+        assert!(!text.contains("hostname"));
+    }
+
+    #[test]
+    fn test_profile_board_entry() {
+        let info = sample_info();
+        let doc = profile(&info, &compatible(), None);
+        let board = &doc["board"];
+        assert_eq!(board["model"], "StarFive VisionFive 2");
+        assert_eq!(board["compatible"], json!(["starfive,visionfive-2-v1.3b"]));
+        assert_eq!(board["soc_compatible"], "starfive,jh7110");
+        assert_eq!(board["ram"], json!(["8 GB"]));
+        assert!(board["year"].is_null());
+        assert!(doc["known_as"].is_null());
+        assert_eq!(doc["hardware"]["hardware_ids"]["mvendorid"], "0x489");
+
+        let known = riscfetch_core::find_board("", &["starfive,visionfive-2-v1.3b"]);
+        let doc = profile(&info, &compatible(), known);
+        assert_eq!(doc["known_as"], "StarFive VisionFive 2");
+        assert_eq!(doc["board"]["soc"], "StarFive JH7110");
+        assert_eq!(doc["board"]["year"], 2023);
+    }
+
+    #[test]
+    fn test_marketed_size() {
+        const MIB: u64 = 1 << 20;
+        // 7.6 GiB MemTotal plus a 512 MiB carve-out
+        assert_eq!(
+            marketed_size(7782 * MIB + 512 * MIB).as_deref(),
+            Some("8 GB")
+        );
+        assert_eq!(marketed_size(7782 * MIB).as_deref(), Some("8 GB"));
+        assert_eq!(marketed_size(3900 * MIB).as_deref(), Some("4 GB"));
+        assert_eq!(marketed_size(480 * MIB).as_deref(), Some("512 MB"));
+        assert_eq!(marketed_size(250 * 1024 * MIB).as_deref(), Some("250 GB"));
+        assert_eq!(marketed_size(0), None);
+    }
+
+    #[test]
+    fn test_issue_url() {
+        assert_eq!(percent_encode("a b/c~"), "a%20b%2Fc~");
+        let url = issue_url(&profile(&sample_info(), &[], None));
+        assert!(url.starts_with("https://github.com/kako-jun/riscfetch/issues/new?title=Board%20profile%3A%20StarFive%20VisionFive%202&body="));
+        assert!(!url.contains(' '));
+        assert!(!url.contains('\n'));
+    }
+}
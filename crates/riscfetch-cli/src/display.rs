@@ -1,32 +1,951 @@
-use crate::logos::{generate_logo, LogoStyle};
+use crate::distros::get_distro_info;
+use crate::logos::{art_logo, generate_logo, FigletFont, LogoStyle};
 use colored::{Color, Colorize};
+use riscfetch_core::{self as info, SystemInfo};
+use std::fmt::Write;
+use std::io::IsTerminal;
 
-pub fn display_logo(vendor: &str, style: &str) {
+/// Line colors cycled through for the default RISC-V logo
+const RAINBOW: [Color; 6] = [
+    Color::BrightCyan,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightRed,
+    Color::BrightYellow,
+    Color::BrightGreen,
+];
+
+/// Decide whether ANSI colors should be emitted.
+///
+/// `always`/`never` are explicit and win over the environment. `auto` colors
+/// only when stdout is a terminal and `NO_COLOR` (<https://no-color.org>) is
+/// unset or empty.
+#[must_use]
+pub fn should_colorize(when: &str, no_color: bool, stdout_is_tty: bool) -> bool {
+    match when {
+        "always" => true,
+        "never" => false,
+        _ => stdout_is_tty && !no_color,
+    }
+}
+
+/// Apply the `--color` setting to all colored output.
+/// `force_tty` makes `auto` behave as if stdout were a terminal.
+pub fn configure_color(when: &str, force_tty: bool) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let enabled = should_colorize(when, no_color, force_tty || std::io::stdout().is_terminal());
+    colored::control::set_override(enabled);
+}
+
+/// Whether the normal display should be plain: stdout is not a terminal
+/// and `--force-tty` was not given
+#[must_use]
+pub fn is_plain(force_tty: bool) -> bool {
+    !force_tty && !std::io::stdout().is_terminal()
+}
+
+/// A display line for pipes: no ANSI escapes, and ASCII instead of the
+/// `✓` / `✗` / `↳` symbols
+#[must_use]
+pub fn plain_line(line: &str) -> String {
+    strip_ansi(line)
+        .replace('✓', "+")
+        .replace('✗', "-")
+        .replace('↳', "->")
+}
+
+/// Whether a vendor uses the rainbow-colored default logo
+fn is_default_logo(vendor: &str) -> bool {
+    vendor == "default" || vendor == "riscv" || vendor == "risc-v"
+}
+
+/// Logo color for distro logos (`--logo os`), None for vendor logos
+fn distro_color(vendor: &str) -> Option<Color> {
+    match get_distro_info(vendor)?.0 {
+        "Debian" | "Ubuntu" => Some(Color::BrightRed),
+        "Fedora" => Some(Color::BrightBlue),
+        _ => Some(Color::BrightCyan),
+    }
+}
+
+/// Logo coloring from `--logo-colors` or `logo_colors` in the config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogoColors {
+    /// Rainbow cycle for the RISC-V logo, the vendor or distro color otherwise
+    Default,
+    /// One 24-bit color for every line
+    Solid(Color),
+    /// 24-bit gradient from the first line to the last
+    Gradient(Color, Color),
+}
+
+fn parse_hex_color(text: &str) -> Result<Color, String> {
+    let hex = text.trim().trim_start_matches('#');
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2).unwrap_or("-"), 16);
+    match (hex.len(), channel(0), channel(2), channel(4)) {
+        (6, Ok(r), Ok(g), Ok(b)) => Ok(Color::TrueColor { r, g, b }),
+        _ => Err(format!(
+            "invalid color '{}' (expected #rrggbb)",
+            text.trim()
+        )),
+    }
+}
+
+impl LogoColors {
+    /// Parse `default`, `#rrggbb` or `#rrggbb,#rrggbb`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        if spec.trim().eq_ignore_ascii_case("default") {
+            return Ok(Self::Default);
+        }
+        match spec.split_once(',') {
+            Some((start, end)) => Ok(Self::Gradient(
+                parse_hex_color(start)?,
+                parse_hex_color(end)?,
+            )),
+            None => parse_hex_color(spec).map(Self::Solid),
+        }
+    }
+}
+
+/// Color of line `index` of `count` on a gradient from `start` to `end`
+#[must_use]
+pub fn gradient_color(start: Color, end: Color, index: usize, count: usize) -> Color {
+    let rgb = |color| match color {
+        Color::TrueColor { r, g, b } => [r, g, b],
+        _ => [255, 255, 255],
+    };
+    let (from, to) = (rgb(start), rgb(end));
+    let steps = count.saturating_sub(1).max(1);
+    let t = index.min(steps);
+    #[allow(clippy::cast_possible_truncation)]
+    let mix = |i: usize| {
+        let (a, b) = (usize::from(from[i]), usize::from(to[i]));
+        // Rounded integer interpolation; the result is between a and b
+        ((a * (steps - t) + b * t + steps / 2) / steps) as u8
+    };
+    Color::TrueColor {
+        r: mix(0),
+        g: mix(1),
+        b: mix(2),
+    }
+}
+
+/// Colored logo lines (empty for `--style none`)
+///
+/// Pre-rendered art keeps its own colors unless `--logo-colors` overrides them.
+#[must_use]
+pub fn logo_lines(vendor: &str, style: &str, font: FigletFont, colors: LogoColors) -> Vec<String> {
     let logo_style = LogoStyle::from_str(style);
+    if colors == LogoColors::Default && colored::control::SHOULD_COLORIZE.should_colorize() {
+        if let Some(art) = art_logo(vendor, logo_style, font) {
+            return art.lines().map(str::to_string).collect();
+        }
+    }
+    let logo = generate_logo(vendor, logo_style, font);
 
-    let logo = generate_logo(vendor, logo_style);
-    if logo.is_empty() {
-        return;
+    if let LogoColors::Solid(color) = colors {
+        logo.lines()
+            .map(|line| line.color(color).bold().to_string())
+            .collect()
+    } else if let LogoColors::Gradient(start, end) = colors {
+        let lines: Vec<&str> = logo.lines().filter(|line| !line.is_empty()).collect();
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                line.color(gradient_color(start, end, i, lines.len()))
+                    .bold()
+                    .to_string()
+            })
+            .collect()
+    } else if is_default_logo(vendor) {
+        // For default RISC-V logo, apply rainbow gradient
+        logo.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| line.color(RAINBOW[i % RAINBOW.len()]).bold().to_string())
+            .collect()
+    } else if let Some(color) = distro_color(vendor) {
+        logo.lines()
+            .map(|line| line.color(color).bold().to_string())
+            .collect()
+    } else {
+        logo.lines()
+            .map(|line| line.bright_cyan().bold().to_string())
+            .collect()
     }
+}
 
-    // For default RISC-V logo, apply rainbow gradient
-    if vendor == "default" || vendor == "riscv" || vendor == "risc-v" {
-        let colors = [
-            Color::BrightCyan,
-            Color::BrightBlue,
-            Color::BrightMagenta,
-            Color::BrightRed,
-            Color::BrightYellow,
-            Color::BrightGreen,
-        ];
+/// Every vendor logo in the normal and small styles, each under a
+/// `id (Name)` heading, for `riscfetch logo --preview`
+#[must_use]
+pub fn logo_gallery(font: FigletFont, colors: LogoColors) -> Vec<String> {
+    let mut out = Vec::new();
+    for (aliases, name, _) in crate::vendors::VENDORS {
+        out.push(
+            format!("{} ({name})", aliases[0])
+                .bright_yellow()
+                .bold()
+                .to_string(),
+        );
+        for style in ["normal", "small"] {
+            out.extend(logo_lines(aliases[0], style, font, colors));
+            out.push(String::new());
+        }
+    }
+    out
+}
+
+// === Layout ===
+
+/// Spaces between the logo column and the info column in the side layout
+const SIDE_GAP: usize = 3;
 
-        for (i, line) in logo.lines().enumerate() {
-            if !line.is_empty() {
-                let color_idx = i % colors.len();
-                println!("{}", line.color(colors[color_idx]).bold());
+/// Display width of a line, ignoring ANSI escape sequences
+#[must_use]
+pub fn visible_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequence: ESC [ parameters final-byte
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
             }
+        } else {
+            width += 1;
         }
+    }
+    width
+}
+
+/// Place the logo in a left column and the info lines to its right.
+///
+/// Both columns are top-aligned; the shorter one is padded with blank space.
+#[must_use]
+pub fn side_by_side(logo: &[String], info: &[String]) -> Vec<String> {
+    let column = logo.iter().map(|l| visible_width(l)).max().unwrap_or(0) + SIDE_GAP;
+    (0..logo.len().max(info.len()))
+        .map(|i| {
+            let left = logo.get(i).map_or("", String::as_str);
+            let right = info.get(i).map_or("", String::as_str);
+            if right.is_empty() {
+                left.to_string()
+            } else {
+                let pad = column - visible_width(left);
+                format!("{left}{}{right}", " ".repeat(pad))
+            }
+        })
+        .collect()
+}
+
+/// Combine logo and info lines according to `--layout`, fitting them to
+/// `width` columns when the terminal width is known
+#[must_use]
+pub fn layout(logo: &[String], info: &[String], layout: &str, width: Option<usize>) -> Vec<String> {
+    let fit_all = |lines: &[String], width: Option<usize>| -> Vec<String> {
+        match width {
+            Some(width) => lines.iter().flat_map(|l| fit_line(l, width)).collect(),
+            None => lines.to_vec(),
+        }
+    };
+
+    if layout == "side" && !logo.is_empty() {
+        let column = logo.iter().map(|l| visible_width(l)).max().unwrap_or(0) + SIDE_GAP;
+        let info_width = width.map(|w| w.saturating_sub(column).max(MIN_INFO_WIDTH));
+        return side_by_side(logo, &fit_all(info, info_width));
+    }
+    let mut lines = fit_all(logo, width);
+    lines.push(String::new());
+    lines.extend(fit_all(info, width));
+    lines
+}
+
+// === Terminal width ===
+
+/// Narrowest info column worth wrapping to; below this lines are elided instead
+const MIN_INFO_WIDTH: usize = 30;
+
+/// Width of the terminal on stdout, or None when not a terminal (no fitting)
+#[must_use]
+pub fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size().map(|(terminal_size::Width(w), _)| usize::from(w))
+}
+
+/// Pick the small logo when the normal one does not fit in `width` columns
+#[must_use]
+pub fn fit_style<'a>(
+    vendor: &str,
+    style: &'a str,
+    font: FigletFont,
+    layout: &str,
+    width: Option<usize>,
+) -> &'a str {
+    let Some(width) = width else {
+        return style;
+    };
+    if LogoStyle::from_str(style) != LogoStyle::Normal {
+        return style;
+    }
+    let logo_width = generate_logo(vendor, LogoStyle::Normal, font)
+        .lines()
+        .map(visible_width)
+        .max()
+        .unwrap_or(0);
+    let needed = if layout == "side" {
+        logo_width + SIDE_GAP + MIN_INFO_WIDTH
+    } else {
+        logo_width
+    };
+    if needed > width {
+        "small"
     } else {
-        println!("{}", logo.bright_cyan().bold());
+        style
+    }
+}
+
+// === Watch screen ===
+
+/// Show the cursor again and leave the alternate screen
+const RESTORE_SCREEN: &str = "\x1b[?25h\x1b[?1049l";
+
+/// Switch to the alternate screen with the cursor hidden for `--watch`.
+/// Ctrl-C or SIGTERM puts the terminal back as it was before exiting.
+pub fn enter_watch_screen() {
+    print!("\x1b[?1049h\x1b[?25l");
+    restore_screen_on_signal();
+}
+
+#[cfg(unix)]
+fn restore_screen_on_signal() {
+    extern "C" fn restore(signal: libc::c_int) {
+        // SAFETY: write and _exit are async-signal-safe
+        unsafe {
+            libc::write(
+                libc::STDOUT_FILENO,
+                RESTORE_SCREEN.as_ptr().cast(),
+                RESTORE_SCREEN.len(),
+            );
+            libc::_exit(128 + signal)
+        }
+    }
+    let handler: extern "C" fn(libc::c_int) = restore;
+    // SAFETY: the handler only calls async-signal-safe functions
+    unsafe {
+        libc::signal(libc::SIGINT, handler as libc::sighandler_t);
+        libc::signal(libc::SIGTERM, handler as libc::sighandler_t);
+    }
+}
+
+#[cfg(not(unix))]
+fn restore_screen_on_signal() {}
+
+/// Copy one ANSI escape sequence (the ESC is already consumed) into `out`
+fn copy_escape(chars: &mut std::str::Chars, out: &mut String) {
+    out.push('\x1b');
+    if let Some(c) = chars.next() {
+        out.push(c);
+        if c == '[' {
+            for c in chars.by_ref() {
+                out.push(c);
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Cut a line to at most `width` visible characters, ending with `…` when shortened
+#[must_use]
+pub fn elide_ansi(line: &str, width: usize) -> String {
+    if visible_width(line) <= width {
+        return line.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut out = String::new();
+    let mut seen = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            copy_escape(&mut chars, &mut out);
+        } else if seen + 1 < width {
+            out.push(c);
+            seen += 1;
+        } else {
+            break;
+        }
+    }
+    out.push('…');
+    if line.contains('\x1b') {
+        out.push_str("\x1b[0m");
+    }
+    out
+}
+
+/// Fit one line to `width` columns.
+///
+/// `Label: value` lines whose value is a space-separated list are wrapped,
+/// with continuation rows aligned under the first value. Anything else
+/// (the ISA string, explain tables) is elided with `…`. Colors active at a
+/// break are closed and reopened on the next row.
+#[must_use]
+pub fn fit_line(line: &str, width: usize) -> Vec<String> {
+    if visible_width(line) <= width {
+        return vec![line.to_string()];
+    }
+
+    let words: Vec<&str> = line.split(' ').collect();
+    let Some(label_end) = words
+        .iter()
+        .take(4)
+        .position(|w| visible_width(w) > 1 && strip_ansi(w).ends_with(':'))
+    else {
+        return vec![elide_ansi(line, width)];
+    };
+    let label = words[..=label_end].join(" ");
+    let indent = visible_width(&label) + 1;
+    if words.len() <= label_end + 2 || indent + MIN_INFO_WIDTH / 2 > width {
+        return vec![elide_ansi(line, width)];
+    }
+
+    let mut rows = Vec::new();
+    let mut row = label;
+    let mut row_width = indent - 1;
+    let mut active = String::new();
+    for word in &words[label_end + 1..] {
+        let word_width = visible_width(word);
+        if row_width > indent - 1 && row_width + 1 + word_width > width {
+            if !active.is_empty() {
+                row.push_str("\x1b[0m");
+            }
+            rows.push(row);
+            row = format!("{}{active}", " ".repeat(indent));
+            row_width = indent;
+        } else {
+            row.push(' ');
+            row_width += 1;
+        }
+        let word = elide_ansi(word, width - indent);
+        row.push_str(&word);
+        row_width += visible_width(&word);
+        track_sgr(&word, &mut active);
+    }
+    rows.push(row);
+    rows
+}
+
+/// Remove ANSI escape sequences from a line
+#[must_use]
+pub fn strip_ansi(line: &str) -> String {
+    let mut plain = String::new();
+    let mut escapes = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            copy_escape(&mut chars, &mut escapes);
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+/// Update `active` with the SGR (color) sequences in `text`; a reset clears it
+fn track_sgr(text: &str, active: &mut String) {
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            let mut seq = String::new();
+            copy_escape(&mut chars, &mut seq);
+            if seq == "\x1b[0m" {
+                active.clear();
+            } else if seq.ends_with('m') {
+                active.push_str(&seq);
+            }
+        }
+    }
+}
+
+// === One-line summary ===
+
+/// Separator between `--oneline` parts
+const ONELINE_SEPARATOR: &str = " · ";
+
+/// Maximum number of Z extensions listed after the base ISA in `--oneline`
+const ONELINE_MAX_EXTS: usize = 4;
+
+/// Single-letter part of the ISA string with IMAFD collapsed to G (`rv64gc`)
+#[must_use]
+pub fn isa_base(isa: &str) -> String {
+    let isa = isa.to_lowercase();
+    let base = isa.split('_').next().unwrap_or(&isa);
+    let (prefix, letters) = if base.starts_with("rv32") || base.starts_with("rv64") {
+        base.split_at(4)
+    } else {
+        ("", base)
+    };
+    letters
+        .strip_prefix("imafd")
+        .map_or_else(|| base.to_string(), |rest| format!("{prefix}g{rest}"))
+}
+
+/// Compact ISA: base with IMAFD collapsed to G, plus notable Z extensions.
+///
+/// Base and hint extensions (Zicsr, Zifencei, Zihintpause, ...) are present
+/// on nearly every Linux-capable core, so they are left out.
+#[must_use]
+pub fn compact_isa(isa: &str) -> String {
+    let isa = isa.to_lowercase();
+    let mut compact = isa_base(&isa);
+
+    let notable: Vec<String> = info::parse_z_extensions_with_category(&isa)
+        .into_iter()
+        .filter(|e| e.category != "base" && e.category != "hint")
+        .map(|e| e.name.to_lowercase())
+        .collect();
+    if !notable.is_empty() {
+        compact.push('+');
+        compact.push_str(&notable[..notable.len().min(ONELINE_MAX_EXTS)].join(" "));
+        if notable.len() > ONELINE_MAX_EXTS {
+            compact.push('…');
+        }
+    }
+    compact
+}
+
+/// Single-line summary for status bars and prompts (`--oneline`)
+#[must_use]
+pub fn format_oneline(data: &SystemInfo) -> String {
+    let mut parts = vec![data.board.clone(), compact_isa(&data.isa)];
+    if data.hart_count > 0 {
+        parts.push(format!(
+            "{} hart{}",
+            data.hart_count,
+            if data.hart_count > 1 { "s" } else { "" }
+        ));
+    }
+    if data.memory_total_bytes > 0 {
+        #[allow(clippy::cast_precision_loss)]
+        let total_gib = data.memory_total_bytes as f64 / 1_073_741_824.0;
+        parts.push(format!("{total_gib:.0} GiB"));
+    }
+    parts.push(
+        data.kernel
+            .split('-')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    );
+    parts.retain(|part| !part.is_empty() && part != "Unknown");
+    parts.join(ONELINE_SEPARATOR)
+}
+
+// === SVG rendering ===
+
+/// Font size of the SVG card in pixels
+const SVG_FONT_SIZE: usize = 14;
+/// Approximate advance of one monospace character at `SVG_FONT_SIZE`
+const SVG_CHAR_WIDTH: f64 = 8.4;
+/// Distance between baselines
+const SVG_LINE_HEIGHT: usize = 18;
+/// Padding around the card content
+const SVG_PADDING: usize = 24;
+/// Longest value shown on the card before it is elided
+const SVG_MAX_VALUE_CHARS: usize = 64;
+
+/// Terminal palette used for the SVG card (approximates bright ANSI colors)
+fn svg_color(color: Color) -> &'static str {
+    match color {
+        Color::BrightBlue => "#89b4fa",
+        Color::BrightMagenta => "#f5c2e7",
+        Color::BrightRed => "#f38ba8",
+        Color::BrightYellow => "#f9e2af",
+        Color::BrightGreen => "#a6e3a1",
+        Color::White | Color::BrightWhite => "#cdd6f4",
+        _ => "#89dceb",
+    }
+}
+
+/// Escape text for SVG/XML content
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Shorten a value to `max` characters, marking the cut with an ellipsis
+fn elide(value: &str, max: usize) -> String {
+    if value.chars().count() <= max {
+        value.to_string()
+    } else {
+        let mut short: String = value.chars().take(max.saturating_sub(1)).collect();
+        short.push('…');
+        short
+    }
+}
+
+/// Key fields shown on the shareable system card
+#[must_use]
+pub fn card_fields(data: &SystemInfo) -> Vec<(&'static str, String)> {
+    let ext_names = |exts: &[info::ExtensionEntry]| {
+        exts.iter()
+            .map(|e| e.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut fields = vec![
+        ("Board", data.board.clone()),
+        ("ISA", data.isa.clone()),
+        ("Ext", ext_names(&data.extensions)),
+        ("Z-Ext", ext_names(&data.z_extensions)),
+        (
+            "Vector",
+            info::parse_vector_from_isa(&data.isa).unwrap_or_default(),
+        ),
+        (
+            "Harts",
+            format!(
+                "{} hart{}",
+                data.hart_count,
+                if data.hart_count > 1 { "s" } else { "" }
+            ),
+        ),
+        ("OS", data.os.clone()),
+        ("Kernel", data.kernel.clone()),
+    ];
+    #[allow(clippy::cast_precision_loss)]
+    let total_gib = data.memory_total_bytes as f64 / 1_073_741_824.0;
+    if data.memory_total_bytes > 0 {
+        fields.push(("Memory", format!("{total_gib:.2} GiB")));
+    }
+    fields.retain(|(_, value)| !value.is_empty());
+    fields
+}
+
+/// Render the logo and key fields as a standalone SVG "system card"
+#[must_use]
+pub fn render_svg(
+    vendor: &str,
+    style: &str,
+    font: FigletFont,
+    fields: &[(&str, String)],
+) -> String {
+    let logo = generate_logo(vendor, LogoStyle::from_str(style), font);
+    let logo_lines: Vec<&str> = logo.lines().filter(|l| !l.trim().is_empty()).collect();
+
+    let label_width = fields.iter().map(|(l, _)| l.len() + 1).max().unwrap_or(0);
+    let field_lines: Vec<(&str, String)> = fields
+        .iter()
+        .map(|(label, value)| (*label, elide(value, SVG_MAX_VALUE_CHARS)))
+        .collect();
+
+    let widest = logo_lines
+        .iter()
+        .map(|l| l.chars().count())
+        .chain(
+            field_lines
+                .iter()
+                .map(|(_, v)| label_width + 1 + v.chars().count()),
+        )
+        .max()
+        .unwrap_or(0);
+    let gap = usize::from(!logo_lines.is_empty());
+    let rows = logo_lines.len() + gap + field_lines.len();
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    let width = (widest as f64 * SVG_CHAR_WIDTH).ceil() as usize + SVG_PADDING * 2;
+    let height = rows * SVG_LINE_HEIGHT + SVG_PADDING * 2;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" rx="12" fill="#1e1e2e"/>"##
+    );
+    let _ = writeln!(
+        svg,
+        r#"<g font-family="'DejaVu Sans Mono', 'Menlo', 'Consolas', monospace" font-size="{SVG_FONT_SIZE}" xml:space="preserve">"#
+    );
+
+    let mut y = SVG_PADDING + SVG_FONT_SIZE;
+    let rainbow = is_default_logo(vendor);
+    for (i, line) in logo_lines.iter().enumerate() {
+        let color = if rainbow {
+            RAINBOW[i % RAINBOW.len()]
+        } else {
+            Color::BrightCyan
+        };
+        let _ = writeln!(
+            svg,
+            r#"<text x="{SVG_PADDING}" y="{y}" fill="{}" font-weight="bold">{}</text>"#,
+            svg_color(color),
+            escape_xml(line)
+        );
+        y += SVG_LINE_HEIGHT;
+    }
+    y += gap * SVG_LINE_HEIGHT;
+
+    for (label, value) in &field_lines {
+        let label = format!("{label}:");
+        let _ = writeln!(
+            svg,
+            r#"<text x="{SVG_PADDING}" y="{y}"><tspan fill="{}" font-weight="bold">{}</tspan><tspan fill="{}">{}</tspan></text>"#,
+            svg_color(Color::BrightCyan),
+            escape_xml(&format!("{label:<width$}", width = label_width + 1)),
+            svg_color(Color::White),
+            escape_xml(value)
+        );
+        y += SVG_LINE_HEIGHT;
+    }
+
+    let _ = writeln!(svg, "</g>");
+    let _ = writeln!(svg, "</svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::sample_info;
+
+    #[test]
+    fn test_should_colorize_auto() {
+        assert!(should_colorize("auto", false, true));
+        assert!(!should_colorize("auto", false, false));
+        assert!(!should_colorize("auto", true, true));
+    }
+
+    #[test]
+    fn test_should_colorize_explicit() {
+        assert!(should_colorize("always", true, false));
+        assert!(!should_colorize("never", false, true));
+    }
+
+    #[test]
+    fn test_visible_width_ignores_ansi() {
+        assert_eq!(visible_width("\x1b[1;96mISA:\x1b[0m rv64"), 9);
+        assert_eq!(visible_width("✓Zba"), 4);
+    }
+
+    #[test]
+    fn test_plain_line() {
+        let line = format!("{}{} {}", "✓".bright_green(), "Zba".bright_green(), "✗Zbc");
+        assert_eq!(plain_line(&line), "+Zba -Zbc");
+        assert_eq!(plain_line("  ↳ /proc/cpuinfo"), "  -> /proc/cpuinfo");
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let logo = vec!["\x1b[1m/\\\x1b[0m".to_string(), "\\/\\/".to_string()];
+        let info = vec![
+            "ISA: rv64gc".to_string(),
+            String::new(),
+            "Harts: 4".to_string(),
+        ];
+        assert_eq!(
+            side_by_side(&logo, &info),
+            vec![
+                "\x1b[1m/\\\x1b[0m     ISA: rv64gc".to_string(),
+                "\\/\\/".to_string(),
+                "       Harts: 4".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_layout_top() {
+        let lines = layout(&["L".to_string()], &["I".to_string()], "top", None);
+        assert_eq!(lines, vec!["L", "", "I"]);
+        // Side layout without a logo falls back to plain info lines
+        assert_eq!(layout(&[], &["I".to_string()], "side", None), vec!["", "I"]);
+    }
+
+    #[test]
+    fn test_logo_colors_parse() {
+        assert_eq!(LogoColors::parse("default"), Ok(LogoColors::Default));
+        assert_eq!(
+            LogoColors::parse("#00c0ff"),
+            Ok(LogoColors::Solid(Color::TrueColor {
+                r: 0,
+                g: 0xc0,
+                b: 0xff
+            }))
+        );
+        assert!(matches!(
+            LogoColors::parse("ff0000, #0000FF"),
+            Ok(LogoColors::Gradient(..))
+        ));
+        assert!(LogoColors::parse("red").is_err());
+        assert!(LogoColors::parse("#ff00").is_err());
+        assert!(LogoColors::parse("#ff0000,").is_err());
+    }
+
+    #[test]
+    fn test_gradient_color_endpoints() {
+        let start = Color::TrueColor { r: 0, g: 0, b: 255 };
+        let end = Color::TrueColor { r: 255, g: 0, b: 0 };
+        assert_eq!(gradient_color(start, end, 0, 5), start);
+        assert_eq!(gradient_color(start, end, 4, 5), end);
+        assert_eq!(
+            gradient_color(start, end, 2, 5),
+            Color::TrueColor {
+                r: 128,
+                g: 0,
+                b: 128
+            }
+        );
+        // A single line uses the start color
+        assert_eq!(gradient_color(start, end, 0, 1), start);
+    }
+
+    #[test]
+    fn test_elide_ansi() {
+        assert_eq!(elide_ansi("rv64imafdc_zicsr", 8), "rv64ima…");
+        assert_eq!(elide_ansi("short", 8), "short");
+        assert_eq!(
+            elide_ansi("\x1b[37mrv64imafdc\x1b[0m", 5),
+            "\x1b[37mrv64…\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_fit_line_wraps_fields() {
+        let line = "Z-Bit Manipulation: Zba Zbb Zbc Zbs Zbkb Zbkc Zbkx";
+        assert_eq!(
+            fit_line(line, 40),
+            vec![
+                "Z-Bit Manipulation: Zba Zbb Zbc Zbs Zbkb",
+                "                    Zbkc Zbkx",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fit_line_reopens_colors() {
+        let line = "\x1b[1mExt:\x1b[0m \x1b[37mA B C D E F G H I J K L M N O P Q R S T\x1b[0m";
+        let rows = fit_line(line, 34);
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].ends_with("\x1b[0m"));
+        assert!(rows[1].starts_with("     \x1b[37m"));
+        assert!(rows.iter().all(|r| visible_width(r) <= 34));
+    }
+
+    #[test]
+    fn test_fit_line_elides_isa() {
+        let line = "ISA: rv64imafdcv_zicbom_zicboz_zicntr_zicsr_zifencei_zihintpause";
+        assert_eq!(fit_line(line, 20), vec!["ISA: rv64imafdcv_zi…"]);
+    }
+
+    #[test]
+    fn test_elide() {
+        assert_eq!(elide("short", 10), "short");
+        assert_eq!(elide("abcdefghij", 5), "abcd…");
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("a<b>&\"c\""), "a&lt;b&gt;&amp;&quot;c&quot;");
+    }
+
+    #[test]
+    fn test_card_fields() {
+        let fields = card_fields(&sample_info());
+        assert_eq!(fields[0], ("Board", "StarFive VisionFive 2".to_string()));
+        assert!(fields.contains(&("Harts", "4 harts".to_string())));
+        assert!(fields.contains(&("Memory", "8.00 GiB".to_string())));
+        // Vector is not available on the sample board, so the field is dropped
+        assert!(!fields.iter().any(|(label, _)| *label == "Vector"));
+    }
+
+    #[test]
+    fn test_compact_isa() {
+        assert_eq!(
+            compact_isa("rv64imafdc_zicsr_zifencei_zba_zbb"),
+            "rv64gc+zba zbb"
+        );
+        assert_eq!(compact_isa("rv64imac"), "rv64imac");
+        assert_eq!(
+            compact_isa("rv64imafdcv_zicbom_zicboz_zba_zbb_zbc_zbs"),
+            "rv64gcv+zicbom zicboz zba zbb…"
+        );
+    }
+
+    #[test]
+    fn test_format_oneline() {
+        assert_eq!(
+            format_oneline(&sample_info()),
+            "StarFive VisionFive 2 · rv64gc+zba zbb · 4 harts · 8 GiB · 6.6.20"
+        );
+    }
+
+    #[test]
+    fn test_logo_colors_override_art() {
+        let red = Color::TrueColor { r: 255, g: 0, b: 0 };
+        let lines = logo_lines(
+            "sifive",
+            "normal",
+            FigletFont::Standard,
+            LogoColors::Solid(red),
+        );
+        assert!(lines.iter().any(|l| strip_ansi(l).contains("███████╗")));
+        assert!(lines.iter().all(|l| !l.contains("\x1b[0;90m")));
+    }
+
+    #[test]
+    fn test_logo_gallery_lists_every_vendor() {
+        let lines: Vec<String> = logo_gallery(FigletFont::Standard, LogoColors::Default)
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        for (aliases, name, _) in crate::vendors::VENDORS {
+            let heading = format!("{} ({name})", aliases[0]);
+            let at = lines.iter().position(|l| *l == heading).unwrap();
+            // Normal logo, blank line, small logo with the name beside the mark
+            let small = &lines[at + 1..];
+            assert!(small.iter().any(|l| l.ends_with(name)));
+        }
+        assert!(lines[0].starts_with("default (RISC-V)"));
+    }
+
+    #[test]
+    fn test_render_svg_document() {
+        let svg = render_svg(
+            "default",
+            "normal",
+            FigletFont::Standard,
+            &card_fields(&sample_info()),
+        );
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("StarFive VisionFive 2"));
+        // Rainbow logo uses more than one color
+        assert!(svg.contains("#89dceb") && svg.contains("#89b4fa"));
+    }
+
+    #[test]
+    fn test_render_svg_no_logo() {
+        let svg = render_svg(
+            "default",
+            "none",
+            FigletFont::Standard,
+            &[("ISA", "rv64gc".to_string())],
+        );
+        assert_eq!(svg.matches("<text").count(), 1);
     }
 }
@@ -0,0 +1,151 @@
+//! Distribution logos for `--logo os`
+//!
+//! Parallel to `vendors.rs`: distributions that ship RISC-V ports, matched
+//! against `ID` / `ID_LIKE` in /etc/os-release.
+
+const DEBIAN_LOGO: &str = r"
+        _____
+       /  __ \
+      |  /    |
+      |  \___-
+      -_
+        --_
+            Debian
+";
+
+const UBUNTU_LOGO: &str = r"
+             _
+         ---(_)
+     _/  ---  \
+    (_) |   |
+      \  --- _/
+         ---(_)
+            Ubuntu
+";
+
+const FEDORA_LOGO: &str = r"
+          _____
+         /   __)\
+         |  /  \ \
+      ___|  |__/ /
+     / (_    _)_/
+    / /  |  |
+    \ \__/  |
+     \(_____/
+            Fedora
+";
+
+const ARCH_LOGO: &str = r"
+           /\
+          /  \
+         /\   \
+        /      \
+       /   ,,   \
+      /   |  |  -\
+     /_-''    ''-_\
+          Arch Linux
+";
+
+/// Distribution definitions
+/// Format: (os-release ids, display_name, logo)
+///
+/// - os-release ids: lowercase `ID` values (first one is primary, also
+///   accepted by `--logo`)
+/// - display_name: name used for the small logo style
+/// - logo: ASCII art for the normal style
+pub const DISTROS: &[(&[&str], &str, &str)] = &[
+    (&["debian", "raspbian"], "Debian", DEBIAN_LOGO),
+    (&["ubuntu"], "Ubuntu", UBUNTU_LOGO),
+    (&["fedora"], "Fedora", FEDORA_LOGO),
+    (&["arch", "archlinux", "archriscv"], "Arch Linux", ARCH_LOGO),
+];
+
+/// Get distro info by id
+/// Returns (display_name, logo) or None if not found
+#[must_use]
+pub fn get_distro_info(id: &str) -> Option<(&'static str, &'static str)> {
+    let id_lower = id.to_lowercase();
+    DISTROS
+        .iter()
+        .find(|(ids, _, _)| ids.contains(&id_lower.as_str()))
+        .map(|(_, name, logo)| (*name, *logo))
+}
+
+/// Read a key from os-release content, without quotes
+fn os_release_value<'a>(os_release: &'a str, key: &str) -> Option<&'a str> {
+    os_release.lines().find_map(|line| {
+        line.strip_prefix(key)?
+            .strip_prefix('=')
+            .map(|v| v.trim().trim_matches('"').trim_matches('\''))
+    })
+}
+
+/// Detect the distro from /etc/os-release content.
+/// `ID` wins; otherwise the first known entry of `ID_LIKE` (e.g. Ubuntu
+/// derivatives fall back to the Ubuntu logo).
+/// Returns the primary id, or None if unknown.
+#[must_use]
+pub fn detect_distro(os_release: &str) -> Option<&'static str> {
+    let primary = |id: &str| {
+        let id_lower = id.to_lowercase();
+        DISTROS
+            .iter()
+            .find(|(ids, _, _)| ids.contains(&id_lower.as_str()))
+            .map(|(ids, _, _)| ids[0])
+    };
+
+    os_release_value(os_release, "ID")
+        .and_then(primary)
+        .or_else(|| {
+            os_release_value(os_release, "ID_LIKE")?
+                .split_whitespace()
+                .find_map(primary)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_distro_info() {
+        assert_eq!(get_distro_info("debian").map(|(n, _)| n), Some("Debian"));
+        assert_eq!(
+            get_distro_info("ArchRISCV").map(|(n, _)| n),
+            Some("Arch Linux")
+        );
+        assert!(get_distro_info("starfive").is_none());
+    }
+
+    #[test]
+    fn test_detect_distro_id() {
+        let os_release =
+            "PRETTY_NAME=\"Debian GNU/Linux trixie/sid\"\nNAME=\"Debian GNU/Linux\"\nID=debian\n";
+        assert_eq!(detect_distro(os_release), Some("debian"));
+        assert_eq!(detect_distro("ID=\"fedora\"\n"), Some("fedora"));
+        assert_eq!(detect_distro("ID=archriscv\n"), Some("arch"));
+    }
+
+    #[test]
+    fn test_detect_distro_id_like() {
+        // Bianbu (SpacemiT) is Ubuntu-based
+        let os_release = "ID=bianbu\nID_LIKE=\"ubuntu debian\"\n";
+        assert_eq!(detect_distro(os_release), Some("ubuntu"));
+    }
+
+    #[test]
+    fn test_detect_distro_unknown() {
+        assert_eq!(detect_distro("ID=openwrt\n"), None);
+        assert_eq!(detect_distro(""), None);
+        // VERSION_ID must not be mistaken for ID
+        assert_eq!(detect_distro("VERSION_ID=\"12\"\n"), None);
+    }
+
+    #[test]
+    fn test_all_distros_have_logos() {
+        for (ids, name, logo) in DISTROS {
+            assert!(!ids.is_empty());
+            assert!(logo.contains(name), "{name} logo lacks its name");
+        }
+    }
+}
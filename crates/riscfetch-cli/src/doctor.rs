@@ -0,0 +1,393 @@
+//! Explanations for missing or odd fields (`riscfetch doctor`)
+//!
+//! Facts about the kernel, firmware and environment are gathered once, then
+//! each check turns them into findings with a suggested fix, so "field X is
+//! empty on my board" can be answered without a bug report.
+
+use colored::Colorize;
+use riscfetch_core::{self as info, Source};
+use serde::Serialize;
+use std::path::Path;
+
+/// Whether new processes may use the vector unit (Linux 6.5+)
+const V_DEFAULT_ALLOW: &str = "/proc/sys/abi/riscv_v_default_allow";
+
+/// Paths riscfetch reads that are worth a permission check
+const READ_PATHS: [&str; 6] = [
+    "/proc/cpuinfo",
+    "/proc/device-tree/model",
+    "/sys/devices/system/cpu/cpu0/cache/index0/size",
+    "/proc/device-tree/cpus/cpu@0/riscv,vlenb",
+    "/sys/devices/system/cpu/cpu0/riscv/vlen",
+    "/etc/os-release",
+];
+
+/// Everything the checks look at
+#[derive(Debug, Default)]
+pub struct Facts {
+    pub kernel: String,
+    pub isa: String,
+    /// ISA properties from the device tree, joined by spaces
+    pub dt_isa: Option<String>,
+    pub has_device_tree: bool,
+    pub has_acpi: bool,
+    /// Container runtime, if riscfetch runs inside one
+    pub container: Option<&'static str>,
+    /// Contents of `riscv_v_default_allow`
+    pub v_default_allow: Option<String>,
+    /// Paths that exist but could not be read
+    pub denied: Vec<String>,
+    pub sources: Vec<(&'static str, Source)>,
+}
+
+impl Facts {
+    /// Gather facts from this machine
+    #[must_use]
+    pub fn gather() -> Self {
+        let denied = READ_PATHS
+            .iter()
+            .filter(|path| {
+                std::fs::File::open(path)
+                    .is_err_and(|e| e.kind() == std::io::ErrorKind::PermissionDenied)
+            })
+            .map(|path| (*path).to_string())
+            .collect();
+        Self {
+            kernel: info::get_kernel_info(),
+            isa: info::get_isa_string(),
+            dt_isa: info::get_dt_isa(),
+            has_device_tree: Path::new("/proc/device-tree").exists()
+                || Path::new("/sys/firmware/devicetree/base").exists(),
+            has_acpi: Path::new("/sys/firmware/acpi").exists(),
+            container: info::get_container(),
+            v_default_allow: std::fs::read_to_string(V_DEFAULT_ALLOW)
+                .ok()
+                .map(|s| s.trim().to_string()),
+            denied,
+            sources: info::collect_sources(),
+        }
+    }
+}
+
+/// Result of one check
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct Finding {
+    /// False when something is missing or degraded
+    pub ok: bool,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    fn ok(title: String) -> Self {
+        Self {
+            ok: true,
+            title,
+            detail: None,
+            fix: None,
+        }
+    }
+
+    fn problem(title: String, detail: &str, fix: &str) -> Self {
+        Self {
+            ok: false,
+            title,
+            detail: Some(detail.to_string()),
+            fix: Some(fix.to_string()),
+        }
+    }
+}
+
+/// Whether the device-tree ISA properties list `ext`
+fn dt_lists(facts: &Facts, ext: &str) -> bool {
+    facts
+        .dt_isa
+        .as_deref()
+        .is_some_and(|dt| info::dt_isa_lists(dt, ext))
+}
+
+fn check_kernel(facts: &Facts) -> Option<Finding> {
+    let hwprobe = info::kernel_feature("hwprobe")?;
+    let (major, minor) = hwprobe.min_version;
+    Some(match info::parse_kernel_version(&facts.kernel) {
+        Some(_) => match hwprobe.requirement(&facts.kernel) {
+            None => Finding::ok(format!("Kernel {} has riscv_hwprobe", facts.kernel)),
+            Some(requirement) => Finding::problem(
+                format!("Kernel {} is older than {major}.{minor}", facts.kernel),
+                &format!("The riscv_hwprobe syscall {requirement}, and /proc/cpuinfo often lists only the single-letter extensions."),
+                &format!("Upgrade to Linux {major}.{minor} or newer (6.8+ also reports per-hart extensions)."),
+            ),
+        },
+        None => Finding::problem(
+            format!("Kernel version unknown ({})", facts.kernel),
+            "The uname system call did not return a version.",
+            "Report the kernel release shown here so it can be parsed.",
+        ),
+    })
+}
+
+fn check_device_tree(facts: &Facts) -> Option<Finding> {
+    if facts.has_device_tree {
+        return Some(Finding::ok("Device tree available".to_string()));
+    }
+    if facts.has_acpi {
+        Some(Finding::problem(
+            "No device tree (ACPI system?)".to_string(),
+            "The board model comes from /proc/device-tree, which ACPI firmware does not provide.",
+            "Boot with a device tree if the firmware offers one; otherwise the board stays unknown.",
+        ))
+    } else if facts.container.is_some() {
+        // Reported by the container check
+        None
+    } else {
+        Some(Finding::problem(
+            "No device tree".to_string(),
+            "Neither /proc/device-tree nor /sys/firmware/devicetree/base exists.",
+            "Check that /proc and /sys are mounted and the kernel has CONFIG_OF and CONFIG_PROC_DEVICETREE.",
+        ))
+    }
+}
+
+/// Extensions the hardware has but the kernel is too old to expose
+fn check_kernel_features(facts: &Facts) -> impl Iterator<Item = Finding> + '_ {
+    info::KERNEL_FEATURES.iter().filter_map(|feature| {
+        let ext = feature.extension?;
+        if !dt_lists(facts, ext) {
+            return None;
+        }
+        let requirement = feature.requirement(&facts.kernel)?;
+        let (major, minor) = feature.min_version;
+        Some(Finding::problem(
+            format!("{}: {requirement}", feature.description),
+            &format!("The device tree lists {ext}, but this kernel cannot expose it to programs."),
+            &format!("Upgrade to Linux {major}.{minor} or newer."),
+        ))
+    })
+}
+
+fn check_vector(facts: &Facts) -> Option<Finding> {
+    let in_cpuinfo = info::isa_supports(&facts.isa, "v");
+    let in_dt = dt_lists(facts, "v");
+    let kernel_too_old = info::kernel_feature("vector")
+        .and_then(|feature| feature.requirement(&facts.kernel))
+        .is_some();
+    if !in_cpuinfo && in_dt && kernel_too_old {
+        // Reported by the kernel feature check
+        None
+    } else if !in_cpuinfo && in_dt {
+        Some(Finding::problem(
+            "Vector disabled by the kernel".to_string(),
+            "The device tree lists V, but /proc/cpuinfo does not.",
+            "Use a kernel built with CONFIG_RISCV_ISA_V=y (Linux 6.5+).",
+        ))
+    } else if in_cpuinfo && facts.v_default_allow.as_deref() == Some("0") {
+        Some(Finding::problem(
+            "Vector off by default for new processes".to_string(),
+            "riscv_v_default_allow is 0, so programs must enable V with prctl(PR_RISCV_V_SET_CONTROL).",
+            "Run `echo 1 > /proc/sys/abi/riscv_v_default_allow` as root.",
+        ))
+    } else if in_cpuinfo {
+        Some(Finding::ok("Vector enabled".to_string()))
+    } else {
+        None
+    }
+}
+
+fn check_container(facts: &Facts) -> Option<Finding> {
+    facts.container.map(|runtime| {
+        Finding::problem(
+            format!("Running in a container ({runtime})"),
+            "The device tree and parts of sysfs are often not mounted in containers, so board, cache and vector details may be missing.",
+            "Run riscfetch on the host, or bind-mount /proc/device-tree and /sys read-only.",
+        )
+    })
+}
+
+fn check_permissions(facts: &Facts) -> impl Iterator<Item = Finding> + '_ {
+    facts.denied.iter().map(|path| {
+        Finding::problem(
+            format!("Permission denied: {path}"),
+            "The file exists but cannot be read by this user.",
+            "Run as a user allowed to read it, or relax the permissions of that path.",
+        )
+    })
+}
+
+fn check_fields(facts: &Facts) -> impl Iterator<Item = Finding> + '_ {
+    facts
+        .sources
+        .iter()
+        .filter_map(|(id, source)| match source {
+            Source::Missing { .. } => Some(Finding::problem(
+                format!("Field `{id}` is empty"),
+                &source.to_string(),
+                "See the findings above; `riscfetch --verbose` shows where each field came from.",
+            )),
+            Source::Fallback { .. } => Some(Finding::problem(
+                format!("Field `{id}` uses a fallback value"),
+                &source.to_string(),
+                "See the findings above; `riscfetch --verbose` shows where each field came from.",
+            )),
+            _ => None,
+        })
+}
+
+/// Run every check, in display order
+#[must_use]
+pub fn diagnose(facts: &Facts) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = check_kernel(facts).into_iter().collect();
+    findings.extend(check_device_tree(facts));
+    findings.extend(check_kernel_features(facts));
+    findings.extend(check_vector(facts));
+    findings.extend(check_container(facts));
+    findings.extend(check_permissions(facts));
+    findings.extend(check_fields(facts));
+    findings
+}
+
+/// Human-readable report: one line per finding, problems followed by
+/// their explanation and fix, then a summary
+#[must_use]
+pub fn render(findings: &[Finding]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for finding in findings {
+        if finding.ok {
+            lines.push(format!("{} {}", "✓".bright_green().bold(), finding.title));
+            continue;
+        }
+        lines.push(format!(
+            "{} {}",
+            "!".bright_yellow().bold(),
+            finding.title.bright_yellow()
+        ));
+        if let Some(detail) = &finding.detail {
+            lines.push(format!("  {detail}"));
+        }
+        if let Some(fix) = &finding.fix {
+            lines.push(format!("  {} {fix}", "Fix:".bright_cyan().bold()));
+        }
+    }
+    let problems = findings.iter().filter(|f| !f.ok).count();
+    lines.push(String::new());
+    lines.push(match problems {
+        0 => "No problems found".bright_green().to_string(),
+        1 => "1 problem found".bright_yellow().to_string(),
+        n => format!("{n} problems found").bright_yellow().to_string(),
+    });
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+
+    fn healthy() -> Facts {
+        Facts {
+            kernel: "6.6.20-starfive".to_string(),
+            isa: "rv64imafdcv_zicsr".to_string(),
+            has_device_tree: true,
+            ..Facts::default()
+        }
+    }
+
+    fn titles(facts: &Facts) -> Vec<String> {
+        diagnose(facts).into_iter().map(|f| f.title).collect()
+    }
+
+    #[test]
+    fn test_diagnose_kernel_feature_gates() {
+        let facts = Facts {
+            kernel: "6.1.80".to_string(),
+            isa: "rv64imafdc_zicsr".to_string(),
+            dt_isa: Some("rv64imafdcv i m a f d c v zicsr zicboz".to_string()),
+            ..healthy()
+        };
+        let findings = diagnose(&facts);
+        let titles: Vec<&str> = findings.iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(titles[0], "Kernel 6.1.80 is older than 6.4");
+        assert_eq!(
+            findings[0].detail.as_deref(),
+            Some("The riscv_hwprobe syscall requires kernel ≥ 6.4 (you have 6.1), and /proc/cpuinfo often lists only the single-letter extensions.")
+        );
+        assert!(
+            titles.contains(&"Vector support for user space: requires kernel ≥ 6.5 (you have 6.1)")
+        );
+        assert!(titles
+            .contains(&"Zicboz (cbo.zero) in user space: requires kernel ≥ 6.7 (you have 6.1)"));
+        // The feature gate explains the missing vector unit
+        assert!(!titles.contains(&"Vector disabled by the kernel"));
+    }
+
+    #[test]
+    fn test_diagnose_healthy() {
+        let findings = diagnose(&healthy());
+        assert!(findings.iter().all(|f| f.ok));
+        assert_eq!(
+            strip_ansi(render(&findings).last().unwrap()),
+            "No problems found"
+        );
+    }
+
+    #[test]
+    fn test_diagnose_old_kernel_and_acpi() {
+        let facts = Facts {
+            kernel: "5.15.0".to_string(),
+            has_device_tree: false,
+            has_acpi: true,
+            ..healthy()
+        };
+        let titles = titles(&facts);
+        assert_eq!(titles[0], "Kernel 5.15.0 is older than 6.4");
+        assert_eq!(titles[1], "No device tree (ACPI system?)");
+    }
+
+    #[test]
+    fn test_diagnose_vector_disabled() {
+        let facts = Facts {
+            isa: "rv64imafdc_zicsr".to_string(),
+            dt_isa: Some("rv64imafdcv i m a f d c v zicsr".to_string()),
+            ..healthy()
+        };
+        assert!(titles(&facts).contains(&"Vector disabled by the kernel".to_string()));
+
+        let facts = Facts {
+            v_default_allow: Some("0".to_string()),
+            ..healthy()
+        };
+        assert!(titles(&facts).contains(&"Vector off by default for new processes".to_string()));
+    }
+
+    #[test]
+    fn test_diagnose_container_and_fields() {
+        let facts = Facts {
+            has_device_tree: false,
+            container: Some("docker"),
+            sources: vec![(
+                "board",
+                Source::Missing {
+                    tried: "/proc/device-tree/model".to_string(),
+                },
+            )],
+            ..healthy()
+        };
+        let findings = diagnose(&facts);
+        let titles: Vec<&str> = findings.iter().map(|f| f.title.as_str()).collect();
+        assert!(titles.contains(&"Running in a container (docker)"));
+        // The container explains the missing device tree; no separate finding
+        assert!(!titles.iter().any(|t| t.starts_with("No device tree")));
+        let board = findings
+            .iter()
+            .find(|f| f.title == "Field `board` is empty")
+            .unwrap();
+        assert_eq!(
+            board.detail.as_deref(),
+            Some("missing (not found in /proc/device-tree/model)")
+        );
+        let lines: Vec<String> = render(&findings).iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(lines.last().unwrap(), "2 problems found");
+    }
+}
@@ -0,0 +1,63 @@
+//! Device-tree dump (`riscfetch dt`)
+//!
+//! Prints the nodes and properties of `/proc/device-tree` that the
+//! detectors read, decoded, to check what a board's device tree actually
+//! provides when a field is missing or wrong.
+
+use colored::Colorize;
+use riscfetch_core::DtNode;
+
+/// One line per node path, then its properties with aligned values
+#[must_use]
+pub fn render(nodes: &[DtNode]) -> Vec<String> {
+    let width = nodes
+        .iter()
+        .flat_map(|node| node.properties.iter().map(|(name, _)| name.len()))
+        .max()
+        .unwrap_or(0);
+    let mut lines = Vec::new();
+    for node in nodes {
+        lines.push(node.path.bright_cyan().bold().to_string());
+        for (name, value) in &node.properties {
+            lines.push(format!("  {}  {value}", format!("{name:<width$}").yellow()));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+    use riscfetch_core::DtValue;
+
+    #[test]
+    fn test_render() {
+        let nodes = [
+            DtNode {
+                path: "/cpus".to_string(),
+                properties: vec![(
+                    "timebase-frequency".to_string(),
+                    DtValue::Cells(vec![4_000_000]),
+                )],
+            },
+            DtNode {
+                path: "/cpus/cpu@0".to_string(),
+                properties: vec![(
+                    "compatible".to_string(),
+                    DtValue::Strings(vec!["sifive,u74-mc".to_string(), "riscv".to_string()]),
+                )],
+            },
+        ];
+        let lines: Vec<String> = render(&nodes).iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(
+            lines,
+            [
+                "/cpus",
+                "  timebase-frequency  4000000",
+                "/cpus/cpu@0",
+                "  compatible          \"sifive,u74-mc\", \"riscv\"",
+            ]
+        );
+    }
+}
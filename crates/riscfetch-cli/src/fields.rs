@@ -0,0 +1,1774 @@
+//! Field registry for the normal display
+//!
+//! Each line group of the decorated output (ISA, extensions, memory, ...) is
+//! a `Field` with a stable id, so fields can be hidden or shown with
+//! `--hide` / `--show` and the config file without writing a template.
+
+use crate::config::Config;
+use colored::Colorize;
+use riscfetch_core as info;
+
+/// Fields that do not change while riscfetch runs (collected once per `--watch` session)
+pub struct StaticInfo {
+    /// Extensions common to all harts
+    pub isa: String,
+    /// Set when the harts' ISA lines differ
+    pub isa_divergence: Option<info::IsaDivergence>,
+    pub vector: String,
+    pub harts: String,
+    pub hw_ids: info::HardwareIds,
+    pub cache: String,
+    pub board: String,
+    /// Catalog entry for the board, shown with `--explain` / `--all`
+    pub board_details: Option<&'static info::Board>,
+    /// Container runtime; the board line notes that host details are missing
+    pub container: Option<&'static str>,
+    pub os: String,
+    pub kernel: String,
+    pub init: Option<info::InitSystem>,
+    /// DRM cards with their kernel and Mesa drivers (no line when empty)
+    pub gpus: Vec<info::GpuInfo>,
+    /// Connected displays (no line when empty)
+    pub displays: Vec<info::DisplayInfo>,
+    pub user: String,
+    pub hostname: String,
+    /// Fields from the user's plugins (empty with `--no-plugins`)
+    pub custom: crate::plugins::Plugins,
+    /// Where each field came from, keyed by field id (empty unless `--verbose`)
+    pub sources: Vec<(&'static str, String)>,
+}
+
+impl StaticInfo {
+    pub fn collect() -> Self {
+        let isa = info::get_isa_string();
+        Self {
+            vector: format_vector(&isa, &info::get_vector_detail()),
+            isa,
+            isa_divergence: info::get_isa_divergence(),
+            harts: info::get_hart_count(),
+            hw_ids: info::get_hardware_ids(),
+            cache: info::get_cache_info(),
+            board: info::get_board_info(),
+            board_details: info::get_board_details(),
+            container: info::get_container(),
+            os: info::get_os_info(),
+            kernel: info::get_kernel_info(),
+            init: info::get_init_system(),
+            displays: info::get_display_info(),
+            gpus: info::get_gpu_info(),
+            user: crate::current_user(),
+            hostname: crate::hostname(),
+            custom: crate::plugins::Plugins::default(),
+            sources: Vec::new(),
+        }
+    }
+
+    /// Record the source of every field for `--verbose`
+    #[must_use]
+    pub fn with_sources(mut self) -> Self {
+        let kernel_hints =
+            info::kernel_hints(&self.kernel, &self.isa, info::get_dt_isa().as_deref());
+        self.sources = info::collect_sources()
+            .into_iter()
+            .map(|(id, source)| match id {
+                // Capabilities the kernel is too old for are explained here
+                "kernel" if !kernel_hints.is_empty() => {
+                    (id, format!("{source}; {}", kernel_hints.join("; ")))
+                }
+                _ => (id, source.to_string()),
+            })
+            .collect();
+        self.sources
+            .push(("ext", "derived from the ISA string".to_string()));
+        self.sources
+            .push(("user", "$USER, gethostname(2)".to_string()));
+        if let Some(dir) = crate::plugins::dir() {
+            let mut source = format!("plugins in {}", dir.display());
+            if !self.custom.failures.is_empty() {
+                source.push_str(&format!("; failed: {}", self.custom.failures.join(", ")));
+            }
+            self.sources.push(("custom", source));
+        }
+        self
+    }
+
+    fn source(&self, id: &str) -> Option<&str> {
+        self.sources
+            .iter()
+            .find(|(field, _)| *field == id)
+            .map(|(_, source)| source.as_str())
+    }
+}
+
+/// Display switches that affect how fields render
+pub struct RenderOptions {
+    pub explain: bool,
+    pub show_all: bool,
+    /// Follow each field with the file, command or fallback it came from
+    pub verbose: bool,
+    /// Output for pipes: no separator between RISC-V and general fields
+    pub plain: bool,
+}
+
+/// One entry of the normal display
+#[derive(Debug)]
+pub struct Field {
+    /// Id used by `--hide`, `--show` and the config file
+    pub id: &'static str,
+    /// RISC-V specific (kept with `--riscv-only`)
+    pub riscv: bool,
+    /// Shown unless hidden; fields with `false` need `--show`
+    pub default_visible: bool,
+    render: fn(&StaticInfo, &RenderOptions, &mut Vec<String>),
+}
+
+/// All fields in display order
+pub const FIELDS: &[Field] = &[
+    Field {
+        id: "isa",
+        riscv: true,
+        default_visible: true,
+        render: render_isa,
+    },
+    Field {
+        id: "ext",
+        riscv: true,
+        default_visible: true,
+        render: render_extensions,
+    },
+    Field {
+        id: "vector",
+        riscv: true,
+        default_visible: true,
+        render: render_vector,
+    },
+    Field {
+        id: "harts",
+        riscv: true,
+        default_visible: true,
+        render: render_harts,
+    },
+    Field {
+        id: "hwids",
+        riscv: true,
+        default_visible: true,
+        render: render_hw_ids,
+    },
+    Field {
+        id: "cache",
+        riscv: true,
+        default_visible: true,
+        render: render_cache,
+    },
+    Field {
+        id: "board",
+        riscv: false,
+        default_visible: true,
+        render: render_board,
+    },
+    Field {
+        id: "os",
+        riscv: false,
+        default_visible: true,
+        render: render_os,
+    },
+    Field {
+        id: "kernel",
+        riscv: false,
+        default_visible: true,
+        render: render_kernel,
+    },
+    Field {
+        id: "init",
+        riscv: false,
+        default_visible: true,
+        render: render_init,
+    },
+    Field {
+        id: "display",
+        riscv: false,
+        default_visible: true,
+        render: render_display,
+    },
+    Field {
+        id: "gpu",
+        riscv: false,
+        default_visible: true,
+        render: render_gpu,
+    },
+    Field {
+        id: "memory",
+        riscv: false,
+        default_visible: true,
+        render: render_memory,
+    },
+    Field {
+        id: "disk",
+        riscv: false,
+        default_visible: true,
+        render: render_disk,
+    },
+    Field {
+        id: "mmc",
+        riscv: false,
+        default_visible: false,
+        render: render_mmc,
+    },
+    Field {
+        id: "uptime",
+        riscv: false,
+        default_visible: true,
+        render: render_uptime,
+    },
+    Field {
+        id: "load",
+        riscv: false,
+        default_visible: true,
+        render: render_load,
+    },
+    Field {
+        id: "cpufreq",
+        riscv: false,
+        default_visible: false,
+        render: render_cpufreq,
+    },
+    Field {
+        id: "thermal",
+        riscv: false,
+        default_visible: false,
+        render: render_thermal,
+    },
+    Field {
+        id: "sensors",
+        riscv: false,
+        default_visible: false,
+        render: render_sensors,
+    },
+    Field {
+        id: "power",
+        riscv: false,
+        default_visible: true,
+        render: render_power,
+    },
+    Field {
+        id: "rails",
+        riscv: false,
+        default_visible: false,
+        render: render_rails,
+    },
+    Field {
+        id: "user",
+        riscv: false,
+        default_visible: true,
+        render: render_user,
+    },
+    Field {
+        id: "custom",
+        riscv: false,
+        default_visible: true,
+        render: render_custom,
+    },
+];
+
+/// Index of a field id in `FIELDS`
+fn index_of(id: &str) -> Result<usize, String> {
+    FIELDS
+        .iter()
+        .position(|f| f.id == id.trim())
+        .ok_or_else(|| format!("unknown field '{id}' (available: {})", ids_list()))
+}
+
+/// Fields hidden by default that `--watch` shows, since they change
+/// between refreshes
+pub const WATCH_FIELDS: [&str; 2] = ["cpufreq", "thermal"];
+
+/// Fields to display, after applying the config file and then `--show` / `--hide`.
+/// Later sources win, so `--show uptime` overrides `hide = ["uptime"]` in the config.
+/// With `watch`, [`WATCH_FIELDS`] start out visible.
+///
+/// Fields named in the config `order` come first, in that order; the rest
+/// follow in their default order.
+pub fn visible_fields(
+    config: &Config,
+    show: &[String],
+    hide: &[String],
+    watch: bool,
+) -> Result<Vec<&'static Field>, String> {
+    let mut visible: Vec<bool> = FIELDS
+        .iter()
+        .map(|f| f.default_visible || (watch && WATCH_FIELDS.contains(&f.id)))
+        .collect();
+    for (ids, state) in [
+        (config.show.as_slice(), true),
+        (config.hide.as_slice(), false),
+        (show, true),
+        (hide, false),
+    ] {
+        for id in ids {
+            visible[index_of(id)?] = state;
+        }
+    }
+
+    let mut order = Vec::with_capacity(FIELDS.len());
+    for id in &config.order {
+        let index = index_of(id)?;
+        if !order.contains(&index) {
+            order.push(index);
+        }
+    }
+    order.extend(
+        (0..FIELDS.len()).filter(|i| !config.order.iter().any(|id| id.trim() == FIELDS[*i].id)),
+    );
+
+    Ok(order
+        .into_iter()
+        .filter(|&i| visible[i])
+        .map(|i| &FIELDS[i])
+        .collect())
+}
+
+/// Comma-separated list of all field ids
+#[must_use]
+pub fn ids_list() -> String {
+    FIELDS.iter().map(|f| f.id).collect::<Vec<_>>().join(", ")
+}
+
+/// Whether `id` is a RISC-V specific field (kept with `--riscv-only`)
+#[must_use]
+pub fn is_riscv_field(id: &str) -> bool {
+    FIELDS.iter().any(|f| f.id == id && f.riscv)
+}
+
+/// Build the colored info lines for `fields`, in the given order.
+///
+/// A separator goes wherever output switches between RISC-V fields and
+/// general ones, so the default order has exactly one (none when `plain`).
+#[must_use]
+pub fn info_lines(
+    fixed: &StaticInfo,
+    fields: &[&Field],
+    opts: &RenderOptions,
+    riscv_only: bool,
+) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut last_group = None;
+    for field in fields {
+        if riscv_only && !field.riscv {
+            continue;
+        }
+        let mut lines = Vec::new();
+        (field.render)(fixed, opts, &mut lines);
+        if lines.is_empty() {
+            continue;
+        }
+        if !opts.plain && last_group.is_some_and(|riscv| riscv != field.riscv) {
+            out.push(String::new());
+            out.push(format!(
+                "{}",
+                "--------------------------------".bright_black()
+            ));
+            out.push(String::new());
+        }
+        last_group = Some(field.riscv);
+        out.extend(lines);
+        if opts.verbose {
+            if let Some(source) = fixed.source(field.id) {
+                out.push(format!("  {}", format!("↳ {source}").bright_black()));
+            }
+        }
+    }
+    out
+}
+
+fn render_isa(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    out.push(format!(
+        "{} {}",
+        "ISA:".bright_cyan().bold(),
+        fixed.isa.white()
+    ));
+    if let Some(divergence) = &fixed.isa_divergence {
+        out.push(format!(
+            "  {} {}",
+            "!".bright_yellow().bold(),
+            format!(
+                "harts differ: {} only on some harts (showing the common set)",
+                divergence.partial_extensions.join(" ")
+            )
+            .bright_yellow()
+        ));
+    }
+}
+
+fn render_extensions(fixed: &StaticInfo, opts: &RenderOptions, out: &mut Vec<String>) {
+    let isa_string = &fixed.isa;
+    if opts.show_all {
+        // Show ALL extensions with checkmarks
+        let all_std = info::get_all_standard_extensions_with_status(isa_string);
+        let all_z = info::get_all_z_extensions_with_status(isa_string);
+        let all_s = info::get_all_s_extensions_with_status(isa_string);
+
+        if opts.explain {
+            display_all_extensions_explained(out, &all_std, &all_z, &all_s);
+        } else {
+            display_all_extensions_compact(out, &all_std, &all_z, &all_s);
+        }
+    } else {
+        // Show only detected extensions
+        let extensions_compact = info::parse_extensions_compact(isa_string);
+        let z_exts_with_cat = info::parse_z_extensions_with_category(isa_string);
+        let s_exts_with_cat = info::parse_s_extensions_with_category(isa_string);
+
+        if opts.explain {
+            display_extensions_explained(out, isa_string, &z_exts_with_cat, &s_exts_with_cat);
+        } else {
+            display_extensions_compact(
+                out,
+                &extensions_compact,
+                &z_exts_with_cat,
+                &s_exts_with_cat,
+            );
+        }
+    }
+}
+
+/// "Enabled, VLEN>=256" from the ISA string, followed by the VLEN measured
+/// on this machine when known; empty without V / Zve*
+fn format_vector(isa: &str, vector: &info::VectorInfo) -> String {
+    let Some(detail) = info::parse_vector_from_isa(isa) else {
+        return String::new();
+    };
+    match vector.vlen {
+        Some(vlen) => format!("{detail}, VLEN={vlen}"),
+        None => detail,
+    }
+}
+
+fn render_vector(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    if !fixed.vector.is_empty() {
+        out.push(format!(
+            "{} {}",
+            "Vector:".bright_magenta().bold(),
+            fixed.vector.white()
+        ));
+    }
+}
+
+fn render_harts(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    out.push(format!(
+        "{} {}",
+        "Harts:".bright_cyan().bold(),
+        fixed.harts.white()
+    ));
+}
+
+fn render_hw_ids(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    let hw_ids = &fixed.hw_ids;
+    let mut ids = Vec::new();
+    if !hw_ids.mvendorid.is_empty() {
+        ids.push(match info::decode_mvendorid(&hw_ids.mvendorid) {
+            Some(name) => format!("vendor:{name} ({})", hw_ids.mvendorid),
+            None => format!("vendor:{}", hw_ids.mvendorid),
+        });
+    }
+    if !hw_ids.marchid.is_empty() {
+        ids.push(format!("arch:{}", hw_ids.marchid));
+    }
+    if !hw_ids.mimpid.is_empty() {
+        ids.push(format!("impl:{}", hw_ids.mimpid));
+    }
+    if !ids.is_empty() {
+        out.push(format!(
+            "{} {}",
+            "HW IDs:".bright_green().bold(),
+            ids.join(" ").white()
+        ));
+    }
+}
+
+fn render_cache(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    if !fixed.cache.is_empty() {
+        out.push(format!(
+            "{} {}",
+            "Cache:".bright_cyan().bold(),
+            fixed.cache.white()
+        ));
+    }
+}
+
+fn render_board(fixed: &StaticInfo, opts: &RenderOptions, out: &mut Vec<String>) {
+    if !fixed.board.is_empty() {
+        out.push(format!(
+            "{} {}",
+            "Board:".bright_blue().bold(),
+            fixed.board.white()
+        ));
+    }
+    // A container that still sees the device tree has nothing to warn about
+    let unresolved = fixed.board.is_empty() || fixed.board.eq_ignore_ascii_case("unknown");
+    if let Some(runtime) = fixed.container.filter(|_| unresolved) {
+        out.push(format!(
+            "  {} {}",
+            "!".bright_yellow().bold(),
+            format!("running in container ({runtime}): host board info unavailable")
+                .bright_yellow()
+        ));
+    }
+    if !(opts.explain || opts.show_all) {
+        return;
+    }
+    if let Some(board) = fixed.board_details {
+        let details = [
+            ("SoC:", board.soc.clone()),
+            (
+                "Released:",
+                format!("{} ({})", board.year, board.form_factor),
+            ),
+            ("RAM Options:", board.ram.join(", ")),
+            ("Link:", board.url.clone()),
+        ];
+        for (label, value) in details {
+            out.push(format!("  {} {}", label.bright_blue(), value.white()));
+        }
+    }
+}
+
+fn render_os(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    out.push(format!(
+        "{} {}",
+        "OS:".bright_blue().bold(),
+        fixed.os.white()
+    ));
+}
+
+fn render_kernel(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    out.push(format!(
+        "{} {}",
+        "Kernel:".bright_blue().bold(),
+        fixed.kernel.white()
+    ));
+}
+
+fn render_init(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    let Some(init) = &fixed.init else {
+        return;
+    };
+    let text = match &init.version {
+        Some(version) => format!("{} {version}", init.name),
+        None => init.name.clone(),
+    };
+    out.push(format!("{} {}", "Init:".bright_blue().bold(), text.white()));
+}
+
+fn render_display(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    if fixed.displays.is_empty() {
+        return;
+    }
+    let displays: Vec<String> = fixed
+        .displays
+        .iter()
+        .map(|d| match &d.resolution {
+            Some(resolution) => format!("{} {resolution}", d.connector),
+            None => d.connector.clone(),
+        })
+        .collect();
+    out.push(format!(
+        "{} {}",
+        "Display:".bright_blue().bold(),
+        displays.join(", ").white()
+    ));
+}
+
+/// `img,img-gpu (pvrsrvkm, no Mesa driver)` or
+/// `1002:6779 (amdgpu, Mesa radeonsi 24.0.5)`
+pub fn format_gpu(gpu: &info::GpuInfo) -> String {
+    let Some(driver) = &gpu.driver else {
+        return format!("{} (no driver bound)", gpu.name);
+    };
+    let mesa = match (&gpu.mesa_driver, &gpu.mesa_version) {
+        (Some(mesa), Some(version)) => format!("Mesa {mesa} {version}"),
+        (Some(mesa), None) => format!("Mesa {mesa}"),
+        (None, _) => "no Mesa driver".to_string(),
+    };
+    format!("{} ({driver}, {mesa})", gpu.name)
+}
+
+fn render_gpu(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    for gpu in &fixed.gpus {
+        out.push(format!(
+            "{} {}",
+            "GPU:".bright_blue().bold(),
+            format_gpu(gpu).white()
+        ));
+    }
+}
+
+fn render_memory(_fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    out.push(format!(
+        "{} {}",
+        "Memory:".bright_blue().bold(),
+        format_memory(info::get_memory_info(), info::get_memory_breakdown()).white()
+    ));
+}
+
+/// GiB with two decimals, or whole MiB below 1 GiB
+#[allow(clippy::cast_precision_loss)]
+pub fn format_memory_size(bytes: u64) -> String {
+    if bytes >= 1_073_741_824 {
+        format!("{:.2} GiB", bytes as f64 / 1_073_741_824.0)
+    } else {
+        format!("{} MiB", bytes / 1_048_576)
+    }
+}
+
+/// `used / total` followed by available, buffers/cache and firmware-reserved
+/// memory, so carve-outs for the GPU or NPU do not look like missing RAM
+fn format_memory(used_total: String, breakdown: Option<info::MemoryBreakdown>) -> String {
+    let Some(breakdown) = breakdown else {
+        return used_total;
+    };
+    let mut parts = vec![
+        format!(
+            "{} available",
+            format_memory_size(breakdown.available_bytes)
+        ),
+        format!(
+            "{} buffers/cache",
+            format_memory_size(breakdown.buffers_cache_bytes)
+        ),
+    ];
+    if let Some(reserved) = breakdown.reserved_bytes.filter(|bytes| *bytes > 0) {
+        parts.push(format!("{} reserved", format_memory_size(reserved)));
+    }
+    format!("{used_total} ({})", parts.join(", "))
+}
+
+fn render_disk(_fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    disk_lines(&info::get_disk_usage(), out);
+}
+
+/// One "Disk (mount):" line per filesystem
+#[allow(clippy::cast_precision_loss)]
+fn disk_lines(disks: &[info::DiskUsage], out: &mut Vec<String>) {
+    for disk in disks {
+        let used_gb = disk.used_bytes as f64 / 1_073_741_824.0;
+        let total_gb = disk.total_bytes as f64 / 1_073_741_824.0;
+        out.push(format!(
+            "{} {}",
+            format!("Disk ({}):", disk.mount_point).bright_blue().bold(),
+            format!(
+                "{used_gb:.2} GiB / {total_gb:.2} GiB ({})",
+                disk.file_system
+            )
+            .white()
+        ));
+    }
+}
+
+fn render_mmc(_fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    mmc_lines(&info::get_mmc_info(), out);
+}
+
+/// One "Storage (device):" line per eMMC / SD card, followed by a warning
+/// for slow cards
+#[allow(clippy::cast_precision_loss)]
+fn mmc_lines(cards: &[info::MmcInfo], out: &mut Vec<String>) {
+    for card in cards {
+        let kind = if card.kind == "MMC" { "eMMC" } else { "SD" };
+        let mut parts = vec![match &card.manufacturer {
+            Some(manufacturer) => format!("{manufacturer} {kind} {}", card.name),
+            None => format!("{kind} {}", card.name),
+        }];
+        parts.push(format!(
+            "{:.2} GiB",
+            card.size_bytes as f64 / 1_073_741_824.0
+        ));
+        parts.extend(card.speed_class.clone());
+        out.push(format!(
+            "{} {}",
+            format!("Storage ({}):", card.device).bright_blue().bold(),
+            parts.join(", ").white()
+        ));
+        if card.slow {
+            out.push(format!(
+                "  {} {}",
+                "!".bright_yellow().bold(),
+                "slow card: expect poor random I/O; a U3 / A2 card or eMMC helps".bright_yellow()
+            ));
+        }
+    }
+}
+
+fn render_uptime(_fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    out.push(format!(
+        "{} {}",
+        "Uptime:".bright_blue().bold(),
+        info::get_uptime().white()
+    ));
+}
+
+fn render_load(_fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    if let Some(load) = info::get_load_average() {
+        out.push(format_load(load));
+    }
+}
+
+fn format_load(load: info::LoadAverage) -> String {
+    format!(
+        "{} {}",
+        "Load:".bright_blue().bold(),
+        format!("{:.2} {:.2} {:.2}", load.one, load.five, load.fifteen).white()
+    )
+}
+
+fn render_cpufreq(_fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    if let Some(line) = format_cpufreq(&info::get_cpufreq()) {
+        out.push(line);
+    }
+}
+
+/// Current frequency (one value when all harts agree, else one per hart),
+/// the highest maximum and the governor: `1500 MHz (max 1500 MHz, schedutil)`
+fn format_cpufreq(freqs: &[info::HartFreq]) -> Option<String> {
+    let first = freqs.first()?;
+    let mhz =
+        |khz: Option<u64>| khz.map_or_else(|| "-".to_string(), |khz| (khz / 1000).to_string());
+    let mut text = if freqs.iter().all(|f| f.current_khz == first.current_khz) {
+        format!("{} MHz", mhz(first.current_khz))
+    } else {
+        let current: Vec<String> = freqs.iter().map(|f| mhz(f.current_khz)).collect();
+        format!("{} MHz", current.join("/"))
+    };
+    let mut details = Vec::new();
+    if let Some(max) = freqs.iter().filter_map(|f| f.max_khz).max() {
+        details.push(format!("max {} MHz", max / 1000));
+    }
+    details.extend(first.governor.clone());
+    if !details.is_empty() {
+        text.push_str(&format!(" ({})", details.join(", ")));
+    }
+    Some(format!(
+        "{} {}",
+        "CPU Freq:".bright_blue().bold(),
+        text.white()
+    ))
+}
+
+fn render_thermal(_fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    if let Some(line) = format_thermal(&info::get_thermal_zones()) {
+        out.push(line);
+    }
+}
+
+/// Every thermal zone: `cpu-thermal 45.2 °C, gpu-thermal 44.0 °C`
+#[allow(clippy::cast_precision_loss)]
+fn format_thermal(zones: &[info::ThermalZone]) -> Option<String> {
+    let readings: Vec<String> = zones
+        .iter()
+        .map(|z| format!("{} {:.1} °C", z.name, z.millicelsius as f64 / 1000.0))
+        .collect();
+    (!readings.is_empty()).then(|| {
+        format!(
+            "{} {}",
+            "Thermal:".bright_blue().bold(),
+            readings.join(", ").white()
+        )
+    })
+}
+
+fn render_sensors(_fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    sensor_lines(&info::get_sensors(), out);
+}
+
+/// "Sensors:" followed by one line per fan and voltage rail
+#[allow(clippy::cast_precision_loss)]
+fn sensor_lines(sensors: &info::Sensors, out: &mut Vec<String>) {
+    // Power readings have their own line
+    if sensors.fans.is_empty() && sensors.voltages.is_empty() {
+        return;
+    }
+    out.push(format!("{}", "Sensors:".bright_blue().bold()));
+    for fan in &sensors.fans {
+        out.push(format!(
+            "  {} {}",
+            format!("{} ({}):", fan.label, fan.chip).bright_blue(),
+            format!("{} RPM", fan.rpm).white()
+        ));
+    }
+    for rail in &sensors.voltages {
+        out.push(format!(
+            "  {} {}",
+            format!("{} ({}):", rail.label, rail.chip).bright_blue(),
+            format!("{:.2} V", rail.millivolts as f64 / 1000.0).white()
+        ));
+    }
+}
+
+fn render_power(_fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    if let Some(line) = format_power(&info::get_sensors().powers) {
+        out.push(line);
+    }
+}
+
+/// Measured power draw per monitor channel: `vdd_in 4.00 W, vdd_cpu 1.00 W`
+#[allow(clippy::cast_precision_loss)]
+fn format_power(powers: &[info::PowerReading]) -> Option<String> {
+    let readings: Vec<String> = powers
+        .iter()
+        .map(|p| format!("{} {:.2} W", p.label, p.microwatts as f64 / 1_000_000.0))
+        .collect();
+    (!readings.is_empty()).then(|| {
+        format!(
+            "{} {}",
+            "Power:".bright_blue().bold(),
+            readings.join(", ").white()
+        )
+    })
+}
+
+fn render_rails(_fixed: &StaticInfo, opts: &RenderOptions, out: &mut Vec<String>) {
+    if let Some(line) = format_rails(&info::get_regulators(), opts.show_all) {
+        out.push(line);
+    }
+}
+
+/// CPU cluster rails, or with `all` every enabled rail as well:
+/// `dvdd_cpu 0.80 V, dvddm_cpu 0.80 V (off)`
+#[allow(clippy::cast_precision_loss)]
+fn format_rails(regulators: &[info::Regulator], all: bool) -> Option<String> {
+    let rails: Vec<String> = regulators
+        .iter()
+        .filter(|r| r.is_cpu_rail() || (all && r.enabled == Some(true)))
+        .map(|r| {
+            let mut text = r.name.clone();
+            if let Some(uv) = r.microvolts {
+                text.push_str(&format!(" {:.2} V", uv as f64 / 1_000_000.0));
+            }
+            if r.enabled == Some(false) {
+                text.push_str(" (off)");
+            }
+            text
+        })
+        .collect();
+    (!rails.is_empty()).then(|| {
+        format!(
+            "{} {}",
+            "Rails:".bright_blue().bold(),
+            rails.join(", ").white()
+        )
+    })
+}
+
+fn render_user(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    out.push(format!(
+        "{} {}@{}",
+        "User:".bright_blue().bold(),
+        fixed.user.white(),
+        fixed.hostname.white()
+    ));
+}
+
+/// One line per plugin field, labelled with its key
+fn render_custom(fixed: &StaticInfo, _opts: &RenderOptions, out: &mut Vec<String>) {
+    for (key, value) in &fixed.custom.fields {
+        out.push(format!(
+            "{} {}",
+            format!("{key}:").bright_blue().bold(),
+            crate::plugins::format_value(value).white()
+        ));
+    }
+}
+
+/// Display extensions in compact mode (category-grouped multiple lines)
+fn display_extensions_compact(
+    out: &mut Vec<String>,
+    std_exts: &str,
+    z_exts: &[info::ExtensionInfo],
+    s_exts: &[info::ExtensionInfo],
+) {
+    // Standard extensions
+    if !std_exts.is_empty() {
+        out.push(format!(
+            "{} {}",
+            "Ext:".bright_yellow().bold(),
+            std_exts.white()
+        ));
+    }
+
+    // Z-extensions grouped by category
+    let z_groups = info::group_by_category(z_exts);
+    for (category, exts) in &z_groups {
+        let cat_name = info::get_z_category_name(category);
+        let ext_names: Vec<&str> = exts.iter().map(|e| e.name.as_str()).collect();
+        out.push(format!(
+            "{} {}",
+            format!("Z-{cat_name}:").bright_yellow().bold(),
+            ext_names.join(" ").white()
+        ));
+    }
+
+    // S-extensions grouped by category
+    let s_groups = info::group_by_category(s_exts);
+    for (category, exts) in &s_groups {
+        let cat_name = info::get_s_category_name(category);
+        let ext_names: Vec<&str> = exts.iter().map(|e| e.name.as_str()).collect();
+        out.push(format!(
+            "{} {}",
+            format!("S-{cat_name}:").bright_magenta().bold(),
+            ext_names.join(" ").white()
+        ));
+    }
+}
+
+/// Display extensions in explained mode (category-grouped with aligned columns)
+fn display_extensions_explained(
+    out: &mut Vec<String>,
+    isa: &str,
+    z_exts: &[info::ExtensionInfo],
+    s_exts: &[info::ExtensionInfo],
+) {
+    // Standard extensions
+    out.push(format!("{}", "Extensions:".bright_yellow().bold()));
+    for (ext, desc) in info::parse_extensions_explained(isa) {
+        out.push(format!("  {:<10} {}", ext.bright_green(), desc));
+    }
+
+    // Z-extensions grouped by category
+    let z_groups = info::group_by_category(z_exts);
+    for (category, exts) in &z_groups {
+        let cat_name = info::get_z_category_name(category);
+        out.push(String::new());
+        out.push(format!(
+            "{}",
+            format!("Z-Extensions ({cat_name}):").bright_yellow().bold()
+        ));
+        for ext in exts {
+            out.push(format!(
+                "  {:<10} {}",
+                ext.name.bright_green(),
+                ext.description
+            ));
+        }
+    }
+
+    // S-extensions grouped by category
+    let s_groups = info::group_by_category(s_exts);
+    for (category, exts) in &s_groups {
+        let cat_name = info::get_s_category_name(category);
+        out.push(String::new());
+        out.push(format!(
+            "{}",
+            format!("S-Extensions ({cat_name}):")
+                .bright_magenta()
+                .bold()
+        ));
+        for ext in exts {
+            out.push(format!(
+                "  {:<10} {}",
+                ext.name.bright_green(),
+                ext.description
+            ));
+        }
+    }
+}
+
+/// Format extension with checkmark based on support status
+fn format_ext_with_check(name: &str, supported: bool) -> String {
+    if supported {
+        format!("{}{}", "✓".bright_green().bold(), name.bright_green())
+    } else {
+        format!("{}{}", "✗".bright_black(), name.bright_black())
+    }
+}
+
+/// Display ALL extensions in compact mode with checkmarks
+fn display_all_extensions_compact(
+    out: &mut Vec<String>,
+    std_exts: &[(String, String, bool)],
+    z_exts: &[info::ExtensionInfo],
+    s_exts: &[info::ExtensionInfo],
+) {
+    // Standard extensions with checkmarks
+    let std_parts: Vec<String> = std_exts
+        .iter()
+        .map(|(name, _, supported)| format_ext_with_check(name, *supported))
+        .collect();
+    out.push(format!(
+        "{} {}",
+        "Ext:".bright_yellow().bold(),
+        std_parts.join(" ")
+    ));
+
+    // Z-extensions grouped by category
+    let z_groups = info::group_by_category(z_exts);
+    for (category, exts) in &z_groups {
+        let cat_name = info::get_z_category_name(category);
+        let ext_parts: Vec<String> = exts
+            .iter()
+            .map(|e| format_ext_with_check(&e.name, e.supported))
+            .collect();
+        out.push(format!(
+            "{} {}",
+            format!("Z-{cat_name}:").bright_yellow().bold(),
+            ext_parts.join(" ")
+        ));
+    }
+
+    // S-extensions grouped by category
+    let s_groups = info::group_by_category(s_exts);
+    for (category, exts) in &s_groups {
+        let cat_name = info::get_s_category_name(category);
+        let ext_parts: Vec<String> = exts
+            .iter()
+            .map(|e| format_ext_with_check(&e.name, e.supported))
+            .collect();
+        out.push(format!(
+            "{} {}",
+            format!("S-{cat_name}:").bright_magenta().bold(),
+            ext_parts.join(" ")
+        ));
+    }
+}
+
+/// Display ALL extensions in explained mode with checkmarks
+fn display_all_extensions_explained(
+    out: &mut Vec<String>,
+    std_exts: &[(String, String, bool)],
+    z_exts: &[info::ExtensionInfo],
+    s_exts: &[info::ExtensionInfo],
+) {
+    // Standard extensions
+    out.push(format!("{}", "Extensions:".bright_yellow().bold()));
+    for (name, desc, supported) in std_exts {
+        let mark = if *supported {
+            "✓".bright_green().bold()
+        } else {
+            "✗".bright_black()
+        };
+        let name_colored = if *supported {
+            name.bright_green()
+        } else {
+            name.bright_black()
+        };
+        let desc_colored = if *supported {
+            desc.normal()
+        } else {
+            desc.bright_black()
+        };
+        out.push(format!(" {mark} {name_colored:<10} {desc_colored}"));
+    }
+
+    // Z-extensions grouped by category
+    let z_groups = info::group_by_category(z_exts);
+    for (category, exts) in &z_groups {
+        let cat_name = info::get_z_category_name(category);
+        out.push(String::new());
+        out.push(format!(
+            "{}",
+            format!("Z-Extensions ({cat_name}):").bright_yellow().bold()
+        ));
+        for ext in exts {
+            let mark = if ext.supported {
+                "✓".bright_green().bold()
+            } else {
+                "✗".bright_black()
+            };
+            let name_colored = if ext.supported {
+                ext.name.bright_green()
+            } else {
+                ext.name.bright_black()
+            };
+            let desc_colored = if ext.supported {
+                ext.description.normal()
+            } else {
+                ext.description.bright_black()
+            };
+            out.push(format!(" {mark} {name_colored:<10} {desc_colored}"));
+        }
+    }
+
+    // S-extensions grouped by category
+    let s_groups = info::group_by_category(s_exts);
+    for (category, exts) in &s_groups {
+        let cat_name = info::get_s_category_name(category);
+        out.push(String::new());
+        out.push(format!(
+            "{}",
+            format!("S-Extensions ({cat_name}):")
+                .bright_magenta()
+                .bold()
+        ));
+        for ext in exts {
+            let mark = if ext.supported {
+                "✓".bright_green().bold()
+            } else {
+                "✗".bright_black()
+            };
+            let name_colored = if ext.supported {
+                ext.name.bright_green()
+            } else {
+                ext.name.bright_black()
+            };
+            let desc_colored = if ext.supported {
+                ext.description.normal()
+            } else {
+                ext.description.bright_black()
+            };
+            out.push(format!(" {mark} {name_colored:<10} {desc_colored}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+
+    fn find(id: &str) -> Option<&'static Field> {
+        FIELDS.iter().find(|f| f.id == id)
+    }
+
+    fn ids(fields: &[&Field]) -> Vec<&'static str> {
+        fields.iter().map(|f| f.id).collect()
+    }
+
+    fn strings(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn test_field_ids_unique() {
+        for (i, field) in FIELDS.iter().enumerate() {
+            assert!(
+                FIELDS[i + 1..].iter().all(|f| f.id != field.id),
+                "duplicate field id {}",
+                field.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_riscv_field() {
+        assert!(is_riscv_field("cache"));
+        assert!(!is_riscv_field("board"));
+        assert!(!is_riscv_field("nonexistent"));
+    }
+
+    #[test]
+    fn test_visible_fields_default() {
+        let fields = visible_fields(&Config::default(), &[], &[], false).unwrap();
+        let hidden = ["mmc", "cpufreq", "thermal", "sensors", "rails"];
+        assert_eq!(fields.len(), FIELDS.len() - hidden.len());
+        for id in hidden {
+            assert!(!ids(&fields).contains(&id));
+        }
+        let shown = strings(&hidden);
+        let fields = visible_fields(&Config::default(), &shown, &[], false).unwrap();
+        assert_eq!(fields.len(), FIELDS.len());
+    }
+
+    #[test]
+    fn test_visible_fields_watch() {
+        let watched = ids(&visible_fields(&Config::default(), &[], &[], true).unwrap());
+        assert!(watched.contains(&"cpufreq") && watched.contains(&"thermal"));
+        assert!(!watched.contains(&"sensors"));
+        // The config and --hide still win
+        let config = Config {
+            hide: strings(&["thermal"]),
+            ..Config::default()
+        };
+        let hidden = ids(&visible_fields(&config, &[], &strings(&["cpufreq"]), true).unwrap());
+        assert!(!hidden.contains(&"cpufreq") && !hidden.contains(&"thermal"));
+    }
+
+    #[test]
+    fn test_visible_fields_hide() {
+        let fields = visible_fields(
+            &Config::default(),
+            &[],
+            &strings(&["uptime", "memory"]),
+            false,
+        )
+        .unwrap();
+        let ids = ids(&fields);
+        assert!(!ids.contains(&"uptime") && !ids.contains(&"memory"));
+        assert!(ids.contains(&"isa"));
+    }
+
+    #[test]
+    fn test_visible_fields_cli_overrides_config() {
+        let config = Config {
+            hide: strings(&["uptime", "cache"]),
+            ..Config::default()
+        };
+        let fields = visible_fields(&config, &strings(&["uptime"]), &[], false).unwrap();
+        let ids = ids(&fields);
+        assert!(ids.contains(&"uptime"));
+        assert!(!ids.contains(&"cache"));
+    }
+
+    #[test]
+    fn test_visible_fields_order() {
+        let config = Config {
+            order: strings(&["board", "isa", "board"]),
+            hide: strings(&["user"]),
+            ..Config::default()
+        };
+        let ids = ids(&visible_fields(&config, &[], &[], false).unwrap());
+        assert_eq!(&ids[..3], &["board", "isa", "ext"]);
+        // user hidden by the config, mmc, cpufreq, thermal, sensors and
+        // rails by default
+        assert_eq!(ids.len(), FIELDS.len() - 6);
+        assert_eq!(ids.last(), Some(&"custom"));
+    }
+
+    #[test]
+    fn test_visible_fields_unknown() {
+        let err = visible_fields(&Config::default(), &strings(&["nope"]), &[], false).unwrap_err();
+        assert!(err.starts_with("unknown field 'nope'"));
+    }
+
+    fn sample_static() -> StaticInfo {
+        StaticInfo {
+            isa: "rv64imac".into(),
+            isa_divergence: None,
+            vector: String::new(),
+            harts: "4 harts".into(),
+            hw_ids: info::HardwareIds::default(),
+            cache: String::new(),
+            board: "VisionFive 2".into(),
+            board_details: None,
+            container: None,
+            os: "Debian".into(),
+            kernel: "6.6.20".into(),
+            init: None,
+            displays: Vec::new(),
+            gpus: Vec::new(),
+            user: "riscv".into(),
+            hostname: "vf2".into(),
+            custom: crate::plugins::Plugins::default(),
+            sources: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_info_lines_separator() {
+        let fixed = sample_static();
+        let opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: false,
+            plain: false,
+        };
+        let fields = [find("harts").unwrap(), find("board").unwrap()];
+        let plain = |riscv_only: bool, fields: &[&Field]| -> Vec<String> {
+            info_lines(&fixed, fields, &opts, riscv_only)
+                .iter()
+                .map(|l| strip_ansi(l))
+                .collect()
+        };
+        assert_eq!(
+            plain(false, &fields),
+            vec![
+                "Harts: 4 harts",
+                "",
+                "--------------------------------",
+                "",
+                "Board: VisionFive 2",
+            ]
+        );
+        assert_eq!(plain(true, &fields), vec!["Harts: 4 harts"]);
+        assert_eq!(plain(false, &fields[1..]), vec!["Board: VisionFive 2"]);
+        // Interleaved groups get a separator at every switch
+        let interleaved = [fields[1], fields[0], find("os").unwrap()];
+        assert_eq!(plain(false, &interleaved).len(), 9);
+    }
+
+    #[test]
+    fn test_info_lines_plain_has_no_separator() {
+        let opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: false,
+            plain: true,
+        };
+        let fields = [find("harts").unwrap(), find("board").unwrap()];
+        let lines = info_lines(&sample_static(), &fields, &opts, false);
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_info_lines_verbose_sources() {
+        let fixed = StaticInfo {
+            sources: vec![("harts", "/proc/cpuinfo".to_string())],
+            ..sample_static()
+        };
+        let opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: true,
+            plain: false,
+        };
+        let fields = [find("harts").unwrap(), find("os").unwrap()];
+        let lines: Vec<String> = info_lines(&fixed, &fields, &opts, true)
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        assert_eq!(lines, vec!["Harts: 4 harts", "  ↳ /proc/cpuinfo"]);
+        // Fields without a recorded source get no annotation
+        let lines = info_lines(&fixed, &fields[1..], &opts, false);
+        assert_eq!(lines.len(), 1);
+    }
+
+    #[test]
+    fn test_format_vector() {
+        let measured = info::VectorInfo {
+            enabled: true,
+            vlen: Some(256),
+            elen: Some(64),
+        };
+        assert_eq!(
+            format_vector("rv64imafdcv_zvl256b", &measured),
+            "Enabled, VLEN>=256, VLEN=256"
+        );
+        assert_eq!(
+            format_vector("rv64imafdcv", &info::VectorInfo::default()),
+            "Enabled"
+        );
+        assert_eq!(format_vector("rv64imafdc", &measured), "");
+    }
+
+    #[test]
+    fn test_render_isa_divergence() {
+        let fixed = StaticInfo {
+            isa_divergence: Some(info::IsaDivergence {
+                hart_isas: vec!["rv64imacv_zfh".into(), "rv64imac".into()],
+                union: "rv64imacv_zfh".into(),
+                partial_extensions: vec!["v".into(), "zfh".into()],
+            }),
+            ..sample_static()
+        };
+        let opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: false,
+            plain: false,
+        };
+        let mut out = Vec::new();
+        render_isa(&fixed, &opts, &mut out);
+        let lines: Vec<String> = out.iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "ISA: rv64imac",
+                "  ! harts differ: v zfh only on some harts (showing the common set)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_custom() {
+        let fixed = StaticInfo {
+            custom: crate::plugins::Plugins {
+                fields: vec![
+                    ("Fan".into(), serde_json::json!("auto, 40%")),
+                    ("fan_rpm".into(), serde_json::json!(3200)),
+                ],
+                failures: Vec::new(),
+            },
+            ..sample_static()
+        };
+        let opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: false,
+            plain: false,
+        };
+        let mut out = Vec::new();
+        render_custom(&fixed, &opts, &mut out);
+        let lines: Vec<String> = out.iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(lines, vec!["Fan: auto, 40%", "fan_rpm: 3200"]);
+    }
+
+    #[test]
+    fn test_render_board_details_only_when_explaining() {
+        let fixed = StaticInfo {
+            board_details: info::find_board("StarFive VisionFive 2 v1.3B", &[]),
+            ..sample_static()
+        };
+        let mut opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: false,
+            plain: false,
+        };
+        let lines = |opts: &RenderOptions| -> Vec<String> {
+            let mut out = Vec::new();
+            render_board(&fixed, opts, &mut out);
+            out.iter().map(|l| strip_ansi(l)).collect()
+        };
+        assert_eq!(lines(&opts), vec!["Board: VisionFive 2"]);
+        opts.explain = true;
+        let explained = lines(&opts);
+        assert_eq!(explained.len(), 5);
+        assert_eq!(explained[1], "  SoC: StarFive JH7110");
+        assert!(explained[2].starts_with("  Released: 2023 ("));
+        assert!(explained[3].contains("8 GB"));
+    }
+
+    #[test]
+    fn test_render_board_in_container() {
+        let fixed = StaticInfo {
+            board: String::new(),
+            container: Some("docker"),
+            ..sample_static()
+        };
+        let opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: false,
+            plain: false,
+        };
+        let mut out = Vec::new();
+        render_board(&fixed, &opts, &mut out);
+        let lines: Vec<String> = out.iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(
+            lines,
+            ["  ! running in container (docker): host board info unavailable"]
+        );
+    }
+
+    #[test]
+    fn test_render_board_in_container_with_board() {
+        let fixed = StaticInfo {
+            container: Some("podman"),
+            ..sample_static()
+        };
+        let opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: false,
+            plain: false,
+        };
+        let mut out = Vec::new();
+        render_board(&fixed, &opts, &mut out);
+        let lines: Vec<String> = out.iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(lines, ["Board: VisionFive 2"]);
+    }
+
+    #[test]
+    fn test_render_hw_ids_names_vendor() {
+        let fixed = StaticInfo {
+            hw_ids: info::HardwareIds {
+                mvendorid: "0x489".into(),
+                marchid: "0x8000000000000007".into(),
+                mimpid: String::new(),
+            },
+            ..sample_static()
+        };
+        let opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: false,
+            plain: false,
+        };
+        let mut out = Vec::new();
+        render_hw_ids(&fixed, &opts, &mut out);
+        assert_eq!(
+            strip_ansi(&out[0]),
+            "HW IDs: vendor:SiFive (0x489) arch:0x8000000000000007"
+        );
+    }
+
+    #[test]
+    fn test_render_display_lists_connectors() {
+        let opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: false,
+            plain: false,
+        };
+        let mut out = Vec::new();
+        render_display(&sample_static(), &opts, &mut out);
+        assert!(out.is_empty());
+
+        let fixed = StaticInfo {
+            displays: vec![
+                info::DisplayInfo {
+                    connector: "HDMI-A-1".into(),
+                    resolution: Some("1920x1080".into()),
+                },
+                info::DisplayInfo {
+                    connector: "DSI-1".into(),
+                    resolution: None,
+                },
+            ],
+            ..sample_static()
+        };
+        render_display(&fixed, &opts, &mut out);
+        assert_eq!(strip_ansi(&out[0]), "Display: HDMI-A-1 1920x1080, DSI-1");
+    }
+
+    #[test]
+    fn test_disk_lines() {
+        let disks = [
+            info::DiskUsage {
+                mount_point: "/".into(),
+                file_system: "ext4".into(),
+                used_bytes: 5 * 1_073_741_824,
+                total_bytes: 29 * 1_073_741_824,
+            },
+            info::DiskUsage {
+                mount_point: "/boot".into(),
+                file_system: "vfat".into(),
+                used_bytes: 0,
+                total_bytes: 536_870_912,
+            },
+        ];
+        let mut out = Vec::new();
+        disk_lines(&disks, &mut out);
+        let out: Vec<String> = out.iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(
+            out,
+            [
+                "Disk (/): 5.00 GiB / 29.00 GiB (ext4)",
+                "Disk (/boot): 0.00 GiB / 0.50 GiB (vfat)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_load() {
+        let load = info::LoadAverage {
+            one: 0.5,
+            five: 1.25,
+            fifteen: 2.0,
+        };
+        assert_eq!(strip_ansi(&format_load(load)), "Load: 0.50 1.25 2.00");
+    }
+
+    #[test]
+    fn test_render_init() {
+        let fixed = StaticInfo {
+            init: Some(info::InitSystem {
+                name: "systemd".into(),
+                version: Some("255".into()),
+            }),
+            ..sample_static()
+        };
+        let opts = RenderOptions {
+            explain: false,
+            show_all: false,
+            verbose: false,
+            plain: false,
+        };
+        let mut out = Vec::new();
+        render_init(&fixed, &opts, &mut out);
+        assert_eq!(strip_ansi(&out[0]), "Init: systemd 255");
+    }
+
+    #[test]
+    fn test_format_gpu() {
+        let gpu = |driver: Option<&str>, mesa_driver: Option<&str>, mesa_version: Option<&str>| {
+            info::GpuInfo {
+                name: "img,img-gpu".into(),
+                driver: driver.map(Into::into),
+                mesa_driver: mesa_driver.map(Into::into),
+                mesa_version: mesa_version.map(Into::into),
+            }
+        };
+        assert_eq!(
+            format_gpu(&gpu(Some("pvrsrvkm"), None, None)),
+            "img,img-gpu (pvrsrvkm, no Mesa driver)"
+        );
+        assert_eq!(
+            format_gpu(&gpu(Some("powervr"), Some("imagination"), Some("24.1.0"))),
+            "img,img-gpu (powervr, Mesa imagination 24.1.0)"
+        );
+        assert_eq!(
+            format_gpu(&gpu(None, None, None)),
+            "img,img-gpu (no driver bound)"
+        );
+    }
+
+    #[test]
+    fn test_format_memory() {
+        let breakdown = info::MemoryBreakdown {
+            available_bytes: 6 * 1_073_741_824,
+            buffers_cache_bytes: 2_415_919_104,
+            reserved_bytes: Some(384 * 1_048_576),
+        };
+        assert_eq!(
+            format_memory("1.20 GiB / 7.60 GiB".into(), Some(breakdown)),
+            "1.20 GiB / 7.60 GiB (6.00 GiB available, 2.25 GiB buffers/cache, 384 MiB reserved)"
+        );
+        let no_dt = info::MemoryBreakdown {
+            reserved_bytes: None,
+            ..breakdown
+        };
+        assert_eq!(
+            format_memory("1.20 GiB / 7.60 GiB".into(), Some(no_dt)),
+            "1.20 GiB / 7.60 GiB (6.00 GiB available, 2.25 GiB buffers/cache)"
+        );
+        assert_eq!(
+            format_memory("1.20 GiB / 7.60 GiB".into(), None),
+            "1.20 GiB / 7.60 GiB"
+        );
+    }
+
+    #[test]
+    fn test_sensor_lines() {
+        let sensors = info::Sensors {
+            fans: vec![info::FanReading {
+                chip: "pwmfan".into(),
+                label: "fan1".into(),
+                rpm: 3200,
+            }],
+            voltages: vec![info::VoltageReading {
+                chip: "da9063".into(),
+                label: "vdd_cpu".into(),
+                millivolts: 900,
+            }],
+            powers: Vec::new(),
+        };
+        let mut out = Vec::new();
+        sensor_lines(&sensors, &mut out);
+        let lines: Vec<String> = out.iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(
+            lines,
+            [
+                "Sensors:",
+                "  fan1 (pwmfan): 3200 RPM",
+                "  vdd_cpu (da9063): 0.90 V"
+            ]
+        );
+
+        let mut out = Vec::new();
+        sensor_lines(&info::Sensors::default(), &mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_format_power() {
+        let power = |label: &str, microwatts| info::PowerReading {
+            chip: "ina3221".into(),
+            label: label.into(),
+            microwatts,
+        };
+        assert_eq!(
+            strip_ansi(
+                &format_power(&[power("vdd_in", 4_000_000), power("vdd_cpu", 1_250_000)]).unwrap()
+            ),
+            "Power: vdd_in 4.00 W, vdd_cpu 1.25 W"
+        );
+        assert_eq!(format_power(&[]), None);
+    }
+
+    #[test]
+    fn test_format_cpufreq() {
+        let freq = |hart, current_khz| info::HartFreq {
+            hart,
+            current_khz,
+            min_khz: Some(375_000),
+            max_khz: Some(1_500_000),
+            governor: Some("schedutil".into()),
+        };
+        assert_eq!(
+            strip_ansi(
+                &format_cpufreq(&[freq(0, Some(1_500_000)), freq(1, Some(1_500_000))]).unwrap()
+            ),
+            "CPU Freq: 1500 MHz (max 1500 MHz, schedutil)"
+        );
+        assert_eq!(
+            strip_ansi(&format_cpufreq(&[freq(0, Some(1_500_000)), freq(1, None)]).unwrap()),
+            "CPU Freq: 1500/- MHz (max 1500 MHz, schedutil)"
+        );
+        let bare = info::HartFreq {
+            current_khz: Some(1_000_000),
+            ..info::HartFreq::default()
+        };
+        assert_eq!(
+            strip_ansi(&format_cpufreq(&[bare]).unwrap()),
+            "CPU Freq: 1000 MHz"
+        );
+        assert_eq!(format_cpufreq(&[]), None);
+    }
+
+    #[test]
+    fn test_format_thermal() {
+        let zone = |name: &str, millicelsius| info::ThermalZone {
+            zone: "thermal_zone0".into(),
+            name: name.into(),
+            millicelsius,
+            harts: Vec::new(),
+        };
+        assert_eq!(
+            strip_ansi(
+                &format_thermal(&[zone("cpu-thermal", 45_250), zone("gpu-thermal", 44_000)])
+                    .unwrap()
+            ),
+            "Thermal: cpu-thermal 45.2 °C, gpu-thermal 44.0 °C"
+        );
+        assert_eq!(format_thermal(&[]), None);
+    }
+
+    #[test]
+    fn test_format_rails() {
+        let rail = |name: &str, enabled: Option<bool>, microvolts: Option<u64>| info::Regulator {
+            name: name.into(),
+            enabled,
+            microvolts,
+        };
+        let regulators = [
+            rail("vcc_3v3", Some(true), Some(3_300_000)),
+            rail("dvdd_cpu", Some(true), Some(800_000)),
+            rail("dvddm_cpu", Some(false), Some(800_000)),
+            rail("vdd_gpu", Some(false), None),
+        ];
+        assert_eq!(
+            strip_ansi(&format_rails(&regulators, false).unwrap()),
+            "Rails: dvdd_cpu 0.80 V, dvddm_cpu 0.80 V (off)"
+        );
+        assert_eq!(
+            strip_ansi(&format_rails(&regulators, true).unwrap()),
+            "Rails: vcc_3v3 3.30 V, dvdd_cpu 0.80 V, dvddm_cpu 0.80 V (off)"
+        );
+        assert_eq!(format_rails(&regulators[..1], false), None);
+    }
+
+    #[test]
+    fn test_mmc_lines() {
+        let cards = [
+            info::MmcInfo {
+                device: "mmcblk0".into(),
+                kind: "MMC".into(),
+                name: "DG4032".into(),
+                manufacturer: Some("SanDisk".into()),
+                cid: String::new(),
+                size_bytes: 32 * 1_073_741_824,
+                speed_class: None,
+                slow: false,
+            },
+            info::MmcInfo {
+                device: "mmcblk1".into(),
+                kind: "SD".into(),
+                name: "SD16G".into(),
+                manufacturer: None,
+                cid: String::new(),
+                size_bytes: 16 * 1_073_741_824,
+                speed_class: Some("Class 4".into()),
+                slow: true,
+            },
+        ];
+        let mut out = Vec::new();
+        mmc_lines(&cards, &mut out);
+        let lines: Vec<String> = out.iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(
+            lines,
+            [
+                "Storage (mmcblk0): SanDisk eMMC DG4032, 32.00 GiB",
+                "Storage (mmcblk1): SD SD16G, 16.00 GiB, Class 4",
+                "  ! slow card: expect poor random I/O; a U3 / A2 card or eMMC helps",
+            ]
+        );
+    }
+}
@@ -0,0 +1,308 @@
+//! Summary table for many boards (`riscfetch fleet`)
+//!
+//! Sources are JSON documents written by `--json` / `snapshot save`, or hosts
+//! reached over ssh where `riscfetch --json` is run remotely. Hosts are
+//! collected in parallel.
+
+use crate::display::{isa_base, visible_width};
+use crate::snapshot;
+use colored::Colorize;
+use riscfetch_core::{self as info, SystemInfo};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment variable naming the ssh program (default: ssh)
+pub const SSH_ENV: &str = "RISCFETCH_SSH";
+
+/// Table columns, in order
+const HEADERS: [&str; 8] = [
+    "Host", "Board", "SoC", "Vendor", "ISA", "Harts", "Memory", "Kernel",
+];
+
+/// Where one machine's information comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Host(String),
+}
+
+impl Source {
+    /// Row label: the host name, or the file name without `.json`
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            Self::File(path) => path.file_stem().map_or_else(
+                || path.display().to_string(),
+                |stem| stem.to_string_lossy().to_string(),
+            ),
+            Self::Host(host) => host.clone(),
+        }
+    }
+}
+
+fn is_json(entry: &str) -> bool {
+    Path::new(entry)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// Parse a hosts list: one ssh destination or `.json` path per line, `#`
+/// starts a comment. Relative JSON paths are resolved against `dir`.
+#[must_use]
+pub fn parse_hosts(text: &str, dir: &Path) -> Vec<Source> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(|entry| {
+            if is_json(entry) {
+                Source::File(dir.join(entry))
+            } else {
+                Source::Host(entry.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Expand command-line arguments: `.json` files are used directly, any other
+/// file is read as a hosts list
+pub fn sources(args: &[PathBuf]) -> Result<Vec<Source>, String> {
+    let mut sources = Vec::new();
+    for arg in args {
+        if is_json(&arg.to_string_lossy()) {
+            sources.push(Source::File(arg.clone()));
+        } else {
+            let text = std::fs::read_to_string(arg)
+                .map_err(|e| format!("cannot read {}: {e}", arg.display()))?;
+            let dir = arg.parent().unwrap_or_else(|| Path::new(""));
+            sources.extend(parse_hosts(&text, dir));
+        }
+    }
+    Ok(sources)
+}
+
+/// ssh arguments running `riscfetch --json` on `host`; `--` ends the
+/// options, so a hosts entry such as `-oProxyCommand=...` is taken as a
+/// destination instead of running a local command
+fn ssh_args(host: &str) -> [&str; 8] {
+    [
+        "-o",
+        "BatchMode=yes",
+        "-o",
+        "ConnectTimeout=10",
+        "--",
+        host,
+        "riscfetch",
+        "--json",
+    ]
+}
+
+/// Run `riscfetch --json` on `host` over ssh
+fn collect_host(host: &str) -> Result<SystemInfo, String> {
+    let ssh = std::env::var(SSH_ENV).unwrap_or_else(|_| "ssh".to_string());
+    let output = Command::new(&ssh)
+        .args(ssh_args(host))
+        .output()
+        .map_err(|e| format!("cannot run {ssh}: {e}"))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if output.status.success() {
+        snapshot::parse(&stdout)
+    } else if stdout.contains("not_riscv") {
+        Err("not RISC-V".to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(stderr
+            .lines()
+            .rfind(|line| !line.trim().is_empty())
+            .unwrap_or("ssh failed")
+            .trim()
+            .to_string())
+    }
+}
+
+/// Load one source
+pub fn collect(source: &Source) -> Result<SystemInfo, String> {
+    match source {
+        Source::File(path) => std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))
+            .and_then(|text| snapshot::parse(&text)),
+        Source::Host(host) => collect_host(host),
+    }
+}
+
+/// Load every source in parallel, keeping the input order
+#[must_use]
+pub fn collect_all(sources: &[Source]) -> Vec<(String, Result<SystemInfo, String>)> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = sources
+            .iter()
+            .map(|source| scope.spawn(move || collect(source)))
+            .collect();
+        sources
+            .iter()
+            .zip(handles)
+            .map(|(source, handle)| {
+                let result = handle
+                    .join()
+                    .unwrap_or_else(|_| Err("collector panicked".to_string()));
+                (source.label(), result)
+            })
+            .collect()
+    })
+}
+
+/// Table cells for one machine, matching `HEADERS`
+#[must_use]
+pub fn cells(label: &str, data: &SystemInfo) -> Vec<String> {
+    #[allow(clippy::cast_precision_loss)]
+    let memory = format!(
+        "{:.1} GiB",
+        data.memory_total_bytes as f64 / 1_073_741_824.0
+    );
+    let vendor = info::detect_vendor(&data.board, &data.hardware_ids).map_or("-", |v| v.name);
+    // The JSON has no `compatible`, so the catalog is searched by model
+    let soc = info::find_board(&data.board, &[]).map_or("-", |board| board.soc.as_str());
+    vec![
+        label.to_string(),
+        data.board.clone(),
+        soc.to_string(),
+        vendor.to_string(),
+        isa_base(&data.isa),
+        data.hart_count.to_string(),
+        memory,
+        data.kernel.clone(),
+    ]
+}
+
+/// Render the summary table; failed sources get an error row
+#[must_use]
+pub fn render(results: &[(String, Result<SystemInfo, String>)]) -> Vec<String> {
+    let rows: Vec<Result<Vec<String>, (String, String)>> = results
+        .iter()
+        .map(|(label, result)| match result {
+            Ok(data) => Ok(cells(label, data)),
+            Err(e) => Err((label.clone(), e.clone())),
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = HEADERS.iter().map(|h| h.len()).collect();
+    for row in &rows {
+        match row {
+            Ok(cells) => {
+                for (width, cell) in widths.iter_mut().zip(cells) {
+                    *width = (*width).max(visible_width(cell));
+                }
+            }
+            Err((label, _)) => widths[0] = widths[0].max(visible_width(label)),
+        }
+    }
+
+    let join = |cells: Vec<String>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| {
+                let fill = width.saturating_sub(visible_width(cell));
+                format!("{cell}{}", " ".repeat(fill))
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![join(
+        HEADERS
+            .iter()
+            .map(|h| h.bright_yellow().bold().to_string())
+            .collect(),
+    )];
+    for row in rows {
+        lines.push(match row {
+            Ok(mut cells) => {
+                cells[0] = cells[0].bright_cyan().bold().to_string();
+                join(cells)
+            }
+            Err((label, e)) => join(vec![
+                label.bright_cyan().bold().to_string(),
+                format!("error: {e}").bright_red().to_string(),
+            ]),
+        });
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+    use crate::output::tests::sample_info;
+
+    #[test]
+    fn test_parse_hosts() {
+        let text = "# lab boards\nvf2.lan\nroot@lpi4a  # spare\n\nmegrez.json\n";
+        assert_eq!(
+            parse_hosts(text, Path::new("lab")),
+            vec![
+                Source::Host("vf2.lan".to_string()),
+                Source::Host("root@lpi4a".to_string()),
+                Source::File(PathBuf::from("lab/megrez.json")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ssh_args_end_options_before_host() {
+        let args = ssh_args("-oProxyCommand=touch /tmp/pwned");
+        let host = args
+            .iter()
+            .position(|arg| arg.starts_with("-oProxy"))
+            .unwrap();
+        assert_eq!(args[host - 1], "--");
+        assert_eq!(&args[host + 1..], ["riscfetch", "--json"]);
+    }
+
+    #[test]
+    fn test_source_label() {
+        assert_eq!(Source::File("out/lpi4a.json".into()).label(), "lpi4a");
+        assert_eq!(Source::Host("root@vf2".into()).label(), "root@vf2");
+    }
+
+    #[test]
+    fn test_cells() {
+        let cells = cells("vf2", &sample_info());
+        assert_eq!(
+            cells,
+            vec![
+                "vf2",
+                "StarFive VisionFive 2",
+                "StarFive JH7110",
+                "StarFive",
+                "rv64gc",
+                "4",
+                "8.0 GiB",
+                "6.6.20-starfive",
+            ]
+        );
+
+        let mut unknown = sample_info();
+        unknown.board = "Homebrew FPGA SoC".to_string();
+        assert_eq!(super::cells("fpga", &unknown)[2], "-");
+    }
+
+    #[test]
+    fn test_render_aligns_and_reports_errors() {
+        let results = vec![
+            ("vf2".to_string(), Ok(sample_info())),
+            (
+                "board-with-long-name".to_string(),
+                Err("not RISC-V".to_string()),
+            ),
+        ];
+        let lines: Vec<String> = render(&results).iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Host                  Board"));
+        assert!(lines[1].starts_with("vf2                   StarFive VisionFive 2"));
+        assert_eq!(lines[2], "board-with-long-name  error: not RISC-V");
+    }
+}
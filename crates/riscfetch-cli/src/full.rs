@@ -0,0 +1,460 @@
+//! Exhaustive report (`--full`) for bug reports and reviews
+//!
+//! Everything riscfetch can detect, in sections (system, board, CPU, caches,
+//! memory, sensors, devices, firmware, kernel configuration), as plain text.
+//! Values that could not be read are listed as warnings at the end instead
+//! of being left out silently.
+
+use crate::fields::{format_gpu, format_memory_size};
+use riscfetch_core::{self as info, SystemInfo};
+use std::fmt::Write;
+
+/// Everything the report shows, collected up front so rendering is pure
+pub struct FullReport {
+    pub hostname: String,
+    pub data: SystemInfo,
+    pub board_details: Option<&'static info::Board>,
+    /// `isa` line of each hart, in `processor` order
+    pub hart_isas: Vec<String>,
+    pub init: Option<info::InitSystem>,
+    pub disks: Vec<info::DiskUsage>,
+    pub regulators: Vec<info::Regulator>,
+    pub gpus: Vec<info::GpuInfo>,
+    pub displays: Vec<info::DisplayInfo>,
+    pub mmc: Vec<info::MmcInfo>,
+    pub firmware: info::FirmwareInfo,
+    /// RISC-V options of the running kernel's configuration
+    pub kernel_config: Vec<(String, String)>,
+    /// Features the running kernel is too old for
+    pub kernel_hints: Vec<String>,
+}
+
+impl FullReport {
+    pub fn collect(hostname: String) -> Self {
+        let data = info::collect_all_info();
+        Self {
+            hostname,
+            board_details: info::get_board_details(),
+            hart_isas: info::get_cpuinfo().hart_isas.clone(),
+            init: info::get_init_system(),
+            disks: info::get_disk_usage(),
+            regulators: info::get_regulators(),
+            gpus: info::get_gpu_info(),
+            displays: info::get_display_info(),
+            mmc: info::get_mmc_info(),
+            firmware: info::get_firmware_info(),
+            kernel_config: info::get_kernel_config(),
+            kernel_hints: info::kernel_hints(
+                &data.kernel,
+                &data.isa,
+                info::get_dt_isa().as_deref(),
+            ),
+            data,
+        }
+    }
+}
+
+/// One titled block of `label value` rows
+struct Section {
+    title: &'static str,
+    rows: Vec<(String, String)>,
+}
+
+impl Section {
+    fn new(title: &'static str) -> Self {
+        Self {
+            title,
+            rows: Vec::new(),
+        }
+    }
+
+    fn row(&mut self, label: impl Into<String>, value: impl Into<String>) {
+        self.rows.push((label.into(), value.into()));
+    }
+
+    /// Add the row unless the value is empty
+    fn row_if(&mut self, label: impl Into<String>, value: impl Into<String>) {
+        let value = value.into();
+        if !value.is_empty() {
+            self.row(label, value);
+        }
+    }
+
+    fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "{}", self.title);
+        if self.rows.is_empty() {
+            let _ = writeln!(out, "  none detected");
+        }
+        let width = self
+            .rows
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0);
+        for (label, value) in &self.rows {
+            let _ = writeln!(
+                out,
+                "  {:<width$}  {value}",
+                format!("{label}:"),
+                width = width + 1
+            );
+        }
+        let _ = writeln!(out);
+    }
+}
+
+fn join_names(exts: &[info::ExtensionEntry]) -> String {
+    exts.iter()
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Harts with identical `isa` lines grouped into ranges: (`0-3`, isa)
+fn hart_groups(hart_isas: &[String]) -> Vec<(String, &str)> {
+    let mut groups: Vec<(usize, usize, &str)> = Vec::new();
+    for (hart, isa) in hart_isas.iter().enumerate() {
+        match groups.last_mut() {
+            Some((_, last, group_isa)) if *group_isa == isa => *last = hart,
+            _ => groups.push((hart, hart, isa)),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(first, last, isa)| {
+            let range = if first == last {
+                first.to_string()
+            } else {
+                format!("{first}-{last}")
+            };
+            (range, isa)
+        })
+        .collect()
+}
+
+fn system_section(report: &FullReport) -> Section {
+    let data = &report.data;
+    let mut section = Section::new("System");
+    section.row_if("Host", report.hostname.clone());
+    section.row_if("OS", data.os.clone());
+    section.row_if("Kernel", data.kernel.clone());
+    if let Some(runtime) = &data.container {
+        section.row("Container", runtime.clone());
+    }
+    if let Some(init) = &report.init {
+        section.row(
+            "Init",
+            match &init.version {
+                Some(version) => format!("{} {version}", init.name),
+                None => init.name.clone(),
+            },
+        );
+    }
+    section.row("Uptime", info::format_uptime(data.uptime_seconds));
+    if let Some(load) = data.load_average {
+        section.row(
+            "Load",
+            format!("{:.2} {:.2} {:.2}", load.one, load.five, load.fifteen),
+        );
+    }
+    section
+}
+
+fn board_section(report: &FullReport) -> Section {
+    let data = &report.data;
+    let mut section = Section::new("Board");
+    section.row_if("Model", data.board.clone());
+    if let Some(board) = report.board_details {
+        section.row("SoC", board.soc.clone());
+        section.row(
+            "Released",
+            format!("{} ({})", board.year, board.form_factor),
+        );
+        section.row("RAM options", board.ram.join(", "));
+        section.row("Link", board.url.clone());
+    }
+    let ids = &data.hardware_ids;
+    section.row_if(
+        "mvendorid",
+        match info::decode_mvendorid(&ids.mvendorid) {
+            Some(name) => format!("{} ({name})", ids.mvendorid),
+            None => ids.mvendorid.clone(),
+        },
+    );
+    section.row_if("marchid", ids.marchid.clone());
+    section.row_if("mimpid", ids.mimpid.clone());
+    section
+}
+
+fn cpu_section(report: &FullReport) -> Section {
+    let data = &report.data;
+    let mut section = Section::new("CPU");
+    section.row("ISA", data.isa.clone());
+    section.row_if("Standard", join_names(&data.extensions));
+    section.row_if("Z-extensions", join_names(&data.z_extensions));
+    section.row_if("S-extensions", join_names(&data.s_extensions));
+    let vector = &data.vector;
+    section.row(
+        "Vector",
+        match (vector.enabled, vector.vlen, vector.elen) {
+            (false, _, _) => "not available".to_string(),
+            (true, Some(vlen), Some(elen)) => format!("VLEN={vlen}, ELEN={elen}"),
+            (true, Some(vlen), None) => format!("VLEN={vlen}"),
+            (true, None, _) => "enabled".to_string(),
+        },
+    );
+    section.row("Harts", data.hart_count.to_string());
+    for (range, isa) in hart_groups(&report.hart_isas) {
+        section.row(format!("Hart {range}"), isa);
+    }
+    if let Some(divergence) = &data.isa_divergence {
+        section.row(
+            "Partial",
+            format!(
+                "{} (not on every hart)",
+                divergence.partial_extensions.join(" ")
+            ),
+        );
+    }
+    section
+}
+
+fn cache_section(report: &FullReport) -> Section {
+    let cache = &report.data.cache;
+    let mut section = Section::new("Caches");
+    for (label, size) in [
+        ("L1d", &cache.l1d),
+        ("L1i", &cache.l1i),
+        ("L2", &cache.l2),
+        ("L3", &cache.l3),
+    ] {
+        if let Some(size) = size {
+            section.row(label, size.clone());
+        }
+    }
+    section
+}
+
+fn memory_section(report: &FullReport) -> Section {
+    let data = &report.data;
+    let mut section = Section::new("Memory");
+    section.row("Total", format_memory_size(data.memory_total_bytes));
+    section.row("Used", format_memory_size(data.memory_used_bytes));
+    if let Some(breakdown) = data.memory_breakdown {
+        section.row("Available", format_memory_size(breakdown.available_bytes));
+        section.row(
+            "Buffers/cache",
+            format_memory_size(breakdown.buffers_cache_bytes),
+        );
+        if let Some(reserved) = breakdown.reserved_bytes {
+            section.row("Reserved", format_memory_size(reserved));
+        }
+    }
+    for disk in &report.disks {
+        section.row(
+            format!("Disk {}", disk.mount_point),
+            format!(
+                "{} / {} ({})",
+                format_memory_size(disk.used_bytes),
+                format_memory_size(disk.total_bytes),
+                disk.file_system
+            ),
+        );
+    }
+    section
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn sensor_section(report: &FullReport) -> Section {
+    let mut section = Section::new("Sensors");
+    for fan in &report.data.sensors.fans {
+        section.row(
+            format!("{} ({})", fan.label, fan.chip),
+            format!("{} RPM", fan.rpm),
+        );
+    }
+    for rail in &report.data.sensors.voltages {
+        section.row(
+            format!("{} ({})", rail.label, rail.chip),
+            format!("{:.2} V", rail.millivolts as f64 / 1000.0),
+        );
+    }
+    for power in &report.data.sensors.powers {
+        section.row(
+            format!("{} ({})", power.label, power.chip),
+            format!("{:.2} W", power.microwatts as f64 / 1_000_000.0),
+        );
+    }
+    for regulator in &report.regulators {
+        let mut value = regulator.microvolts.map_or_else(
+            || "-".to_string(),
+            |uv| format!("{:.2} V", uv as f64 / 1_000_000.0),
+        );
+        match regulator.enabled {
+            Some(true) => value.push_str(" (on)"),
+            Some(false) => value.push_str(" (off)"),
+            None => {}
+        }
+        section.row(format!("Rail {}", regulator.name), value);
+    }
+    section
+}
+
+fn device_section(report: &FullReport) -> Section {
+    let mut section = Section::new("Devices");
+    for gpu in &report.gpus {
+        section.row("GPU", format_gpu(gpu));
+    }
+    for display in &report.displays {
+        section.row(
+            "Display",
+            match &display.resolution {
+                Some(resolution) => format!("{} {resolution}", display.connector),
+                None => display.connector.clone(),
+            },
+        );
+    }
+    for card in &report.mmc {
+        let kind = if card.kind == "MMC" { "eMMC" } else { "SD" };
+        let mut parts = vec![match &card.manufacturer {
+            Some(manufacturer) => format!("{manufacturer} {kind} {}", card.name),
+            None => format!("{kind} {}", card.name),
+        }];
+        parts.push(format_memory_size(card.size_bytes));
+        parts.extend(card.speed_class.clone());
+        parts.push(format!("CID {}", card.cid));
+        section.row(format!("Storage {}", card.device), parts.join(", "));
+    }
+    section
+}
+
+fn firmware_section(report: &FullReport) -> Section {
+    let firmware = &report.firmware;
+    let mut section = Section::new("Firmware");
+    let interfaces: Vec<&str> = [
+        (firmware.uefi, "UEFI"),
+        (firmware.acpi, "ACPI"),
+        (firmware.device_tree, "device tree"),
+    ]
+    .iter()
+    .filter(|(present, _)| *present)
+    .map(|(_, name)| *name)
+    .collect();
+    section.row_if("Boot", interfaces.join(", "));
+    if let Some(version) = &firmware.bootloader {
+        section.row("Bootloader", format!("U-Boot {version}"));
+    }
+    section.row_if("BIOS", firmware.bios.clone().unwrap_or_default());
+    section
+}
+
+fn kernel_section(report: &FullReport) -> Section {
+    let mut section = Section::new("Kernel configuration");
+    for (name, value) in &report.kernel_config {
+        section.row(name.clone(), value.clone());
+    }
+    for hint in &report.kernel_hints {
+        section.row("Hint", hint.clone());
+    }
+    section
+}
+
+/// The whole report
+#[must_use]
+pub fn render(report: &FullReport) -> String {
+    let sections = [
+        system_section(report),
+        board_section(report),
+        cpu_section(report),
+        cache_section(report),
+        memory_section(report),
+        sensor_section(report),
+        device_section(report),
+        firmware_section(report),
+        kernel_section(report),
+    ];
+    let mut out = String::new();
+    for section in &sections {
+        section.render(&mut out);
+    }
+
+    if !report.data.warnings.is_empty() {
+        let mut warnings = Section::new("Warnings");
+        for warning in &report.data.warnings {
+            warnings.row(warning.field.clone(), warning.reason.clone());
+        }
+        warnings.render(&mut out);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::sample_info;
+
+    fn sample_report() -> FullReport {
+        FullReport {
+            hostname: "vf2".to_string(),
+            data: SystemInfo {
+                warnings: vec![info::FieldWarning {
+                    field: "cache".to_string(),
+                    reason: "not found in /sys/devices/system/cpu/cpu0/cache".to_string(),
+                }],
+                ..sample_info()
+            },
+            board_details: info::find_board("StarFive VisionFive 2 v1.3B", &[]),
+            hart_isas: vec!["rv64imafdc_zicsr_zba_zbb".to_string(); 4],
+            init: None,
+            disks: Vec::new(),
+            regulators: Vec::new(),
+            gpus: Vec::new(),
+            displays: Vec::new(),
+            mmc: Vec::new(),
+            firmware: info::FirmwareInfo {
+                device_tree: true,
+                bootloader: Some("2021.10".to_string()),
+                ..info::FirmwareInfo::default()
+            },
+            kernel_config: vec![("CONFIG_RISCV_ISA_V".to_string(), "n".to_string())],
+            kernel_hints: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_hart_groups() {
+        let isas = ["rv64gcv", "rv64gcv", "rv64gc", "rv64gc", "rv64gcv"].map(String::from);
+        assert_eq!(
+            hart_groups(&isas),
+            [("0-1", "rv64gcv"), ("2-3", "rv64gc"), ("4", "rv64gcv")]
+                .map(|(range, isa)| (range.to_string(), isa))
+        );
+        assert!(hart_groups(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_render_sections() {
+        let text = render(&sample_report());
+        for title in [
+            "System\n",
+            "Board\n",
+            "CPU\n",
+            "Caches\n",
+            "Memory\n",
+            "Sensors\n",
+            "Devices\n",
+            "Firmware\n",
+            "Kernel configuration\n",
+            "Warnings\n",
+        ] {
+            assert!(text.contains(title), "missing section {title}");
+        }
+        assert!(text.contains("  SoC:          StarFive JH7110\n"));
+        assert!(text.contains("  Hart 0-3:      rv64imafdc_zicsr_zba_zbb\n"));
+        assert!(text.contains("  Boot:        device tree\n  Bootloader:  U-Boot 2021.10\n"));
+        assert!(text.contains("  CONFIG_RISCV_ISA_V:  n\n"));
+        assert!(text.contains("Devices\n  none detected\n"));
+        assert!(text.contains("  cache:  not found in"));
+        assert!(!text.contains('\x1b'));
+    }
+}
@@ -0,0 +1,210 @@
+//! Per-hart frequency, load and temperature (`riscfetch harts`)
+//!
+//! Utilization needs two readings of /proc/stat, so collecting the table
+//! takes one sample interval. Each hart is shown with the thermal zone that
+//! throttles it, if any.
+
+use crate::display::visible_width;
+use colored::Colorize;
+use riscfetch_core::{self as info, CpuTimes, HartFreq, ThermalZone};
+use serde::Serialize;
+use std::time::Duration;
+
+/// Time between the two /proc/stat readings
+pub const SAMPLE: Duration = Duration::from_millis(500);
+
+/// Table columns, in order
+const HEADERS: [&str; 7] = ["Hart", "Freq", "Range", "Governor", "Load", "Zone", "Temp"];
+
+/// One hart's status
+#[derive(Debug, Serialize, PartialEq)]
+pub struct HartRow {
+    pub hart: usize,
+    pub current_khz: Option<u64>,
+    pub min_khz: Option<u64>,
+    pub max_khz: Option<u64>,
+    pub governor: Option<String>,
+    /// Busy percentage over the sample interval
+    pub utilization: Option<f64>,
+    /// Type of the thermal zone covering this hart
+    pub thermal_zone: Option<String>,
+    pub millicelsius: Option<i64>,
+}
+
+/// Rows for every hart seen in cpufreq or /proc/stat, by hart number
+#[must_use]
+pub fn rows(
+    freqs: &[HartFreq],
+    before: &[(usize, CpuTimes)],
+    after: &[(usize, CpuTimes)],
+    zones: &[ThermalZone],
+) -> Vec<HartRow> {
+    let mut harts: Vec<usize> = freqs
+        .iter()
+        .map(|f| f.hart)
+        .chain(after.iter().map(|(hart, _)| *hart))
+        .collect();
+    harts.sort_unstable();
+    harts.dedup();
+
+    let times = |list: &[(usize, CpuTimes)], hart| {
+        list.iter()
+            .find(|(h, _)| *h == hart)
+            .map(|(_, times)| *times)
+    };
+    harts
+        .into_iter()
+        .map(|hart| {
+            let freq = freqs.iter().find(|f| f.hart == hart);
+            let zone = zones.iter().find(|z| z.harts.contains(&hart));
+            HartRow {
+                hart,
+                current_khz: freq.and_then(|f| f.current_khz),
+                min_khz: freq.and_then(|f| f.min_khz),
+                max_khz: freq.and_then(|f| f.max_khz),
+                governor: freq.and_then(|f| f.governor.clone()),
+                utilization: times(before, hart)
+                    .zip(times(after, hart))
+                    .and_then(|(b, a)| info::cpu_utilization(b, a)),
+                thermal_zone: zone.map(|z| z.name.clone()),
+                millicelsius: zone.map(|z| z.millicelsius),
+            }
+        })
+        .collect()
+}
+
+/// Read everything, waiting `sample` between the two /proc/stat readings
+#[must_use]
+pub fn collect(sample: Duration) -> Vec<HartRow> {
+    let before = info::get_cpu_times();
+    std::thread::sleep(sample);
+    let after = info::get_cpu_times();
+    rows(
+        &info::get_cpufreq(),
+        &before,
+        &after,
+        &info::get_thermal_zones(),
+    )
+}
+
+fn mhz(khz: Option<u64>) -> String {
+    khz.map_or_else(|| "-".to_string(), |khz| format!("{} MHz", khz / 1000))
+}
+
+/// Table lines, header first; `-` where a value is unavailable
+#[must_use]
+pub fn render(rows: &[HartRow]) -> Vec<String> {
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            let range = match (row.min_khz, row.max_khz) {
+                (Some(min), Some(max)) => format!("{}-{} MHz", min / 1000, max / 1000),
+                _ => "-".to_string(),
+            };
+            #[allow(clippy::cast_precision_loss)]
+            let temp = row.millicelsius.map_or_else(
+                || "-".to_string(),
+                |m| format!("{:.1} °C", m as f64 / 1000.0),
+            );
+            vec![
+                row.hart.to_string(),
+                mhz(row.current_khz),
+                range,
+                row.governor.clone().unwrap_or_else(|| "-".to_string()),
+                row.utilization
+                    .map_or_else(|| "-".to_string(), |u| format!("{u:.1}%")),
+                row.thermal_zone.clone().unwrap_or_else(|| "-".to_string()),
+                temp,
+            ]
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = HEADERS.iter().map(|h| h.len()).collect();
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(visible_width(cell));
+        }
+    }
+    let join = |cells: Vec<String>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| {
+                let fill = width.saturating_sub(visible_width(cell));
+                format!("{cell}{}", " ".repeat(fill))
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![join(
+        HEADERS
+            .iter()
+            .map(|h| h.bright_yellow().bold().to_string())
+            .collect(),
+    )];
+    for mut row in cells {
+        row[0] = row[0].bright_cyan().bold().to_string();
+        lines.push(join(row));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+
+    fn times(busy: u64, total: u64) -> CpuTimes {
+        CpuTimes { busy, total }
+    }
+
+    fn sample() -> Vec<HartRow> {
+        let freqs = [HartFreq {
+            hart: 0,
+            current_khz: Some(1_500_000),
+            min_khz: Some(375_000),
+            max_khz: Some(1_500_000),
+            governor: Some("schedutil".to_string()),
+        }];
+        let zones = [ThermalZone {
+            zone: "thermal_zone0".to_string(),
+            name: "cpu-thermal".to_string(),
+            millicelsius: 45_250,
+            harts: vec![0],
+        }];
+        rows(
+            &freqs,
+            &[(0, times(100, 1000)), (1, times(0, 1000))],
+            &[(0, times(150, 1200)), (1, times(0, 1200))],
+            &zones,
+        )
+    }
+
+    #[test]
+    fn test_rows() {
+        let rows = sample();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].utilization, Some(25.0));
+        assert_eq!(rows[0].thermal_zone.as_deref(), Some("cpu-thermal"));
+        // A hart without cpufreq or a zone still has its load
+        assert_eq!(rows[1].current_khz, None);
+        assert_eq!(rows[1].utilization, Some(0.0));
+        assert_eq!(rows[1].millicelsius, None);
+    }
+
+    #[test]
+    fn test_render() {
+        let lines: Vec<String> = render(&sample()).iter().map(|l| strip_ansi(l)).collect();
+        assert_eq!(
+            lines,
+            [
+                "Hart  Freq      Range         Governor   Load   Zone         Temp",
+                "0     1500 MHz  375-1500 MHz  schedutil  25.0%  cpu-thermal  45.2 °C",
+                "1     -         -             -          0.0%   -            -",
+            ]
+        );
+    }
+}
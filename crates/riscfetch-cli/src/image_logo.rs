@@ -0,0 +1,315 @@
+//! Image logos via terminal graphics protocols (requires the `image-logo` cargo feature)
+//!
+//! Shows a user-supplied PNG logo with the kitty graphics protocol, the
+//! iTerm2 inline image protocol or sixel, whichever the terminal supports.
+//! No vendor images are bundled, so no trademarks are redistributed; logos
+//! come from the logo directory or an explicit file. Callers fall back to
+//! the ASCII logo when `render` returns None.
+
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that forces a protocol (kitty, iterm, sixel, none)
+pub const PROTOCOL_ENV: &str = "RISCFETCH_IMAGE_PROTOCOL";
+
+/// Widest sixel image emitted; larger logos are scaled down
+const SIXEL_MAX_WIDTH: u32 = 256;
+
+/// Payload bytes per kitty graphics escape sequence
+const KITTY_CHUNK: usize = 4096;
+
+/// Terminal graphics protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Kitty,
+    Iterm,
+    Sixel,
+}
+
+impl Protocol {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "kitty" => Some(Self::Kitty),
+            "iterm" | "iterm2" => Some(Self::Iterm),
+            "sixel" => Some(Self::Sixel),
+            _ => None,
+        }
+    }
+}
+
+/// Pick a protocol from the terminal environment.
+///
+/// Querying the terminal would need raw mode, so detection relies on the
+/// variables terminals set; `RISCFETCH_IMAGE_PROTOCOL` overrides it.
+#[must_use]
+pub fn detect_protocol(get: impl Fn(&str) -> Option<String>) -> Option<Protocol> {
+    if let Some(forced) = get(PROTOCOL_ENV) {
+        return Protocol::from_name(&forced);
+    }
+    let term = get("TERM").unwrap_or_default();
+    let program = get("TERM_PROGRAM").unwrap_or_default();
+
+    if term == "xterm-kitty" || term == "xterm-ghostty" || get("KITTY_WINDOW_ID").is_some() {
+        Some(Protocol::Kitty)
+    } else if program == "iTerm.app" || program == "WezTerm" {
+        Some(Protocol::Iterm)
+    } else if term.contains("sixel") || term.starts_with("foot") || term == "mlterm" {
+        Some(Protocol::Sixel)
+    } else {
+        None
+    }
+}
+
+/// Directory searched for `<vendor>.png` logos
+#[must_use]
+pub fn logo_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .map(|dir| dir.join("riscfetch/logos"))
+}
+
+/// Standard base64 (RFC 4648) with padding
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = (u32::from(chunk[0]) << 16)
+            | (u32::from(*chunk.get(1).unwrap_or(&0)) << 8)
+            | u32::from(*chunk.get(2).unwrap_or(&0));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 63]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// kitty graphics protocol: transmit the PNG as-is and display it
+fn encode_kitty(png: &[u8]) -> String {
+    let payload = base64(png);
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK).collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            out.push_str(&format!("\x1b_Gf=100,a=T,m={more};{chunk}\x1b\\"));
+        } else {
+            out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    out.push('\n');
+    out
+}
+
+/// iTerm2 inline image protocol (also understood by WezTerm)
+fn encode_iterm(png: &[u8]) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};preserveAspectRatio=1:{}\x07\n",
+        png.len(),
+        base64(png)
+    )
+}
+
+/// Decode a PNG into RGBA pixels
+fn decode_rgba(png: &[u8]) -> Result<(u32, u32, Vec<u8>), String> {
+    let mut decoder = ::png::Decoder::new(png);
+    decoder.set_transformations(::png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buf).map_err(|e| e.to_string())?;
+    buf.truncate(frame.buffer_size());
+
+    let rgba = match frame.color_type {
+        ::png::ColorType::Rgba => buf,
+        ::png::ColorType::Rgb => buf
+            .chunks(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        ::png::ColorType::GrayscaleAlpha => buf
+            .chunks(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        ::png::ColorType::Grayscale => buf.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        ::png::ColorType::Indexed => return Err("unexpected indexed PNG".to_string()),
+    };
+    Ok((frame.width, frame.height, rgba))
+}
+
+/// Index into the 6x6x6 color cube, or None for transparent pixels
+fn cube_index(pixel: &[u8]) -> Option<usize> {
+    if pixel[3] < 128 {
+        return None;
+    }
+    let level = |c: u8| (usize::from(c) * 5 + 127) / 255;
+    Some(level(pixel[0]) * 36 + level(pixel[1]) * 6 + level(pixel[2]))
+}
+
+/// Sixel: scale down to `SIXEL_MAX_WIDTH`, quantize to a 216-color cube
+fn encode_sixel(png: &[u8]) -> Result<String, String> {
+    let (width, height, rgba) = decode_rgba(png)?;
+    let step = width.div_ceil(SIXEL_MAX_WIDTH).max(1);
+    let (w, h) = (width / step, height / step);
+    let pixel = |x: u32, y: u32| {
+        let i = ((y * step * width + x * step) * 4) as usize;
+        cube_index(&rgba[i..i + 4])
+    };
+
+    let mut out = format!("\x1bP0;1;0q\"1;1;{w};{h}");
+    for i in 0..216 {
+        let pct = |level: usize| level * 100 / 5;
+        out.push_str(&format!(
+            "#{i};2;{};{};{}",
+            pct(i / 36),
+            pct(i / 6 % 6),
+            pct(i % 6)
+        ));
+    }
+
+    for band in (0..h).step_by(6) {
+        let mut used = [false; 216];
+        for y in band..(band + 6).min(h) {
+            for x in 0..w {
+                if let Some(c) = pixel(x, y) {
+                    used[c] = true;
+                }
+            }
+        }
+        for color in (0..216).filter(|&c| used[c]) {
+            out.push_str(&format!("#{color}"));
+            let mut run: Option<(char, usize)> = None;
+            for x in 0..w {
+                let mut bits = 0u8;
+                for dy in 0..6 {
+                    if band + dy < h && pixel(x, band + dy) == Some(color) {
+                        bits |= 1 << dy;
+                    }
+                }
+                let ch = char::from(63 + bits);
+                run = match run {
+                    Some((prev, n)) if prev == ch => Some((prev, n + 1)),
+                    Some((prev, n)) => {
+                        push_run(&mut out, prev, n);
+                        Some((ch, 1))
+                    }
+                    None => Some((ch, 1)),
+                };
+            }
+            if let Some((prev, n)) = run {
+                push_run(&mut out, prev, n);
+            }
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\\n");
+    Ok(out)
+}
+
+fn push_run(out: &mut String, ch: char, n: usize) {
+    if n > 3 {
+        out.push_str(&format!("!{n}{ch}"));
+    } else {
+        out.extend(std::iter::repeat_n(ch, n));
+    }
+}
+
+/// Escape sequence that draws `path` on this terminal, or None to fall back to ASCII
+#[must_use]
+pub fn render(path: &Path) -> Option<String> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let protocol = detect_protocol(|key| std::env::var(key).ok())?;
+    let png = std::fs::read(path).ok()?;
+    match protocol {
+        Protocol::Kitty => Some(encode_kitty(&png)),
+        Protocol::Iterm => Some(encode_iterm(&png)),
+        Protocol::Sixel => encode_sixel(&png).ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| (*v).to_string())
+        }
+    }
+
+    /// 2x1 PNG: one opaque red pixel, one transparent pixel
+    fn tiny_png() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut encoder = ::png::Encoder::new(&mut bytes, 2, 1);
+        encoder.set_color(::png::ColorType::Rgba);
+        encoder.set_depth(::png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer
+            .write_image_data(&[255, 0, 0, 255, 0, 0, 0, 0])
+            .unwrap();
+        drop(writer);
+        bytes
+    }
+
+    #[test]
+    fn test_detect_protocol() {
+        assert_eq!(
+            detect_protocol(env(&[("TERM", "xterm-kitty")])),
+            Some(Protocol::Kitty)
+        );
+        assert_eq!(
+            detect_protocol(env(&[("TERM_PROGRAM", "iTerm.app")])),
+            Some(Protocol::Iterm)
+        );
+        assert_eq!(
+            detect_protocol(env(&[("TERM", "foot")])),
+            Some(Protocol::Sixel)
+        );
+        assert_eq!(detect_protocol(env(&[("TERM", "xterm-256color")])), None);
+    }
+
+    #[test]
+    fn test_detect_protocol_forced() {
+        assert_eq!(
+            detect_protocol(env(&[(PROTOCOL_ENV, "sixel"), ("TERM", "xterm-kitty")])),
+            Some(Protocol::Sixel)
+        );
+        assert_eq!(
+            detect_protocol(env(&[(PROTOCOL_ENV, "none"), ("TERM", "xterm-kitty")])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_encode_kitty_chunks() {
+        let out = encode_kitty(&[0u8; 4000]);
+        // 4000 bytes -> 5336 base64 chars -> two chunks
+        assert!(out.starts_with("\x1b_Gf=100,a=T,m=1;"));
+        assert!(out.contains("\x1b_Gm=0;"));
+    }
+
+    #[test]
+    fn test_encode_sixel() {
+        let out = encode_sixel(&tiny_png()).unwrap();
+        assert!(out.starts_with("\x1bP0;1;0q\"1;1;2;1"));
+        // Red is cube index 5*36 = 180; only the first column is set
+        assert!(out.contains("#180@?$-"));
+        assert!(out.ends_with("\x1b\\\n"));
+    }
+}
@@ -1,9 +1,15 @@
 //! Logo generation using figlet-rs
 //!
-//! Dynamically generates ASCII art logos for vendors using `FIGlet` fonts.
+//! Vendors with pre-rendered ANSI art in `assets/logos/` use it; the others
+//! get an ASCII art logo generated with `FIGlet` fonts. The fonts other than
+//! standard (built into figlet-rs) are embedded from `fonts/*.flf`, so
+//! `--figlet-font` works offline.
 
-use crate::vendors::{get_default_vendor, get_vendor_info};
+use crate::display::strip_ansi;
+use crate::distros::get_distro_info;
+use crate::vendors::{get_default_vendor, get_vendor_info, small_mark};
 use figlet_rs::FIGfont;
+use riscfetch_core as info;
 use std::fmt::Write;
 
 /// RISC-V block letter logo
@@ -17,6 +23,14 @@ const RISCV_LOGO: &str = r"
                     Architecture Info
 ";
 
+/// Pre-rendered ANSI art for the normal style, keyed by primary alias.
+/// Every line ends with a reset so lines can be laid out independently.
+const ART_LOGOS: &[(&str, &str)] = &[
+    ("sifive", include_str!("../assets/logos/sifive.ans")),
+    ("starfive", include_str!("../assets/logos/starfive.ans")),
+    ("sipeed", include_str!("../assets/logos/sipeed.ans")),
+];
+
 /// Logo display styles
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogoStyle {
@@ -36,29 +50,122 @@ impl LogoStyle {
     }
 }
 
+/// Bundled `FIGlet` fonts for the generated vendor logos
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FigletFont {
+    #[default]
+    Standard,
+    Slant,
+    Small,
+    Banner,
+}
+
+impl FigletFont {
+    /// Font names accepted by `--figlet-font` and `figlet_font` in the config file
+    pub const NAMES: [&'static str; 4] = ["standard", "slant", "small", "banner"];
+
+    /// Parse a font name (case-insensitive)
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "standard" => Ok(Self::Standard),
+            "slant" => Ok(Self::Slant),
+            "small" => Ok(Self::Small),
+            "banner" => Ok(Self::Banner),
+            _ => Err(format!(
+                "unknown FIGlet font '{name}' (expected one of: {})",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+
+    fn load(self) -> Result<FIGfont, String> {
+        match self {
+            Self::Standard => FIGfont::standard(),
+            Self::Slant => FIGfont::from_content(include_str!("../fonts/slant.flf")),
+            Self::Small => FIGfont::from_content(include_str!("../fonts/small.flf")),
+            Self::Banner => FIGfont::from_content(include_str!("../fonts/banner.flf")),
+        }
+    }
+}
+
+/// Primary alias of `vendor`, `default` for unknown vendors
+fn primary_alias(vendor: &str) -> &'static str {
+    info::find_vendor(vendor).map_or("default", |v| v.id)
+}
+
+/// Pre-rendered ANSI art (with color escapes) for the vendor's normal logo
+///
+/// Picking a `--figlet-font` other than standard asks for a generated logo,
+/// so art is only used with the standard font.
+#[must_use]
+pub fn art_logo(vendor: &str, style: LogoStyle, font: FigletFont) -> Option<&'static str> {
+    if style != LogoStyle::Normal || font != FigletFont::Standard {
+        return None;
+    }
+    let primary = primary_alias(vendor);
+    ART_LOGOS
+        .iter()
+        .find(|(alias, _)| *alias == primary)
+        .map(|(_, art)| *art)
+}
+
 /// Generate ASCII art logo for the specified vendor
+///
+/// Pre-rendered art is returned without its colors. `font` applies to the
+/// generated vendor logos only; the RISC-V and distro logos are drawn by hand.
 #[must_use]
-pub fn generate_logo(vendor: &str, style: LogoStyle) -> String {
+pub fn generate_logo(vendor: &str, style: LogoStyle, font: FigletFont) -> String {
+    if let Some((display_name, logo)) = get_distro_info(vendor) {
+        return match style {
+            LogoStyle::None => String::new(),
+            LogoStyle::Small => format!("  {display_name}"),
+            LogoStyle::Normal => logo.to_string(),
+        };
+    }
+
+    if let Some(art) = art_logo(vendor, style, font) {
+        return art.lines().map(strip_ansi).collect::<Vec<_>>().join("\n") + "\n";
+    }
+
     let (display_name, subtitle) = get_vendor_info(vendor).unwrap_or_else(get_default_vendor);
 
     match style {
         LogoStyle::None => String::new(),
-        LogoStyle::Small => format!("  {display_name} - {subtitle}"),
+        LogoStyle::Small => small_logo(vendor, display_name, subtitle),
         LogoStyle::Normal => {
             if display_name == "RISC-V" {
                 RISCV_LOGO.to_string()
             } else {
-                generate_figlet_logo(display_name, subtitle)
+                generate_figlet_logo(display_name, subtitle, font)
             }
         }
     }
 }
 
-/// Generate `FIGlet` ASCII art logo
-fn generate_figlet_logo(display_name: &str, subtitle: &str) -> String {
-    let standard_font = FIGfont::standard();
+/// Hand-drawn vendor mark with the name and subtitle to its right
+fn small_logo(vendor: &str, display_name: &str, subtitle: &str) -> String {
+    let Some(mark) = small_mark(primary_alias(vendor)) else {
+        return format!("  {display_name} - {subtitle}");
+    };
+
+    let width = mark.lines().map(str::len).max().unwrap_or(0);
+    let mut result = String::new();
+    for (i, line) in mark.lines().enumerate() {
+        let text = match i {
+            0 => display_name,
+            1 => subtitle,
+            _ => "",
+        };
+        let row = format!("  {line:<width$}   {text}");
+        result.push_str(row.trim_end());
+        result.push('\n');
+    }
+    result
+}
 
-    match standard_font {
+/// Generate `FIGlet` ASCII art logo
+fn generate_figlet_logo(display_name: &str, subtitle: &str, font: FigletFont) -> String {
+    match font.load() {
         Ok(font) => match font.convert(display_name) {
             Some(figure) => {
                 let fig_str = figure.to_string();
@@ -114,27 +221,38 @@ mod tests {
 
     #[test]
     fn test_generate_logo_none_style() {
-        let logo = generate_logo("default", LogoStyle::None);
+        let logo = generate_logo("default", LogoStyle::None, FigletFont::Standard);
         assert!(logo.is_empty());
     }
 
     #[test]
     fn test_generate_logo_small_style() {
-        let logo = generate_logo("default", LogoStyle::Small);
+        let logo = generate_logo("default", LogoStyle::Small, FigletFont::Standard);
         assert!(logo.contains("RISC-V"));
         assert!(logo.contains("Architecture Info"));
     }
 
+    #[test]
+    fn test_generate_logo_distro() {
+        let logo = generate_logo("debian", LogoStyle::Normal, FigletFont::Standard);
+        assert!(logo.contains("Debian"));
+        assert_eq!(
+            generate_logo("fedora", LogoStyle::Small, FigletFont::Standard),
+            "  Fedora"
+        );
+        assert!(generate_logo("ubuntu", LogoStyle::None, FigletFont::Standard).is_empty());
+    }
+
     #[test]
     fn test_generate_logo_small_vendor() {
-        let logo = generate_logo("sifive", LogoStyle::Small);
+        let logo = generate_logo("sifive", LogoStyle::Small, FigletFont::Standard);
         assert!(logo.contains("SiFive"));
         assert!(logo.contains("RISC-V by SiFive"));
     }
 
     #[test]
     fn test_generate_logo_normal_not_empty() {
-        let logo = generate_logo("default", LogoStyle::Normal);
+        let logo = generate_logo("default", LogoStyle::Normal, FigletFont::Standard);
         assert!(!logo.is_empty());
         // Should contain block letters from RISCV_LOGO
         assert!(logo.contains("██████╗"));
@@ -142,7 +260,7 @@ mod tests {
 
     #[test]
     fn test_unknown_vendor_uses_default() {
-        let logo = generate_logo("unknown_vendor", LogoStyle::Small);
+        let logo = generate_logo("unknown_vendor", LogoStyle::Small, FigletFont::Standard);
         assert!(logo.contains("RISC-V"));
         assert!(logo.contains("Architecture Info"));
     }
@@ -151,7 +269,7 @@ mod tests {
     fn test_all_vendors_have_logos() {
         for (aliases, _, _) in VENDORS {
             let vendor = aliases[0];
-            let logo = generate_logo(vendor, LogoStyle::Normal);
+            let logo = generate_logo(vendor, LogoStyle::Normal, FigletFont::Standard);
             assert!(!logo.is_empty(), "Logo for {vendor} should not be empty");
         }
     }
@@ -159,11 +277,87 @@ mod tests {
     #[test]
     fn test_new_vendors_logos() {
         // Pine64
-        let logo = generate_logo("pine64", LogoStyle::Small);
+        let logo = generate_logo("pine64", LogoStyle::Small, FigletFont::Standard);
         assert!(logo.contains("Pine64"));
 
         // WCH
-        let logo = generate_logo("wch", LogoStyle::Small);
+        let logo = generate_logo("wch", LogoStyle::Small, FigletFont::Standard);
         assert!(logo.contains("WCH"));
     }
+
+    #[test]
+    fn test_figlet_font_parse() {
+        assert_eq!(FigletFont::parse("slant"), Ok(FigletFont::Slant));
+        assert_eq!(FigletFont::parse("Banner"), Ok(FigletFont::Banner));
+        let err = FigletFont::parse("doom").unwrap_err();
+        assert!(err.contains("standard, slant, small, banner"));
+    }
+
+    #[test]
+    fn test_bundled_fonts_render_all_vendors() {
+        for name in FigletFont::NAMES {
+            let font = FigletFont::parse(name).unwrap();
+            assert!(font.load().is_ok(), "{name} should load");
+            for (aliases, display_name, subtitle) in VENDORS {
+                let logo = generate_logo(aliases[0], LogoStyle::Normal, font);
+                if *display_name != "RISC-V" {
+                    assert_ne!(logo, fallback_logo(display_name, subtitle), "{name}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_figlet_font_changes_vendor_logo() {
+        let standard = generate_logo("sifive", LogoStyle::Normal, FigletFont::Standard);
+        let banner = generate_logo("sifive", LogoStyle::Normal, FigletFont::Banner);
+        assert_ne!(standard, banner);
+        assert!(banner.contains('#'));
+        assert!(banner.contains("RISC-V by SiFive"));
+        // The hand-drawn RISC-V logo ignores the font
+        assert_eq!(
+            generate_logo("default", LogoStyle::Normal, FigletFont::Slant),
+            generate_logo("default", LogoStyle::Normal, FigletFont::Standard)
+        );
+    }
+
+    #[test]
+    fn test_small_logo_mark_beside_name() {
+        let logo = generate_logo("starfive", LogoStyle::Small, FigletFont::Standard);
+        let lines: Vec<&str> = logo.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("   \\ | /"));
+        assert!(lines[0].ends_with("StarFive"));
+        assert!(lines[1].ends_with("RISC-V by StarFive"));
+        // Aliases resolve to the primary vendor's mark
+        assert_eq!(
+            generate_logo("canaan", LogoStyle::Small, FigletFont::Standard),
+            generate_logo("kendryte", LogoStyle::Small, FigletFont::Standard)
+        );
+    }
+
+    #[test]
+    fn test_art_logos() {
+        for (vendor, art) in ART_LOGOS {
+            assert!(
+                get_vendor_info(vendor).is_some(),
+                "{vendor} is not a vendor"
+            );
+            for line in art.lines().filter(|l| l.contains('\x1b')) {
+                assert!(line.ends_with("\x1b[0m"), "{vendor}: line without reset");
+            }
+        }
+        assert!(art_logo("SiFive", LogoStyle::Normal, FigletFont::Standard).is_some());
+        assert!(art_logo("sifive", LogoStyle::Small, FigletFont::Standard).is_none());
+        assert!(art_logo("sifive", LogoStyle::Normal, FigletFont::Slant).is_none());
+        assert!(art_logo("wch", LogoStyle::Normal, FigletFont::Standard).is_none());
+    }
+
+    #[test]
+    fn test_generate_logo_strips_art_colors() {
+        let logo = generate_logo("starfive", LogoStyle::Normal, FigletFont::Standard);
+        assert!(!logo.contains('\x1b'));
+        assert!(logo.contains("███████╗"));
+        assert!(logo.contains("RISC-V by StarFive"));
+    }
 }
@@ -1,19 +1,65 @@
+mod banner;
 mod benchmark;
+#[cfg_attr(not(feature = "tui"), allow(dead_code))]
+mod browser;
+mod buildflags;
+mod capability;
 mod cli;
+mod compare;
+mod config;
+mod contribute;
 mod display;
+mod distros;
+mod doctor;
+mod dt;
+mod fields;
+mod fleet;
+mod full;
+mod harts;
+#[cfg(feature = "image-logo")]
+mod image_logo;
 mod logos;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+mod output;
+mod plugins;
+#[cfg(feature = "png")]
+mod png;
+mod qemu;
+mod query;
+mod report;
+mod sensors;
+#[cfg(feature = "network")]
+mod share;
+mod snapshot;
+#[cfg(feature = "network")]
+mod submit;
+mod template;
+mod toolchain;
 mod vendors;
+#[cfg(feature = "wasm-plugins")]
+mod wasm_plugins;
 
 use clap::Parser;
-use cli::Args;
+use cli::{Args, Command, SnapshotAction};
 use colored::Colorize;
 use riscfetch_core as info;
 
 fn main() {
     let args = Args::parse();
+    display::configure_color(&args.color, args.force_tty);
+    if args.debug {
+        init_debug_log();
+    }
+
+    // Logos can be previewed on any machine
+    if let Some(Command::Logo { preview }) = args.command {
+        run_logo(&args, preview);
+        return;
+    }
 
     if !info::is_riscv() {
-        if args.json {
+        if args.json || args.query.is_some() {
             println!(r#"{{"error": "not_riscv", "message": "This system is not RISC-V"}}"#);
         } else {
             println!("\n{}\n", "Sorry, not RISC-V".red().bold());
@@ -21,50 +67,1154 @@ fn main() {
         std::process::exit(1);
     }
 
+    if let Some(command) = args.command {
+        run_command(command);
+        return;
+    }
+
+    let bench_options = bench_options(args.runs, args.threads.as_deref(), args.per_hart);
+
+    if !args.check.is_empty() {
+        run_check(&args.check, args.quiet);
+    }
+
+    if !args.require.is_empty() {
+        run_require(&args.require, args.json);
+    }
+
+    if let Some(path) = args.query.as_deref() {
+        output_query(path, args.riscv_only, args.all, !args.no_plugins);
+        return;
+    }
+
     if args.json {
-        output_json(args.riscv_only, args.all);
+        output_json(
+            args.riscv_only,
+            args.all,
+            args.verbose,
+            !args.no_plugins,
+            args.benchmark.then_some(bench_options),
+        );
+        return;
+    }
+
+    if let Some(path) = &args.diff {
+        output_diff(path);
+        return;
+    }
+
+    if let Some(url) = args.compare_url.as_deref() {
+        output_compare_url(url);
+        return;
+    }
+
+    if let Some(broker) = args.mqtt.as_deref() {
+        run_mqtt(
+            broker,
+            args.topic.as_deref(),
+            args.mqtt_interval,
+            args.riscv_only,
+        );
+    }
+
+    if args.oneline {
+        println!("{}", display::format_oneline(&info::collect_all_info()));
+        return;
+    }
+
+    if args.full {
+        print!("{}", full::render(&full::FullReport::collect(hostname())));
+        return;
+    }
+
+    if args.rustflags {
+        output_rustflags();
+        return;
+    }
+
+    if args.qemu_cpu {
+        let cpu = qemu::qemu_cpu(&info::get_isa_string(), &info::get_vector_detail());
+        if !cpu.skipped.is_empty() {
+            eprintln!(
+                "{} vendor extensions left out: {}",
+                "Warning:".yellow().bold(),
+                cpu.skipped.join(" ")
+            );
+        }
+        println!("{}", cpu.option);
+        return;
+    }
+
+    if let Some(name) = args.field.as_deref() {
+        output_field(name);
+        return;
+    }
+
+    if let Some(path) = &args.template {
+        let template = read_file_or_exit(path);
+        let data = info::collect_all_info();
+        let ctx = template::Context {
+            info: &data,
+            hostname: &hostname(),
+            user: &current_user(),
+        };
+        print!("{}", template::render(&template, &ctx));
+        return;
+    }
+
+    if let Some(format) = args.output.as_deref() {
+        output_formatted(format);
+        return;
+    }
+
+    if let Some(name) = args.wasm_output.as_deref() {
+        output_wasm(name);
+        return;
+    }
+
+    let logo = resolve_logo(&args.logo);
+    let config = load_config();
+    let font = figlet_font(args.figlet_font.as_deref(), &config);
+
+    if args.export_svg.is_some() || args.export_png.is_some() {
+        let fields = display::card_fields(&info::collect_all_info());
+        let svg = display::render_svg(&logo, &args.style, font, &fields);
+        if let Some(path) = &args.export_svg {
+            write_file_or_exit(path, &svg);
+            println!("Card written to {}", path.display());
+        }
+        if let Some(path) = &args.export_png {
+            export_png(&svg, path);
+            println!("Card written to {}", path.display());
+        }
+        return;
+    }
+
+    let plain = display::is_plain(args.force_tty);
+    if plain && config.piped == config::Piped::Json {
+        output_json(
+            args.riscv_only,
+            args.all,
+            args.verbose,
+            !args.no_plugins,
+            args.benchmark.then_some(bench_options),
+        );
         return;
     }
 
-    // Auto-detect vendor logo when not explicitly specified
-    let logo = if args.logo == "default" {
+    let opts = DisplayOptions {
+        vendor: &logo,
+        style: &args.style,
+        font,
+        logo_colors: logo_colors(args.logo_colors.as_deref(), &config),
+        layout: &args.layout,
+        image_logo: args.image_logo.as_deref(),
+        fields: visible_fields(&config, &args.show, &args.hide, args.watch.is_some()),
+        explain: args.explain,
+        verbose: args.verbose,
+        plugins: !args.no_plugins,
+        plain,
+        riscv_only: args.riscv_only,
+        show_all: args.all,
+    };
+
+    if let Some(interval) = args.watch {
+        run_watch(&opts, interval);
+    }
+
+    display_riscv_info(&opts);
+
+    if args.benchmark {
+        println!();
+        benchmark::run_benchmarks(bench_options, !args.no_history);
+    }
+}
+
+/// Print the trace of the core library (file reads, commands, system calls,
+/// fallbacks taken) to stderr, for `--debug`
+fn init_debug_log() {
+    use std::io::IsTerminal;
+
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(tracing_subscriber::filter::LevelFilter::TRACE)
+        .with_ansi(std::io::stderr().is_terminal())
+        .without_time()
+        .init();
+}
+
+/// A saved run for `bench --compare`; unreadable or invalid files exit 1
+fn load_saved_run(path: &std::path::Path) -> benchmark::compare::Saved {
+    benchmark::compare::parse(&read_file_or_exit(path)).unwrap_or_else(|e| {
+        eprintln!("{} {}: {e}", "Error:".red().bold(), path.display());
+        std::process::exit(1);
+    })
+}
+
+/// Side-by-side table of this run and a saved one
+fn print_bench_comparison(
+    results: &riscfetch_bench::BenchResult,
+    saved: &benchmark::compare::Saved,
+    path: &std::path::Path,
+) {
+    let label = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().to_string(),
+    );
+    let heading = saved.board.as_ref().map_or_else(
+        || format!("Compared with {label}:"),
+        |board| format!("Compared with {label} ({board}):"),
+    );
+    println!("{}", heading.bright_yellow().bold());
+    let rows = benchmark::compare::rows(&serde_json::json!(results), &saved.benchmarks);
+    for line in benchmark::compare::render(&rows, "this board", &label) {
+        println!("  {line}");
+    }
+    println!();
+}
+
+/// Benchmark options from the command line; an invalid `--threads` exits 2
+fn bench_options(
+    runs: u16,
+    threads: Option<&str>,
+    per_hart: bool,
+) -> riscfetch_bench::BenchOptions {
+    let threads = threads.map(|text| {
+        riscfetch_bench::scaling::parse_threads(text).unwrap_or_else(|e| {
+            eprintln!("{} {e}", "Error:".red().bold());
+            std::process::exit(2);
+        })
+    });
+    riscfetch_bench::BenchOptions {
+        runs: usize::from(runs),
+        threads,
+        per_hart,
+    }
+}
+
+/// Auto-detect vendor logo when not explicitly specified; `os` picks the
+/// distro logo from /etc/os-release. Without device-tree board info
+/// (containers, QEMU) the `mvendorid` CSR decides. An unknown name warns,
+/// with a suggestion when it looks like a typo, and uses the default logo.
+fn resolve_logo(logo: &str) -> String {
+    if logo == "os" {
+        let os_release = std::fs::read_to_string("/etc/os-release").unwrap_or_default();
+        distros::detect_distro(&os_release)
+            .unwrap_or("default")
+            .to_string()
+    } else if logo == "default" {
         let board_info = info::get_board_info();
         let compatible =
             std::fs::read_to_string("/proc/device-tree/compatible").unwrap_or_default();
-        vendors::detect_vendor(&board_info, &compatible)
-            .unwrap_or("default")
-            .to_string()
+        info::detect_vendor(
+            &format!("{board_info} {compatible}"),
+            &info::get_hardware_ids(),
+        )
+        .map_or("default", |v| v.id)
+        .to_string()
+    } else if let Some(vendor) = info::find_vendor(logo) {
+        vendor.id.to_string()
+    } else if distros::get_distro_info(logo).is_some() {
+        logo.to_string()
+    } else {
+        let distro_ids = distros::DISTROS
+            .iter()
+            .flat_map(|(ids, _, _)| ids.iter())
+            .chain(&["os"])
+            .copied();
+        let suggestion =
+            info::suggest_vendor(logo).or_else(|| info::closest_match(logo, distro_ids));
+        match suggestion {
+            Some(name) => eprintln!(
+                "{} unknown logo '{logo}', did you mean '{name}'?",
+                "Warning:".yellow().bold()
+            ),
+            None => eprintln!(
+                "{} unknown logo '{logo}' (see `riscfetch vendors`)",
+                "Warning:".yellow().bold()
+            ),
+        }
+        "default".to_string()
+    }
+}
+
+fn run_command(command: Command) {
+    match command {
+        Command::Report {
+            html,
+            benchmark,
+            logo,
+        } => {
+            let data = info::collect_all_info();
+            let hostname = hostname();
+            let logo_text = logos::generate_logo(
+                &resolve_logo(&logo),
+                logos::LogoStyle::Normal,
+                logos::FigletFont::default(),
+            );
+            let bench =
+                benchmark.then(|| riscfetch_bench::run(riscfetch_bench::BenchOptions::default()));
+            let document = report::render_html(&data, &hostname, &logo_text, bench.as_ref());
+
+            write_file_or_exit(&html, &document);
+            println!("Report written to {}", html.display());
+        }
+        Command::Share { endpoint, yes } => run_share(endpoint.as_deref(), yes),
+        Command::Bench {
+            runs,
+            threads,
+            per_hart,
+            no_history,
+            json,
+            compare,
+            submit,
+            endpoint,
+            yes,
+        } => {
+            let options = bench_options(runs, threads.as_deref(), per_hart);
+            let endpoint = submit.then(|| submit_endpoint(endpoint.as_deref()));
+            let saved = compare.map(|path| (load_saved_run(&path), path));
+
+            if json {
+                let results = riscfetch_bench::run(options);
+                if !no_history {
+                    let _ = benchmark::history::record(&results);
+                }
+                let doc =
+                    serde_json::json!({ "board": info::get_board_info(), "benchmarks": results });
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&doc).unwrap_or_else(|_| "{}".to_string())
+                );
+                return;
+            }
+
+            let results = benchmark::run_benchmarks(options, !no_history);
+            if let Some((saved, path)) = saved {
+                print_bench_comparison(&results, &saved, &path);
+            }
+            if let Some(endpoint) = endpoint {
+                run_submit(&endpoint, &results, yes);
+            }
+        }
+        Command::Extensions {
+            search,
+            category,
+            interactive,
+        } => run_extensions(&search, category.as_deref(), interactive),
+        Command::Explain { name, long } => run_explain(&name, long),
+        Command::Contribute { issue, open } => run_contribute(issue, open),
+        Command::Banner {
+            write,
+            agetty,
+            install_hook,
+        } => run_banner(write.as_deref(), agetty, install_hook),
+        Command::Snapshot { action } => run_snapshot(action),
+        Command::Fleet { files } => run_fleet(&files),
+        Command::Vendors { json } => run_vendors(json),
+        Command::Doctor { json } => run_doctor(json),
+        Command::Dt { json } => run_dt(json),
+        Command::Harts { json } => run_harts(json),
+        Command::Sensors {
+            interval,
+            count,
+            json_lines,
+        } => sensors::monitor(
+            std::time::Duration::from_secs(interval.max(1)),
+            count,
+            json_lines,
+        ),
+        Command::Toolchain { json } => run_toolchain(json),
+        Command::Buildflags { cmake, json } => run_buildflags(cmake, json),
+        Command::Logo { .. } => unreachable!("handled before the RISC-V check"),
+    }
+}
+
+/// Print the selected logo, or with `preview` every vendor logo in both styles
+fn run_logo(args: &Args, preview: bool) {
+    let config = load_config();
+    let font = figlet_font(args.figlet_font.as_deref(), &config);
+    let colors = logo_colors(args.logo_colors.as_deref(), &config);
+    let lines = if preview {
+        display::logo_gallery(font, colors)
     } else {
-        args.logo.clone()
+        display::logo_lines(&resolve_logo(&args.logo), &args.style, font, colors)
     };
+    for line in lines {
+        println!("{line}");
+    }
+}
 
-    display_riscv_info(&logo, &args.style, args.explain, args.riscv_only, args.all);
+/// Check the environment and explain missing fields
+fn run_doctor(json: bool) {
+    let findings = doctor::diagnose(&doctor::Facts::gather());
+    if json {
+        let problems = findings.iter().filter(|f| !f.ok).count();
+        let doc = serde_json::json!({ "problems": problems, "findings": findings });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&doc).unwrap_or_else(|_| "{}".to_string())
+        );
+    } else {
+        for line in doctor::render(&findings) {
+            println!("{line}");
+        }
+    }
+}
 
-    if args.benchmark {
-        println!();
-        benchmark::run_benchmarks();
+/// Compare the extensions installed compilers can target with the hardware
+fn run_toolchain(json: bool) {
+    let isa = info::get_isa_string();
+    let compilers = toolchain::detect(&isa);
+    if json {
+        let doc = serde_json::json!({
+            "hardware": toolchain::hardware_extensions(&isa),
+            "compilers": compilers,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&doc).unwrap_or_else(|_| "{}".to_string())
+        );
+    } else {
+        for line in toolchain::render(&compilers) {
+            println!("{line}");
+        }
     }
 }
 
-fn output_json(riscv_only: bool, show_all: bool) {
-    if show_all {
-        output_json_all(riscv_only);
-    } else if riscv_only {
-        let data = info::collect_riscv_info();
+/// Dump the device-tree nodes the detectors read
+fn run_dt(json: bool) {
+    let nodes = info::get_dt_nodes();
+    if json {
         println!(
             "{}",
-            serde_json::to_string_pretty(&data).unwrap_or_else(|_| "{}".to_string())
+            serde_json::to_string_pretty(&nodes).unwrap_or_else(|_| "[]".to_string())
         );
+        return;
+    }
+    if nodes.is_empty() {
+        eprintln!(
+            "{} no device tree at /proc/device-tree (ACPI-only boot or no access)",
+            "Error:".red().bold()
+        );
+        std::process::exit(1);
+    }
+    for line in dt::render(&nodes) {
+        println!("{line}");
+    }
+}
+
+/// Print the per-hart table, sampling utilization over `harts::SAMPLE`
+fn run_harts(json: bool) {
+    let rows = harts::collect(harts::SAMPLE);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&rows).unwrap_or_else(|_| "[]".to_string())
+        );
+        return;
+    }
+    for line in harts::render(&rows) {
+        println!("{line}");
+    }
+}
+
+/// Print the build configuration as text, CMake or JSON
+fn run_buildflags(cmake: bool, json: bool) {
+    let flags = buildflags::BuildFlags::new(&info::get_isa_string(), info::get_core());
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&flags).unwrap_or_else(|_| "{}".to_string())
+        );
+    } else if cmake {
+        print!("{}", flags.cmake());
     } else {
-        let data = info::collect_all_info();
+        print!("{}", flags.render());
+    }
+}
+
+/// Print the recommended RUSTFLAGS; notes about what was left out go to stderr
+fn output_rustflags() {
+    let rustc = toolchain::rustc_target_info();
+    if rustc.is_none() {
+        eprintln!(
+            "{} rustc with the riscv64gc-unknown-linux-gnu target not found; features are not checked against it",
+            "Warning:".yellow().bold()
+        );
+    }
+    let rustc_lists = rustc
+        .as_ref()
+        .map(|(features, cpus)| (features.as_slice(), cpus.as_slice()));
+    let flags = toolchain::Rustflags::new(&info::get_isa_string(), info::get_core(), rustc_lists);
+    if !flags.unknown.is_empty() {
+        eprintln!(
+            "{} rustc does not know these extensions, left out: {}",
+            "Warning:".yellow().bold(),
+            flags.unknown.join(" ")
+        );
+    }
+    println!("{}", flags.flags());
+}
+
+/// List every `--logo` value: vendors with aliases and detection keywords, then distros
+fn run_vendors(json: bool) {
+    if json {
+        let vendors: Vec<serde_json::Value> = vendors::VENDORS
+            .iter()
+            .map(|(aliases, name, subtitle)| {
+                serde_json::json!({
+                    "id": aliases[0],
+                    "name": name,
+                    "subtitle": subtitle,
+                    "aliases": &aliases[1..],
+                    "keywords": info::detection_keywords(aliases[0]),
+                })
+            })
+            .collect();
+        let distros: Vec<serde_json::Value> = distros::DISTROS
+            .iter()
+            .map(|(ids, name, _)| {
+                serde_json::json!({ "id": ids[0], "name": name, "os_release_ids": ids })
+            })
+            .collect();
+        let doc = serde_json::json!({ "vendors": vendors, "distros": distros });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&doc).unwrap_or_else(|_| "{}".to_string())
+        );
+        return;
+    }
+
+    println!("{}", "Vendor logos:".bright_yellow().bold());
+    for (aliases, name, _) in vendors::VENDORS {
+        let mut line = format!("  {:<12} {:<11}", aliases[0].bright_cyan().bold(), name);
+        if aliases.len() > 1 {
+            line.push_str(&format!(" aliases: {}", aliases[1..].join(", ")));
+        }
+        let keywords = info::detection_keywords(aliases[0]);
+        if !keywords.is_empty() {
+            line.push_str(&format!(
+                "{}",
+                format!(" detects: {}", keywords.join(", ")).bright_black()
+            ));
+        }
+        println!("{line}");
+    }
+    println!();
+    println!(
+        "{}",
+        "Distro logos (-l os detects from /etc/os-release):"
+            .bright_yellow()
+            .bold()
+    );
+    for (ids, name, _) in distros::DISTROS {
+        let mut line = format!("  {:<12} {:<11}", ids[0].bright_cyan().bold(), name);
+        if ids.len() > 1 {
+            line.push_str(&format!(" os-release: {}", ids[1..].join(", ")));
+        }
+        println!("{line}");
+    }
+}
+
+/// Print the fleet table, exiting 1 if any source could not be collected
+fn run_fleet(files: &[std::path::PathBuf]) {
+    let sources = fleet::sources(files).unwrap_or_else(|e| {
+        eprintln!("{} {e}", "Error:".red().bold());
+        std::process::exit(1);
+    });
+    let results = fleet::collect_all(&sources);
+    for line in fleet::render(&results) {
+        println!("{line}");
+    }
+    if results.iter().any(|(_, result)| result.is_err()) {
+        std::process::exit(1);
+    }
+}
+
+/// Print the board profile, or the issue URL that submits it
+fn run_contribute(issue: bool, open: bool) {
+    let profile = contribute::profile(
+        &info::collect_all_info(),
+        &info::get_compatible(),
+        info::get_board_details(),
+    );
+    if !(issue || open) {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&profile).unwrap_or_else(|_| "{}".to_string())
+        );
+        return;
+    }
+
+    let url = contribute::issue_url(&profile);
+    if open {
+        match contribute::open(&url) {
+            Ok(()) => return,
+            Err(e) => eprintln!(
+                "{} cannot open a browser: {e}; open this URL instead:",
+                "Warning:".yellow().bold()
+            ),
+        }
+    }
+    println!("{url}");
+}
+
+/// Print or write the login banner, optionally with its boot hook
+fn run_banner(path: Option<&std::path::Path>, agetty: bool, install_hook: bool) {
+    let text = banner::render(&info::collect_all_info(), agetty);
+    let Some(path) = path else {
+        print!("{text}");
+        return;
+    };
+    write_file_or_exit(path, &text);
+    println!("Banner written to {}", path.display());
+
+    if install_hook {
+        // systemd runs the unit from / without riscfetch's PATH, so both the
+        // executable and the banner need absolute paths
+        let exe = std::env::current_exe().unwrap_or_else(|e| {
+            eprintln!(
+                "{} cannot locate the riscfetch executable for the boot hook: {e}",
+                "Error:".red().bold()
+            );
+            std::process::exit(1);
+        });
+        let path = std::fs::canonicalize(path).unwrap_or_else(|e| {
+            eprintln!(
+                "{} cannot resolve {}: {e}",
+                "Error:".red().bold(),
+                path.display()
+            );
+            std::process::exit(1);
+        });
+        let unit = std::path::Path::new(banner::HOOK_UNIT);
+        write_file_or_exit(unit, &banner::hook_unit(&exe, &path, agetty));
+        println!("Boot hook written to {}", unit.display());
+        println!("Enable it with: systemctl enable riscfetch-banner.service");
+    }
+}
+
+fn run_snapshot(action: SnapshotAction) {
+    match action {
+        SnapshotAction::Save { file } => {
+            let body = serde_json::to_string_pretty(&info::collect_all_info())
+                .unwrap_or_else(|_| "{}".to_string());
+            write_file_or_exit(&file, &format!("{body}\n"));
+            println!("Snapshot written to {}", file.display());
+        }
+        SnapshotAction::Diff { file, json } => {
+            let saved = snapshot::parse(&read_file_or_exit(&file)).unwrap_or_else(|e| {
+                eprintln!("{} {}: {e}", "Error:".red().bold(), file.display());
+                std::process::exit(1);
+            });
+            let changes = info::diff_system_info(&saved, &info::collect_all_info());
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&changes).unwrap_or_else(|_| "[]".to_string())
+                );
+            } else if changes.is_empty() {
+                println!("No changes since {}", file.display());
+            } else {
+                println!("Changes since {}:", file.display());
+                for change in &changes {
+                    println!("{}", snapshot::format_change(change));
+                }
+            }
+        }
+    }
+}
+
+fn run_extensions(search: &str, category: Option<&str>, interactive: bool) {
+    if let Some(category) = category {
+        let ids = browser::category_ids();
+        if !ids.contains(&category) {
+            eprintln!(
+                "{} unknown category '{category}' (available: {})",
+                "Error:".red().bold(),
+                ids.join(", ")
+            );
+            std::process::exit(2);
+        }
+    }
+    let entries = browser::entries(&info::get_isa_string());
+
+    if interactive {
+        run_browser(browser::Browser::new(entries, search, category));
+    } else {
+        for line in browser::list_lines(&entries, search, category) {
+            println!("{line}");
+        }
+    }
+}
+
+/// Print one extension; unknown names exit 2 with a suggestion
+fn run_explain(name: &str, long: bool) {
+    let entries = browser::entries(&info::get_isa_string());
+    let Some(entry) = browser::find(&entries, name) else {
+        let names = entries.iter().map(|e| e.name.as_str());
+        match info::closest_match(name, names) {
+            Some(known) => eprintln!(
+                "{} unknown extension '{name}', did you mean '{known}'?",
+                "Error:".red().bold()
+            ),
+            None => eprintln!(
+                "{} unknown extension '{name}' (see `riscfetch extensions`)",
+                "Error:".red().bold()
+            ),
+        }
+        std::process::exit(2);
+    };
+    for line in browser::explain_lines(entry, long, browser::EXPLAIN_WIDTH) {
+        println!("{line}");
+    }
+}
+
+#[cfg(feature = "tui")]
+fn run_browser(mut state: browser::Browser) {
+    use std::io::IsTerminal;
+
+    if !std::io::stdout().is_terminal() {
+        eprintln!("{} --interactive needs a terminal", "Error:".red().bold());
+        std::process::exit(2);
+    }
+    if let Err(e) = browser::run(&mut state) {
+        eprintln!("{} {e}", "Error:".red().bold());
+        std::process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "tui"))]
+fn run_browser(_state: browser::Browser) {
+    feature_disabled("tui");
+}
+
+#[cfg(feature = "network")]
+fn run_share(endpoint: Option<&str>, yes: bool) {
+    let endpoint = share::resolve_endpoint(endpoint);
+    let body = serde_json::to_string_pretty(&info::collect_all_info())
+        .unwrap_or_else(|_| "{}".to_string());
+
+    println!("{body}");
+    println!();
+    println!(
+        "The JSON above ({} bytes) will be uploaded to {} and be publicly readable.",
+        body.len(),
+        endpoint.bright_white().bold()
+    );
+    if !yes && !share::confirm("Upload?") {
+        eprintln!("Aborted (use --yes to upload without a prompt).");
+        std::process::exit(1);
+    }
+
+    match share::upload(&endpoint, &body, "application/json") {
+        Ok(url) => println!("{}", url.bright_green().bold()),
+        Err(e) => {
+            eprintln!("{} upload failed: {e}", "Error:".red().bold());
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "network"))]
+fn run_share(_endpoint: Option<&str>, _yes: bool) {
+    feature_disabled("network");
+}
+
+/// The results endpoint for `bench --submit`; exits 2 when none is configured
+#[cfg(feature = "network")]
+fn submit_endpoint(flag: Option<&str>) -> String {
+    submit::resolve_endpoint(flag).unwrap_or_else(|| {
+        eprintln!(
+            "{} no results endpoint configured (use --endpoint URL or set {})",
+            "Error:".red().bold(),
+            submit::ENDPOINT_ENV
+        );
+        std::process::exit(2);
+    })
+}
+
+#[cfg(not(feature = "network"))]
+fn submit_endpoint(_flag: Option<&str>) -> String {
+    feature_disabled("network");
+}
+
+/// Show the anonymized submission, confirm and upload it
+#[cfg(feature = "network")]
+fn run_submit(endpoint: &str, results: &riscfetch_bench::BenchResult, yes: bool) {
+    let document = submit::document(&info::collect_all_info(), results);
+    let body = serde_json::to_string_pretty(&document).unwrap_or_else(|_| "{}".to_string());
+
+    println!("{body}");
+    println!();
+    println!(
+        "The JSON above ({} bytes) will be submitted to {} and be publicly readable.",
+        body.len(),
+        endpoint.bright_white().bold()
+    );
+    if !yes && !share::confirm("Submit?") {
+        eprintln!("Aborted (use --yes to submit without a prompt).");
+        std::process::exit(1);
+    }
+
+    match share::upload(endpoint, &body, "application/json") {
+        Ok(url) => println!("{}", url.bright_green().bold()),
+        Err(e) => {
+            eprintln!("{} submission failed: {e}", "Error:".red().bold());
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "network"))]
+fn run_submit(_endpoint: &str, _results: &riscfetch_bench::BenchResult, _yes: bool) {
+    feature_disabled("network");
+}
+
+#[cfg(feature = "png")]
+fn export_png(svg: &str, path: &std::path::Path) {
+    if let Err(e) = png::save_svg_as_png(svg, path) {
+        eprintln!(
+            "{} cannot write {}: {e}",
+            "Error:".red().bold(),
+            path.display()
+        );
+        std::process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "png"))]
+fn export_png(_svg: &str, _path: &std::path::Path) {
+    feature_disabled("png");
+}
+
+/// The config file; an unreadable one is reported and ignored
+fn load_config() -> config::Config {
+    config::load().unwrap_or_else(|e| {
+        eprintln!("{} ignoring config file {e}", "Warning:".yellow().bold());
+        config::Config::default()
+    })
+}
+
+/// Logo colors from `--logo-colors`, else the config file; invalid colors exit 2
+fn logo_colors(arg: Option<&str>, config: &config::Config) -> display::LogoColors {
+    arg.or(config.logo_colors.as_deref())
+        .map_or(Ok(display::LogoColors::Default), display::LogoColors::parse)
+        .unwrap_or_else(|e| {
+            eprintln!("{} {e}", "Error:".red().bold());
+            std::process::exit(2);
+        })
+}
+
+/// `FIGlet` font from `--figlet-font`, else the config file; unknown names exit 2
+fn figlet_font(arg: Option<&str>, config: &config::Config) -> logos::FigletFont {
+    arg.or(config.figlet_font.as_deref())
+        .map_or(Ok(logos::FigletFont::default()), logos::FigletFont::parse)
+        .unwrap_or_else(|e| {
+            eprintln!("{} {e}", "Error:".red().bold());
+            std::process::exit(2);
+        })
+}
+
+/// Fields for the normal display from the config file and `--show` / `--hide`,
+/// plus the frequency and temperature fields in watch mode.
+/// Unknown field ids exit 2.
+fn visible_fields(
+    config: &config::Config,
+    show: &[String],
+    hide: &[String],
+    watch: bool,
+) -> Vec<&'static fields::Field> {
+    fields::visible_fields(config, show, hide, watch).unwrap_or_else(|e| {
+        eprintln!("{} {e}", "Error:".red().bold());
+        std::process::exit(2);
+    })
+}
+
+/// Terminal graphics sequence for the image logo, or None to use the ASCII logo.
+/// An empty `file` means `<vendor>.png` in the logo directory.
+#[cfg(feature = "image-logo")]
+fn image_logo_sequence(file: &str, vendor: &str) -> Option<String> {
+    let path = if file.is_empty() {
+        image_logo::logo_dir()?.join(format!("{vendor}.png"))
+    } else {
+        std::path::PathBuf::from(file)
+    };
+    image_logo::render(&path)
+}
+
+#[cfg(not(feature = "image-logo"))]
+fn image_logo_sequence(_file: &str, _vendor: &str) -> Option<String> {
+    feature_disabled("image-logo");
+}
+
+/// Host name of this machine
+fn hostname() -> String {
+    gethostname::gethostname().to_string_lossy().to_string()
+}
+
+/// Login name of the current user
+fn current_user() -> String {
+    std::env::var("USER").unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Read a text file, exiting with an error message on failure
+fn read_file_or_exit(path: &std::path::Path) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!(
+            "{} cannot read {}: {e}",
+            "Error:".red().bold(),
+            path.display()
+        );
+        std::process::exit(1);
+    })
+}
+
+/// Write `contents` to `path`, exiting with an error message on failure
+fn write_file_or_exit(path: &std::path::Path, contents: &str) {
+    if let Err(e) = std::fs::write(path, contents) {
+        eprintln!(
+            "{} cannot write {}: {e}",
+            "Error:".red().bold(),
+            path.display()
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Print the JSON document; `sources` adds where each field came from and
+/// `benchmark` adds benchmark results
+fn output_json(
+    riscv_only: bool,
+    show_all: bool,
+    sources: bool,
+    plugins: bool,
+    benchmark: Option<riscfetch_bench::BenchOptions>,
+) {
+    let mut output = json_document(riscv_only, show_all, plugins);
+    if sources {
+        output["sources"] = json_sources(riscv_only);
+    }
+    if let Some(options) = benchmark {
+        output["benchmarks"] = serde_json::json!(riscfetch_bench::run(options));
+    }
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string())
+    );
+}
+
+/// Print the value at `path` in the JSON document, exiting 1 if it is missing
+fn output_query(path: &str, riscv_only: bool, show_all: bool, plugins: bool) {
+    let output = json_document(riscv_only, show_all, plugins);
+    match query::select(&output, path) {
+        Ok(value) => println!("{}", query::format_value(value)),
+        Err(e) => {
+            eprintln!("{} {e}", "Error:".red().bold());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Check extensions / profiles and exit 0 if all are supported, 1 otherwise
+fn run_check(names: &[String], quiet: bool) -> ! {
+    let outcomes = capability::evaluate(&info::get_isa_string(), names);
+    if !quiet {
+        for outcome in &outcomes {
+            println!("{}", capability::format_outcome(outcome));
+        }
+    }
+    let supported = outcomes.iter().all(capability::Outcome::supported);
+    std::process::exit(i32::from(!supported));
+}
+
+/// Report missing required extensions / profiles and exit 1 if there are any
+fn run_require(names: &[String], json: bool) -> ! {
+    let outcomes = capability::evaluate(&info::get_isa_string(), names);
+    let missing = capability::all_missing(&outcomes);
+
+    if json {
         println!(
             "{}",
-            serde_json::to_string_pretty(&data).unwrap_or_else(|_| "{}".to_string())
+            serde_json::to_string_pretty(&capability::require_json(&outcomes))
+                .unwrap_or_else(|_| "{}".to_string())
+        );
+    } else if missing.is_empty() {
+        println!(
+            "{} all required extensions present ({})",
+            "✓".bright_green().bold(),
+            names.join(", ")
+        );
+    } else {
+        eprintln!(
+            "{} missing required extensions: {}",
+            "Error:".red().bold(),
+            missing.join(", ")
         );
+        for outcome in outcomes.iter().filter(|o| o.profile && !o.supported()) {
+            eprintln!("  {}", capability::format_outcome(outcome));
+        }
+    }
+    std::process::exit(i32::from(!missing.is_empty()));
+}
+
+/// Print this machine and the one saved in `path` side by side
+fn output_diff(path: &std::path::Path) {
+    let other = snapshot::parse(&read_file_or_exit(path)).unwrap_or_else(|e| {
+        eprintln!("{} {}: {e}", "Error:".red().bold(), path.display());
+        std::process::exit(1);
+    });
+    let label = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().to_string(),
+    );
+    print_comparison(&other, &label);
+}
+
+/// `--diff` against a document downloaded from `url`; download and parse
+/// errors exit 1
+#[cfg(feature = "network")]
+fn output_compare_url(url: &str) {
+    let other = share::download(url)
+        .and_then(|text| snapshot::parse(&text))
+        .unwrap_or_else(|e| {
+            eprintln!("{} {url}: {e}", "Error:".red().bold());
+            std::process::exit(1);
+        });
+    print_comparison(&other, &compare::url_label(url));
+}
+
+#[cfg(not(feature = "network"))]
+fn output_compare_url(_url: &str) {
+    feature_disabled("network");
+}
+
+fn print_comparison(other: &info::SystemInfo, label: &str) {
+    for line in compare::render(&info::collect_all_info(), &hostname(), other, label) {
+        println!("{line}");
+    }
+}
+
+/// Print the raw value of one template key, exiting 2 if the key is unknown
+fn output_field(name: &str) {
+    let data = info::collect_all_info();
+    let ctx = template::Context {
+        info: &data,
+        hostname: &hostname(),
+        user: &current_user(),
+    };
+    if let Some(value) = template::resolve(&ctx, name.trim()) {
+        println!("{value}");
+    } else {
+        eprintln!(
+            "{} unknown field '{name}' (available: {})",
+            "Error:".red().bold(),
+            template::KEYS.join(", ")
+        );
+        std::process::exit(2);
+    }
+}
+
+/// Build the document printed by `--json` (with `-r` / `-a` variants);
+/// `plugins` adds the user's plugin fields under `custom`
+fn json_document(riscv_only: bool, show_all: bool, plugins: bool) -> serde_json::Value {
+    let mut output = if show_all {
+        json_all(riscv_only)
+    } else {
+        let value = if riscv_only {
+            serde_json::to_value(info::collect_riscv_info())
+        } else {
+            serde_json::to_value(info::collect_all_info())
+        };
+        value.unwrap_or_else(|_| serde_json::json!({}))
+    };
+    if plugins && !riscv_only {
+        output["custom"] = plugins::collect().to_json();
+    }
+    output
+}
+
+/// Field id to source description, for `--json --verbose`
+fn json_sources(riscv_only: bool) -> serde_json::Value {
+    let sources: serde_json::Map<String, serde_json::Value> = info::collect_sources()
+        .into_iter()
+        .filter(|(id, _)| !riscv_only || fields::is_riscv_field(id))
+        .map(|(id, source)| (id.to_string(), serde_json::json!(source.to_string())))
+        .collect();
+    serde_json::Value::Object(sources)
+}
+
+#[cfg(feature = "mqtt")]
+fn run_mqtt(broker: &str, topic: Option<&str>, interval_secs: u64, riscv_only: bool) -> ! {
+    let hostname = hostname();
+    let topic = topic.map_or_else(|| format!("riscfetch/{hostname}"), str::to_string);
+    let client_id = format!("riscfetch-{hostname}");
+    let interval = std::time::Duration::from_secs(interval_secs.max(1));
+
+    let payload = move || {
+        let json = if riscv_only {
+            serde_json::to_string(&info::collect_riscv_info())
+        } else {
+            serde_json::to_string(&info::collect_all_info())
+        };
+        json.unwrap_or_else(|_| "{}".to_string())
+    };
+
+    if let Err(e) = mqtt::publish_loop(broker, &topic, &client_id, interval, payload) {
+        eprintln!("{} {e}", "Error:".red().bold());
     }
+    std::process::exit(1);
+}
+
+#[cfg(not(feature = "mqtt"))]
+fn run_mqtt(_broker: &str, _topic: Option<&str>, _interval_secs: u64, _riscv_only: bool) -> ! {
+    feature_disabled("mqtt");
 }
 
-fn output_json_all(riscv_only: bool) {
+/// Report that an optional cargo feature was not compiled in and exit
+#[allow(dead_code)]
+fn feature_disabled(feature: &str) -> ! {
+    eprintln!(
+        "{} riscfetch was built without the `{feature}` feature (rebuild with `--features {feature}`)",
+        "Error:".red().bold()
+    );
+    std::process::exit(2);
+}
+
+fn output_formatted(format: &str) {
+    let data = info::collect_all_info();
+    let hostname = hostname();
+    match format {
+        "tsv" => println!("{}", output::format_tsv(&data, &hostname)),
+        "env" => println!("{}", output::format_env(&data, &hostname)),
+        "markdown" => print!("{}", output::format_markdown(&data, &hostname)),
+        _ => println!("{}", output::format_csv(&data, &hostname)),
+    }
+}
+
+/// Print the report of the WebAssembly formatter plugin `name`, exiting 1 if it fails
+#[cfg(feature = "wasm-plugins")]
+fn output_wasm(name: &str) {
+    let result = plugins::dir()
+        .ok_or_else(|| "no plugin directory (HOME is not set)".to_string())
+        .and_then(|dir| wasm_plugins::format(&dir, name));
+    match result {
+        Ok(text) => print!("{text}"),
+        Err(e) => {
+            eprintln!("{} {e}", "Error:".red().bold());
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "wasm-plugins"))]
+fn output_wasm(_name: &str) {
+    feature_disabled("wasm-plugins");
+}
+
+fn json_all(riscv_only: bool) -> serde_json::Value {
     use serde_json::json;
 
     let isa_string = info::get_isa_string();
@@ -123,9 +1273,17 @@ fn output_json_all(riscv_only: bool) {
             "marchid": hw_ids.marchid,
             "mimpid": hw_ids.mimpid
         },
-        "cache": cache_info
+        "cache": cache_info,
+        "isa_divergence": info::get_isa_divergence(),
+        "container": info::get_container()
     });
 
+    let warning_ids: Vec<&str> = info::FIELD_IDS
+        .into_iter()
+        .filter(|id| !riscv_only || fields::is_riscv_field(id))
+        .collect();
+    output["warnings"] = json!(info::collect_warnings(&warning_ids));
+
     if !riscv_only {
         let (mem_used, mem_total) = info::get_memory_bytes();
         output["board"] = json!(info::get_board_info());
@@ -134,354 +1292,106 @@ fn output_json_all(riscv_only: bool) {
         output["kernel"] = json!(info::get_kernel_info());
         output["os"] = json!(info::get_os_info());
         output["uptime_seconds"] = json!(info::get_uptime_seconds());
+        output["load_average"] = json!(info::get_load_average());
+        output["memory_breakdown"] = json!(info::get_memory_breakdown());
+        output["sensors"] = json!(info::get_sensors());
+        output["regulators"] = json!(info::get_regulators());
+        output["mmc"] = json!(info::get_mmc_info());
     }
 
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string())
-    );
+    output
 }
 
-fn display_riscv_info(vendor: &str, style: &str, explain: bool, riscv_only: bool, show_all: bool) {
-    println!();
-    display::display_logo(vendor, style);
-    println!();
-
-    // === RISC-V Specific Information ===
-    let isa_string = info::get_isa_string();
-    let vector_info = info::get_vector_detail();
-    let hart_count = info::get_hart_count();
-    let hw_ids = info::get_hardware_ids();
-    let cache_info = info::get_cache_info();
-
-    // ISA (base architecture)
-    println!("{} {}", "ISA:".bright_cyan().bold(), isa_string.white());
-
-    // Extensions
-    if show_all {
-        // Show ALL extensions with checkmarks
-        let all_std = info::get_all_standard_extensions_with_status(&isa_string);
-        let all_z = info::get_all_z_extensions_with_status(&isa_string);
-        let all_s = info::get_all_s_extensions_with_status(&isa_string);
-
-        if explain {
-            display_all_extensions_explained(&all_std, &all_z, &all_s);
-        } else {
-            display_all_extensions_compact(&all_std, &all_z, &all_s);
-        }
-    } else {
-        // Show only detected extensions
-        let extensions_compact = info::get_extensions_compact();
-        let z_exts_with_cat = info::get_z_extensions_with_category();
-        let s_exts_with_cat = info::get_s_extensions_with_category();
-
-        if explain {
-            display_extensions_explained(&extensions_compact, &z_exts_with_cat, &s_exts_with_cat);
-        } else {
-            display_extensions_compact(&extensions_compact, &z_exts_with_cat, &s_exts_with_cat);
-        }
-    }
-
-    // Vector extension
-    if !vector_info.is_empty() {
-        println!(
-            "{} {}",
-            "Vector:".bright_magenta().bold(),
-            vector_info.white()
-        );
-    }
-
-    // Hart count
-    println!("{} {}", "Harts:".bright_cyan().bold(), hart_count.white());
-
-    // Hardware IDs (CSR values)
-    if !hw_ids.mvendorid.is_empty() || !hw_ids.marchid.is_empty() || !hw_ids.mimpid.is_empty() {
-        let mut ids = Vec::new();
-        if !hw_ids.mvendorid.is_empty() {
-            ids.push(format!("vendor:{}", hw_ids.mvendorid));
-        }
-        if !hw_ids.marchid.is_empty() {
-            ids.push(format!("arch:{}", hw_ids.marchid));
-        }
-        if !hw_ids.mimpid.is_empty() {
-            ids.push(format!("impl:{}", hw_ids.mimpid));
-        }
-        println!(
-            "{} {}",
-            "HW IDs:".bright_green().bold(),
-            ids.join(" ").white()
-        );
-    }
-
-    // Cache info
-    if !cache_info.is_empty() {
-        println!("{} {}", "Cache:".bright_cyan().bold(), cache_info.white());
-    }
-
-    // Skip general system info if --riscv-only flag is set
-    if riscv_only {
-        println!();
-        return;
-    }
-
-    // === Separator ===
-    println!();
-    println!("{}", "--------------------------------".bright_black());
-    println!();
-
-    // === General System Information ===
-    let board_info = info::get_board_info();
-    let os_info = info::get_os_info();
-    let kernel_info = info::get_kernel_info();
-    let memory_info = info::get_memory_info();
-    let uptime = info::get_uptime();
-
-    // Board/Model
-    if !board_info.is_empty() {
-        println!("{} {}", "Board:".bright_blue().bold(), board_info.white());
-    }
-
-    // OS
-    println!("{} {}", "OS:".bright_blue().bold(), os_info.white());
-
-    // Kernel
-    println!("{} {}", "Kernel:".bright_blue().bold(), kernel_info.white());
-
-    // Memory
-    println!("{} {}", "Memory:".bright_blue().bold(), memory_info.white());
-
-    // Uptime
-    println!("{} {}", "Uptime:".bright_blue().bold(), uptime.white());
-
-    // User@Hostname
-    let user = std::env::var("USER").unwrap_or_else(|_| "unknown".to_string());
-    let hostname = gethostname::gethostname().to_string_lossy().to_string();
-    println!(
-        "{} {}@{}",
-        "User:".bright_blue().bold(),
-        user.white(),
-        hostname.white()
-    );
-
-    println!();
+/// Options for the normal (decorated) display
+struct DisplayOptions<'a> {
+    vendor: &'a str,
+    style: &'a str,
+    font: logos::FigletFont,
+    logo_colors: display::LogoColors,
+    layout: &'a str,
+    image_logo: Option<&'a str>,
+    fields: Vec<&'static fields::Field>,
+    explain: bool,
+    verbose: bool,
+    /// Run the user's plugins for custom fields
+    plugins: bool,
+    /// Piped: field lines only, without logo, colors or symbols
+    plain: bool,
+    riscv_only: bool,
+    show_all: bool,
 }
 
-/// Display extensions in compact mode (category-grouped multiple lines)
-fn display_extensions_compact(
-    std_exts: &str,
-    z_exts: &[info::ExtensionInfo],
-    s_exts: &[info::ExtensionInfo],
-) {
-    // Standard extensions
-    if !std_exts.is_empty() {
-        println!("{} {}", "Ext:".bright_yellow().bold(), std_exts.white());
-    }
-
-    // Z-extensions grouped by category
-    let z_groups = info::group_by_category(z_exts);
-    for (category, exts) in &z_groups {
-        let cat_name = info::get_z_category_name(category);
-        let ext_names: Vec<&str> = exts.iter().map(|e| e.name.as_str()).collect();
-        println!(
-            "{} {}",
-            format!("Z-{cat_name}:").bright_yellow().bold(),
-            ext_names.join(" ").white()
-        );
-    }
-
-    // S-extensions grouped by category
-    let s_groups = info::group_by_category(s_exts);
-    for (category, exts) in &s_groups {
-        let cat_name = info::get_s_category_name(category);
-        let ext_names: Vec<&str> = exts.iter().map(|e| e.name.as_str()).collect();
-        println!(
-            "{} {}",
-            format!("S-{cat_name}:").bright_magenta().bold(),
-            ext_names.join(" ").white()
-        );
-    }
+fn display_riscv_info(opts: &DisplayOptions) {
+    render_info(&static_info(opts), opts);
 }
 
-/// Display extensions in explained mode (category-grouped with aligned columns)
-fn display_extensions_explained(
-    _std_exts: &str,
-    z_exts: &[info::ExtensionInfo],
-    s_exts: &[info::ExtensionInfo],
-) {
-    // Standard extensions
-    println!("{}", "Extensions:".bright_yellow().bold());
-    for (ext, desc) in info::get_extensions_explained() {
-        println!("  {:<10} {}", ext.bright_green(), desc);
-    }
-
-    // Z-extensions grouped by category
-    let z_groups = info::group_by_category(z_exts);
-    for (category, exts) in &z_groups {
-        let cat_name = info::get_z_category_name(category);
-        println!();
-        println!(
-            "{}",
-            format!("Z-Extensions ({cat_name}):").bright_yellow().bold()
-        );
-        for ext in exts {
-            println!("  {:<10} {}", ext.name.bright_green(), ext.description);
-        }
-    }
-
-    // S-extensions grouped by category
-    let s_groups = info::group_by_category(s_exts);
-    for (category, exts) in &s_groups {
-        let cat_name = info::get_s_category_name(category);
-        println!();
-        println!(
-            "{}",
-            format!("S-Extensions ({cat_name}):")
-                .bright_magenta()
-                .bold()
-        );
-        for ext in exts {
-            println!("  {:<10} {}", ext.name.bright_green(), ext.description);
-        }
+/// Collect the static fields, with plugin fields unless `--no-plugins` and
+/// their sources when `--verbose`
+fn static_info(opts: &DisplayOptions) -> fields::StaticInfo {
+    let mut fixed = fields::StaticInfo::collect();
+    if opts.plugins {
+        fixed.custom = plugins::collect();
     }
-}
-
-/// Format extension with checkmark based on support status
-fn format_ext_with_check(name: &str, supported: bool) -> String {
-    if supported {
-        format!("{}{}", "✓".bright_green().bold(), name.bright_green())
+    if opts.verbose {
+        fixed.with_sources()
     } else {
-        format!("{}{}", "✗".bright_black(), name.bright_black())
+        fixed
     }
 }
 
-/// Display ALL extensions in compact mode with checkmarks
-fn display_all_extensions_compact(
-    std_exts: &[(String, String, bool)],
-    z_exts: &[info::ExtensionInfo],
-    s_exts: &[info::ExtensionInfo],
-) {
-    // Standard extensions with checkmarks
-    let std_parts: Vec<String> = std_exts
-        .iter()
-        .map(|(name, _, supported)| format_ext_with_check(name, *supported))
-        .collect();
-    println!("{} {}", "Ext:".bright_yellow().bold(), std_parts.join(" "));
-
-    // Z-extensions grouped by category
-    let z_groups = info::group_by_category(z_exts);
-    for (category, exts) in &z_groups {
-        let cat_name = info::get_z_category_name(category);
-        let ext_parts: Vec<String> = exts
-            .iter()
-            .map(|e| format_ext_with_check(&e.name, e.supported))
-            .collect();
-        println!(
-            "{} {}",
-            format!("Z-{cat_name}:").bright_yellow().bold(),
-            ext_parts.join(" ")
-        );
+/// Clear the screen and re-render every `interval_secs` until interrupted.
+/// Static fields are collected once; memory, disk usage, uptime, load, CPU
+/// frequency, thermal zones, sensors and rails are refreshed each time.
+fn run_watch(opts: &DisplayOptions, interval_secs: u64) -> ! {
+    let fixed = static_info(opts);
+    let interval = std::time::Duration::from_secs(interval_secs.max(1));
+    if !opts.plain {
+        display::enter_watch_screen();
     }
-
-    // S-extensions grouped by category
-    let s_groups = info::group_by_category(s_exts);
-    for (category, exts) in &s_groups {
-        let cat_name = info::get_s_category_name(category);
-        let ext_parts: Vec<String> = exts
-            .iter()
-            .map(|e| format_ext_with_check(&e.name, e.supported))
-            .collect();
-        println!(
-            "{} {}",
-            format!("S-{cat_name}:").bright_magenta().bold(),
-            ext_parts.join(" ")
-        );
+    loop {
+        if !opts.plain {
+            print!("\x1b[H\x1b[2J");
+        }
+        render_info(&fixed, opts);
+        std::thread::sleep(interval);
     }
 }
 
-/// Display ALL extensions in explained mode with checkmarks
-fn display_all_extensions_explained(
-    std_exts: &[(String, String, bool)],
-    z_exts: &[info::ExtensionInfo],
-    s_exts: &[info::ExtensionInfo],
-) {
-    // Standard extensions
-    println!("{}", "Extensions:".bright_yellow().bold());
-    for (name, desc, supported) in std_exts {
-        let mark = if *supported {
-            "✓".bright_green().bold()
-        } else {
-            "✗".bright_black()
-        };
-        let name_colored = if *supported {
-            name.bright_green()
-        } else {
-            name.bright_black()
-        };
-        let desc_colored = if *supported {
-            desc.normal()
-        } else {
-            desc.bright_black()
-        };
-        println!(" {mark} {name_colored:<10} {desc_colored}");
-    }
+/// Print the logo and info lines in the selected layout
+fn render_info(fixed: &fields::StaticInfo, opts: &DisplayOptions) {
+    let width = display::terminal_width();
+    let render = fields::RenderOptions {
+        explain: opts.explain,
+        show_all: opts.show_all,
+        verbose: opts.verbose,
+        plain: opts.plain,
+    };
+    let lines = fields::info_lines(fixed, &opts.fields, &render, opts.riscv_only);
 
-    // Z-extensions grouped by category
-    let z_groups = info::group_by_category(z_exts);
-    for (category, exts) in &z_groups {
-        let cat_name = info::get_z_category_name(category);
-        println!();
-        println!(
-            "{}",
-            format!("Z-Extensions ({cat_name}):").bright_yellow().bold()
-        );
-        for ext in exts {
-            let mark = if ext.supported {
-                "✓".bright_green().bold()
-            } else {
-                "✗".bright_black()
-            };
-            let name_colored = if ext.supported {
-                ext.name.bright_green()
-            } else {
-                ext.name.bright_black()
-            };
-            let desc_colored = if ext.supported {
-                ext.description.normal()
-            } else {
-                ext.description.bright_black()
-            };
-            println!(" {mark} {name_colored:<10} {desc_colored}");
+    if opts.plain {
+        for line in &lines {
+            println!("{}", display::plain_line(line));
         }
+        return;
     }
 
-    // S-extensions grouped by category
-    let s_groups = info::group_by_category(s_exts);
-    for (category, exts) in &s_groups {
-        let cat_name = info::get_s_category_name(category);
-        println!();
-        println!(
-            "{}",
-            format!("S-Extensions ({cat_name}):")
-                .bright_magenta()
-                .bold()
-        );
-        for ext in exts {
-            let mark = if ext.supported {
-                "✓".bright_green().bold()
-            } else {
-                "✗".bright_black()
-            };
-            let name_colored = if ext.supported {
-                ext.name.bright_green()
-            } else {
-                ext.name.bright_black()
-            };
-            let desc_colored = if ext.supported {
-                ext.description.normal()
-            } else {
-                ext.description.bright_black()
-            };
-            println!(" {mark} {name_colored:<10} {desc_colored}");
-        }
+    println!();
+    let image = opts
+        .image_logo
+        .and_then(|file| image_logo_sequence(file, opts.vendor));
+    let (logo, layout) = if let Some(image) = image {
+        // Images cannot share rows with text, so they always go on top
+        print!("{image}");
+        (Vec::new(), "top")
+    } else {
+        let style = display::fit_style(opts.vendor, opts.style, opts.font, opts.layout, width);
+        (
+            display::logo_lines(opts.vendor, style, opts.font, opts.logo_colors),
+            opts.layout,
+        )
+    };
+    for line in display::layout(&logo, &lines, layout, width) {
+        println!("{line}");
     }
+    println!();
 }
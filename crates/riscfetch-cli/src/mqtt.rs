@@ -0,0 +1,100 @@
+//! MQTT publishing mode (requires the `mqtt` cargo feature)
+//!
+//! Periodically publishes the JSON snapshot to a broker so boards can feed
+//! existing MQTT / Home Assistant pipelines.
+
+use rumqttc::{Client, MqttOptions, QoS};
+use std::thread;
+use std::time::Duration;
+
+/// Default MQTT port (unencrypted)
+const DEFAULT_PORT: u16 = 1883;
+
+/// Split a `host[:port]` broker address into host and port
+#[must_use]
+pub fn parse_broker(broker: &str) -> Option<(String, u16)> {
+    let broker = broker.strip_prefix("mqtt://").unwrap_or(broker);
+    match broker.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() => {
+            port.parse().ok().map(|port| (host.to_string(), port))
+        }
+        Some(_) => None,
+        None if broker.is_empty() => None,
+        None => Some((broker.to_string(), DEFAULT_PORT)),
+    }
+}
+
+/// Publish `payload()` to `topic` every `interval` until the process is killed.
+///
+/// Connection errors are reported on stderr and retried; the event loop
+/// reconnects on its own.
+pub fn publish_loop<F>(
+    broker: &str,
+    topic: &str,
+    client_id: &str,
+    interval: Duration,
+    payload: F,
+) -> Result<(), String>
+where
+    F: Fn() -> String + Send + 'static,
+{
+    let (host, port) =
+        parse_broker(broker).ok_or_else(|| format!("invalid MQTT broker address: {broker}"))?;
+
+    let mut options = MqttOptions::new(client_id, host, port);
+    options.set_keep_alive(Duration::from_secs(30));
+
+    let (client, mut connection) = Client::new(options, 10);
+    let topic = topic.to_string();
+
+    thread::spawn(move || loop {
+        if client
+            .publish(topic.as_str(), QoS::AtLeastOnce, false, payload())
+            .is_err()
+        {
+            break;
+        }
+        thread::sleep(interval);
+    });
+
+    for event in connection.iter() {
+        if let Err(e) = event {
+            eprintln!("MQTT: {e}");
+            thread::sleep(Duration::from_secs(5));
+        }
+    }
+
+    Err("MQTT connection closed".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_broker_default_port() {
+        assert_eq!(
+            parse_broker("homeassistant.local"),
+            Some(("homeassistant.local".to_string(), 1883))
+        );
+    }
+
+    #[test]
+    fn test_parse_broker_with_port() {
+        assert_eq!(
+            parse_broker("10.0.0.2:1884"),
+            Some(("10.0.0.2".to_string(), 1884))
+        );
+        assert_eq!(
+            parse_broker("mqtt://broker:1883"),
+            Some(("broker".to_string(), 1883))
+        );
+    }
+
+    #[test]
+    fn test_parse_broker_invalid() {
+        assert_eq!(parse_broker(""), None);
+        assert_eq!(parse_broker(":1883"), None);
+        assert_eq!(parse_broker("broker:port"), None);
+    }
+}
@@ -0,0 +1,374 @@
+//! Text output formats for scripting, inventory and reports
+//!
+//! The flat formats (CSV, TSV, env) turn `SystemInfo` into a single record so
+//! results from many boards can be concatenated (e.g. into a spreadsheet) or
+//! consumed by shell scripts without parsing the pretty output. The Markdown
+//! format is a report meant to be pasted into issues and forum posts.
+
+use riscfetch_core::{self as info, ExtensionEntry, SystemInfo};
+use std::fmt::Write;
+
+/// Column names for flat output.
+///
+/// These are part of the public interface: append new columns at the end,
+/// never rename or reorder existing ones.
+pub const COLUMNS: &[&str] = &[
+    "hostname",
+    "board",
+    "isa",
+    "extensions",
+    "z_extensions",
+    "s_extensions",
+    "vector_enabled",
+    "hart_count",
+    "mvendorid",
+    "marchid",
+    "mimpid",
+    "os",
+    "kernel",
+    "memory_used_bytes",
+    "memory_total_bytes",
+    "uptime_seconds",
+];
+
+/// Join extension names with spaces (e.g. "I M A F D C")
+fn join_names(exts: &[ExtensionEntry]) -> String {
+    exts.iter()
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Flatten system info into values matching `COLUMNS`
+#[must_use]
+pub fn flat_values(info: &SystemInfo, hostname: &str) -> Vec<String> {
+    vec![
+        hostname.to_string(),
+        info.board.clone(),
+        info.isa.clone(),
+        join_names(&info.extensions),
+        join_names(&info.z_extensions),
+        join_names(&info.s_extensions),
+        info.vector.enabled.to_string(),
+        info.hart_count.to_string(),
+        info.hardware_ids.mvendorid.clone(),
+        info.hardware_ids.marchid.clone(),
+        info.hardware_ids.mimpid.clone(),
+        info.os.clone(),
+        info.kernel.clone(),
+        info.memory_used_bytes.to_string(),
+        info.memory_total_bytes.to_string(),
+        info.uptime_seconds.to_string(),
+    ]
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break (RFC 4180)
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// TSV has no quoting, so tabs and line breaks are replaced with spaces
+fn escape_tsv(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Quote a value for POSIX shells using single quotes
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Variable name for a column: `RISCFETCH_` and the column in upper case,
+/// with the shorter `RISCFETCH_HARTS` for `hart_count`
+fn env_name(column: &str) -> String {
+    match column {
+        "hart_count" => "RISCFETCH_HARTS".to_string(),
+        _ => format!("RISCFETCH_{}", column.to_uppercase()),
+    }
+}
+
+/// Format as `RISCFETCH_<COLUMN>='value'` lines, safe to `eval` in shell scripts
+#[must_use]
+pub fn format_env(info: &SystemInfo, hostname: &str) -> String {
+    COLUMNS
+        .iter()
+        .zip(flat_values(info, hostname))
+        .map(|(column, value)| format!("{}={}", env_name(column), shell_quote(&value)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format as CSV (header line + one value line)
+#[must_use]
+pub fn format_csv(info: &SystemInfo, hostname: &str) -> String {
+    let header = COLUMNS.join(",");
+    let row: Vec<String> = flat_values(info, hostname)
+        .iter()
+        .map(|v| escape_csv(v))
+        .collect();
+    format!("{header}\n{}", row.join(","))
+}
+
+/// Format as TSV (header line + one value line)
+#[must_use]
+pub fn format_tsv(info: &SystemInfo, hostname: &str) -> String {
+    let header = COLUMNS.join("\t");
+    let row: Vec<String> = flat_values(info, hostname)
+        .iter()
+        .map(|v| escape_tsv(v))
+        .collect();
+    format!("{header}\n{}", row.join("\t"))
+}
+
+/// Escape characters that would break a Markdown table cell
+fn escape_md(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Format a byte count as GiB with two decimals
+#[allow(clippy::cast_precision_loss)]
+fn format_gib(bytes: u64) -> String {
+    format!("{:.2} GiB", bytes as f64 / 1_073_741_824.0)
+}
+
+/// Write a collapsible extension table (category-grouped when categories are known)
+fn write_md_extensions(out: &mut String, title: &str, rows: &[(String, String, String)]) {
+    if rows.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "<details>");
+    let _ = writeln!(out, "<summary>{title} ({})</summary>", rows.len());
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Extension | Category | Description |");
+    let _ = writeln!(out, "|-----------|----------|-------------|");
+    for (name, category, desc) in rows {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} |",
+            escape_md(name),
+            escape_md(category),
+            escape_md(desc)
+        );
+    }
+    let _ = writeln!(out);
+    let _ = writeln!(out, "</details>");
+    let _ = writeln!(out);
+}
+
+/// Format as a Markdown report for bug reports and forum posts
+#[must_use]
+pub fn format_markdown(info: &SystemInfo, hostname: &str) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "## riscfetch report: {}", escape_md(hostname));
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Field | Value |");
+    let _ = writeln!(out, "|-------|-------|");
+
+    let hw = &info.hardware_ids;
+    let hw_ids = [
+        ("vendor", &hw.mvendorid),
+        ("arch", &hw.marchid),
+        ("impl", &hw.mimpid),
+    ]
+    .iter()
+    .filter(|(_, v)| !v.is_empty())
+    .map(|(k, v)| format!("{k}:{v}"))
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    let std_exts = join_names(&info.extensions);
+    let vector = if info.vector.enabled {
+        "Enabled"
+    } else {
+        "Not available"
+    };
+    let memory = format!(
+        "{} / {}",
+        format_gib(info.memory_used_bytes),
+        format_gib(info.memory_total_bytes)
+    );
+    let harts = info.hart_count.to_string();
+    let fields = [
+        ("Board", info.board.as_str()),
+        ("ISA", info.isa.as_str()),
+        ("Extensions", std_exts.as_str()),
+        ("Vector", vector),
+        ("Harts", harts.as_str()),
+        ("HW IDs", hw_ids.as_str()),
+        ("OS", info.os.as_str()),
+        ("Kernel", info.kernel.as_str()),
+        ("Memory", memory.as_str()),
+    ];
+    for (label, value) in fields {
+        if !value.is_empty() {
+            let _ = writeln!(out, "| {label} | `{}` |", escape_md(value));
+        }
+    }
+    let _ = writeln!(out);
+
+    let std_rows: Vec<(String, String, String)> = info
+        .extensions
+        .iter()
+        .map(|e| {
+            (
+                e.name.clone(),
+                "Standard".to_string(),
+                e.description.clone(),
+            )
+        })
+        .collect();
+    write_md_extensions(&mut out, "Standard extensions", &std_rows);
+
+    let z_rows: Vec<(String, String, String)> = info::parse_z_extensions_with_category(&info.isa)
+        .into_iter()
+        .map(|e| {
+            let category = info::get_z_category_name(&e.category).to_string();
+            (e.name, category, e.description)
+        })
+        .collect();
+    write_md_extensions(&mut out, "Z-extensions", &z_rows);
+
+    let s_rows: Vec<(String, String, String)> = info::parse_s_extensions_with_category(&info.isa)
+        .into_iter()
+        .map(|e| {
+            let category = info::get_s_category_name(&e.category).to_string();
+            (e.name, category, e.description)
+        })
+        .collect();
+    write_md_extensions(&mut out, "S-extensions", &s_rows);
+
+    let _ = writeln!(out, "<details>");
+    let _ = writeln!(out, "<summary>Raw ISA string</summary>");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "```");
+    let _ = writeln!(out, "{}", info.isa);
+    let _ = writeln!(out, "```");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "</details>");
+
+    out
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use riscfetch_core::{CacheInfo, HardwareIds, VectorInfo};
+
+    fn entry(name: &str) -> ExtensionEntry {
+        ExtensionEntry {
+            name: name.to_string(),
+            description: String::new(),
+        }
+    }
+
+    pub(crate) fn sample_info() -> SystemInfo {
+        SystemInfo {
+            isa: "rv64imafdc_zicsr_zba_zbb_sstc".to_string(),
+            extensions: ["I", "M", "A", "F", "D", "C"].map(entry).to_vec(),
+            z_extensions: ["Zicsr", "Zba", "Zbb"].map(entry).to_vec(),
+            s_extensions: vec![entry("Sstc")],
+            vector: VectorInfo::default(),
+            hart_count: 4,
+            hardware_ids: HardwareIds {
+                mvendorid: "0x489".to_string(),
+                marchid: "0x8000000000000007".to_string(),
+                mimpid: String::new(),
+            },
+            cache: CacheInfo::default(),
+            board: "StarFive VisionFive 2".to_string(),
+            memory_used_bytes: 1024,
+            memory_total_bytes: 8_589_934_592,
+            kernel: "6.6.20-starfive".to_string(),
+            os: "Debian GNU/Linux trixie/sid".to_string(),
+            uptime_seconds: 3600,
+            load_average: None,
+            memory_breakdown: None,
+            sensors: info::Sensors::default(),
+            container: None,
+            isa_divergence: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_flat_values_match_columns() {
+        assert_eq!(flat_values(&sample_info(), "vf2").len(), COLUMNS.len());
+    }
+
+    #[test]
+    fn test_csv_header_and_row() {
+        let csv = format_csv(&sample_info(), "vf2");
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("hostname,board,isa,"));
+        assert!(lines[1].starts_with("vf2,StarFive VisionFive 2,rv64imafdc_zicsr_zba_zbb_sstc,"));
+        assert!(lines[1].contains(",I M A F D C,Zicsr Zba Zbb,Sstc,false,4,0x489,"));
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        assert_eq!(escape_csv("plain"), "plain");
+        assert_eq!(escape_csv("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_tsv_row() {
+        let tsv = format_tsv(&sample_info(), "vf2");
+        let lines: Vec<&str> = tsv.lines().collect();
+        assert_eq!(lines[0].split('\t').count(), COLUMNS.len());
+        assert_eq!(lines[1].split('\t').count(), COLUMNS.len());
+    }
+
+    #[test]
+    fn test_env_lines() {
+        let env = format_env(&sample_info(), "vf2");
+        assert_eq!(env.lines().count(), COLUMNS.len());
+        assert!(env.contains("RISCFETCH_ISA='rv64imafdc_zicsr_zba_zbb_sstc'"));
+        assert!(env.contains("RISCFETCH_HARTS='4'"));
+        assert!(env.contains("RISCFETCH_BOARD='StarFive VisionFive 2'"));
+    }
+
+    #[test]
+    fn test_env_quoting() {
+        assert_eq!(shell_quote("plain"), "'plain'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote("$(rm -rf /)"), "'$(rm -rf /)'");
+    }
+
+    #[test]
+    fn test_markdown_summary_table() {
+        let md = format_markdown(&sample_info(), "vf2");
+        assert!(md.starts_with("## riscfetch report: vf2"));
+        assert!(md.contains("| Board | `StarFive VisionFive 2` |"));
+        assert!(md.contains("| Harts | `4` |"));
+        assert!(md.contains("| HW IDs | `vendor:0x489 arch:0x8000000000000007` |"));
+    }
+
+    #[test]
+    fn test_markdown_collapsible_extensions() {
+        let md = format_markdown(&sample_info(), "vf2");
+        assert!(md.contains("<summary>Standard extensions (6)</summary>"));
+        assert!(md.contains("<summary>Z-extensions (3)</summary>"));
+        assert!(md.contains("| Zba | Bit Manipulation | Address Generation |"));
+        assert!(md.contains("| Sstc | Supervisor | Supervisor Timer |"));
+        assert_eq!(
+            md.matches("<details>").count(),
+            md.matches("</details>").count()
+        );
+    }
+
+    #[test]
+    fn test_markdown_escapes_pipes() {
+        assert_eq!(escape_md("a|b"), "a\\|b");
+    }
+
+    #[test]
+    fn test_tsv_strips_tabs() {
+        assert_eq!(escape_tsv("a\tb\nc"), "a b c");
+    }
+}
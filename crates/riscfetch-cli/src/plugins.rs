@@ -0,0 +1,229 @@
+//! External plugins for custom fields
+//!
+//! Every executable in `~/.config/riscfetch/plugins/` (next to the config
+//! file) is run without arguments; its `key: value` lines, or one JSON
+//! object, become custom fields in the normal display and under `custom` in
+//! JSON. Plugins run in parallel and are stopped after `TIMEOUT`.
+
+use serde_json::Value;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long a plugin may run before it is killed and skipped
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Custom fields from all plugins, in plugin name order
+#[derive(Debug, Default)]
+pub struct Plugins {
+    /// (key, value) pairs; a later plugin's key replaces an earlier one in JSON
+    pub fields: Vec<(String, Value)>,
+    /// Plugins that failed, with the reason (`fan.sh: timed out`)
+    pub failures: Vec<String>,
+}
+
+impl Plugins {
+    /// The fields as the JSON `custom` object
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        Value::Object(self.fields.iter().cloned().collect())
+    }
+}
+
+/// Plugin directory: `plugins/` next to the config file
+#[must_use]
+pub fn dir() -> Option<PathBuf> {
+    Some(crate::config::path()?.parent()?.join("plugins"))
+}
+
+/// Display text of a value: strings without quotes, anything else as JSON
+#[must_use]
+pub fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Parse a plugin's output: a JSON object, or `key: value` lines (blank
+/// lines, `#` comments and lines without a colon are ignored)
+#[must_use]
+pub fn parse_output(stdout: &str) -> Vec<(String, Value)> {
+    if stdout.trim_start().starts_with('{') {
+        return match serde_json::from_str::<Value>(stdout) {
+            Ok(Value::Object(map)) => map.into_iter().collect(),
+            _ => Vec::new(),
+        };
+    }
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| (key.to_string(), Value::String(value.to_string())))
+        .collect()
+}
+
+/// Executable regular files in `dir`, sorted by name; hidden files and
+/// WebAssembly plugins are skipped
+fn executables(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter(|entry| entry.path().extension().is_none_or(|ext| ext != "wasm"))
+        .filter(|entry| entry.metadata().is_ok_and(|meta| is_executable(&meta)))
+        .map(|entry| entry.path())
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    meta.is_file() && meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(meta: &std::fs::Metadata) -> bool {
+    meta.is_file()
+}
+
+/// Run one plugin and return its stdout
+fn run(path: &Path) -> Result<String, String> {
+    let mut child = Command::new(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    // Read in a thread so a chatty plugin cannot block on a full pipe
+    let mut stdout = child.stdout.take().ok_or("no stdout")?;
+    let reader = std::thread::spawn(move || {
+        let mut text = String::new();
+        stdout.read_to_string(&mut text).map(|_| text)
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            break status;
+        }
+        if started.elapsed() > TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("timed out".to_string());
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    if !status.success() {
+        return Err(format!("exited with {status}"));
+    }
+    reader
+        .join()
+        .map_err(|_| "could not read output".to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Run every plugin in `dir` in parallel
+#[must_use]
+pub fn run_all(dir: &Path) -> Plugins {
+    let paths = executables(dir);
+    let results: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .iter()
+            .map(|path| scope.spawn(move || run(path)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err("panicked".to_string()))
+            })
+            .collect()
+    });
+
+    let mut plugins = Plugins::default();
+    for (path, result) in paths.iter().zip(results) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        match result {
+            Ok(stdout) => plugins.fields.extend(parse_output(&stdout)),
+            Err(reason) => plugins.failures.push(format!("{name}: {reason}")),
+        }
+    }
+    plugins
+}
+
+/// Run the plugins in the default directory (none when it does not exist),
+/// followed by the WebAssembly detectors with the `wasm-plugins` feature
+#[must_use]
+pub fn collect() -> Plugins {
+    let Some(dir) = dir() else {
+        return Plugins::default();
+    };
+    #[allow(unused_mut)]
+    let mut plugins = run_all(&dir);
+    #[cfg(feature = "wasm-plugins")]
+    crate::wasm_plugins::detect_all(&dir, &mut plugins);
+    plugins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_value_lines() {
+        let fields = parse_output("Fan: auto, 40%\n# comment\n\nnot a field\nEEPROM: MARS-V1.2\n");
+        assert_eq!(
+            fields,
+            vec![
+                ("Fan".to_string(), Value::String("auto, 40%".to_string())),
+                ("EEPROM".to_string(), Value::String("MARS-V1.2".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_object() {
+        let fields = parse_output("{\"fan_rpm\": 3200, \"carrier\": \"IO board v2\"}");
+        assert_eq!(fields.len(), 2);
+        assert!(fields.contains(&("fan_rpm".to_string(), serde_json::json!(3200))));
+        assert_eq!(format_value(&fields[0].1), "IO board v2");
+        assert!(parse_output("{not json").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_all() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("riscfetch-plugins-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = |name: &str, body: &str, mode: u32| {
+            let path = dir.join(name);
+            std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        };
+        script("10-fan", "echo 'Fan: 40%'", 0o755);
+        script("20-fail", "exit 3", 0o755);
+        script("30-not-executable", "echo 'Skipped: yes'", 0o644);
+
+        let plugins = run_all(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            plugins.fields,
+            vec![("Fan".to_string(), Value::String("40%".to_string()))]
+        );
+        assert_eq!(plugins.failures.len(), 1);
+        assert!(plugins.failures[0].starts_with("20-fail: exited with"));
+        assert_eq!(plugins.to_json(), serde_json::json!({ "Fan": "40%" }));
+    }
+}
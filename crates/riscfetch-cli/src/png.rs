@@ -0,0 +1,52 @@
+//! PNG export of the SVG system card (requires the `png` cargo feature)
+//!
+//! Rasterizes the card produced by `display::render_svg` with resvg, a
+//! pure-Rust renderer, so no external tools are needed on the board.
+
+use resvg::{tiny_skia, usvg};
+use std::path::Path;
+
+/// Render at 2x so text stays crisp when scaled by image viewers
+const SCALE: u32 = 2;
+
+/// Rasterize an SVG document and save it as PNG
+pub fn save_svg_as_png(svg: &str, path: &Path) -> Result<(), String> {
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    if options.fontdb.is_empty() {
+        return Err(
+            "no system fonts found; install a monospace font (e.g. fonts-dejavu-core)".to_string(),
+        );
+    }
+
+    let tree = usvg::Tree::from_str(svg, &options).map_err(|e| e.to_string())?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width() * SCALE, size.height() * SCALE)
+        .ok_or_else(|| "invalid image size".to_string())?;
+
+    #[allow(clippy::cast_precision_loss)]
+    let transform = tiny_skia::Transform::from_scale(SCALE as f32, SCALE as f32);
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    pixmap.save_png(path).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_svg_as_png() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="2"><rect width="100%" height="100%" fill="#1e1e2e"/></svg>"##;
+        let path = std::env::temp_dir().join("riscfetch-test-card.png");
+        match save_svg_as_png(svg, &path) {
+            Ok(()) => {
+                let bytes = std::fs::read(&path).unwrap();
+                assert!(bytes.starts_with(b"\x89PNG"));
+                let _ = std::fs::remove_file(&path);
+            }
+            // Minimal CI images may have no fonts at all
+            Err(e) => assert!(e.contains("no system fonts")),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! QEMU `-cpu` option reproducing this machine's ISA (`riscfetch --qemu-cpu`)
+//!
+//! QEMU's bare `rv64i` / `rv32i` CPUs (QEMU 8.2 and later) start with
+//! nothing but the base ISA and take one boolean property per extension,
+//! named like the lowercase ISA string, and `vlen` / `elen` for the vector
+//! unit. Unlike the generic `rv64` CPU they enable nothing the board lacks.
+//! Vendor (`x...`) extensions are left out: QEMU models only a few of them,
+//! and only on vendor CPU models.
+
+use riscfetch_core::{self as info, VectorInfo};
+
+/// `-cpu` option for QEMU and the extensions it could not express
+#[derive(Debug, PartialEq, Eq)]
+pub struct QemuCpu {
+    /// `-cpu rv64i,m=true,...`
+    pub option: String,
+    /// Vendor extensions left out
+    pub skipped: Vec<String>,
+}
+
+/// `-cpu` option enabling every extension of `isa`, with the vector length
+/// from `vector` when V is present
+#[must_use]
+pub fn qemu_cpu(isa: &str, vector: &VectorInfo) -> QemuCpu {
+    let isa = info::expand_isa(isa);
+    let mut parts = isa.split('_');
+    let base = parts.next().unwrap_or_default();
+    let model = if base.starts_with("rv32") {
+        "rv32i"
+    } else {
+        "rv64i"
+    };
+    let letters = base
+        .strip_prefix("rv64")
+        .or_else(|| base.strip_prefix("rv32"))
+        .unwrap_or(base);
+
+    let mut properties = vec![model.to_string()];
+    // I is the base of the bare CPUs and cannot be switched; B is passed
+    // as the Zba, Zbb and Zbs it expands to, which older QEMU knows too
+    for ch in letters.chars().filter(|ch| !matches!(ch, 'i' | 'b')) {
+        if info::STANDARD_EXTENSIONS.iter().any(|(c, _, _)| *c == ch) {
+            properties.push(format!("{ch}=true"));
+        }
+    }
+    let mut skipped = Vec::new();
+    for ext in parts.filter(|part| !part.is_empty()) {
+        if ext.starts_with('x') {
+            skipped.push(ext.to_string());
+        } else {
+            properties.push(format!("{ext}=true"));
+        }
+    }
+    if letters.contains('v') {
+        if let Some(vlen) = vector.vlen {
+            properties.push(format!("vlen={vlen}"));
+        }
+        if let Some(elen) = vector.elen {
+            properties.push(format!("elen={elen}"));
+        }
+    }
+    QemuCpu {
+        option: format!("-cpu {}", properties.join(",")),
+        skipped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qemu_cpu() {
+        let vector = VectorInfo {
+            enabled: true,
+            vlen: Some(256),
+            elen: Some(64),
+        };
+        let cpu = qemu_cpu("rv64gcv_zba_zbb_sstc_xtheadvector", &vector);
+        assert_eq!(
+            cpu.option,
+            "-cpu rv64i,m=true,a=true,f=true,d=true,c=true,v=true,zicsr=true,zifencei=true,\
+             zba=true,zbb=true,sstc=true,vlen=256,elen=64"
+        );
+        assert_eq!(cpu.skipped, ["xtheadvector"]);
+    }
+
+    #[test]
+    fn test_qemu_cpu_without_vector() {
+        // A vector length without V (e.g. only XTheadVector) is not passed on
+        let vector = VectorInfo {
+            enabled: false,
+            vlen: Some(128),
+            elen: None,
+        };
+        let cpu = qemu_cpu("rv32imacb", &vector);
+        assert_eq!(
+            cpu.option,
+            "-cpu rv32i,m=true,a=true,c=true,zba=true,zbb=true,zbs=true"
+        );
+        assert!(cpu.skipped.is_empty());
+    }
+}
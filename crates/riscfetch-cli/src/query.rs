@@ -0,0 +1,156 @@
+//! Minimal JSON path queries (`--query '.hardware_ids.marchid'`)
+//!
+//! Supports dotted keys and array indexing (`.z_extensions[0].name`) so
+//! scripts on minimal board images do not need jq.
+
+use serde_json::Value;
+
+/// One step of a parsed query path
+#[derive(Debug, PartialEq, Eq)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Parse a path like `.a.b[2].c` (the leading dot is optional, `.` is the root)
+pub fn parse(path: &str) -> Result<Vec<Segment>, String> {
+    let path = path.trim();
+    let mut segments = Vec::new();
+    let mut rest = path.strip_prefix('.').unwrap_or(path);
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after
+                .find(']')
+                .ok_or_else(|| format!("unclosed '[' in query: {path}"))?;
+            let index = after[..end]
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid array index '{}' in query: {path}", &after[..end]))?;
+            segments.push(Segment::Index(index));
+            rest = &after[end + 1..];
+        } else {
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            if end == 0 {
+                return Err(format!("empty key in query: {path}"));
+            }
+            segments.push(Segment::Key(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+        if let Some(after) = rest.strip_prefix('.') {
+            if after.is_empty() || after.starts_with(['.', '[']) {
+                return Err(format!("empty key in query: {path}"));
+            }
+            rest = after;
+        }
+    }
+    Ok(segments)
+}
+
+/// Walk `value` along `path`, returning an error naming the first missing step
+pub fn select<'a>(value: &'a Value, path: &str) -> Result<&'a Value, String> {
+    let mut current = value;
+    let mut walked = String::new();
+
+    for segment in parse(path)? {
+        let next = match &segment {
+            Segment::Key(key) => {
+                walked.push('.');
+                walked.push_str(key);
+                current.get(key.as_str())
+            }
+            Segment::Index(index) => {
+                walked.push_str(&format!("[{index}]"));
+                current.get(index)
+            }
+        };
+        current = next.ok_or_else(|| format!("no value at {walked}"))?;
+    }
+    Ok(current)
+}
+
+/// Format a selected value: strings and scalars raw, objects and arrays as JSON
+#[must_use]
+pub fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(_) | Value::Object(_) => {
+            serde_json::to_string_pretty(value).unwrap_or_else(|_| "null".to_string())
+        }
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Value {
+        json!({
+            "isa": "rv64gc",
+            "hart_count": 4,
+            "hardware_ids": { "marchid": "0x8000000000000007" },
+            "z_extensions": [
+                { "name": "Zicsr" },
+                { "name": "Zba" }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse(".z_extensions[1].name").unwrap(),
+            vec![
+                Segment::Key("z_extensions".into()),
+                Segment::Index(1),
+                Segment::Key("name".into())
+            ]
+        );
+        assert_eq!(parse("isa").unwrap(), vec![Segment::Key("isa".into())]);
+        assert!(parse(".").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse(".a..b").is_err());
+        assert!(parse(".a[").is_err());
+        assert!(parse(".a[x]").is_err());
+        assert!(parse(".a.").is_err());
+    }
+
+    #[test]
+    fn test_select() {
+        let doc = sample();
+        assert_eq!(select(&doc, ".isa").unwrap(), "rv64gc");
+        assert_eq!(
+            select(&doc, ".hardware_ids.marchid").unwrap(),
+            "0x8000000000000007"
+        );
+        assert_eq!(select(&doc, ".z_extensions[1].name").unwrap(), "Zba");
+        assert_eq!(select(&doc, ".").unwrap(), &doc);
+    }
+
+    #[test]
+    fn test_select_missing() {
+        let doc = sample();
+        assert_eq!(
+            select(&doc, ".hardware_ids.nope").unwrap_err(),
+            "no value at .hardware_ids.nope"
+        );
+        assert_eq!(
+            select(&doc, ".z_extensions[5]").unwrap_err(),
+            "no value at .z_extensions[5]"
+        );
+        assert!(select(&doc, ".isa.deeper").is_err());
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(&json!("rv64gc")), "rv64gc");
+        assert_eq!(format_value(&json!(4)), "4");
+        assert_eq!(format_value(&json!(null)), "null");
+        assert_eq!(format_value(&json!([1])), "[\n  1\n]");
+    }
+}
@@ -0,0 +1,425 @@
+//! Self-contained HTML report generation (`riscfetch report --html`)
+
+use riscfetch_bench::{memory, BenchResult};
+use riscfetch_core::{self as info, SystemInfo};
+use std::fmt::Write;
+
+/// Inline stylesheet so the report is a single file
+const STYLE: &str = "
+body { font-family: sans-serif; background: #1e1e2e; color: #cdd6f4; margin: 2em auto; max-width: 60em; }
+h1, h2 { color: #89dceb; }
+h3 { color: #f9e2af; margin-bottom: 0.3em; }
+pre.logo { color: #89dceb; font-weight: bold; line-height: 1.1; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { text-align: left; padding: 0.2em 1em 0.2em 0; vertical-align: top; }
+th { color: #a6e3a1; }
+td.yes { color: #a6e3a1; }
+td.no, tr.no td { color: #6c7086; }
+code { word-break: break-all; }
+footer { color: #6c7086; margin-top: 2em; font-size: 0.9em; }
+";
+
+/// Escape text for HTML element content and attribute values
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Write one row of a checkmark table
+fn write_ext_row(out: &mut String, name: &str, desc: &str, supported: bool) {
+    let (class, mark) = if supported {
+        ("yes", "✓")
+    } else {
+        ("no", "✗")
+    };
+    let _ = writeln!(
+        out,
+        "<tr class=\"{class}\"><td class=\"{class}\">{mark}</td><td>{}</td><td>{}</td></tr>",
+        escape_html(name),
+        escape_html(desc)
+    );
+}
+
+/// Write all extensions of one kind, grouped by category
+fn write_ext_groups(
+    out: &mut String,
+    prefix: &str,
+    exts: &[info::ExtensionInfo],
+    category_name: fn(&str) -> &'static str,
+) {
+    for (category, group) in info::group_by_category(exts) {
+        let _ = writeln!(
+            out,
+            "<h3>{prefix}-Extensions ({})</h3>",
+            escape_html(category_name(&category))
+        );
+        let _ = writeln!(out, "<table>");
+        for ext in group {
+            write_ext_row(out, &ext.name, &ext.description, ext.supported);
+        }
+        let _ = writeln!(out, "</table>");
+    }
+}
+
+/// Render a complete HTML document
+#[must_use]
+pub fn render_html(
+    data: &SystemInfo,
+    hostname: &str,
+    logo: &str,
+    benchmarks: Option<&BenchResult>,
+) -> String {
+    let mut out = String::new();
+    let title = if data.board.is_empty() {
+        format!("riscfetch report: {hostname}")
+    } else {
+        format!("riscfetch report: {} ({hostname})", data.board)
+    };
+
+    let _ = writeln!(out, "<!DOCTYPE html>");
+    let _ = writeln!(out, "<html lang=\"en\">");
+    let _ = writeln!(out, "<head>");
+    let _ = writeln!(out, "<meta charset=\"utf-8\">");
+    let _ = writeln!(out, "<title>{}</title>", escape_html(&title));
+    let _ = writeln!(out, "<style>{STYLE}</style>");
+    let _ = writeln!(out, "</head>");
+    let _ = writeln!(out, "<body>");
+
+    if !logo.is_empty() {
+        let _ = writeln!(out, "<pre class=\"logo\">{}</pre>", escape_html(logo));
+    }
+    let _ = writeln!(out, "<h1>{}</h1>", escape_html(&title));
+
+    // Hardware details
+    let _ = writeln!(out, "<h2>Hardware</h2>");
+    let _ = writeln!(out, "<table>");
+    let hw = &data.hardware_ids;
+    let memory_gib = |bytes: u64| {
+        #[allow(clippy::cast_precision_loss)]
+        let gib = bytes as f64 / 1_073_741_824.0;
+        format!("{gib:.2} GiB")
+    };
+    let rows = [
+        ("Board", data.board.clone()),
+        ("ISA", data.isa.clone()),
+        ("Harts", data.hart_count.to_string()),
+        (
+            "Vector",
+            info::parse_vector_from_isa(&data.isa).unwrap_or_else(|| "Not available".into()),
+        ),
+        ("mvendorid", hw.mvendorid.clone()),
+        ("marchid", hw.marchid.clone()),
+        ("mimpid", hw.mimpid.clone()),
+        ("OS", data.os.clone()),
+        ("Kernel", data.kernel.clone()),
+        (
+            "Memory",
+            format!(
+                "{} / {}",
+                memory_gib(data.memory_used_bytes),
+                memory_gib(data.memory_total_bytes)
+            ),
+        ),
+    ];
+    for (label, value) in rows {
+        if !value.is_empty() {
+            let _ = writeln!(
+                out,
+                "<tr><th>{label}</th><td><code>{}</code></td></tr>",
+                escape_html(&value)
+            );
+        }
+    }
+    let _ = writeln!(out, "</table>");
+
+    // Benchmarks
+    if let Some(bench) = benchmarks {
+        let _ = writeln!(out, "<h2>Benchmarks</h2>");
+        let _ = writeln!(out, "<table>");
+        for (label, score, unit) in [
+            ("Integer Ops (M)", bench.integer_mops, "MOPS"),
+            ("Float Ops (F/D)", bench.float_mflops, "MFLOPS"),
+            ("Memory Bandwidth", bench.memory_mb_per_sec, "MB/s"),
+        ] {
+            let _ = writeln!(out, "<tr><th>{label}</th><td>{score:.2} {unit}</td></tr>");
+        }
+        for point in &bench.memory_sweep {
+            let _ = writeln!(
+                out,
+                "<tr><th>Memory ({}, {})</th><td>{:.2} MB/s</td></tr>",
+                point.level.unwrap_or("-"),
+                memory::format_size(point.working_set_bytes),
+                point.mb_per_sec
+            );
+        }
+        let atomics = &bench.atomics;
+        let _ = writeln!(
+            out,
+            "<tr><th>Atomic Counter (A)</th><td>{:.2} Mops/s ({} threads)</td></tr>",
+            atomics.counter_mops, atomics.threads
+        );
+        let _ = writeln!(
+            out,
+            "<tr><th>CAS Loop (LR/SC)</th><td>{:.2} Mops/s</td></tr>",
+            atomics.cas_lrsc_mops
+        );
+        if let Some(mops) = atomics.cas_zacas_mops {
+            let _ = writeln!(
+                out,
+                "<tr><th>CAS Loop (Zacas)</th><td>{mops:.2} Mops/s</td></tr>"
+            );
+        }
+        for (label, kernel) in [
+            ("Address Gen (Zba)", &bench.bitmanip.address_generation),
+            ("Popcount/CLZ (Zbb)", &bench.bitmanip.bit_count),
+            ("Bit Set (Zbs)", &bench.bitmanip.bit_set),
+        ] {
+            let score = match kernel.bitmanip_mops {
+                Some(mops) => format!("{mops:.2} Mops (base {:.2} Mops)", kernel.base_mops),
+                None => format!("{:.2} Mops (extension not detected)", kernel.base_mops),
+            };
+            let _ = writeln!(out, "<tr><th>{label}</th><td>{score}</td></tr>");
+        }
+        let _ = writeln!(
+            out,
+            "<tr><th>Branch Mispredict</th><td>{:.1} ns (predictable {:.1} ns, random {:.1} ns per branch)</td></tr>",
+            bench.branch.mispredict_penalty_ns, bench.branch.predictable_ns, bench.branch.random_ns
+        );
+        if let Some(crypto) = &bench.crypto {
+            for (label, comparison) in [
+                ("AES-128 (Zkne)", &crypto.aes128),
+                ("SHA-256 (Zknh)", &crypto.sha256),
+            ] {
+                if let Some(c) = comparison {
+                    let _ = writeln!(
+                        out,
+                        "<tr><th>{label}</th><td>{:.2} MB/s (software {:.2} MB/s)</td></tr>",
+                        c.hardware_mb_per_sec, c.software_mb_per_sec
+                    );
+                }
+            }
+        }
+        if let Some(misaligned) = &bench.misaligned {
+            let _ = writeln!(
+                out,
+                "<tr><th>Misaligned Access</th><td>{:.1}x slower ({})</td></tr>",
+                misaligned.penalty, misaligned.measured
+            );
+        }
+        if let Some(syscalls) = &bench.syscalls {
+            let _ = writeln!(
+                out,
+                "<tr><th>Syscall (getpid)</th><td>{:.1} ns</td></tr>",
+                syscalls.getpid_ns
+            );
+            let _ = writeln!(
+                out,
+                "<tr><th>Pipe Round Trip</th><td>{:.2} µs</td></tr>",
+                syscalls.pipe_round_trip_us
+            );
+        }
+        if let Some(counters) = &bench.counters {
+            for counter in &counters.counters {
+                let value = counter
+                    .ns
+                    .map_or_else(|| "trapped".to_string(), |ns| format!("{ns:.1} ns"));
+                let _ = writeln!(
+                    out,
+                    "<tr><th>CSR rd{}</th><td>{value}</td></tr>",
+                    counter.csr
+                );
+            }
+        }
+        if let Some(fp16) = &bench.fp16 {
+            if let Some(mflops) = fp16.fma_mflops {
+                let _ = writeln!(
+                    out,
+                    "<tr><th>Half FMA (Zfh)</th><td>{mflops:.2} MFLOPS (fp32 {:.2} MFLOPS)</td></tr>",
+                    fp16.fp32_fma_mflops
+                );
+            }
+            let _ = writeln!(
+                out,
+                "<tr><th>Half Convert ({})</th><td>{:.2} Mops</td></tr>",
+                fp16.extension, fp16.convert_mops
+            );
+        }
+        if let Some(reason) = bench.vector_skipped {
+            let _ = writeln!(
+                out,
+                "<tr><th>memcpy / memset / GEMM (V)</th><td>skipped ({reason})</td></tr>"
+            );
+        }
+        if let Some(vector) = &bench.vector {
+            for (label, speedup) in [
+                ("memcpy (V)", &vector.memcpy),
+                ("memset (V)", &vector.memset),
+            ] {
+                let _ = writeln!(
+                    out,
+                    "<tr><th>{label}</th><td>{:.2} MB/s (scalar {:.2} MB/s)</td></tr>",
+                    speedup.vector_mb_per_sec, speedup.scalar_mb_per_sec
+                );
+            }
+        }
+        if let Some(gemm) = &bench.gemm {
+            let _ = writeln!(
+                out,
+                "<tr><th>int8 GEMM (V)</th><td>{:.2} GOPS (scalar {:.2} GOPS, {n}×{n})</td></tr>",
+                gemm.vector_gops,
+                gemm.scalar_gops,
+                n = gemm.size
+            );
+        }
+        let _ = writeln!(out, "</table>");
+    }
+
+    // All extensions with checkmarks
+    let _ = writeln!(out, "<h2>Extensions</h2>");
+    let _ = writeln!(out, "<h3>Standard Extensions</h3>");
+    let _ = writeln!(out, "<table>");
+    for (name, desc, supported) in info::get_all_standard_extensions_with_status(&data.isa) {
+        write_ext_row(&mut out, &name, &desc, supported);
+    }
+    let _ = writeln!(out, "</table>");
+    write_ext_groups(
+        &mut out,
+        "Z",
+        &info::get_all_z_extensions_with_status(&data.isa),
+        info::get_z_category_name,
+    );
+    write_ext_groups(
+        &mut out,
+        "S",
+        &info::get_all_s_extensions_with_status(&data.isa),
+        info::get_s_category_name,
+    );
+
+    let _ = writeln!(
+        out,
+        "<footer>Generated by riscfetch {}</footer>",
+        env!("CARGO_PKG_VERSION")
+    );
+    let _ = writeln!(out, "</body>");
+    let _ = writeln!(out, "</html>");
+
+    out
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::output::tests::sample_info;
+    use riscfetch_bench::atomics::AtomicsResults;
+    use riscfetch_bench::bitmanip::{BitmanipResults, Kernel};
+    use riscfetch_bench::branch::BranchResults;
+    use riscfetch_bench::stats::{BasicStats, Samples};
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<a href=\"x\">&'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;&lt;/a&gt;"
+        );
+    }
+
+    pub(crate) fn sample_bench() -> BenchResult {
+        BenchResult {
+            integer_mops: 812.5,
+            float_mflops: 120.0,
+            memory_mb_per_sec: 2048.0,
+            memory_sweep: vec![memory::SweepPoint {
+                level: Some("L2"),
+                working_set_bytes: 1 << 20,
+                mb_per_sec: 8000.0,
+                stddev_mb_per_sec: 40.0,
+            }],
+            stats: BasicStats {
+                runs: 3,
+                integer_mops: Samples::from_samples(vec![800.0, 812.5, 815.0]),
+                float_mflops: Samples::from_samples(vec![120.0, 119.0, 121.0]),
+                memory_mb_per_sec: Samples::from_samples(vec![2048.0, 2000.0, 2050.0]),
+            },
+            atomics: AtomicsResults {
+                threads: 4,
+                counter_mops: 45.0,
+                cas_lrsc_mops: 12.5,
+                cas_zacas_mops: None,
+            },
+            bitmanip: BitmanipResults {
+                detected: vec!["zbb"],
+                address_generation: Kernel {
+                    extension: "zba",
+                    base_mops: 300.0,
+                    bitmanip_mops: None,
+                },
+                bit_count: Kernel {
+                    extension: "zbb",
+                    base_mops: 100.0,
+                    bitmanip_mops: Some(400.0),
+                },
+                bit_set: Kernel {
+                    extension: "zbs",
+                    base_mops: 200.0,
+                    bitmanip_mops: None,
+                },
+            },
+            branch: BranchResults {
+                predictable_ns: 1.5,
+                random_ns: 4.0,
+                mispredict_penalty_ns: 5.0,
+            },
+            crypto: None,
+            fp16: None,
+            vector: None,
+            vector_skipped: None,
+            gemm: None,
+            misaligned: None,
+            syscalls: None,
+            counters: None,
+            scaling: None,
+            per_hart: None,
+            power: None,
+        }
+    }
+
+    #[test]
+    fn test_render_html_structure() {
+        let html = render_html(&sample_info(), "vf2", "LOGO", None);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<pre class=\"logo\">LOGO</pre>"));
+        assert!(html.contains("riscfetch report: StarFive VisionFive 2 (vf2)"));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert!(!html.contains("Benchmarks"));
+    }
+
+    #[test]
+    fn test_render_html_checkmarks() {
+        let html = render_html(&sample_info(), "vf2", "", None);
+        assert!(html.contains("<td class=\"yes\">✓</td><td>Zba</td>"));
+        assert!(html.contains("<td class=\"no\">✗</td><td>Zbc</td>"));
+        assert!(html.contains("<td class=\"yes\">✓</td><td>Sstc</td>"));
+    }
+
+    #[test]
+    fn test_render_html_benchmarks() {
+        let bench = sample_bench();
+        let html = render_html(&sample_info(), "vf2", "", Some(&bench));
+        assert!(html.contains("<h2>Benchmarks</h2>"));
+        assert!(html.contains("812.50 MOPS"));
+        assert!(html.contains("400.00 Mops (base 100.00 Mops)"));
+        assert!(html.contains("45.00 Mops/s (4 threads)"));
+        assert!(html.contains("<th>Branch Mispredict</th><td>5.0 ns"));
+        assert!(html.contains("<th>Memory (L2, 1 MiB)</th><td>8000.00 MB/s</td>"));
+    }
+}
@@ -0,0 +1,202 @@
+//! Live sensor monitoring (`riscfetch sensors`)
+//!
+//! Every interval one sample is taken: thermal zone temperatures, per-hart
+//! frequency and the load since the previous sample, and hwmon fan speeds.
+//! Samples are printed as one line each, or as JSON Lines for logging.
+
+use crate::harts;
+use colored::Colorize;
+use riscfetch_core::{self as info, CpuTimes, FanReading, HartFreq, ThermalZone};
+use serde::Serialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// One hart in a sample
+#[derive(Debug, Serialize, PartialEq)]
+pub struct HartSample {
+    pub hart: usize,
+    pub current_khz: Option<u64>,
+    /// Busy percentage since the previous sample
+    pub utilization: Option<f64>,
+}
+
+/// Everything read at one point in time
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Sample {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub zones: Vec<ThermalZone>,
+    pub harts: Vec<HartSample>,
+    pub fans: Vec<FanReading>,
+}
+
+impl Sample {
+    /// Combine readings; utilization is taken between `before` and `after`
+    #[must_use]
+    pub fn new(
+        timestamp: u64,
+        freqs: &[HartFreq],
+        before: &[(usize, CpuTimes)],
+        after: &[(usize, CpuTimes)],
+        zones: Vec<ThermalZone>,
+        fans: Vec<FanReading>,
+    ) -> Self {
+        let harts = harts::rows(freqs, before, after, &[])
+            .into_iter()
+            .map(|row| HartSample {
+                hart: row.hart,
+                current_khz: row.current_khz,
+                utilization: row.utilization,
+            })
+            .collect();
+        Self {
+            timestamp,
+            zones,
+            harts,
+            fans,
+        }
+    }
+
+    /// One status line, prefixed with the time since monitoring started;
+    /// sections without readings are left out
+    #[must_use]
+    pub fn line(&self, elapsed: Duration) -> String {
+        let mut parts = vec![format!("{:>6.1}s", elapsed.as_secs_f64())];
+        let mut section = |label: &str, values: Vec<String>| {
+            if !values.is_empty() {
+                parts.push(format!(
+                    "{} {}",
+                    format!("{label}:").bright_yellow().bold(),
+                    values.join(" ")
+                ));
+            }
+        };
+        #[allow(clippy::cast_precision_loss)]
+        section(
+            "temp",
+            self.zones
+                .iter()
+                .map(|z| format!("{} {:.1} °C", z.name, z.millicelsius as f64 / 1000.0))
+                .collect(),
+        );
+        let freqs: Vec<String> = self
+            .harts
+            .iter()
+            .map(|h| {
+                h.current_khz
+                    .map_or_else(|| "-".to_string(), |khz| (khz / 1000).to_string())
+            })
+            .collect();
+        if self.harts.iter().any(|h| h.current_khz.is_some()) {
+            section("freq", vec![format!("{} MHz", freqs.join(" "))]);
+        }
+        if self.harts.iter().any(|h| h.utilization.is_some()) {
+            section(
+                "load",
+                self.harts
+                    .iter()
+                    .map(|h| {
+                        h.utilization
+                            .map_or_else(|| "-".to_string(), |u| format!("{u:.0}%"))
+                    })
+                    .collect(),
+            );
+        }
+        section(
+            "fan",
+            self.fans
+                .iter()
+                .map(|f| format!("{} {} rpm", f.label, f.rpm))
+                .collect(),
+        );
+        parts.join("  ")
+    }
+}
+
+/// Sample every `interval` until interrupted, or `count` times
+pub fn monitor(interval: Duration, count: Option<u64>, json_lines: bool) {
+    let start = std::time::Instant::now();
+    let mut before = info::get_cpu_times();
+    let mut taken = 0;
+    while count.is_none_or(|count| taken < count) {
+        std::thread::sleep(interval);
+        let after = info::get_cpu_times();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let sample = Sample::new(
+            timestamp,
+            &info::get_cpufreq(),
+            &before,
+            &after,
+            info::get_thermal_zones(),
+            info::get_sensors().fans,
+        );
+        if json_lines {
+            println!(
+                "{}",
+                serde_json::to_string(&sample).unwrap_or_else(|_| "{}".to_string())
+            );
+        } else {
+            println!("{}", sample.line(start.elapsed()));
+        }
+        before = after;
+        taken += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+
+    fn sample() -> Sample {
+        let freqs = [HartFreq {
+            hart: 0,
+            current_khz: Some(1_500_000),
+            ..HartFreq::default()
+        }];
+        let times = |busy, total| CpuTimes { busy, total };
+        Sample::new(
+            1_700_000_000,
+            &freqs,
+            &[(0, times(100, 1000)), (1, times(0, 1000))],
+            &[(0, times(150, 1200)), (1, times(0, 1200))],
+            vec![ThermalZone {
+                zone: "thermal_zone0".to_string(),
+                name: "cpu-thermal".to_string(),
+                millicelsius: 45_250,
+                harts: vec![0, 1],
+            }],
+            vec![FanReading {
+                chip: "pwmfan".to_string(),
+                label: "fan1".to_string(),
+                rpm: 3200,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_line() {
+        let line = strip_ansi(&sample().line(Duration::from_secs(2)));
+        assert_eq!(
+            line,
+            "   2.0s  temp: cpu-thermal 45.2 °C  freq: 1500 - MHz  load: 25% 0%  fan: fan1 3200 rpm"
+        );
+
+        let empty = Sample::new(0, &[], &[], &[], Vec::new(), Vec::new());
+        assert_eq!(
+            strip_ansi(&empty.line(Duration::from_millis(500))),
+            "   0.5s"
+        );
+    }
+
+    #[test]
+    fn test_json_line() {
+        let json = serde_json::to_string(&sample()).unwrap();
+        assert!(!json.contains('\n'));
+        assert!(json.starts_with("{\"timestamp\":1700000000,\"zones\":[{"));
+        assert!(
+            json.contains("\"harts\":[{\"hart\":0,\"current_khz\":1500000,\"utilization\":25.0}")
+        );
+    }
+}
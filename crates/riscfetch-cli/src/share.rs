@@ -0,0 +1,128 @@
+//! Upload riscfetch output to a paste service (`riscfetch share`) and
+//! download published board profiles (`--compare-url`)
+//!
+//! Requires the `network` cargo feature (enabled by default).
+
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::time::Duration;
+
+/// Paste service used when no endpoint is configured.
+/// Accepts the raw document as a POST body and answers with the paste URL.
+pub const DEFAULT_ENDPOINT: &str = "https://paste.rs/";
+
+/// Environment variable that overrides the default endpoint
+pub const ENDPOINT_ENV: &str = "RISCFETCH_PASTE_URL";
+
+/// Resolve the endpoint: CLI flag, then environment, then default
+#[must_use]
+pub fn resolve_endpoint(flag: Option<&str>) -> String {
+    flag.map(str::to_string)
+        .or_else(|| std::env::var(ENDPOINT_ENV).ok().filter(|v| !v.is_empty()))
+        .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string())
+}
+
+/// Ask a yes/no question on the terminal (defaults to no).
+/// Returns false when stdin is not interactive.
+#[must_use]
+pub fn confirm(prompt: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+    print!("{prompt} [y/N] ");
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Extract the paste URL from a service response.
+///
+/// Plain-text services (paste.rs, 0x0.st, ix.io) return the URL as the body;
+/// JSON services usually return it in a `url` or `link` field.
+#[must_use]
+pub fn extract_url(response: &str) -> Option<String> {
+    let response = response.trim();
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(response) {
+        return ["url", "link", "html_url"]
+            .iter()
+            .find_map(|key| value.get(key).and_then(|v| v.as_str()))
+            .map(str::to_string);
+    }
+    response
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("https://") || line.starts_with("http://"))
+        .map(str::to_string)
+}
+
+/// POST `body` to `endpoint` and return the paste URL
+pub fn upload(endpoint: &str, body: &str, content_type: &str) -> Result<String, String> {
+    let response = ureq::post(endpoint)
+        .timeout(Duration::from_secs(30))
+        .set("Content-Type", content_type)
+        .set(
+            "User-Agent",
+            concat!("riscfetch/", env!("CARGO_PKG_VERSION")),
+        )
+        .send_string(body)
+        .map_err(|e| e.to_string())?;
+
+    let text = response.into_string().map_err(|e| e.to_string())?;
+    extract_url(&text).ok_or_else(|| format!("unexpected response from {endpoint}: {text}"))
+}
+
+/// GET `url` and return the body
+pub fn download(url: &str) -> Result<String, String> {
+    ureq::get(url)
+        .timeout(Duration::from_secs(30))
+        .set(
+            "User-Agent",
+            concat!("riscfetch/", env!("CARGO_PKG_VERSION")),
+        )
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_string()
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_url_plain_text() {
+        assert_eq!(
+            extract_url("https://paste.rs/AbCd\n"),
+            Some("https://paste.rs/AbCd".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_url_json() {
+        assert_eq!(
+            extract_url(r#"{"id": "x1", "url": "https://example.org/x1"}"#),
+            Some("https://example.org/x1".to_string())
+        );
+        assert_eq!(
+            extract_url(r#"{"link": "https://example.org/x2"}"#),
+            Some("https://example.org/x2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_url_garbage() {
+        assert_eq!(extract_url("Internal Server Error"), None);
+        assert_eq!(extract_url(r#"{"error": "too large"}"#), None);
+    }
+
+    #[test]
+    fn test_resolve_endpoint_flag_wins() {
+        assert_eq!(
+            resolve_endpoint(Some("https://paste.example/")),
+            "https://paste.example/"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Saved snapshots of `SystemInfo` (`riscfetch snapshot save|diff`)
+//!
+//! A snapshot is the same document as `--json`, so files written by
+//! `riscfetch --json > board.json` can be compared as well.
+
+use colored::Colorize;
+use riscfetch_core::{Change, SystemInfo};
+
+/// Parse a snapshot written by `snapshot save` or `--json`
+pub fn parse(text: &str) -> Result<SystemInfo, String> {
+    serde_json::from_str(text).map_err(|e| format!("not a riscfetch snapshot: {e}"))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn gib(bytes: &str) -> String {
+    bytes.parse::<u64>().map_or_else(
+        |_| bytes.to_string(),
+        |b| format!("{:.2} GiB", b as f64 / 1_073_741_824.0),
+    )
+}
+
+/// One line per change: `~` changed field, `+` added and `-` removed extension
+#[must_use]
+pub fn format_change(change: &Change) -> String {
+    match change {
+        Change::Changed {
+            field,
+            before,
+            after,
+        } => {
+            let (before, after) = if *field == "memory_total_bytes" {
+                (gib(before), gib(after))
+            } else {
+                (before.clone(), after.clone())
+            };
+            format!(
+                "{} {:<24} {} → {}",
+                "~".bright_yellow().bold(),
+                field,
+                before.bright_black(),
+                after.bright_white()
+            )
+        }
+        Change::Added { field, name } => format!(
+            "{} {:<24} {}",
+            "+".bright_green().bold(),
+            field,
+            name.bright_green()
+        ),
+        Change::Removed { field, name } => format!(
+            "{} {:<24} {}",
+            "-".bright_red().bold(),
+            field,
+            name.bright_red()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+    use crate::output::tests::sample_info;
+
+    #[test]
+    fn test_parse_round_trip() {
+        let data = sample_info();
+        let text = serde_json::to_string_pretty(&data).unwrap();
+        let parsed = parse(&text).unwrap();
+        assert!(riscfetch_core::diff_system_info(&data, &parsed).is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_other_json() {
+        let err = parse(r#"{"error": "not_riscv"}"#).unwrap_err();
+        assert!(err.starts_with("not a riscfetch snapshot:"));
+    }
+
+    #[test]
+    fn test_format_change() {
+        let line = format_change(&Change::Changed {
+            field: "memory_total_bytes",
+            before: "8589934592".to_string(),
+            after: "17179869184".to_string(),
+        });
+        assert_eq!(
+            strip_ansi(&line),
+            "~ memory_total_bytes       8.00 GiB → 16.00 GiB"
+        );
+        let line = format_change(&Change::Added {
+            field: "z_extensions",
+            name: "Zvbb".to_string(),
+        });
+        assert_eq!(strip_ansi(&line), "+ z_extensions             Zvbb");
+    }
+}
@@ -0,0 +1,91 @@
+//! Community benchmark submission (`riscfetch bench --submit`)
+//!
+//! The document is built from an allowlist so that nothing identifying
+//! (hostname, user, uptime, memory in use, history) leaves the machine.
+
+use riscfetch_bench::BenchResult;
+use riscfetch_core::SystemInfo;
+use serde_json::{json, Value};
+
+/// Format version of the submitted document
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Environment variable holding the results endpoint
+pub const ENDPOINT_ENV: &str = "RISCFETCH_RESULTS_URL";
+
+/// Resolve the endpoint: CLI flag, then environment. There is no default;
+/// results are only sent where the user points them.
+#[must_use]
+pub fn resolve_endpoint(flag: Option<&str>) -> Option<String> {
+    flag.map(str::to_string)
+        .or_else(|| std::env::var(ENDPOINT_ENV).ok())
+        .filter(|v| !v.is_empty())
+}
+
+/// The anonymized document: hardware description plus benchmark results
+#[must_use]
+pub fn document(info: &SystemInfo, results: &BenchResult) -> Value {
+    let names = |entries: &[riscfetch_core::ExtensionEntry]| -> Vec<String> {
+        entries.iter().map(|e| e.name.clone()).collect()
+    };
+    json!({
+        "schema": SCHEMA_VERSION,
+        "riscfetch_version": env!("CARGO_PKG_VERSION"),
+        "hardware": {
+            "board": info.board,
+            "isa": info.isa,
+            "extensions": names(&info.extensions),
+            "z_extensions": names(&info.z_extensions),
+            "s_extensions": names(&info.s_extensions),
+            "vector": info.vector,
+            "hart_count": info.hart_count,
+            "hardware_ids": info.hardware_ids,
+            "cache": info.cache,
+            "memory_total_bytes": info.memory_total_bytes,
+            "kernel": info.kernel,
+            "os": info.os,
+        },
+        "benchmarks": results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_is_anonymized() {
+        let mut info = crate::output::tests::sample_info();
+        info.memory_used_bytes = 123_456_789;
+        info.uptime_seconds = 987_654;
+        let doc = document(&info, &crate::report::tests::sample_bench());
+
+        let hardware = doc["hardware"].as_object().unwrap();
+        assert!(!hardware.contains_key("memory_used_bytes"));
+        assert!(!hardware.contains_key("uptime_seconds"));
+        let text = doc.to_string();
+        assert!(!text.contains("123456789"));
+        assert!(!text.contains("987654"));
+    }
+
+    #[test]
+    fn test_document_fields() {
+        let info = crate::output::tests::sample_info();
+        let doc = document(&info, &crate::report::tests::sample_bench());
+
+        assert_eq!(doc["schema"], SCHEMA_VERSION);
+        assert_eq!(doc["hardware"]["board"], info.board.as_str());
+        assert_eq!(doc["hardware"]["hart_count"], info.hart_count);
+        assert!(doc["hardware"]["extensions"][0].is_string());
+        assert_eq!(doc["benchmarks"]["integer_mops"], 812.5);
+    }
+
+    #[test]
+    fn test_resolve_endpoint_flag_wins() {
+        assert_eq!(
+            resolve_endpoint(Some("https://results.example/api")).as_deref(),
+            Some("https://results.example/api")
+        );
+        assert_eq!(resolve_endpoint(Some("")), None);
+    }
+}
@@ -0,0 +1,212 @@
+//! User-defined output templates (`--template FILE`)
+//!
+//! Templates are plain text with `{key}` placeholders resolved against
+//! `SystemInfo`, independent of the pretty display. `{{` and `}}` produce
+//! literal braces. Unknown keys are left as-is so typos are easy to spot.
+
+use riscfetch_core::{self as info, ExtensionEntry, ExtensionInfo, SystemInfo};
+
+/// Values available to templates that are not part of `SystemInfo`
+pub struct Context<'a> {
+    pub info: &'a SystemInfo,
+    pub hostname: &'a str,
+    pub user: &'a str,
+}
+
+/// Keys accepted by `resolve` (category keys take any id from the core tables)
+pub const KEYS: &[&str] = &[
+    "isa",
+    "board",
+    "os",
+    "kernel",
+    "hostname",
+    "user",
+    "harts",
+    "hart_count",
+    "vector",
+    "mvendorid",
+    "marchid",
+    "mimpid",
+    "memory",
+    "memory_used",
+    "memory_total",
+    "memory_used_bytes",
+    "memory_total_bytes",
+    "uptime",
+    "uptime_seconds",
+    "extensions",
+    "extensions.z",
+    "extensions.s",
+    "extensions.z.<category>",
+    "extensions.s.<category>",
+];
+
+fn names(exts: &[ExtensionEntry]) -> String {
+    exts.iter()
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn names_in_category(exts: &[ExtensionInfo], category: &str) -> String {
+    exts.iter()
+        .filter(|e| e.category == category)
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn gib(bytes: u64) -> String {
+    format!("{:.2} GiB", bytes as f64 / 1_073_741_824.0)
+}
+
+/// Resolve a single key to its value, or None if the key is unknown
+#[must_use]
+pub fn resolve(ctx: &Context, key: &str) -> Option<String> {
+    let data = ctx.info;
+    let value = match key {
+        "isa" => data.isa.clone(),
+        "board" => data.board.clone(),
+        "os" => data.os.clone(),
+        "kernel" => data.kernel.clone(),
+        "hostname" => ctx.hostname.to_string(),
+        "user" => ctx.user.to_string(),
+        "harts" => format!(
+            "{} hart{}",
+            data.hart_count,
+            if data.hart_count > 1 { "s" } else { "" }
+        ),
+        "hart_count" => data.hart_count.to_string(),
+        "vector" => info::parse_vector_from_isa(&data.isa).unwrap_or_default(),
+        "mvendorid" => data.hardware_ids.mvendorid.clone(),
+        "marchid" => data.hardware_ids.marchid.clone(),
+        "mimpid" => data.hardware_ids.mimpid.clone(),
+        "memory" => format!(
+            "{} / {}",
+            gib(data.memory_used_bytes),
+            gib(data.memory_total_bytes)
+        ),
+        "memory_used" => gib(data.memory_used_bytes),
+        "memory_total" => gib(data.memory_total_bytes),
+        "memory_used_bytes" => data.memory_used_bytes.to_string(),
+        "memory_total_bytes" => data.memory_total_bytes.to_string(),
+        "uptime" => info::format_uptime(data.uptime_seconds),
+        "uptime_seconds" => data.uptime_seconds.to_string(),
+        "extensions" => names(&data.extensions),
+        "extensions.z" => names(&data.z_extensions),
+        "extensions.s" => names(&data.s_extensions),
+        _ => {
+            if let Some(category) = key.strip_prefix("extensions.z.") {
+                if !info::Z_CATEGORY_NAMES.iter().any(|(id, _)| *id == category) {
+                    return None;
+                }
+                names_in_category(&info::parse_z_extensions_with_category(&data.isa), category)
+            } else if let Some(category) = key.strip_prefix("extensions.s.") {
+                if !info::S_CATEGORY_NAMES.iter().any(|(id, _)| *id == category) {
+                    return None;
+                }
+                names_in_category(&info::parse_s_extensions_with_category(&data.isa), category)
+            } else {
+                return None;
+            }
+        }
+    };
+    Some(value)
+}
+
+/// Render a template, substituting every `{key}` placeholder
+#[must_use]
+pub fn render(template: &str, ctx: &Context) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+
+        if let Some(after) = tail.strip_prefix("{{") {
+            out.push('{');
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("}}") {
+            out.push('}');
+            rest = after;
+        } else if let (true, Some(end)) = (tail.starts_with('{'), tail.find('}')) {
+            let key = &tail[1..end];
+            match resolve(ctx, key.trim()) {
+                Some(value) => out.push_str(&value),
+                None => out.push_str(&tail[..=end]),
+            }
+            rest = &tail[end + 1..];
+        } else {
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::sample_info;
+
+    fn with_ctx<T>(f: impl FnOnce(&Context) -> T) -> T {
+        let data = sample_info();
+        let ctx = Context {
+            info: &data,
+            hostname: "vf2",
+            user: "riscv",
+        };
+        f(&ctx)
+    }
+
+    #[test]
+    fn test_render_basic_keys() {
+        let out = with_ctx(|ctx| render("{user}@{hostname}: {board} ({harts})", ctx));
+        assert_eq!(out, "riscv@vf2: StarFive VisionFive 2 (4 harts)");
+    }
+
+    #[test]
+    fn test_render_category_keys() {
+        let out = with_ctx(|ctx| render("bit={extensions.z.bit} sup={extensions.s.sup}", ctx));
+        assert_eq!(out, "bit=Zba Zbb sup=Sstc");
+    }
+
+    #[test]
+    fn test_render_unknown_key_kept() {
+        let out = with_ctx(|ctx| render("{nope} {extensions.z.nope}", ctx));
+        assert_eq!(out, "{nope} {extensions.z.nope}");
+    }
+
+    #[test]
+    fn test_render_escaped_braces() {
+        let out = with_ctx(|ctx| render("{{isa}} = {isa}", ctx));
+        assert_eq!(out, "{isa} = rv64imafdc_zicsr_zba_zbb_sstc");
+    }
+
+    #[test]
+    fn test_render_unterminated_brace() {
+        let out = with_ctx(|ctx| render("open { brace", ctx));
+        assert_eq!(out, "open { brace");
+    }
+
+    #[test]
+    fn test_all_plain_keys_resolve() {
+        with_ctx(|ctx| {
+            for key in KEYS.iter().filter(|k| !k.contains('<')) {
+                assert!(resolve(ctx, key).is_some(), "{key} did not resolve");
+            }
+        });
+    }
+
+    #[test]
+    fn test_resolve_formatted_values() {
+        with_ctx(|ctx| {
+            assert_eq!(resolve(ctx, "memory_total").as_deref(), Some("8.00 GiB"));
+            assert_eq!(resolve(ctx, "uptime").as_deref(), Some("1h 0m"));
+            assert_eq!(resolve(ctx, "extensions").as_deref(), Some("I M A F D C"));
+        });
+    }
+}
@@ -0,0 +1,473 @@
+//! Toolchain capability cross-check (`riscfetch toolchain`) and compiler
+//! flag advice (`riscfetch --rustflags`)
+//!
+//! Installed gcc, clang and rustc are asked which RISC-V extensions they can
+//! target, and every hardware extension none of them can emit yet is
+//! flagged: the chip may have Zvbb while the compiler cannot use it.
+
+use colored::Colorize;
+use riscfetch_core as info;
+use serde::Serialize;
+use std::process::Command;
+
+/// GCC drivers tried in order; the first one targeting riscv64 is used
+const GCC_COMMANDS: [&str; 3] = [
+    "gcc",
+    "riscv64-linux-gnu-gcc",
+    "riscv64-unknown-linux-gnu-gcc",
+];
+
+/// Target clang is asked about
+const CLANG_TARGET: &str = "riscv64-linux-gnu";
+
+/// Target rustc is asked about
+const RUST_TARGET: &str = "riscv64gc-unknown-linux-gnu";
+
+/// Extensions the `riscv64gc` target enables already
+const RUST_BASELINE: [&str; 7] = ["m", "a", "f", "d", "c", "zicsr", "zifencei"];
+
+/// One installed compiler with a riscv64 target
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct Compiler {
+    /// `gcc`, `clang` or `rustc`
+    pub name: &'static str,
+    /// Command that was run
+    pub command: &'static str,
+    pub version: Option<String>,
+    /// Extensions the compiler can target, lowercase
+    pub extensions: Vec<String>,
+    /// Hardware extensions missing from `extensions`
+    pub unsupported: Vec<String>,
+}
+
+/// Run a command and return its stdout, if it succeeded
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn detect_gcc() -> Option<(&'static str, Option<String>, Vec<String>)> {
+    let command = GCC_COMMANDS.into_iter().find(|gcc| {
+        run(gcc, &["-dumpmachine"]).is_some_and(|machine| machine.starts_with("riscv64"))
+    })?;
+    let version = run(command, &["-dumpfullversion"]).map(|v| v.trim().to_string());
+    // -march=help exists since GCC 14
+    let extensions = run(command, &["-march=help"])
+        .map(|help| parse_extension_table(&help))
+        .unwrap_or_default();
+    Some((command, version, extensions))
+}
+
+fn detect_clang() -> Option<(&'static str, Option<String>, Vec<String>)> {
+    let target = format!("--target={CLANG_TARGET}");
+    let table = run("clang", &[&target, "--print-supported-extensions"])?;
+    let version = run("clang", &["--version"]).and_then(|v| parse_clang_version(&v));
+    Some(("clang", version, parse_extension_table(&table)))
+}
+
+/// Whether rustc is installed and knows the riscv64 Linux target
+fn rustc_has_target() -> bool {
+    run("rustc", &["--print", "target-list"])
+        .is_some_and(|targets| targets.lines().any(|t| t == RUST_TARGET))
+}
+
+/// `rustc --print <what>` for the riscv64 Linux target
+fn rustc_print(what: &str) -> Option<String> {
+    run("rustc", &["--print", what, "--target", RUST_TARGET])
+}
+
+fn detect_rustc() -> Option<(&'static str, Option<String>, Vec<String>)> {
+    if !rustc_has_target() {
+        return None;
+    }
+    let version =
+        run("rustc", &["--version"]).and_then(|v| v.split_whitespace().nth(1).map(str::to_string));
+    let features = rustc_print("target-features")
+        .map(|f| parse_rustc_features(&f))
+        .unwrap_or_default();
+    Some(("rustc", version, features))
+}
+
+/// Every installed compiler with a riscv64 target, compared with `isa`
+#[must_use]
+pub fn detect(isa: &str) -> Vec<Compiler> {
+    let hardware = hardware_extensions(isa);
+    [
+        ("gcc", detect_gcc()),
+        ("clang", detect_clang()),
+        ("rustc", detect_rustc()),
+    ]
+    .into_iter()
+    .filter_map(|(name, found)| {
+        let (command, version, extensions) = found?;
+        Some(compiler(name, command, version, extensions, &hardware))
+    })
+    .collect()
+}
+
+fn compiler(
+    name: &'static str,
+    command: &'static str,
+    version: Option<String>,
+    extensions: Vec<String>,
+    hardware: &[String],
+) -> Compiler {
+    // An empty list means the compiler cannot list its extensions (GCC
+    // before 14), not that it supports none of them
+    let unsupported = if extensions.is_empty() {
+        Vec::new()
+    } else {
+        hardware
+            .iter()
+            .filter(|ext| !extensions.contains(ext))
+            .cloned()
+            .collect()
+    };
+    Compiler {
+        name,
+        command,
+        version,
+        extensions,
+        unsupported,
+    }
+}
+
+/// User-level extensions in an ISA string that a compiler could target:
+/// single letters except the I base (G expanded), then Z extensions.
+/// Supervisor and vendor extensions are left out.
+#[must_use]
+pub fn hardware_extensions(isa: &str) -> Vec<String> {
+    let isa = isa.to_lowercase();
+    let mut parts = isa.split('_');
+    let base = parts.next().unwrap_or_default();
+    let base = base
+        .strip_prefix("rv64")
+        .or_else(|| base.strip_prefix("rv32"))
+        .unwrap_or(base);
+    let mut extensions: Vec<String> = Vec::new();
+    for ch in base.replace('g', "imafd").chars() {
+        let known = info::STANDARD_EXTENSIONS.iter().any(|(c, _, _)| *c == ch);
+        if ch != 'i' && known && !extensions.contains(&ch.to_string()) {
+            extensions.push(ch.to_string());
+        }
+    }
+    if base.contains('g') {
+        extensions.extend(["zicsr".to_string(), "zifencei".to_string()]);
+    }
+    for ext in parts.filter(|p| p.starts_with('z')) {
+        if !extensions.iter().any(|e| e == ext) {
+            extensions.push(ext.to_string());
+        }
+    }
+    extensions
+}
+
+/// Extension names from `gcc -march=help` or `clang --print-supported-extensions`:
+/// an indented `Name  Version ...` table. Clang's experimental extensions
+/// need an extra flag and are left out.
+#[must_use]
+pub fn parse_extension_table(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .take_while(|line| !line.trim_start().starts_with("Experimental"))
+        .filter(|line| line.starts_with(char::is_whitespace))
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|name| *name != "Name")
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Feature names from `rustc --print target-features`, stopping before the
+/// LLVM-only codegen features
+#[must_use]
+pub fn parse_rustc_features(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .take_while(|line| !line.starts_with("Code-generation features"))
+        .filter(|line| line.starts_with(char::is_whitespace) && line.contains(" - "))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// CPU names from `rustc --print target-cpus`, without `native`
+#[must_use]
+pub fn parse_rustc_cpus(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| line.starts_with(char::is_whitespace))
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|name| *name != "native")
+        .map(str::to_string)
+        .collect()
+}
+
+/// Recommended rustc codegen flags for the hardware
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rustflags {
+    /// Target features to enable beyond `riscv64gc`, lowercase
+    pub features: Vec<String>,
+    /// Hardware extensions left out because rustc does not know them
+    pub unknown: Vec<String>,
+    /// LLVM name of the recognized core
+    pub target_cpu: Option<String>,
+}
+
+impl Rustflags {
+    /// Flags for `isa` and `core`, keeping only the features and CPU names
+    /// rustc knows (everything when `rustc` is None)
+    #[must_use]
+    pub fn new(
+        isa: &str,
+        core: Option<&info::Core>,
+        rustc: Option<(&[String], &[String])>,
+    ) -> Self {
+        let (features, unknown) = hardware_extensions(isa)
+            .into_iter()
+            .filter(|ext| !RUST_BASELINE.contains(&ext.as_str()))
+            .partition(|ext| rustc.is_none_or(|(features, _)| features.contains(ext)));
+        let target_cpu = core
+            .and_then(|core| core.llvm_cpu)
+            .filter(|cpu| rustc.is_none_or(|(_, cpus)| cpus.iter().any(|c| c == cpu)))
+            .map(str::to_string);
+        Self {
+            features,
+            unknown,
+            target_cpu,
+        }
+    }
+
+    /// The flags for `RUSTFLAGS`; empty when the target defaults already fit
+    #[must_use]
+    pub fn flags(&self) -> String {
+        let mut flags = Vec::new();
+        if !self.features.is_empty() {
+            let features: Vec<String> = self.features.iter().map(|f| format!("+{f}")).collect();
+            flags.push(format!("-C target-feature={}", features.join(",")));
+        }
+        if let Some(cpu) = &self.target_cpu {
+            flags.push(format!("-C target-cpu={cpu}"));
+        }
+        flags.join(" ")
+    }
+}
+
+/// Target features and CPU names of rustc's riscv64 Linux target; None
+/// without rustc or without that target
+#[must_use]
+pub fn rustc_target_info() -> Option<(Vec<String>, Vec<String>)> {
+    if !rustc_has_target() {
+        return None;
+    }
+    let features = parse_rustc_features(&rustc_print("target-features")?);
+    let cpus = rustc_print("target-cpus")
+        .map(|c| parse_rustc_cpus(&c))
+        .unwrap_or_default();
+    Some((features, cpus))
+}
+
+/// `18.1.3` from `Ubuntu clang version 18.1.3 (1ubuntu1)`
+fn parse_clang_version(output: &str) -> Option<String> {
+    let line = output.lines().next()?;
+    let (_, rest) = line.split_once("clang version ")?;
+    rest.split_whitespace().next().map(str::to_string)
+}
+
+/// Human-readable report: one line per compiler with the hardware
+/// extensions it cannot target
+#[must_use]
+pub fn render(compilers: &[Compiler]) -> Vec<String> {
+    if compilers.is_empty() {
+        return vec![format!(
+            "{} no gcc, clang or rustc with a riscv64 target found",
+            "!".bright_yellow().bold()
+        )];
+    }
+    let mut lines = Vec::new();
+    for compiler in compilers {
+        let mut title = compiler.name.to_string();
+        if let Some(version) = &compiler.version {
+            title.push_str(&format!(" {version}"));
+        }
+        if compiler.command != compiler.name {
+            title.push_str(&format!(" ({})", compiler.command));
+        }
+        if compiler.extensions.is_empty() {
+            lines.push(format!(
+                "{} {} {}",
+                "?".bright_yellow().bold(),
+                title,
+                "(cannot list its extensions; GCC 14+ supports -march=help)".bright_black()
+            ));
+        } else if compiler.unsupported.is_empty() {
+            lines.push(format!(
+                "{} {} {}",
+                "✓".bright_green().bold(),
+                title,
+                "can target every hardware extension".bright_black()
+            ));
+        } else {
+            lines.push(format!(
+                "{} {} {}",
+                "!".bright_yellow().bold(),
+                title.bright_yellow(),
+                "cannot target:".bright_black()
+            ));
+            lines.push(format!("  {}", compiler.unsupported.join(" ")));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::strip_ansi;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn test_hardware_extensions() {
+        assert_eq!(
+            hardware_extensions("rv64gcv_zba_zvbb_sstc_xtheadvector"),
+            names(&["m", "a", "f", "d", "c", "v", "zicsr", "zifencei", "zba", "zvbb"])
+        );
+        assert_eq!(
+            hardware_extensions("rv64imafdcsu_zicsr"),
+            names(&["m", "a", "f", "d", "c", "zicsr"])
+        );
+    }
+
+    #[test]
+    fn test_parse_gcc_march_help() {
+        let output = "\
+All available -march extensions for RISC-V:
+\tName                Version
+\ti                       2.0, 2.1
+\tzba                     1.0
+\tzvbb                    1.0
+";
+        assert_eq!(parse_extension_table(output), names(&["i", "zba", "zvbb"]));
+    }
+
+    #[test]
+    fn test_parse_clang_extensions() {
+        let output = "\
+All available -march extensions for RISC-V
+
+    Name                 Version   Description
+    i                    2.1       This is a long dummy description
+    zvbb                 1.0       'Zvbb' (Vector basic bit-manipulation instructions)
+
+Experimental extensions
+    zicfilp              1.0       'Zicfilp' (Landing pad)
+";
+        assert_eq!(parse_extension_table(output), names(&["i", "zvbb"]));
+        assert_eq!(
+            parse_clang_version("Ubuntu clang version 18.1.3 (1ubuntu1)\nTarget: x86_64\n")
+                .as_deref(),
+            Some("18.1.3")
+        );
+    }
+
+    #[test]
+    fn test_parse_rustc_features() {
+        let output = "\
+Features supported by rustc for this target:
+    a                             - 'A' (Atomic Instructions).
+    zba                           - 'Zba' (Address Generation Instructions).
+    crt-static                    - Enables C Run-time Libraries to be statically linked.
+
+Code-generation features supported by LLVM for this target:
+    zvbb                          - 'Zvbb' (Vector basic bit-manipulation instructions).
+";
+        assert_eq!(
+            parse_rustc_features(output),
+            names(&["a", "zba", "crt-static"])
+        );
+    }
+
+    #[test]
+    fn test_parse_rustc_cpus() {
+        let output = "\
+Available CPUs for this target:
+    native                  - Select the CPU of the current host (currently sifive-u74).
+    generic-rv64
+    sifive-u74
+";
+        assert_eq!(
+            parse_rustc_cpus(output),
+            names(&["generic-rv64", "sifive-u74"])
+        );
+    }
+
+    #[test]
+    fn test_rustflags() {
+        let isa = "rv64imafdcv_zicsr_zifencei_zba_zbb_zvbb_zicond_sstc";
+        let u74 = info::identify_core(&["sifive,u74-mc"], &info::HardwareIds::default());
+        let features = names(&["m", "a", "f", "d", "c", "v", "zba", "zbb", "zicond"]);
+        let cpus = names(&["generic-rv64", "sifive-u74"]);
+
+        let flags = Rustflags::new(isa, u74, Some((&features, &cpus)));
+        assert_eq!(flags.unknown, names(&["zvbb"]));
+        assert_eq!(
+            flags.flags(),
+            "-C target-feature=+v,+zba,+zbb,+zicond -C target-cpu=sifive-u74"
+        );
+
+        // CPU names rustc does not know are dropped; without rustc nothing is
+        let x60 = info::identify_core(&["spacemit,x60"], &info::HardwareIds::default());
+        let flags = Rustflags::new("rv64gc", x60, Some((&features, &cpus)));
+        assert_eq!(flags.flags(), "");
+        let flags = Rustflags::new("rv64gc_zvbb", x60, None);
+        assert_eq!(
+            flags.flags(),
+            "-C target-feature=+zvbb -C target-cpu=spacemit-x60"
+        );
+    }
+
+    #[test]
+    fn test_compiler_and_render() {
+        let hardware = names(&["m", "v", "zvbb"]);
+        let gcc = compiler(
+            "gcc",
+            "riscv64-linux-gnu-gcc",
+            Some("13.2.0".to_string()),
+            names(&["i", "m", "v"]),
+            &hardware,
+        );
+        assert_eq!(gcc.unsupported, names(&["zvbb"]));
+        let old = compiler("gcc", "gcc", None, Vec::new(), &hardware);
+        assert!(old.unsupported.is_empty());
+        let rustc = compiler(
+            "rustc",
+            "rustc",
+            Some("1.80.0".to_string()),
+            names(&["m", "v", "zvbb"]),
+            &hardware,
+        );
+
+        let lines: Vec<String> = render(&[gcc, old, rustc])
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "! gcc 13.2.0 (riscv64-linux-gnu-gcc) cannot target:",
+                "  zvbb",
+                "? gcc (cannot list its extensions; GCC 14+ supports -march=help)",
+                "✓ rustc 1.80.0 can target every hardware extension",
+            ]
+        );
+        assert_eq!(
+            strip_ansi(&render(&[])[0]),
+            "! no gcc, clang or rustc with a riscv64 target found"
+        );
+    }
+}
@@ -1,56 +1,179 @@
-//! RISC-V vendor definitions
+//! RISC-V vendor logos
 //!
-//! This module contains the constant definitions for all supported RISC-V vendors.
-//! To add a new vendor, simply add a new entry to the VENDORS array.
+//! The vendor table and detection live in riscfetch-core; this module adds
+//! the logo text for each vendor. A new vendor in `VENDORS` also needs an
+//! entry in `SMALL_MARKS`.
 
-/// Vendor definitions
-/// Format: (aliases, display_name, subtitle)
-///
-/// - aliases: lowercase strings used to match CLI input (first one is primary)
-/// - display_name: name shown in logo
-/// - subtitle: text shown below logo
-pub const VENDORS: &[(&[&str], &str, &str)] = &[
-    // Default
+pub use riscfetch_core::VENDORS;
+
+/// Hand-drawn marks for `--style small`, 3-5 lines each.
+/// Format: (vendor_primary_alias, mark); the vendor name and subtitle are
+/// printed to the right of the mark, so it should not spell the name out.
+const SMALL_MARKS: &[(&str, &str)] = &[
+    (
+        "default",
+        r" ___ __   __
+| _ \\ \ / /
+|_|_\ \_V_/",
+    ),
+    (
+        "sifive",
+        r"  /\
+ /__\
+/_\/_\",
+    ),
+    (
+        "starfive",
+        r" \ | /
+-- * --
+ / | \",
+    ),
+    (
+        "thead",
+        r" _____
+|_   _|
+  | |
+  |_|",
+    ),
+    (
+        "nuclei",
+        r"  .-o-.
+ ( (@) )
+  '-o-'",
+    ),
+    (
+        "milkv",
+        r"\     /
+ \   /
+  \_/",
+    ),
+    (
+        "sipeed",
+        r"  ___
+ / __)
+ \__ \
+ (___/",
+    ),
+    (
+        "pine64",
+        r"   /\
+  /\/\
+ /\/\/\
+   ||",
+    ),
+    (
+        "deepcomputing",
+        r" ______
+|      |
+|______|
+/______\",
+    ),
+    (
+        "framework",
+        r" ___ ___
+| _ | _ |
+|___|___|
+|___|___|",
+    ),
+    (
+        "eswin",
+        r" ___
+| __|
+| _|
+|___|",
+    ),
+    (
+        "ultrarisc",
+        r" _   _
+| | | |
+| |_| |
+ \___/",
+    ),
+    (
+        "kendryte",
+        r" _|_|_
+-| K |-
+-|___|-
+  | |",
+    ),
+    (
+        "allwinner",
+        r"   /\
+  /  \
+ / /\ \
+/_/  \_\",
+    ),
+    (
+        "espressif",
+        r" .---.
+( ))) )
+ '---'",
+    ),
+    (
+        "spacemit",
+        r"   .--.
+ -(-()-)-
+   '--'",
+    ),
+    (
+        "sophgo",
+        r"  __
+ /  \
+ \__/",
+    ),
+    (
+        "bouffalo",
+        r" ___
+| _ )
+| _ \
+|___/",
+    ),
+    (
+        "renesas",
+        r" ___
+| _ \
+|   /
+|_|_\",
+    ),
+    (
+        "wch",
+        r" _|_|_|_
+-|     |-
+-|_____|-
+  | | |",
+    ),
     (
-        &["default", "riscv", "risc-v"],
-        "RISC-V",
-        "Architecture Info",
+        "gigadevice",
+        r"  ___ ___
+ / __|   \
+| (_ | |) |
+ \___|___/",
     ),
-    // Major IP/SoC Providers
-    (&["sifive"], "SiFive", "RISC-V by SiFive"),
-    (&["starfive"], "StarFive", "RISC-V by StarFive"),
     (
-        &["thead", "t-head", "alibaba"],
-        "T-Head",
-        "RISC-V by T-Head",
+        "ventana",
+        r"__   __
+\ \ / /
+ \ V /
+  \_/",
+    ),
+    (
+        "tenstorrent",
+        r" /\  /\
+/  \/  \
+\  /\  /
+ \/  \/",
     ),
-    // Board Manufacturers
-    (&["milkv", "milk-v"], "Milk-V", "RISC-V by Milk-V"),
-    (&["sipeed"], "Sipeed", "RISC-V by Sipeed"),
-    (&["pine64", "pine"], "Pine64", "RISC-V by Pine64"),
-    // SoC Vendors
-    (&["eswin"], "ESWIN", "RISC-V by ESWIN"),
-    (&["ultrarisc"], "UltraRISC", "RISC-V by UltraRISC"),
-    (&["kendryte", "canaan"], "Kendryte", "RISC-V by Kendryte"),
-    (&["allwinner"], "Allwinner", "RISC-V by Allwinner"),
-    (&["espressif", "esp"], "Espressif", "RISC-V by Espressif"),
-    (&["spacemit"], "SpacemiT", "RISC-V by SpacemiT"),
-    (&["sophgo"], "Sophgo", "RISC-V by Sophgo"),
-    // MCU Vendors
-    (&["wch", "winchiphead"], "WCH", "RISC-V by WCH"),
 ];
 
-/// Get vendor info by alias
+/// Get vendor info by alias, ignoring case, dashes and spaces
 /// Returns (display_name, subtitle) or None if not found
 #[must_use]
 pub fn get_vendor_info(alias: &str) -> Option<(&'static str, &'static str)> {
-    let alias_lower = alias.to_lowercase();
-    for (aliases, display_name, subtitle) in VENDORS {
-        if aliases.contains(&alias_lower.as_str()) {
-            return Some((display_name, subtitle));
-        }
-    }
-    None
+    let id = riscfetch_core::find_vendor(alias)?.id;
+    VENDORS
+        .iter()
+        .find(|(aliases, _, _)| aliases[0] == id)
+        .map(|(_, display_name, subtitle)| (*display_name, *subtitle))
 }
 
 /// Get default vendor info
@@ -61,71 +184,13 @@ pub fn get_default_vendor() -> (&'static str, &'static str) {
     (display_name, subtitle)
 }
 
-/// Detection keywords for auto-detecting vendor from board/compatible strings.
-/// Broader than CLI aliases — includes board names and SoC identifiers.
-/// Format: (keyword, vendor_primary_alias)
-///
-/// **Order matters**: more specific keywords (SoC IDs, board names) must come
-/// before generic vendor names. The first match wins, so e.g. "eic7700" (ESWIN)
-/// must precede "sifive" to correctly identify HiFive Premier P550.
-const VENDOR_KEYWORDS: &[(&str, &str)] = &[
-    // SoC / board-specific (most specific first)
-    ("eic7700", "eswin"),
-    ("eic7702", "eswin"),
-    ("ebc77", "eswin"),
-    ("hifive premier", "eswin"),
-    ("starpro64", "eswin"),
-    ("megrez", "eswin"),
-    ("dc-roma", "eswin"),
-    ("ur-dp1000", "ultrarisc"),
-    ("urdp1000", "ultrarisc"),
-    ("milkv titan", "ultrarisc"),
-    ("milk-v titan", "ultrarisc"),
-    ("visionfive", "starfive"),
-    ("jh7110", "starfive"),
-    ("xuantie", "thead"),
-    ("lichee", "sipeed"),
-    ("maix", "sipeed"),
-    ("star64", "pine64"),
-    ("nezha", "allwinner"),
-    ("esp32", "espressif"),
-    ("cv1800", "sophgo"),
-    ("sg2000", "sophgo"),
-    ("ch32v", "wch"),
-    ("ky,x1", "spacemit"),
-    // Vendor names (generic, checked after specific keywords)
-    ("eswin", "eswin"),
-    ("ultrarisc", "ultrarisc"),
-    ("hifive", "sifive"),
-    ("sifive", "sifive"),
-    ("starfive", "starfive"),
-    ("thead", "thead"),
-    ("t-head", "thead"),
-    ("milkv", "milkv"),
-    ("milk-v", "milkv"),
-    ("sipeed", "sipeed"),
-    ("pine64", "pine64"),
-    ("kendryte", "kendryte"),
-    ("canaan", "kendryte"),
-    ("allwinner", "allwinner"),
-    ("espressif", "espressif"),
-    ("spacemit", "spacemit"),
-    ("sophgo", "sophgo"),
-    ("wch", "wch"),
-    ("winchiphead", "wch"),
-];
-
-/// Auto-detect vendor from board model and device-tree compatible strings.
-/// Returns the vendor alias if a known keyword is found, or None.
+/// Small-style mark for the vendor with primary alias `vendor`
 #[must_use]
-pub fn detect_vendor(board_info: &str, compatible: &str) -> Option<&'static str> {
-    let combined = format!("{board_info} {compatible}").to_lowercase();
-    for &(keyword, vendor) in VENDOR_KEYWORDS {
-        if combined.contains(keyword) {
-            return Some(vendor);
-        }
-    }
-    None
+pub fn small_mark(vendor: &str) -> Option<&'static str> {
+    SMALL_MARKS
+        .iter()
+        .find(|(alias, _)| *alias == vendor)
+        .map(|(_, mark)| *mark)
 }
 
 #[cfg(test)]
@@ -157,6 +222,9 @@ mod tests {
 
         let (name, _) = get_vendor_info("Pine64").unwrap();
         assert_eq!(name, "Pine64");
+
+        let (name, _) = get_vendor_info("Milk V").unwrap();
+        assert_eq!(name, "Milk-V");
     }
 
     #[test]
@@ -190,75 +258,6 @@ mod tests {
         assert_eq!(name, "UltraRISC");
     }
 
-    #[test]
-    fn test_detect_vendor_from_model() {
-        assert_eq!(detect_vendor("StarFive VisionFive 2", ""), Some("starfive"));
-        assert_eq!(detect_vendor("Milk-V Mars", ""), Some("milkv"));
-        assert_eq!(detect_vendor("", ""), None);
-    }
-
-    #[test]
-    fn test_detect_vendor_from_compatible() {
-        assert_eq!(detect_vendor("", "sipeed,licheerv-nano"), Some("sipeed"));
-        assert_eq!(
-            detect_vendor("", "starfive,visionfive-2-jh7110"),
-            Some("starfive")
-        );
-    }
-
-    #[test]
-    fn test_detect_eswin_boards() {
-        assert_eq!(detect_vendor("", "eswin,eic7700x"), Some("eswin"));
-        assert_eq!(detect_vendor("Pine64 StarPro64", ""), Some("eswin"));
-        assert_eq!(detect_vendor("Milk-V Megrez", ""), Some("eswin"));
-        assert_eq!(detect_vendor("DeepComputing DC-ROMA II", ""), Some("eswin"));
-        assert_eq!(detect_vendor("ESWIN EBC7702", ""), Some("eswin"));
-    }
-
-    #[test]
-    fn test_detect_ultrarisc_boards() {
-        assert_eq!(detect_vendor("Milk-V Titan", ""), Some("ultrarisc"));
-        assert_eq!(detect_vendor("", "ultrarisc,ur-dp1000"), Some("ultrarisc"));
-    }
-
-    #[test]
-    fn test_hifive_unmatched_still_sifive() {
-        // HiFive Unmatched uses SiFive FU740, not ESWIN
-        assert_eq!(detect_vendor("HiFive Unmatched", ""), Some("sifive"));
-        assert_eq!(detect_vendor("HiFive Unleashed", ""), Some("sifive"));
-    }
-
-    #[test]
-    fn test_detect_vendor_case_insensitive() {
-        assert_eq!(detect_vendor("SIFIVE HIFIVE UNMATCHED", ""), Some("sifive"));
-        // HiFive Premier P550 uses ESWIN EIC7700X, not SiFive silicon
-        assert_eq!(
-            detect_vendor("SiFive HiFive Premier P550", ""),
-            Some("eswin")
-        );
-    }
-
-    #[test]
-    fn test_detect_vendor_unknown_board() {
-        assert_eq!(detect_vendor("Some Unknown Board", "unknown,board"), None);
-    }
-
-    #[test]
-    fn test_detect_orangepi_rv2_as_spacemit() {
-        assert_eq!(
-            detect_vendor("ky x1 orangepi-rv2 board", "ky,orangepi-rv2 ky,x1"),
-            Some("spacemit")
-        );
-    }
-
-    #[test]
-    fn test_ky_orangepi_alone_does_not_match() {
-        // We deliberately key on "ky,x1" only. A bare "ky,orangepi-rv2" without
-        // "ky,x1" is rejected so future Ky-derived boards on non-SpacemiT SoCs
-        // are not silently miscategorized.
-        assert_eq!(detect_vendor("", "ky,orangepi-rv2"), None);
-    }
-
     #[test]
     fn test_all_vendors_have_info() {
         for (aliases, display_name, subtitle) in VENDORS {
@@ -267,4 +266,18 @@ mod tests {
             assert!(!subtitle.is_empty(), "Vendor must have subtitle");
         }
     }
+
+    #[test]
+    fn test_all_vendors_have_small_marks() {
+        for (aliases, _, _) in VENDORS {
+            let mark = small_mark(aliases[0])
+                .unwrap_or_else(|| panic!("{} needs a small mark", aliases[0]));
+            let lines = mark.lines().count();
+            assert!((3..=5).contains(&lines), "{}: {lines} lines", aliases[0]);
+            assert!(mark.is_ascii(), "{} mark must be ASCII", aliases[0]);
+        }
+        for (alias, _) in SMALL_MARKS {
+            assert!(get_vendor_info(alias).is_some(), "{alias} is not a vendor");
+        }
+    }
 }
@@ -0,0 +1,185 @@
+//! Sandboxed WebAssembly plugins (`wasm-plugins` cargo feature)
+//!
+//! `*.wasm` files in the plugin directory get the `SystemInfo` JSON and
+//! return either extra fields (detectors) or a whole report (formatters).
+//! Modules are instantiated without any imports, so they cannot touch files,
+//! the network or the clock; fuel and a memory cap bound their run time and
+//! size.
+//!
+//! Host API, all lengths in bytes and every string UTF-8:
+//!
+//! - `memory`: the exported linear memory
+//! - `riscfetch_alloc(len: i32) -> i32`: buffer for the host to write the input into
+//! - `riscfetch_detect(ptr: i32, len: i32) -> i64`: JSON object of custom fields
+//! - `riscfetch_format(ptr: i32, len: i32) -> i64`: report text for `--wasm-output`
+//!
+//! Both entry points receive the `SystemInfo` JSON at `ptr`/`len` and return
+//! `(out_ptr << 32) | out_len`; 0 means no output.
+
+use crate::plugins::{parse_output, Plugins};
+use std::path::{Path, PathBuf};
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Instructions (roughly) a plugin may execute per call
+const FUEL: u64 = 200_000_000;
+
+/// Largest linear memory a plugin may grow to
+const MAX_MEMORY: usize = 64 << 20;
+
+/// Export producing custom fields
+const DETECT: &str = "riscfetch_detect";
+
+/// Export producing a report
+const FORMAT: &str = "riscfetch_format";
+
+/// Call `export` of the module at `path` with `input`; None when the module
+/// does not have that export
+fn call(path: &Path, export: &str, input: &str) -> Result<Option<String>, String> {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config).map_err(|e| e.to_string())?;
+    let module = Module::from_file(&engine, path).map_err(|e| e.to_string())?;
+    if module.get_export(export).is_none() {
+        return Ok(None);
+    }
+    call_module(&engine, &module, export, input).map(Some)
+}
+
+fn call_module(
+    engine: &Engine,
+    module: &Module,
+    export: &str,
+    input: &str,
+) -> Result<String, String> {
+    let limits = StoreLimitsBuilder::new().memory_size(MAX_MEMORY).build();
+    let mut store: Store<StoreLimits> = Store::new(engine, limits);
+    store.limiter(|limits| limits);
+    store.set_fuel(FUEL).map_err(|e| e.to_string())?;
+
+    // No imports: the module gets nothing but its own memory
+    let instance = Instance::new(&mut store, module, &[]).map_err(|e| e.to_string())?;
+    let memory = instance
+        .get_memory(&mut store, "memory")
+        .ok_or("no exported memory")?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&mut store, "riscfetch_alloc")
+        .map_err(|e| e.to_string())?;
+    let entry = instance
+        .get_typed_func::<(i32, i32), i64>(&mut store, export)
+        .map_err(|e| e.to_string())?;
+
+    let len = i32::try_from(input.len()).map_err(|_| "input too large")?;
+    let ptr = alloc.call(&mut store, len).map_err(|e| e.to_string())?;
+    let offset = usize::try_from(ptr).map_err(|_| "bad input pointer")?;
+    memory
+        .write(&mut store, offset, input.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let packed = entry
+        .call(&mut store, (ptr, len))
+        .map_err(|e| e.to_string())?;
+    #[allow(clippy::cast_sign_loss)]
+    let packed = packed as u64;
+    let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+    let mut out = vec![0; out_len];
+    memory
+        .read(&store, out_ptr, &mut out)
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(out).map_err(|_| "output is not UTF-8".to_string())
+}
+
+/// The input of every plugin: `SystemInfo` as printed by `--json`
+fn system_json() -> String {
+    serde_json::to_string(&riscfetch_core::collect_all_info()).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// `*.wasm` files in `dir`, sorted by name
+fn modules(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Add the fields of every detector in `dir` to `plugins`
+pub fn detect_all(dir: &Path, plugins: &mut Plugins) {
+    let paths = modules(dir);
+    if paths.is_empty() {
+        return;
+    }
+    let input = system_json();
+    for path in paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        match call(&path, DETECT, &input) {
+            Ok(Some(json)) if json.trim().is_empty() => {}
+            Ok(Some(json)) if json.trim_start().starts_with('{') => {
+                plugins.fields.extend(parse_output(&json));
+            }
+            Ok(Some(_)) => plugins
+                .failures
+                .push(format!("{name}: {DETECT} did not return a JSON object")),
+            Ok(None) => {}
+            Err(reason) => plugins.failures.push(format!("{name}: {reason}")),
+        }
+    }
+}
+
+/// Run the formatter `<dir>/<name>.wasm` on this machine's `SystemInfo`
+pub fn format(dir: &Path, name: &str) -> Result<String, String> {
+    let path = dir.join(format!("{name}.wasm"));
+    call(&path, FORMAT, &system_json())
+        .map_err(|e| format!("{}: {e}", path.display()))?
+        .ok_or_else(|| format!("{} does not export {FORMAT}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Echoes its input from `riscfetch_format` and returns a fixed field
+    /// from `riscfetch_detect`
+    const MODULE: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (data (i32.const 16) "{\"npu\": \"2 TOPS\"}")
+          (func (export "riscfetch_alloc") (param i32) (result i32) i32.const 1024)
+          (func (export "riscfetch_detect") (param i32 i32) (result i64)
+            i64.const 68719476753)
+          (func (export "riscfetch_format") (param $ptr i32) (param $len i32) (result i64)
+            (i64.or
+              (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+              (i64.extend_i32_u (local.get $len))))
+          (func (export "spin") (param i32 i32) (result i64)
+            (loop $forever (br $forever))
+            i64.const 0))
+    "#;
+
+    fn run(export: &str, input: &str) -> Result<String, String> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).unwrap();
+        let module = Module::new(&engine, MODULE).unwrap();
+        call_module(&engine, &module, export, input)
+    }
+
+    #[test]
+    fn test_detect_and_format() {
+        // 68719476753 = (16 << 32) | 17
+        assert_eq!(run(DETECT, "{}").unwrap(), "{\"npu\": \"2 TOPS\"}");
+        assert_eq!(
+            run(FORMAT, "{\"isa\":\"rv64gc\"}").unwrap(),
+            "{\"isa\":\"rv64gc\"}"
+        );
+    }
+
+    #[test]
+    fn test_runaway_plugin_is_stopped() {
+        assert!(run("spin", "{}").is_err());
+    }
+}
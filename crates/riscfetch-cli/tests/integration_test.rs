@@ -115,3 +115,113 @@ fn test_riscv_only_json_non_riscv() {
         assert!(stdout.contains(r#""not_riscv""#));
     }
 }
+
+#[test]
+fn test_output_flag_exists() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--help"])
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--output"));
+    assert!(stdout.contains("csv"));
+}
+
+#[test]
+fn test_query_flag_exists() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--help"])
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--query"));
+}
+
+#[test]
+fn test_output_rejects_unknown_format() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--output", "xml"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_report_subcommand_help() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "report", "--help"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--html"));
+    assert!(stdout.contains("--benchmark"));
+}
+
+#[test]
+fn test_check_flag_exit_code() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--check", "zba,rva23", "--quiet"])
+        .output()
+        .expect("Failed to execute command");
+
+    // 0 = supported, 1 = unsupported or not RISC-V; never a usage error
+    assert!(matches!(output.status.code(), Some(0 | 1)));
+}
+
+#[test]
+fn test_threads_requires_benchmark() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--threads", "all"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_bench_endpoint_requires_submit() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "bench",
+            "--endpoint",
+            "https://results.example/",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_logo_preview_runs_on_any_architecture() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--color", "never", "logo", "--preview"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("sifive (SiFive)"));
+    assert!(stdout.contains("RISC-V by WCH"));
+}
+
+#[test]
+fn test_unknown_logo_suggests_vendor() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--color", "never", "--logo", "starfiv", "logo"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown logo 'starfiv', did you mean 'starfive'?"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Architecture Info"));
+}
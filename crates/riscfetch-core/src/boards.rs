@@ -0,0 +1,137 @@
+//! Curated database of known RISC-V boards
+//!
+//! Entries live in `data/boards.toml`, embedded at build time so the catalog
+//! works offline and can be extended by editing a single file.
+
+use crate::hardware::{get_compatible, DT_MODEL};
+use crate::provenance::read_file;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+const BOARDS_TOML: &str = include_str!("../data/boards.toml");
+
+/// Format version of `data/boards.toml` understood by this build
+const FORMAT_VERSION: u32 = 1;
+
+/// Known specifications of a RISC-V board
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Board {
+    pub name: String,
+    /// Case-insensitive substring of the device-tree model
+    pub model: String,
+    /// Device-tree `compatible` strings identifying the board
+    #[serde(default)]
+    pub compatible: Vec<String>,
+    pub soc: String,
+    /// Memory options the board was sold with
+    pub ram: Vec<String>,
+    /// Year the board became available
+    pub year: u16,
+    pub form_factor: String,
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    version: u32,
+    #[serde(default)]
+    board: Vec<Board>,
+}
+
+fn parse(text: &str) -> Result<Vec<Board>, String> {
+    let file: File = toml::from_str(text).map_err(|e| e.to_string())?;
+    if file.version != FORMAT_VERSION {
+        return Err(format!(
+            "unsupported board database version {} (expected {FORMAT_VERSION})",
+            file.version
+        ));
+    }
+    Ok(file.board)
+}
+
+/// All boards in the embedded catalog
+#[must_use]
+pub fn boards() -> &'static [Board] {
+    static BOARDS: OnceLock<Vec<Board>> = OnceLock::new();
+    BOARDS.get_or_init(|| parse(BOARDS_TOML).unwrap_or_default())
+}
+
+/// Look up a board by device-tree model and `compatible` strings
+///
+/// An exact `compatible` match wins; otherwise the entry with the longest
+/// `model` contained (case-insensitively) in `model` is returned.
+#[must_use]
+pub fn find_board(model: &str, compatible: &[&str]) -> Option<&'static Board> {
+    find_in(boards(), model, compatible)
+}
+
+fn find_in<'a>(boards: &'a [Board], model: &str, compatible: &[&str]) -> Option<&'a Board> {
+    if let Some(board) = boards.iter().find(|b| {
+        b.compatible
+            .iter()
+            .any(|c| compatible.contains(&c.as_str()))
+    }) {
+        return Some(board);
+    }
+
+    let model = model.to_lowercase();
+    if model.is_empty() {
+        return None;
+    }
+    boards
+        .iter()
+        .filter(|b| model.contains(&b.model.to_lowercase()))
+        .max_by_key(|b| b.model.len())
+}
+
+/// Known specifications of the running board, if it is in the catalog
+#[must_use]
+pub fn get_board_details() -> Option<&'static Board> {
+    let model = read_file(DT_MODEL).unwrap_or_default();
+    let compatible = get_compatible();
+    let compatible: Vec<&str> = compatible.iter().map(String::as_str).collect();
+    find_board(model.trim_matches('\0').trim(), &compatible)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_catalog_parses() {
+        let boards = parse(BOARDS_TOML).unwrap();
+        assert!(!boards.is_empty());
+        for board in &boards {
+            assert!(!board.name.is_empty());
+            assert!(!board.model.is_empty());
+            assert!(!board.ram.is_empty(), "{} has no RAM options", board.name);
+            assert!(board.url.starts_with("http"), "{} has no link", board.name);
+        }
+        assert_eq!(boards.len(), self::boards().len());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_version() {
+        assert!(parse("version = 2").unwrap_err().contains("version 2"));
+        assert!(parse("version = 1\n[[board]]\nname = \"x\"").is_err());
+    }
+
+    #[test]
+    fn test_find_by_compatible() {
+        let board = find_board("", &["milkv,jupiter", "spacemit,k1-x"]).unwrap();
+        assert_eq!(board.name, "Milk-V Jupiter");
+        assert!(find_board("", &["vendor,unknown"]).is_none());
+    }
+
+    #[test]
+    fn test_find_by_model_prefers_longest_match() {
+        let board = find_board("StarFive VisionFive 2 v1.3B", &[]).unwrap();
+        assert_eq!(board.soc, "StarFive JH7110");
+        assert_eq!(find_board("milk-v duos", &[]).unwrap().name, "Milk-V Duo S");
+        assert_eq!(find_board("Milk-V Duo", &[]).unwrap().name, "Milk-V Duo");
+        assert!(find_board("QEMU riscv64 virt", &[]).is_none());
+        assert!(find_board("", &[]).is_none());
+    }
+}
@@ -0,0 +1,124 @@
+//! Process-wide cache of /proc/cpuinfo and the parses of its ISA string
+//!
+//! Everything is computed on first use and kept until `refresh()`, so the
+//! convenience getters can be called freely; long-running programs call
+//! `refresh()` to pick up hot-plugged harts or a changed kernel view.
+
+use crate::cpuinfo::{parse_cpuinfo, CpuInfo, CPUINFO};
+use crate::parsing::{
+    parse_extensions_compact, parse_extensions_explained, parse_s_extensions,
+    parse_s_extensions_explained, parse_s_extensions_with_category, parse_z_extensions,
+    parse_z_extensions_explained, parse_z_extensions_with_category, ExtensionInfo,
+};
+use crate::provenance::read_file;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+static CACHE: RwLock<Option<Arc<Cached>>> = RwLock::new(None);
+
+/// One reading of /proc/cpuinfo with its ISA parses, each made on first use
+#[derive(Default)]
+pub(crate) struct Cached {
+    pub(crate) cpuinfo: Arc<CpuInfo>,
+    /// ISA string with the extensions all harts share, `unknown` without one
+    pub(crate) isa: String,
+    extensions_compact: OnceLock<String>,
+    extensions_explained: OnceLock<Vec<(String, String)>>,
+    z_extensions: OnceLock<String>,
+    z_extensions_explained: OnceLock<Vec<(String, String)>>,
+    z_extensions_with_category: OnceLock<Vec<ExtensionInfo>>,
+    s_extensions: OnceLock<String>,
+    s_extensions_explained: OnceLock<Vec<(String, String)>>,
+    s_extensions_with_category: OnceLock<Vec<ExtensionInfo>>,
+}
+
+impl Cached {
+    fn read() -> Self {
+        let cpuinfo = parse_cpuinfo(&read_file(CPUINFO).unwrap_or_default());
+        Self {
+            isa: cpuinfo
+                .common_isa()
+                .unwrap_or_else(|| "unknown".to_string()),
+            cpuinfo: Arc::new(cpuinfo),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn extensions_compact(&self) -> &String {
+        self.extensions_compact
+            .get_or_init(|| parse_extensions_compact(&self.isa))
+    }
+
+    pub(crate) fn extensions_explained(&self) -> &Vec<(String, String)> {
+        self.extensions_explained
+            .get_or_init(|| parse_extensions_explained(&self.isa))
+    }
+
+    pub(crate) fn z_extensions(&self) -> &String {
+        self.z_extensions
+            .get_or_init(|| parse_z_extensions(&self.isa))
+    }
+
+    pub(crate) fn z_extensions_explained(&self) -> &Vec<(String, String)> {
+        self.z_extensions_explained
+            .get_or_init(|| parse_z_extensions_explained(&self.isa))
+    }
+
+    pub(crate) fn z_extensions_with_category(&self) -> &Vec<ExtensionInfo> {
+        self.z_extensions_with_category
+            .get_or_init(|| parse_z_extensions_with_category(&self.isa))
+    }
+
+    pub(crate) fn s_extensions(&self) -> &String {
+        self.s_extensions
+            .get_or_init(|| parse_s_extensions(&self.isa))
+    }
+
+    pub(crate) fn s_extensions_explained(&self) -> &Vec<(String, String)> {
+        self.s_extensions_explained
+            .get_or_init(|| parse_s_extensions_explained(&self.isa))
+    }
+
+    pub(crate) fn s_extensions_with_category(&self) -> &Vec<ExtensionInfo> {
+        self.s_extensions_with_category
+            .get_or_init(|| parse_s_extensions_with_category(&self.isa))
+    }
+}
+
+/// The current cache entry, reading /proc/cpuinfo if there is none
+pub(crate) fn cached() -> Arc<Cached> {
+    if let Some(cached) = CACHE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+    {
+        return Arc::clone(cached);
+    }
+    let mut slot = CACHE.write().unwrap_or_else(PoisonError::into_inner);
+    Arc::clone(slot.get_or_insert_with(|| Arc::new(Cached::read())))
+}
+
+/// Forget the cached /proc/cpuinfo and ISA parses; the next getter call
+/// reads them again. Values already returned are not affected.
+pub fn refresh() {
+    *CACHE.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_until_refresh() {
+        let first = cached();
+        assert!(Arc::ptr_eq(&first, &cached()));
+        assert!(std::ptr::eq(
+            first.extensions_compact(),
+            first.extensions_compact()
+        ));
+
+        refresh();
+        let second = cached();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(first.isa, second.isa);
+    }
+}
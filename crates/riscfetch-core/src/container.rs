@@ -0,0 +1,118 @@
+//! Container runtime detection
+//!
+//! Inside Docker, Podman, LXC or systemd-nspawn the device tree and parts
+//! of sysfs are often not mounted, so board, cache and vector details come
+//! up empty. Runtimes leave markers: `/.dockerenv`, `/run/.containerenv`,
+//! the `container` value systemd records in `/run/systemd/container`, and
+//! the cgroup path of PID 1.
+
+use crate::provenance::{read_file, traced, Source};
+use std::path::Path;
+
+const DOCKERENV: &str = "/.dockerenv";
+const CONTAINERENV: &str = "/run/.containerenv";
+const SYSTEMD_CONTAINER: &str = "/run/systemd/container";
+const INIT_CGROUP: &str = "/proc/1/cgroup";
+
+/// Container runtime riscfetch runs in (`docker`, `podman`, `lxc`,
+/// `systemd-nspawn`, `kubernetes`, `containerd`), None on the host
+#[must_use]
+pub fn get_container() -> Option<&'static str> {
+    traced("container", container_with_source())
+}
+
+pub(crate) fn container_with_source() -> (Option<&'static str>, Source) {
+    if Path::new(DOCKERENV).exists() {
+        return (Some("docker"), Source::File(DOCKERENV.to_string()));
+    }
+    if Path::new(CONTAINERENV).exists() {
+        return (Some("podman"), Source::File(CONTAINERENV.to_string()));
+    }
+    if let Ok(text) = read_file(SYSTEMD_CONTAINER) {
+        if let Some(runtime) = runtime_from_name(&text) {
+            return (Some(runtime), Source::File(SYSTEMD_CONTAINER.to_string()));
+        }
+    }
+    let cgroup = read_file(INIT_CGROUP).unwrap_or_default();
+    let source = Source::File(INIT_CGROUP.to_string());
+    match runtime_from_cgroup(&cgroup) {
+        Some(runtime) => (Some(runtime), source),
+        // Running on the host is an answer, not a missing value
+        None => (
+            None,
+            Source::File(format!(
+                "{DOCKERENV}, {CONTAINERENV}, {SYSTEMD_CONTAINER}, {INIT_CGROUP}"
+            )),
+        ),
+    }
+}
+
+/// Runtime named by the `container` environment value systemd records
+fn runtime_from_name(name: &str) -> Option<&'static str> {
+    let name = name.trim();
+    Some(match name {
+        "" => return None,
+        "docker" => "docker",
+        "podman" => "podman",
+        "systemd-nspawn" => "systemd-nspawn",
+        _ if name.starts_with("lxc") => "lxc",
+        _ => "container",
+    })
+}
+
+/// Runtime from the cgroup path of PID 1; a bare `0::/` (cgroup namespace)
+/// gives nothing away
+fn runtime_from_cgroup(cgroup: &str) -> Option<&'static str> {
+    const PATTERNS: [(&str, &str); 6] = [
+        ("kubepods", "kubernetes"),
+        ("libpod", "podman"),
+        ("docker", "docker"),
+        ("lxc", "lxc"),
+        ("/machine.slice/machine-", "systemd-nspawn"),
+        ("containerd", "containerd"),
+    ];
+    PATTERNS
+        .iter()
+        .find(|(pattern, _)| cgroup.contains(pattern))
+        .map(|(_, runtime)| *runtime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_from_name() {
+        assert_eq!(
+            runtime_from_name("systemd-nspawn\n"),
+            Some("systemd-nspawn")
+        );
+        assert_eq!(runtime_from_name("lxc-libvirt\n"), Some("lxc"));
+        assert_eq!(runtime_from_name("oci\n"), Some("container"));
+        assert_eq!(runtime_from_name("\n"), None);
+    }
+
+    #[test]
+    fn test_runtime_from_cgroup() {
+        assert_eq!(
+            runtime_from_cgroup("0::/system.slice/docker-3f2a.scope\n"),
+            Some("docker")
+        );
+        assert_eq!(
+            runtime_from_cgroup("12:pids:/kubepods/besteffort/pod1/4b1c\n"),
+            Some("kubernetes")
+        );
+        assert_eq!(
+            runtime_from_cgroup(
+                "0::/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-9e.scope\n"
+            ),
+            Some("podman")
+        );
+        assert_eq!(
+            runtime_from_cgroup("0::/machine.slice/machine-build.scope/payload\n"),
+            Some("systemd-nspawn")
+        );
+        assert_eq!(runtime_from_cgroup("0::/\n"), None);
+        assert_eq!(runtime_from_cgroup("0::/init.scope\n"), None);
+    }
+}
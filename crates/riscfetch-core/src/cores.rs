@@ -0,0 +1,161 @@
+//! Known RISC-V CPU cores and the names compilers know them by
+//!
+//! The core is recognized from the `compatible` strings of the first CPU
+//! node in the device tree (`sifive,u74-mc`), or from the `mvendorid` /
+//! `marchid` CSRs when the device tree only says `riscv`. To add a core,
+//! add an entry to `CORES`.
+
+use crate::hardware::get_hardware_ids;
+use crate::provenance::{read_file, traced, Source};
+use crate::types::HardwareIds;
+use serde::Serialize;
+
+const DT_CPU0_COMPATIBLE: &str = "/proc/device-tree/cpus/cpu@0/compatible";
+
+/// A CPU core and its compiler CPU names
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct Core {
+    /// Display name, e.g. `SiFive U74`
+    pub name: &'static str,
+    /// Device-tree `compatible` strings of the core's CPU nodes
+    pub compatible: &'static [&'static str],
+    /// `(mvendorid, marchid)` as printed in /proc/cpuinfo, when unique to the core
+    pub ids: Option<(&'static str, &'static str)>,
+    /// `-C target-cpu` / `-mcpu` name in LLVM (rustc, clang)
+    pub llvm_cpu: Option<&'static str>,
+    /// `-mcpu` / `-mtune` name in GCC
+    pub gcc_cpu: Option<&'static str>,
+}
+
+/// Cores riscfetch recognizes
+pub const CORES: &[Core] = &[
+    Core {
+        name: "SiFive U54",
+        compatible: &["sifive,u54-mc", "sifive,u54"],
+        ids: None,
+        llvm_cpu: Some("sifive-u54"),
+        gcc_cpu: Some("sifive-u54"),
+    },
+    Core {
+        name: "SiFive U74",
+        compatible: &["sifive,u74-mc", "sifive,u74"],
+        ids: Some(("0x489", "0x8000000000000007")),
+        llvm_cpu: Some("sifive-u74"),
+        gcc_cpu: Some("sifive-u74"),
+    },
+    Core {
+        name: "SiFive P550",
+        compatible: &["sifive,p550"],
+        ids: None,
+        llvm_cpu: Some("sifive-p550"),
+        gcc_cpu: None,
+    },
+    Core {
+        name: "SpacemiT X60",
+        compatible: &["spacemit,x60"],
+        ids: Some(("0x710", "0x8000000058000001")),
+        llvm_cpu: Some("spacemit-x60"),
+        gcc_cpu: None,
+    },
+    Core {
+        name: "T-Head C906",
+        compatible: &["thead,c906"],
+        ids: None,
+        llvm_cpu: None,
+        gcc_cpu: Some("thead-c906"),
+    },
+    Core {
+        name: "T-Head C908",
+        compatible: &["thead,c908"],
+        ids: None,
+        llvm_cpu: None,
+        gcc_cpu: None,
+    },
+    Core {
+        name: "T-Head C910",
+        compatible: &["thead,c910"],
+        ids: None,
+        llvm_cpu: None,
+        gcc_cpu: None,
+    },
+    Core {
+        name: "T-Head C920",
+        compatible: &["thead,c920"],
+        ids: None,
+        llvm_cpu: None,
+        gcc_cpu: None,
+    },
+];
+
+/// The core of hart 0, if riscfetch knows it
+#[must_use]
+pub fn get_core() -> Option<&'static Core> {
+    traced("core", core_with_source())
+}
+
+pub(crate) fn core_with_source() -> (Option<&'static Core>, Source) {
+    let compatible: Vec<String> = read_file(DT_CPU0_COMPATIBLE)
+        .unwrap_or_default()
+        .split('\0')
+        .filter(|c| !c.is_empty())
+        .map(str::to_string)
+        .collect();
+    let compatible: Vec<&str> = compatible.iter().map(String::as_str).collect();
+    let core = identify_core(&compatible, &get_hardware_ids());
+    let source = match core {
+        Some(_) => Source::File(format!("{DT_CPU0_COMPATIBLE}, /proc/cpuinfo")),
+        None => Source::Missing {
+            tried: format!("{DT_CPU0_COMPATIBLE} and mvendorid/marchid match no known core"),
+        },
+    };
+    (core, source)
+}
+
+/// Core matching CPU-node `compatible` strings, else the CSR IDs
+#[must_use]
+pub fn identify_core(compatible: &[&str], hw: &HardwareIds) -> Option<&'static Core> {
+    CORES
+        .iter()
+        .find(|core| core.compatible.iter().any(|c| compatible.contains(c)))
+        .or_else(|| {
+            CORES.iter().find(|core| {
+                core.ids.is_some_and(|(vendor, arch)| {
+                    hw.mvendorid.eq_ignore_ascii_case(vendor)
+                        && hw.marchid.eq_ignore_ascii_case(arch)
+                })
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(mvendorid: &str, marchid: &str) -> HardwareIds {
+        HardwareIds {
+            mvendorid: mvendorid.to_string(),
+            marchid: marchid.to_string(),
+            mimpid: "0x0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_identify_core_by_compatible() {
+        let core = identify_core(&["sifive,u74-mc", "riscv"], &HardwareIds::default()).unwrap();
+        assert_eq!(core.name, "SiFive U74");
+        assert_eq!(core.llvm_cpu, Some("sifive-u74"));
+        assert_eq!(
+            identify_core(&["thead,c910", "riscv"], &ids("0x5b7", "0x0")).map(|c| c.name),
+            Some("T-Head C910")
+        );
+        assert!(identify_core(&["riscv"], &HardwareIds::default()).is_none());
+    }
+
+    #[test]
+    fn test_identify_core_by_ids() {
+        let core = identify_core(&["riscv"], &ids("0x710", "0x8000000058000001")).unwrap();
+        assert_eq!(core.llvm_cpu, Some("spacemit-x60"));
+        // T-Head reports marchid 0 for every core
+        assert!(identify_core(&[], &ids("0x5b7", "0x0")).is_none());
+    }
+}
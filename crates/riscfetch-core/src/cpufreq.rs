@@ -0,0 +1,164 @@
+//! Per-hart frequency scaling and CPU time
+//!
+//! cpufreq exposes each hart's current frequency, limits and governor under
+//! `/sys/devices/system/cpu/cpuN/cpufreq`. `/proc/stat` has the time each
+//! hart spent busy and idle since boot; utilization over an interval is the
+//! busy share of the time between two readings.
+
+use crate::provenance::{read_file, traced, Source};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+pub(crate) const CPU_DIR: &str = "/sys/devices/system/cpu";
+
+/// Frequency scaling state of one hart
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct HartFreq {
+    pub hart: usize,
+    pub current_khz: Option<u64>,
+    pub min_khz: Option<u64>,
+    pub max_khz: Option<u64>,
+    /// cpufreq governor, e.g. `schedutil`
+    pub governor: Option<String>,
+}
+
+/// Frequency of every hart with a cpufreq driver, by hart number
+#[must_use]
+pub fn get_cpufreq() -> Vec<HartFreq> {
+    traced("cpufreq", cpufreq_with_source())
+}
+
+pub(crate) fn cpufreq_with_source() -> (Vec<HartFreq>, Source) {
+    let harts: Vec<HartFreq> = hart_numbers()
+        .into_iter()
+        .filter_map(|hart| {
+            let dir = format!("{CPU_DIR}/cpu{hart}/cpufreq");
+            if !Path::new(&dir).is_dir() {
+                return None;
+            }
+            let read = |file: &str| read_file(format!("{dir}/{file}")).ok();
+            let khz = |file: &str| read(file)?.trim().parse().ok();
+            Some(HartFreq {
+                hart,
+                current_khz: khz("scaling_cur_freq").or_else(|| khz("cpuinfo_cur_freq")),
+                min_khz: khz("scaling_min_freq"),
+                max_khz: khz("scaling_max_freq"),
+                governor: read("scaling_governor").map(|g| g.trim().to_string()),
+            })
+        })
+        .collect();
+    let source = if harts.is_empty() {
+        Source::Missing {
+            tried: format!("{CPU_DIR}/cpu*/cpufreq (no cpufreq driver)"),
+        }
+    } else {
+        Source::File(format!("{CPU_DIR}/cpu*/cpufreq"))
+    };
+    (harts, source)
+}
+
+/// Numbers of the `cpuN` directories, ascending
+pub(crate) fn hart_numbers() -> Vec<usize> {
+    let mut harts: Vec<usize> = fs::read_dir(CPU_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("cpu")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    harts.sort_unstable();
+    harts
+}
+
+/// Time one hart spent since boot, in clock ticks
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    /// Everything but idle and I/O wait
+    pub busy: u64,
+    pub total: u64,
+}
+
+/// Per-hart times from /proc/stat (empty where procfs is unavailable)
+#[must_use]
+pub fn get_cpu_times() -> Vec<(usize, CpuTimes)> {
+    read_file("/proc/stat")
+        .map(|text| parse_proc_stat(&text))
+        .unwrap_or_default()
+}
+
+/// The `cpuN` lines of /proc/stat: user, nice, system, idle, iowait, irq,
+/// softirq and steal ticks (guest time is already part of user)
+#[must_use]
+pub fn parse_proc_stat(text: &str) -> Vec<(usize, CpuTimes)> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let hart = fields.next()?.strip_prefix("cpu")?.parse().ok()?;
+            let ticks: Vec<u64> = fields.take(8).filter_map(|f| f.parse().ok()).collect();
+            if ticks.len() < 4 {
+                return None;
+            }
+            let total: u64 = ticks.iter().sum();
+            let idle = ticks[3] + ticks.get(4).copied().unwrap_or(0);
+            Some((
+                hart,
+                CpuTimes {
+                    busy: total - idle,
+                    total,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Busy percentage between two readings; None when no time passed
+#[must_use]
+pub fn cpu_utilization(before: CpuTimes, after: CpuTimes) -> Option<f64> {
+    let total = after.total.checked_sub(before.total)?;
+    let busy = after.busy.checked_sub(before.busy)?;
+    #[allow(clippy::cast_precision_loss)]
+    (total > 0).then(|| busy as f64 * 100.0 / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let text = "\
+cpu  400 0 100 1500 0 0 0 0 0 0
+cpu0 100 0 50 800 50 0 0 0 0 0
+cpu1 300 0 50 700 0 0 0 0 0 0
+intr 12345
+";
+        let times = |busy, total| CpuTimes { busy, total };
+        assert_eq!(
+            parse_proc_stat(text),
+            [(0, times(150, 1000)), (1, times(350, 1050))]
+        );
+    }
+
+    #[test]
+    fn test_cpu_utilization() {
+        let before = CpuTimes {
+            busy: 100,
+            total: 1000,
+        };
+        let after = CpuTimes {
+            busy: 150,
+            total: 1200,
+        };
+        assert_eq!(cpu_utilization(before, after), Some(25.0));
+        assert_eq!(cpu_utilization(after, after), None);
+        // A counter that went backwards (hart hot-plugged) is no reading
+        assert_eq!(cpu_utilization(after, before), None);
+    }
+}
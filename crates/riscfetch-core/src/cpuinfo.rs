@@ -0,0 +1,181 @@
+//! Parsed /proc/cpuinfo
+//!
+//! The file is read and scanned once (until `refresh()`); the ISA string,
+//! hardware IDs, hart count and architecture check all come from the same
+//! parse.
+
+use crate::cache::cached;
+use crate::parsing::{isa_difference, isa_intersection, isa_union};
+use crate::types::{HardwareIds, IsaDivergence};
+use std::sync::Arc;
+
+pub(crate) const CPUINFO: &str = "/proc/cpuinfo";
+
+/// The RISC-V relevant parts of /proc/cpuinfo
+#[derive(Clone, Debug, Default)]
+pub struct CpuInfo {
+    /// `isa` line of each hart, in `processor` order
+    pub hart_isas: Vec<String>,
+    /// Last non-zero `mvendorid` / `marchid` / `mimpid` values
+    pub hardware_ids: HardwareIds,
+    /// Number of `processor` lines
+    pub hart_count: usize,
+    /// Whether the file mentions `riscv` or `RISC-V` anywhere
+    pub mentions_riscv: bool,
+}
+
+impl CpuInfo {
+    /// ISA string of the first hart
+    #[must_use]
+    pub fn isa(&self) -> Option<&str> {
+        self.hart_isas.first().map(String::as_str)
+    }
+
+    /// ISA string with the extensions every hart has; the `isa` line itself
+    /// when all harts agree
+    #[must_use]
+    pub fn common_isa(&self) -> Option<String> {
+        isa_intersection(&self.isa_lines())
+    }
+
+    /// Per-hart `isa` lines and the extensions only some harts have, when
+    /// the lines differ
+    #[must_use]
+    pub fn divergence(&self) -> Option<IsaDivergence> {
+        let lines = self.isa_lines();
+        let union = isa_union(&lines)?;
+        let partial_extensions = isa_difference(&union, &isa_intersection(&lines)?);
+        if partial_extensions.is_empty() {
+            return None;
+        }
+        Some(IsaDivergence {
+            hart_isas: self.hart_isas.clone(),
+            union,
+            partial_extensions,
+        })
+    }
+
+    fn isa_lines(&self) -> Vec<&str> {
+        self.hart_isas.iter().map(String::as_str).collect()
+    }
+
+    /// Whether this is a RISC-V machine's cpuinfo
+    #[must_use]
+    pub fn is_riscv(&self) -> bool {
+        self.mentions_riscv || self.isa().is_some_and(|isa| isa.starts_with("rv"))
+    }
+}
+
+/// /proc/cpuinfo of this machine, read on first use (empty where the file
+/// is unreadable)
+#[must_use]
+pub fn get_cpuinfo() -> Arc<CpuInfo> {
+    Arc::clone(&cached().cpuinfo)
+}
+
+/// Parse the contents of /proc/cpuinfo
+#[must_use]
+pub fn parse_cpuinfo(content: &str) -> CpuInfo {
+    let mut info = CpuInfo {
+        mentions_riscv: content.contains("riscv") || content.contains("RISC-V"),
+        ..CpuInfo::default()
+    };
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let ids = &mut info.hardware_ids;
+        let id = match key.trim() {
+            "processor" => {
+                info.hart_count += 1;
+                continue;
+            }
+            "isa" => {
+                info.hart_isas.push(value.to_string());
+                continue;
+            }
+            "mvendorid" => &mut ids.mvendorid,
+            "marchid" => &mut ids.marchid,
+            "mimpid" => &mut ids.mimpid,
+            _ => continue,
+        };
+        // QEMU and some cores leave the CSRs at zero
+        if !value.is_empty() && value != "0x0" {
+            *id = value.to_string();
+        }
+    }
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VISIONFIVE2: &str = "\
+processor\t: 0
+hart\t\t: 1
+isa\t\t: rv64imafdc_zicntr_zicsr_zifencei_zihpm_zba_zbb
+mmu\t\t: sv39
+uarch\t\t: sifive,u74-mc
+mvendorid\t: 0x489
+marchid\t\t: 0x8000000000000007
+mimpid\t\t: 0x4210427
+hart isa\t: rv64imafdc_zicntr_zicsr_zifencei_zihpm_zba_zbb
+
+processor\t: 1
+hart\t\t: 2
+isa\t\t: rv64imafdc_zicntr_zicsr_zifencei_zihpm_zba_zbb
+mmu\t\t: sv39
+uarch\t\t: sifive,u74-mc
+mvendorid\t: 0x489
+marchid\t\t: 0x8000000000000007
+mimpid\t\t: 0x4210427
+hart isa\t: rv64imafdc_zicntr_zicsr_zifencei_zihpm_zba_zbb
+";
+
+    #[test]
+    fn test_parse_cpuinfo() {
+        let info = parse_cpuinfo(VISIONFIVE2);
+        assert_eq!(info.hart_count, 2);
+        assert_eq!(info.hart_isas.len(), 2);
+        assert_eq!(
+            info.isa(),
+            Some("rv64imafdc_zicntr_zicsr_zifencei_zihpm_zba_zbb")
+        );
+        assert_eq!(info.hardware_ids.mvendorid, "0x489");
+        assert_eq!(info.hardware_ids.marchid, "0x8000000000000007");
+        assert_eq!(info.hardware_ids.mimpid, "0x4210427");
+        assert!(info.is_riscv());
+    }
+
+    #[test]
+    fn test_cpuinfo_divergence() {
+        let info = parse_cpuinfo(VISIONFIVE2);
+        assert_eq!(info.divergence(), None);
+        assert_eq!(info.common_isa().as_deref(), info.isa());
+
+        let mixed = parse_cpuinfo(
+            "processor : 0\nisa : rv64imafdcv_zicsr_zfh\nprocessor : 1\nisa : rv64imafdc_zicsr\n",
+        );
+        assert_eq!(mixed.common_isa().as_deref(), Some("rv64imafdc_zicsr"));
+        let divergence = mixed.divergence().unwrap();
+        assert_eq!(divergence.union, "rv64imafdcv_zicsr_zfh");
+        assert_eq!(divergence.partial_extensions, ["v", "zfh"]);
+        assert_eq!(divergence.hart_isas.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_cpuinfo_zero_ids_and_other_arch() {
+        let info = parse_cpuinfo("processor : 0\nisa : rv64gc\nmvendorid : 0x0\nmarchid : 0x0\n");
+        assert!(info.hardware_ids.mvendorid.is_empty());
+        assert!(info.hardware_ids.marchid.is_empty());
+
+        let x86 = parse_cpuinfo("processor\t: 0\nvendor_id\t: GenuineIntel\n");
+        assert_eq!(x86.hart_count, 1);
+        assert_eq!(x86.isa(), None);
+        assert!(!x86.is_riscv());
+        assert_eq!(parse_cpuinfo("").hart_count, 0);
+    }
+}
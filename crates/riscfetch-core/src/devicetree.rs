@@ -0,0 +1,268 @@
+//! Device-tree nodes the detectors read
+//!
+//! `/proc/device-tree` has a directory per node and a file per property
+//! holding its raw value (NUL-terminated strings or big-endian 32-bit
+//! cells). Only what riscfetch uses is collected: the root model and
+//! `compatible`, the timebase, every CPU node (ISA, MMU, caches, vector
+//! length), cache nodes and the SoC `compatible`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+const DT_ROOT: &str = "/proc/device-tree";
+
+const ROOT_PROPERTIES: &[&str] = &["model", "compatible"];
+const CPUS_PROPERTIES: &[&str] = &["timebase-frequency"];
+const CPU_PROPERTIES: &[&str] = &[
+    "compatible",
+    "status",
+    "riscv,isa",
+    "riscv,isa-base",
+    "riscv,isa-extensions",
+    "mmu-type",
+    "riscv,vlenb",
+    "clock-frequency",
+    "timebase-frequency",
+    "riscv,cbom-block-size",
+    "riscv,cboz-block-size",
+    "i-cache-size",
+    "i-cache-sets",
+    "i-cache-block-size",
+    "d-cache-size",
+    "d-cache-sets",
+    "d-cache-block-size",
+];
+const CACHE_PROPERTIES: &[&str] = &[
+    "compatible",
+    "cache-level",
+    "cache-unified",
+    "cache-size",
+    "cache-sets",
+    "cache-block-size",
+];
+const SOC_PROPERTIES: &[&str] = &["compatible"];
+
+/// A device-tree property value
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DtValue {
+    /// Property without a value (`cache-unified;`)
+    Flag,
+    /// One or more strings (`"sifive,u74-mc", "riscv"`)
+    Strings(Vec<String>),
+    /// 32-bit cells (`<0x8000>`)
+    Cells(Vec<u32>),
+    /// Anything else
+    Bytes(Vec<u8>),
+}
+
+impl fmt::Display for DtValue {
+    /// Single cells in decimal (sizes, frequencies), several in hex as in
+    /// a `.dts` file
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag => write!(f, "true"),
+            Self::Strings(strings) => {
+                let quoted: Vec<String> = strings.iter().map(|s| format!("\"{s}\"")).collect();
+                write!(f, "{}", quoted.join(", "))
+            }
+            Self::Cells(cells) if cells.len() == 1 => write!(f, "{}", cells[0]),
+            Self::Cells(cells) => {
+                let hex: Vec<String> = cells.iter().map(|c| format!("{c:#x}")).collect();
+                write!(f, "<{}>", hex.join(" "))
+            }
+            Self::Bytes(bytes) => {
+                let hex: Vec<String> = bytes.iter().map(|b| format!("{b:02x}")).collect();
+                write!(f, "[{}]", hex.join(" "))
+            }
+        }
+    }
+}
+
+/// One node with the properties riscfetch uses, in a fixed order
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DtNode {
+    /// Path below the device-tree root, `/` for the root
+    pub path: String,
+    pub properties: Vec<(String, DtValue)>,
+}
+
+/// Decode a raw property: NUL-terminated printable text is a string list,
+/// a multiple of four bytes a cell list
+#[must_use]
+pub fn parse_dt_value(raw: &[u8]) -> DtValue {
+    if raw.is_empty() {
+        return DtValue::Flag;
+    }
+    if let Some(text) = raw.strip_suffix(&[0]) {
+        let strings: Vec<&[u8]> = text.split(|b| *b == 0).collect();
+        let printable = |s: &&[u8]| !s.is_empty() && s.iter().all(|b| (0x20..0x7f).contains(b));
+        if strings.iter().all(printable) {
+            return DtValue::Strings(
+                strings
+                    .iter()
+                    .map(|s| String::from_utf8_lossy(s).into_owned())
+                    .collect(),
+            );
+        }
+    }
+    if raw.len().is_multiple_of(4) {
+        return DtValue::Cells(
+            raw.chunks_exact(4)
+                .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+                .collect(),
+        );
+    }
+    DtValue::Bytes(raw.to_vec())
+}
+
+/// The nodes of this machine's device tree riscfetch reads; empty without
+/// a device tree
+#[must_use]
+pub fn get_dt_nodes() -> Vec<DtNode> {
+    read_dt_nodes(Path::new(DT_ROOT))
+}
+
+fn read_dt_nodes(root: &Path) -> Vec<DtNode> {
+    if !root.is_dir() {
+        return Vec::new();
+    }
+    let mut nodes = Vec::new();
+    let mut push = |path: &str, names: &[&str]| {
+        if let Some(node) = read_node(root, path, names) {
+            nodes.push(node);
+        }
+    };
+    push("/", ROOT_PROPERTIES);
+    push("/cpus", CPUS_PROPERTIES);
+    for cpu in children(root, "/cpus", |name| name.starts_with("cpu@")) {
+        push(&cpu, CPU_PROPERTIES);
+        for cache in children(root, &cpu, is_cache) {
+            push(&cache, CACHE_PROPERTIES);
+        }
+    }
+    for cache in children(root, "/cpus", is_cache) {
+        push(&cache, CACHE_PROPERTIES);
+    }
+    push("/soc", SOC_PROPERTIES);
+    for cache in children(root, "/soc", is_cache) {
+        push(&cache, CACHE_PROPERTIES);
+    }
+    nodes
+}
+
+/// Cache nodes are named `l2-cache`, `cache-controller@...` and the like
+fn is_cache(name: &str) -> bool {
+    name.contains("cache")
+}
+
+/// Child nodes of `parent` whose name passes `filter`, `cpu@2` before
+/// `cpu@10`
+fn children(root: &Path, parent: &str, filter: fn(&str) -> bool) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(root.join(parent.trim_start_matches('/'))) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| filter(name))
+        .collect();
+    names.sort_by_key(|name| {
+        let (base, unit) = name.split_once('@').unwrap_or((name, ""));
+        (
+            base.to_string(),
+            u64::from_str_radix(unit, 16).unwrap_or(u64::MAX),
+            name.clone(),
+        )
+    });
+    names
+        .into_iter()
+        .map(|name| format!("{}/{name}", parent.trim_end_matches('/')))
+        .collect()
+}
+
+/// `path` with the listed properties it has; None when the node is missing
+fn read_node(root: &Path, path: &str, names: &[&str]) -> Option<DtNode> {
+    let dir = root.join(path.trim_start_matches('/'));
+    if !dir.is_dir() {
+        return None;
+    }
+    let properties = names
+        .iter()
+        .filter_map(|name| {
+            let raw = fs::read(dir.join(name)).ok()?;
+            Some(((*name).to_string(), parse_dt_value(&raw)))
+        })
+        .collect();
+    Some(DtNode {
+        path: path.to_string(),
+        properties,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dt_value() {
+        assert_eq!(parse_dt_value(b""), DtValue::Flag);
+        assert_eq!(
+            parse_dt_value(b"sifive,u74-mc\0riscv\0"),
+            DtValue::Strings(vec!["sifive,u74-mc".to_string(), "riscv".to_string()])
+        );
+        assert_eq!(
+            parse_dt_value(&[0, 0x3d, 0x09, 0]),
+            DtValue::Cells(vec![4_000_000])
+        );
+        assert_eq!(parse_dt_value(&[1, 2, 3]), DtValue::Bytes(vec![1, 2, 3]));
+
+        assert_eq!(parse_dt_value(b"riscv\0").to_string(), "\"riscv\"");
+        assert_eq!(DtValue::Cells(vec![32768]).to_string(), "32768");
+        assert_eq!(DtValue::Cells(vec![0, 0x8000]).to_string(), "<0x0 0x8000>");
+    }
+
+    #[test]
+    fn test_read_dt_nodes() {
+        let root = std::env::temp_dir().join(format!("riscfetch-dt-{}", std::process::id()));
+        let write = |path: &str, value: &[u8]| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, value).unwrap();
+        };
+        write("model", b"StarFive VisionFive 2 v1.3B\0");
+        write("cpus/timebase-frequency", &4_000_000u32.to_be_bytes());
+        write("cpus/cpu@10/riscv,isa", b"rv64imafdc_zba\0");
+        write("cpus/cpu@2/riscv,isa", b"rv64imafdc_zba\0");
+        write("cpus/cpu@2/phandle", &[0, 0, 0, 1]);
+        write("cpus/l2-cache/cache-size", &(2u32 << 20).to_be_bytes());
+        write("cpus/l2-cache/cache-unified", b"");
+
+        let nodes = read_dt_nodes(&root);
+        fs::remove_dir_all(&root).unwrap();
+        let paths: Vec<&str> = nodes.iter().map(|n| n.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/",
+                "/cpus",
+                "/cpus/cpu@2",
+                "/cpus/cpu@10",
+                "/cpus/l2-cache"
+            ]
+        );
+        // Only the listed properties, in the listed order
+        assert_eq!(nodes[2].properties.len(), 1);
+        assert_eq!(
+            nodes[4].properties,
+            [
+                ("cache-unified".to_string(), DtValue::Flag),
+                ("cache-size".to_string(), DtValue::Cells(vec![2 << 20])),
+            ]
+        );
+        assert!(read_dt_nodes(Path::new("/nonexistent")).is_empty());
+    }
+}
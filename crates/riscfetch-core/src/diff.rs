@@ -0,0 +1,250 @@
+//! Differences between two `SystemInfo` snapshots
+//!
+//! Volatile values (`memory_used_bytes`, `uptime_seconds`) are ignored so
+//! that comparing a snapshot with the running system only reports changes
+//! to the hardware, the extension set or the installed software.
+
+use crate::types::{ExtensionEntry, SystemInfo};
+use serde::Serialize;
+use std::fmt::Display;
+
+/// One difference between two snapshots
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    /// A scalar field has a different value
+    Changed {
+        field: &'static str,
+        before: String,
+        after: String,
+    },
+    /// An extension only present in the newer snapshot
+    Added { field: &'static str, name: String },
+    /// An extension only present in the older snapshot
+    Removed { field: &'static str, name: String },
+}
+
+fn optional<T: Display>(value: Option<&T>) -> String {
+    value.map_or_else(|| "none".to_string(), ToString::to_string)
+}
+
+fn extension_changes(
+    field: &'static str,
+    before: &[ExtensionEntry],
+    after: &[ExtensionEntry],
+    changes: &mut Vec<Change>,
+) {
+    let contains = |list: &[ExtensionEntry], name: &str| list.iter().any(|e| e.name == name);
+    for ext in before.iter().filter(|e| !contains(after, &e.name)) {
+        changes.push(Change::Removed {
+            field,
+            name: ext.name.clone(),
+        });
+    }
+    for ext in after.iter().filter(|e| !contains(before, &e.name)) {
+        changes.push(Change::Added {
+            field,
+            name: ext.name.clone(),
+        });
+    }
+}
+
+/// Compare two snapshots, listing scalar changes first, then extensions
+#[must_use]
+pub fn diff_system_info(before: &SystemInfo, after: &SystemInfo) -> Vec<Change> {
+    let scalars: [(&'static str, String, String); 16] = [
+        ("isa", before.isa.clone(), after.isa.clone()),
+        ("board", before.board.clone(), after.board.clone()),
+        ("os", before.os.clone(), after.os.clone()),
+        ("kernel", before.kernel.clone(), after.kernel.clone()),
+        (
+            "hart_count",
+            before.hart_count.to_string(),
+            after.hart_count.to_string(),
+        ),
+        (
+            "memory_total_bytes",
+            before.memory_total_bytes.to_string(),
+            after.memory_total_bytes.to_string(),
+        ),
+        (
+            "hardware_ids.mvendorid",
+            before.hardware_ids.mvendorid.clone(),
+            after.hardware_ids.mvendorid.clone(),
+        ),
+        (
+            "hardware_ids.marchid",
+            before.hardware_ids.marchid.clone(),
+            after.hardware_ids.marchid.clone(),
+        ),
+        (
+            "hardware_ids.mimpid",
+            before.hardware_ids.mimpid.clone(),
+            after.hardware_ids.mimpid.clone(),
+        ),
+        (
+            "vector.enabled",
+            before.vector.enabled.to_string(),
+            after.vector.enabled.to_string(),
+        ),
+        (
+            "vector.vlen",
+            optional(before.vector.vlen.as_ref()),
+            optional(after.vector.vlen.as_ref()),
+        ),
+        (
+            "vector.elen",
+            optional(before.vector.elen.as_ref()),
+            optional(after.vector.elen.as_ref()),
+        ),
+        (
+            "cache.l1d",
+            optional(before.cache.l1d.as_ref()),
+            optional(after.cache.l1d.as_ref()),
+        ),
+        (
+            "cache.l1i",
+            optional(before.cache.l1i.as_ref()),
+            optional(after.cache.l1i.as_ref()),
+        ),
+        (
+            "cache.l2",
+            optional(before.cache.l2.as_ref()),
+            optional(after.cache.l2.as_ref()),
+        ),
+        (
+            "cache.l3",
+            optional(before.cache.l3.as_ref()),
+            optional(after.cache.l3.as_ref()),
+        ),
+    ];
+
+    let mut changes: Vec<Change> = scalars
+        .into_iter()
+        .filter(|(_, b, a)| b != a)
+        .map(|(field, before, after)| Change::Changed {
+            field,
+            before,
+            after,
+        })
+        .collect();
+
+    extension_changes(
+        "extensions",
+        &before.extensions,
+        &after.extensions,
+        &mut changes,
+    );
+    extension_changes(
+        "z_extensions",
+        &before.z_extensions,
+        &after.z_extensions,
+        &mut changes,
+    );
+    extension_changes(
+        "s_extensions",
+        &before.s_extensions,
+        &after.s_extensions,
+        &mut changes,
+    );
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CacheInfo, HardwareIds, VectorInfo};
+
+    fn entry(name: &str) -> ExtensionEntry {
+        ExtensionEntry {
+            name: name.to_string(),
+            description: String::new(),
+        }
+    }
+
+    fn sample() -> SystemInfo {
+        SystemInfo {
+            isa: "rv64imafdc_zicsr_zba".to_string(),
+            extensions: ["I", "M", "A", "F", "D", "C"].map(entry).to_vec(),
+            z_extensions: vec![entry("Zicsr"), entry("Zba")],
+            s_extensions: vec![],
+            vector: VectorInfo::default(),
+            hart_count: 4,
+            hardware_ids: HardwareIds::default(),
+            cache: CacheInfo::default(),
+            board: "StarFive VisionFive 2".to_string(),
+            memory_used_bytes: 1_000,
+            memory_total_bytes: 8_000,
+            kernel: "6.6.20".to_string(),
+            os: "Debian GNU/Linux trixie/sid".to_string(),
+            uptime_seconds: 3_600,
+            load_average: None,
+            memory_breakdown: None,
+            sensors: crate::Sensors::default(),
+            container: None,
+            isa_divergence: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_identical_snapshots() {
+        assert!(diff_system_info(&sample(), &sample()).is_empty());
+    }
+
+    #[test]
+    fn test_volatile_fields_ignored() {
+        let mut after = sample();
+        after.memory_used_bytes = 5_000;
+        after.uptime_seconds = 99_999;
+        assert!(diff_system_info(&sample(), &after).is_empty());
+    }
+
+    #[test]
+    fn test_kernel_upgrade_adds_extensions() {
+        let mut after = sample();
+        after.kernel = "6.12.1".to_string();
+        after.isa = "rv64imafdc_zicsr_zba_zbb".to_string();
+        after.z_extensions.push(entry("Zbb"));
+        after.vector.vlen = Some(128);
+
+        let changes = diff_system_info(&sample(), &after);
+        assert_eq!(
+            changes,
+            vec![
+                Change::Changed {
+                    field: "isa",
+                    before: "rv64imafdc_zicsr_zba".to_string(),
+                    after: "rv64imafdc_zicsr_zba_zbb".to_string(),
+                },
+                Change::Changed {
+                    field: "kernel",
+                    before: "6.6.20".to_string(),
+                    after: "6.12.1".to_string(),
+                },
+                Change::Changed {
+                    field: "vector.vlen",
+                    before: "none".to_string(),
+                    after: "128".to_string(),
+                },
+                Change::Added {
+                    field: "z_extensions",
+                    name: "Zbb".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_removed_extension() {
+        let mut after = sample();
+        after.extensions.retain(|e| e.name != "C");
+        assert_eq!(
+            diff_system_info(&sample(), &after),
+            vec![Change::Removed {
+                field: "extensions",
+                name: "C".to_string(),
+            }]
+        );
+    }
+}
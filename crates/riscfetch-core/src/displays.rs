@@ -0,0 +1,208 @@
+//! Connected displays and their resolutions
+//!
+//! A running X or Wayland session knows the current mode, so `xrandr` or
+//! `wlr-randr` is asked first; otherwise the DRM connectors in sysfs give the
+//! preferred mode of every connected output, which also works on a console.
+
+use crate::provenance::{read_file, run_command, traced, Source};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+
+const DRM_DIR: &str = "/sys/class/drm";
+
+/// One connected display output
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DisplayInfo {
+    /// Connector name, e.g. `HDMI-A-1`
+    pub connector: String,
+    /// Mode as `WIDTHxHEIGHT`, if known
+    pub resolution: Option<String>,
+}
+
+/// Connected displays (empty on headless systems)
+#[must_use]
+pub fn get_display_info() -> Vec<DisplayInfo> {
+    traced("display", display_info_with_source())
+}
+
+pub(crate) fn display_info_with_source() -> (Vec<DisplayInfo>, Source) {
+    if env::var_os("WAYLAND_DISPLAY").is_some() {
+        if let Some(displays) = command_displays("wlr-randr", &[], parse_wlr_randr) {
+            return (displays, Source::Command("wlr-randr"));
+        }
+    }
+    if env::var_os("DISPLAY").is_some() {
+        if let Some(displays) = command_displays("xrandr", &["--current"], parse_xrandr) {
+            return (displays, Source::Command("xrandr --current"));
+        }
+    }
+
+    // No connected display is a valid answer on headless boards, not a
+    // missing value
+    (drm_displays(), Source::File(format!("{DRM_DIR}/*/status")))
+}
+
+/// Run a mode-listing command; None if it fails or lists no display
+fn command_displays(
+    program: &str,
+    args: &[&str],
+    parse: fn(&str) -> Vec<DisplayInfo>,
+) -> Option<Vec<DisplayInfo>> {
+    let output = run_command(program, args).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let displays = parse(&String::from_utf8_lossy(&output.stdout));
+    (!displays.is_empty()).then_some(displays)
+}
+
+fn drm_displays() -> Vec<DisplayInfo> {
+    let Ok(entries) = fs::read_dir(DRM_DIR) else {
+        return Vec::new();
+    };
+    let mut displays: Vec<DisplayInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let status = read_file(path.join("status")).ok()?;
+            let modes = read_file(path.join("modes")).unwrap_or_default();
+            parse_drm_connector(&entry.file_name().to_string_lossy(), &status, &modes)
+        })
+        .collect();
+    displays.sort_by(|a, b| a.connector.cmp(&b.connector));
+    displays
+}
+
+/// Display for a DRM connector directory (`card0-HDMI-A-1`), if connected.
+/// The first line of `modes` is the preferred mode.
+#[must_use]
+fn parse_drm_connector(name: &str, status: &str, modes: &str) -> Option<DisplayInfo> {
+    if status.trim() != "connected" {
+        return None;
+    }
+    let connector = name.split_once('-').map_or(name, |(_, c)| c);
+    Some(DisplayInfo {
+        connector: connector.to_string(),
+        resolution: modes
+            .lines()
+            .next()
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(str::to_string),
+    })
+}
+
+/// Connected outputs from `xrandr --current`
+/// (`HDMI-1 connected primary 1920x1080+0+0 ...`)
+#[must_use]
+fn parse_xrandr(output: &str) -> Vec<DisplayInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let connector = words.next()?;
+            if words.next()? != "connected" {
+                return None;
+            }
+            let resolution = words
+                .find(|w| w.contains('x') && w.contains('+'))
+                .and_then(|geometry| geometry.split('+').next())
+                .map(str::to_string);
+            Some(DisplayInfo {
+                connector: connector.to_string(),
+                resolution,
+            })
+        })
+        .collect()
+}
+
+/// Enabled outputs from `wlr-randr`, with the mode marked `current`
+#[must_use]
+fn parse_wlr_randr(output: &str) -> Vec<DisplayInfo> {
+    let mut displays = Vec::new();
+    let mut current: Option<(DisplayInfo, bool)> = None;
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            displays.extend(current.take().filter(|(_, on)| *on).map(|(d, _)| d));
+            if let Some(connector) = line.split_whitespace().next() {
+                let display = DisplayInfo {
+                    connector: connector.to_string(),
+                    resolution: None,
+                };
+                current = Some((display, true));
+            }
+        } else if let Some((display, enabled)) = current.as_mut() {
+            let line = line.trim();
+            if line == "Enabled: no" {
+                *enabled = false;
+            } else if line.contains("current") && line.contains(" px") {
+                display.resolution = line.split_whitespace().next().map(str::to_string);
+            }
+        }
+    }
+    displays.extend(current.filter(|(_, on)| *on).map(|(d, _)| d));
+    displays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(connector: &str, resolution: Option<&str>) -> DisplayInfo {
+        DisplayInfo {
+            connector: connector.to_string(),
+            resolution: resolution.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_drm_connector() {
+        assert_eq!(
+            parse_drm_connector("card0-HDMI-A-1", "connected\n", "1920x1080\n1280x720\n"),
+            Some(display("HDMI-A-1", Some("1920x1080")))
+        );
+        assert_eq!(
+            parse_drm_connector("card1-DSI-1", "connected\n", ""),
+            Some(display("DSI-1", None))
+        );
+        assert_eq!(
+            parse_drm_connector("card0-HDMI-A-2", "disconnected\n", ""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_xrandr() {
+        let output = "\
+Screen 0: minimum 320 x 200, current 1920 x 1080, maximum 16384 x 16384
+HDMI-1 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm
+   1920x1080     60.00*+
+DP-1 disconnected (normal left inverted right x axis y axis)
+DSI-1 connected (normal left inverted right x axis y axis)
+";
+        assert_eq!(
+            parse_xrandr(output),
+            vec![display("HDMI-1", Some("1920x1080")), display("DSI-1", None)]
+        );
+    }
+
+    #[test]
+    fn test_parse_wlr_randr() {
+        let output = "\
+HDMI-A-1 \"Dell Inc. DELL U2415 (HDMI-A-1)\"
+  Enabled: yes
+  Modes:
+    1920x1200 px, 59.950001 Hz (preferred, current)
+    1920x1080 px, 60.000000 Hz
+DP-1 \"Unknown (DP-1)\"
+  Enabled: no
+  Modes:
+    2560x1440 px, 59.951000 Hz (preferred)
+";
+        assert_eq!(
+            parse_wlr_randr(output),
+            vec![display("HDMI-A-1", Some("1920x1200"))]
+        );
+    }
+}
@@ -228,3 +228,158 @@ pub const S_CATEGORY_NAMES: &[(&str, &str)] = &[
     ("debug", "Debug"),
     ("user", "User"),
 ];
+
+/// RVA application profiles (64-bit user mode)
+/// Format: (name, aliases, required extensions)
+///
+/// Only extensions Linux can report in the ISA string are listed. The
+/// memory-system requirements (Ziccif, Ziccrse, Ziccamoa, Zicclsm, Za64rs,
+/// Za128rs, Zic64b) are not observable from software and are left out.
+pub const PROFILES: &[(&str, &[&str], &[&str])] = &[
+    (
+        "RVA20U64",
+        &["rva20"],
+        &["i", "m", "a", "f", "d", "c", "zicsr", "zicntr", "zihpm"],
+    ),
+    (
+        "RVA22U64",
+        &["rva22"],
+        &[
+            "i",
+            "m",
+            "a",
+            "f",
+            "d",
+            "c",
+            "zicsr",
+            "zicntr",
+            "zihpm",
+            "zihintpause",
+            "zba",
+            "zbb",
+            "zbs",
+            "zicbom",
+            "zicbop",
+            "zicboz",
+            "zfhmin",
+            "zkt",
+        ],
+    ),
+    (
+        "RVA23U64",
+        &["rva23"],
+        &[
+            "i",
+            "m",
+            "a",
+            "f",
+            "d",
+            "c",
+            "zicsr",
+            "zicntr",
+            "zihpm",
+            "zihintpause",
+            "zba",
+            "zbb",
+            "zbs",
+            "zicbom",
+            "zicbop",
+            "zicboz",
+            "zfhmin",
+            "zkt",
+            "v",
+            "zvfhmin",
+            "zvbb",
+            "zvkt",
+            "zihintntl",
+            "zicond",
+            "zimop",
+            "zcmop",
+            "zcb",
+            "zfa",
+            "zawrs",
+            "supm",
+        ],
+    ),
+];
+
+/// Longer documentation for the extensions newcomers ask about most
+/// Format: (lowercase name, paragraphs separated by blank lines)
+///
+/// Shown by `riscfetch explain --long`; the one-line descriptions above stay
+/// the source for every other view.
+pub const EXTENSION_DOCS: &[(&str, &str)] = &[
+    (
+        "v",
+        "The vector extension (RVV 1.0) adds 32 vector registers whose width, VLEN, is \
+         chosen by the implementation (128 bits and up) instead of being fixed by the ISA. \
+         A loop asks for a vector length with vsetvli and the hardware answers how many \
+         elements it will process per instruction, so the same binary uses narrow and wide \
+         cores fully without recompiling (\"vector-length agnostic\" code).\n\n\
+         Elements are 8 to 64 bits wide (up to ELEN), registers can be grouped (LMUL) to \
+         work on 2, 4 or 8 registers at once, and masks predicate individual elements. \
+         Loads and stores cover unit-stride, strided and indexed (gather/scatter) access, \
+         and fault-only-first loads make loops such as strlen safe to vectorize.\n\n\
+         Linux lets user space use V from kernel 6.5 on; `riscfetch doctor` says when the \
+         hardware has V but the kernel does not expose it. Early T-Head cores (C906, C910) \
+         implement the pre-ratification draft 0.7.1, reported as XTheadVector, which is \
+         not binary-compatible with RVV 1.0. V is mandatory in RVA23U64.",
+    ),
+    (
+        "zba",
+        "Zba (address generation) adds sh1add, sh2add and sh3add, which shift one register \
+         left by 1, 2 or 3 bits and add another: exactly base + index * 2, 4 or 8, the \
+         address of an array element. The .uw forms (add.uw, sh1add.uw, ..., slli.uw) \
+         zero-extend a 32-bit index first, which is what indexing with an unsigned int \
+         needs on RV64.\n\n\
+         Without Zba the same address takes two or three instructions (slli and add, plus \
+         a zero extension). Compilers use it automatically when it is in -march; it is \
+         part of B and mandatory in RVA22U64 and RVA23U64.",
+    ),
+    (
+        "zbb",
+        "Zbb (basic bit manipulation) covers the bit operations most code needs: count \
+         leading and trailing zeros (clz, ctz), population count (cpop), signed and \
+         unsigned min/max, sign and zero extension of bytes and halfwords (sext.b, sext.h, \
+         zext.h), rotates (rol, ror, rori), byte reversal (rev8), OR-combine (orc.b) and \
+         logic with an inverted operand (andn, orn, xnor).\n\n\
+         Each replaces a multi-instruction sequence or a library call: __builtin_clz and \
+         popcount, byte swapping for endianness conversion, and string routines that use \
+         orc.b to find a zero byte in a whole register at once. Zbb is part of B and \
+         mandatory in RVA22U64 and RVA23U64.",
+    ),
+    (
+        "zicond",
+        "Zicond adds two instructions, czero.eqz and czero.nez, which write either zero or \
+         a source register depending on whether a condition register is zero. Combined \
+         with an or they make a branch-free select (x = c ? a : b) in three \
+         instructions.\n\n\
+         Branch-free code avoids misprediction penalties on data-dependent conditions and \
+         keeps constant-time code free of secret-dependent branches. Compilers generate it \
+         for conditional expressions when it is in -march. It is mandatory in RVA23U64.",
+    ),
+    (
+        "sstc",
+        "Sstc (supervisor timer compare) gives supervisor mode its own timer compare CSR, \
+         stimecmp. Without it the kernel asks the SBI firmware (OpenSBI) to program the \
+         machine-mode timer with an ecall, and the firmware forwards the interrupt back to \
+         the kernel, so every timer event costs extra trips through machine mode.\n\n\
+         With Sstc the kernel programs timer interrupts directly, which lowers the cost of \
+         scheduler ticks, high-resolution timers and short sleeps. Together with the \
+         hypervisor extension it also gives guests their own timer (vstimecmp), avoiding \
+         exits to the hypervisor. Linux uses it automatically when the ISA string lists \
+         it. It is mandatory in RVA22S64 and RVA23S64.",
+    ),
+    (
+        "h",
+        "The hypervisor extension adds virtualized supervisor and user modes (VS and VU) \
+         so guest kernels run directly on the hardware, with two-stage address translation \
+         (guest virtual to guest physical to host physical) done by the MMU. Virtual \
+         supervisor CSRs (vsstatus, vsatp, ...), trap delegation to the guest and \
+         hypervisor load/store instructions complete it.\n\n\
+         With H, KVM (in Linux since 5.16) runs guests at near-native speed; without it \
+         QEMU has to emulate every guest instruction in software (TCG), which is many \
+         times slower. /dev/kvm exists when the running kernel can use it. H is mandatory \
+         in RVA23S64.",
+    ),
+];
@@ -0,0 +1,107 @@
+//! Boot firmware: how the kernel was booted (UEFI, ACPI, device tree) and
+//! the bootloader / BIOS versions it passed on
+
+use crate::provenance::{read_file, traced, Source};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const EFI_DIR: &str = "/sys/firmware/efi";
+const ACPI_TABLES: &str = "/sys/firmware/acpi/tables";
+const DT_ROOT: &str = "/proc/device-tree";
+/// Set by U-Boot in the device tree it hands to the kernel
+const DT_UBOOT_VERSION: &str = "/proc/device-tree/chosen/u-boot,version";
+/// SMBIOS tables, present on UEFI boards (EDK2, U-Boot with SMBIOS)
+const DMI_DIR: &str = "/sys/class/dmi/id";
+
+/// Boot interfaces and firmware versions
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FirmwareInfo {
+    /// Booted through UEFI (EFI runtime services available)
+    pub uefi: bool,
+    /// Hardware described by ACPI tables
+    pub acpi: bool,
+    /// Hardware described by a device tree
+    pub device_tree: bool,
+    /// U-Boot version from the device tree, e.g. `2021.10`
+    pub bootloader: Option<String>,
+    /// SMBIOS BIOS vendor, version and date, e.g. `U-Boot 2024.01 (01/08/2024)`
+    pub bios: Option<String>,
+}
+
+/// Boot firmware of this machine
+#[must_use]
+pub fn get_firmware_info() -> FirmwareInfo {
+    traced("firmware", firmware_info_with_source())
+}
+
+pub(crate) fn firmware_info_with_source() -> (FirmwareInfo, Source) {
+    let dmi = |file: &str| read_file(format!("{DMI_DIR}/{file}")).ok();
+    let info = FirmwareInfo {
+        uefi: Path::new(EFI_DIR).exists(),
+        acpi: Path::new(ACPI_TABLES).exists(),
+        device_tree: Path::new(DT_ROOT).exists(),
+        bootloader: read_file(DT_UBOOT_VERSION)
+            .ok()
+            .and_then(|version| dt_string(&version)),
+        bios: format_bios(
+            dmi("bios_vendor").as_deref(),
+            dmi("bios_version").as_deref(),
+            dmi("bios_date").as_deref(),
+        ),
+    };
+    (
+        info,
+        Source::File(format!(
+            "{EFI_DIR}, {ACPI_TABLES}, {DT_UBOOT_VERSION}, {DMI_DIR}/bios_*"
+        )),
+    )
+}
+
+/// A NUL-terminated device-tree string property; None when empty
+fn dt_string(raw: &str) -> Option<String> {
+    let value = raw.trim_matches('\0').trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// `vendor version (date)` from the SMBIOS BIOS strings; the vendor is left
+/// out when the version already starts with it
+fn format_bios(vendor: Option<&str>, version: Option<&str>, date: Option<&str>) -> Option<String> {
+    let version = clean(version)?;
+    let mut bios = match clean(vendor) {
+        Some(vendor) if !version.starts_with(vendor) => format!("{vendor} {version}"),
+        _ => version.to_string(),
+    };
+    if let Some(date) = clean(date) {
+        bios.push_str(&format!(" ({date})"));
+    }
+    Some(bios)
+}
+
+/// Trimmed SMBIOS string; None when empty
+fn clean(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dt_string() {
+        assert_eq!(dt_string("2021.10\0").as_deref(), Some("2021.10"));
+        assert_eq!(dt_string("\0"), None);
+    }
+
+    #[test]
+    fn test_format_bios() {
+        assert_eq!(
+            format_bios(Some("U-Boot\n"), Some("2024.01\n"), Some("01/08/2024\n")).as_deref(),
+            Some("U-Boot 2024.01 (01/08/2024)")
+        );
+        assert_eq!(
+            format_bios(Some("EDK II"), Some("EDK II 2023.11"), None).as_deref(),
+            Some("EDK II 2023.11")
+        );
+        assert_eq!(format_bios(Some("U-Boot"), None, None), None);
+    }
+}
@@ -0,0 +1,176 @@
+//! GPUs, their kernel driver and the Mesa driver that would use it
+//!
+//! Every DRM card in sysfs is a GPU or display controller. Whether a kernel
+//! driver is bound and whether Mesa has a matching driver is what decides
+//! if 3D acceleration can work (the JH7110's PowerVR GPU, for one, needs the
+//! vendor `pvrsrvkm` driver and has no Mesa OpenGL driver).
+
+use crate::provenance::{read_file, run_command, traced, Source};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+
+const DRM_DIR: &str = "/sys/class/drm";
+
+/// Kernel DRM driver → Mesa driver that renders with it
+const MESA_DRIVERS: &[(&str, &str)] = &[
+    ("amdgpu", "radeonsi"),
+    ("radeon", "r600"),
+    ("nouveau", "nouveau"),
+    ("i915", "iris"),
+    ("xe", "iris"),
+    ("etnaviv", "etnaviv"),
+    ("panfrost", "panfrost"),
+    ("panthor", "panfrost"),
+    ("lima", "lima"),
+    ("vc4", "vc4"),
+    ("v3d", "v3d"),
+    ("virtio_gpu", "virgl"),
+    ("powervr", "imagination"),
+];
+
+/// One DRM card
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GpuInfo {
+    /// Device-tree `compatible` (`img,img-gpu`) or PCI ID (`1002:6779`)
+    pub name: String,
+    /// Bound kernel driver, None if no driver is bound
+    pub driver: Option<String>,
+    /// Mesa driver for the kernel driver, if Mesa has one
+    pub mesa_driver: Option<String>,
+    /// Mesa version reported by `glxinfo -B`, if a Mesa driver is in use
+    pub mesa_version: Option<String>,
+}
+
+/// GPUs and display controllers (empty without DRM)
+#[must_use]
+pub fn get_gpu_info() -> Vec<GpuInfo> {
+    traced("gpu", gpu_info_with_source())
+}
+
+pub(crate) fn gpu_info_with_source() -> (Vec<GpuInfo>, Source) {
+    let mut gpus = drm_gpus();
+    if gpus.iter().any(|gpu| gpu.mesa_driver.is_some()) {
+        if let Some(version) = mesa_version() {
+            for gpu in gpus.iter_mut().filter(|gpu| gpu.mesa_driver.is_some()) {
+                gpu.mesa_version = Some(version.clone());
+            }
+        }
+    }
+    // No GPU is a valid answer on headless boards, not a missing value
+    (gpus, Source::File(format!("{DRM_DIR}/card*/device")))
+}
+
+fn drm_gpus() -> Vec<GpuInfo> {
+    let Ok(entries) = fs::read_dir(DRM_DIR) else {
+        return Vec::new();
+    };
+    let mut cards: Vec<(String, GpuInfo)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let card = entry.file_name().to_string_lossy().into_owned();
+            // Connectors (`card0-HDMI-A-1`) and render nodes are skipped
+            let number = card.strip_prefix("card")?;
+            if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let device = entry.path().join("device");
+            let uevent = read_file(device.join("uevent")).unwrap_or_default();
+            let driver = fs::read_link(device.join("driver"))
+                .ok()
+                .and_then(|link| link.file_name().map(|n| n.to_string_lossy().into_owned()));
+            Some((card, parse_gpu(&uevent, driver)?))
+        })
+        .collect();
+    cards.sort_by(|a, b| a.0.cmp(&b.0));
+    cards.into_iter().map(|(_, gpu)| gpu).collect()
+}
+
+/// GPU from a DRM device's `uevent` and the name of its bound driver
+fn parse_gpu(uevent: &str, driver: Option<String>) -> Option<GpuInfo> {
+    let value = |key: &str| {
+        uevent
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .map(str::to_string)
+    };
+    let name = value("OF_COMPATIBLE_0").or_else(|| value("PCI_ID"))?;
+    let driver = driver.or_else(|| value("DRIVER"));
+    Some(GpuInfo {
+        name,
+        mesa_driver: driver.as_deref().and_then(mesa_driver),
+        driver,
+        mesa_version: None,
+    })
+}
+
+/// Mesa driver name for a kernel DRM driver
+#[must_use]
+fn mesa_driver(kernel_driver: &str) -> Option<String> {
+    MESA_DRIVERS
+        .iter()
+        .find(|(kernel, _)| *kernel == kernel_driver)
+        .map(|(_, mesa)| (*mesa).to_string())
+}
+
+/// Mesa version of the running X session, if any
+fn mesa_version() -> Option<String> {
+    env::var_os("DISPLAY")?;
+    let output = run_command("glxinfo", &["-B"]).ok()?;
+    parse_mesa_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// `24.0.5` from `OpenGL version string: 4.6 (Compatibility Profile) Mesa 24.0.5-1ubuntu1`
+#[must_use]
+fn parse_mesa_version(glxinfo: &str) -> Option<String> {
+    glxinfo
+        .lines()
+        .filter(|line| line.contains("version string:"))
+        .find_map(|line| line.split_once("Mesa ").map(|(_, rest)| rest))
+        .and_then(|rest| rest.split(['-', ' ']).next())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gpu_device_tree() {
+        let uevent =
+            "DRIVER=pvrsrvkm\nOF_NAME=gpu\nOF_COMPATIBLE_0=img,img-gpu\nOF_COMPATIBLE_N=1\n";
+        assert_eq!(
+            parse_gpu(uevent, None),
+            Some(GpuInfo {
+                name: "img,img-gpu".to_string(),
+                driver: Some("pvrsrvkm".to_string()),
+                mesa_driver: None,
+                mesa_version: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_gpu_pci() {
+        let gpu = parse_gpu("PCI_ID=1002:6779\n", Some("amdgpu".to_string())).unwrap();
+        assert_eq!(gpu.name, "1002:6779");
+        assert_eq!(gpu.mesa_driver.as_deref(), Some("radeonsi"));
+        // Unbound device
+        let gpu = parse_gpu("OF_COMPATIBLE_0=img,img-gpu\n", None).unwrap();
+        assert_eq!(gpu.driver, None);
+        assert_eq!(parse_gpu("MODALIAS=platform:foo\n", None), None);
+    }
+
+    #[test]
+    fn test_parse_mesa_version() {
+        let glxinfo = "\
+name of display: :0
+OpenGL vendor string: AMD
+OpenGL core profile version string: 4.6 (Core Profile) Mesa 24.0.5-1ubuntu1
+OpenGL version string: 4.6 (Compatibility Profile) Mesa 24.0.5-1ubuntu1
+";
+        assert_eq!(parse_mesa_version(glxinfo).as_deref(), Some("24.0.5"));
+        let proprietary = "OpenGL version string: 3.3 PowerVR Rogue GL 1.19@6345021\n";
+        assert_eq!(parse_mesa_version(proprietary), None);
+    }
+}
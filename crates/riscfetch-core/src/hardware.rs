@@ -1,168 +1,313 @@
 //! Hardware information reading from /proc and /sys
 
-use crate::parsing::parse_vector_from_isa;
-use crate::types::HardwareIds;
-use std::fmt::Write;
+use crate::cache::cached;
+use crate::cpuinfo::{get_cpuinfo, CPUINFO};
+use crate::hwprobe::hwprobe_vector;
+use crate::parsing::{parse_cache_size, parse_vector_from_isa, strip_rv_prefix};
+use crate::provenance::{read_file, traced, Source};
+use crate::system::sysinfo_cpu_count;
+use crate::types::{CacheInfo, HardwareIds, IsaDivergence, VectorInfo};
 use std::fs;
-use sysinfo::System;
 
-/// Get raw ISA string (e.g., `rv64imafdcv_zicsr_...`)
+pub(crate) const DT_MODEL: &str = "/proc/device-tree/model";
+pub(crate) const DT_COMPATIBLE: &str = "/proc/device-tree/compatible";
+const SYSFS_VLEN: &str = "/sys/devices/system/cpu/cpu0/riscv/vlen";
+const DT_CPU0_VLENB: &str = "/proc/device-tree/cpus/cpu@0/riscv,vlenb";
+
+/// Cache levels in `/sys/devices/system/cpu/cpu0/cache/index<N>/size` order
+const CACHE_LEVELS: [&str; 4] = ["L1D", "L1I", "L2", "L3"];
+
+/// Get raw ISA string (e.g., `rv64imafdcv_zicsr_...`); when harts differ,
+/// only the extensions all of them have
 #[must_use]
 pub fn get_isa_string() -> String {
-    if let Ok(content) = fs::read_to_string("/proc/cpuinfo") {
-        for line in content.lines() {
-            if line.starts_with("isa") {
-                if let Some(isa) = line.split(':').nth(1) {
-                    return isa.trim().to_string();
-                }
-            }
-        }
+    cached().isa.clone()
+}
+
+pub(crate) fn isa_string_with_source() -> (String, Source) {
+    let cpuinfo = get_cpuinfo();
+    if let Some(isa) = cpuinfo.common_isa() {
+        let source = if cpuinfo.divergence().is_some() {
+            format!("{CPUINFO} (isa, extensions common to all harts)")
+        } else {
+            format!("{CPUINFO} (isa)")
+        };
+        return (isa, Source::File(source));
     }
-    "unknown".to_string()
+    (
+        "unknown".to_string(),
+        Source::Fallback {
+            value: "unknown",
+            tried: CPUINFO.to_string(),
+        },
+    )
+}
+
+/// Per-hart ISA lines and the extensions only some harts have, when the
+/// harts' `isa` lines differ
+#[must_use]
+pub fn get_isa_divergence() -> Option<IsaDivergence> {
+    get_cpuinfo().divergence()
 }
 
 /// Get hardware IDs (mvendorid, marchid, mimpid)
 #[must_use]
 pub fn get_hardware_ids() -> HardwareIds {
-    let mut ids = HardwareIds::default();
-
-    if let Ok(content) = fs::read_to_string("/proc/cpuinfo") {
-        for line in content.lines() {
-            if line.starts_with("mvendorid") {
-                if let Some(val) = line.split(':').nth(1) {
-                    let val = val.trim();
-                    if !val.is_empty() && val != "0x0" {
-                        ids.mvendorid = val.to_string();
-                    }
-                }
-            } else if line.starts_with("marchid") {
-                if let Some(val) = line.split(':').nth(1) {
-                    let val = val.trim();
-                    if !val.is_empty() && val != "0x0" {
-                        ids.marchid = val.to_string();
-                    }
-                }
-            } else if line.starts_with("mimpid") {
-                if let Some(val) = line.split(':').nth(1) {
-                    let val = val.trim();
-                    if !val.is_empty() && val != "0x0" {
-                        ids.mimpid = val.to_string();
-                    }
-                }
-            }
-        }
-    }
+    traced("hwids", hardware_ids_with_source())
+}
+
+pub(crate) fn hardware_ids_with_source() -> (HardwareIds, Source) {
+    let ids = get_cpuinfo().hardware_ids.clone();
 
-    ids
+    let source = if ids.mvendorid.is_empty() && ids.marchid.is_empty() && ids.mimpid.is_empty() {
+        Source::Missing {
+            tried: format!("{CPUINFO} (mvendorid, marchid, mimpid)"),
+        }
+    } else {
+        Source::File(format!("{CPUINFO} (mvendorid, marchid, mimpid)"))
+    };
+    (ids, source)
 }
 
 /// Get hart count as formatted string
 #[must_use]
 pub fn get_hart_count() -> String {
-    if let Ok(content) = fs::read_to_string("/proc/cpuinfo") {
-        let count = content
-            .lines()
-            .filter(|line| line.starts_with("processor"))
-            .count();
-        if count > 0 {
-            return format!("{count} hart{}", if count > 1 { "s" } else { "" });
-        }
-    }
-
-    let mut sys = System::new();
-    sys.refresh_cpu_all();
-    let count = sys.cpus().len();
+    let count = get_hart_count_num();
     format!("{count} hart{}", if count > 1 { "s" } else { "" })
 }
 
 /// Get hart count as number
 #[must_use]
 pub fn get_hart_count_num() -> usize {
-    if let Ok(content) = fs::read_to_string("/proc/cpuinfo") {
-        let count = content
-            .lines()
-            .filter(|line| line.starts_with("processor"))
-            .count();
-        if count > 0 {
-            return count;
-        }
+    traced("harts", hart_count_with_source())
+}
+
+pub(crate) fn hart_count_with_source() -> (usize, Source) {
+    let count = get_cpuinfo().hart_count;
+    if count > 0 {
+        return (count, Source::File(format!("{CPUINFO} (processor)")));
     }
 
-    let mut sys = System::new();
-    sys.refresh_cpu_all();
-    sys.cpus().len()
+    (sysinfo_cpu_count(), Source::Library("sysinfo"))
 }
 
 /// Get cache information
 #[must_use]
 pub fn get_cache_info() -> String {
+    traced("cache", cache_info_with_source())
+}
+
+pub(crate) fn cache_info_with_source() -> (String, Source) {
     let mut cache_parts = Vec::new();
 
-    if let Ok(l1d_size) = fs::read_to_string("/sys/devices/system/cpu/cpu0/cache/index0/size") {
-        let size = l1d_size.trim();
-        if !size.is_empty() {
-            cache_parts.push(format!("L1D:{size}"));
+    for (index, level) in CACHE_LEVELS.iter().enumerate() {
+        let path = format!("/sys/devices/system/cpu/cpu0/cache/index{index}/size");
+        if let Ok(size) = read_file(path) {
+            let size = size.trim();
+            if !size.is_empty() {
+                cache_parts.push(format!("{level}:{size}"));
+            }
         }
     }
 
-    if let Ok(l1i_size) = fs::read_to_string("/sys/devices/system/cpu/cpu0/cache/index1/size") {
-        let size = l1i_size.trim();
-        if !size.is_empty() {
-            cache_parts.push(format!("L1I:{size}"));
-        }
-    }
+    let paths = "/sys/devices/system/cpu/cpu0/cache/index*/size".to_string();
+    let source = if cache_parts.is_empty() {
+        Source::Missing { tried: paths }
+    } else {
+        Source::File(paths)
+    };
+    (cache_parts.join(" "), source)
+}
 
-    if let Ok(l2_size) = fs::read_to_string("/sys/devices/system/cpu/cpu0/cache/index2/size") {
-        let size = l2_size.trim();
-        if !size.is_empty() {
-            cache_parts.push(format!("L2:{size}"));
-        }
+/// Cache sizes as sysfs lists them (`32K`); None for levels it does not
+/// list
+#[must_use]
+pub fn get_cache_detail() -> CacheInfo {
+    let size = |index: usize| {
+        let path = format!("/sys/devices/system/cpu/cpu0/cache/index{index}/size");
+        let size = read_file(path).ok()?.trim().to_string();
+        (!size.is_empty()).then_some(size)
+    };
+    CacheInfo {
+        l1d: size(0),
+        l1i: size(1),
+        l2: size(2),
+        l3: size(3),
     }
+}
 
-    if let Ok(l3_size) = fs::read_to_string("/sys/devices/system/cpu/cpu0/cache/index3/size") {
-        let size = l3_size.trim();
-        if !size.is_empty() {
-            cache_parts.push(format!("L3:{size}"));
-        }
-    }
+/// Cache sizes in bytes as (level, size), in L1D / L1I / L2 / L3 order;
+/// levels missing from sysfs are left out
+#[must_use]
+pub fn get_cache_sizes() -> Vec<(&'static str, u64)> {
+    CACHE_LEVELS
+        .iter()
+        .enumerate()
+        .filter_map(|(index, level)| {
+            let path = format!("/sys/devices/system/cpu/cpu0/cache/index{index}/size");
+            let size = parse_cache_size(&read_file(path).ok()?)?;
+            Some((*level, size))
+        })
+        .collect()
+}
 
-    cache_parts.join(" ")
+/// Device-tree `compatible` strings, most specific (the board) first and
+/// usually the SoC last; empty without a device tree
+#[must_use]
+pub fn get_compatible() -> Vec<String> {
+    read_file(DT_COMPATIBLE)
+        .unwrap_or_default()
+        .split('\0')
+        .filter(|c| !c.is_empty())
+        .map(str::to_string)
+        .collect()
 }
 
 /// Get board/model information from device tree
 #[must_use]
 pub fn get_board_info() -> String {
-    if let Ok(content) = fs::read_to_string("/proc/device-tree/model") {
+    traced("board", board_info_with_source())
+}
+
+pub(crate) fn board_info_with_source() -> (String, Source) {
+    if let Ok(content) = read_file(DT_MODEL) {
         let model = content.trim_matches('\0').trim();
         if !model.is_empty() {
-            return model.to_string();
+            return (model.to_string(), Source::File(DT_MODEL.to_string()));
         }
     }
 
-    if let Ok(content) = fs::read_to_string("/proc/device-tree/compatible") {
+    if let Ok(content) = read_file(DT_COMPATIBLE) {
         let parts: Vec<&str> = content.split('\0').collect();
         if let Some(&first) = parts.first() {
             if !first.is_empty() {
-                return first.to_string();
+                return (first.to_string(), Source::File(DT_COMPATIBLE.to_string()));
             }
         }
     }
 
-    String::new()
+    (
+        String::new(),
+        Source::Missing {
+            tried: format!("{DT_MODEL}, {DT_COMPATIBLE}"),
+        },
+    )
 }
 
-/// Get vector extension details (VLEN, ELEN)
+/// Get vector extension details: whether V / Zve* is present, VLEN as
+/// measured on this machine and ELEN implied by the ISA string
 #[must_use]
-pub fn get_vector_detail() -> String {
+pub fn get_vector_detail() -> VectorInfo {
+    traced("vector", vector_detail_with_source())
+}
+
+pub(crate) fn vector_detail_with_source() -> (VectorInfo, Source) {
     let isa = get_isa_string();
-    let mut result = parse_vector_from_isa(&isa).unwrap_or_default();
+    if parse_vector_from_isa(&isa).is_none() {
+        return (VectorInfo::default(), Source::Derived("the ISA string"));
+    }
+    // Without a measured VLEN the ISA string still answers what matters
+    // (V is there, with its minimum VLEN), so this is no warning
+    let (vlen, source) = match measured_vlen() {
+        Some((vlen, source)) => (Some(vlen), source),
+        None => (None, Source::Derived("the ISA string")),
+    };
+    let info = VectorInfo {
+        enabled: true,
+        vlen,
+        elen: elen_from_isa(&isa),
+    };
+    (info, source)
+}
 
-    // Try to get actual VLEN from sysfs
-    if !result.is_empty() {
-        if let Ok(vlen) = fs::read_to_string("/sys/devices/system/cpu/cpu0/riscv/vlen") {
-            let _ = write!(result, ", VLEN={}", vlen.trim());
+/// VLEN in bits from, in order: the `vlenb` CSR (only when the kernel
+/// reports V through hwprobe and lets this process use it, since it traps
+/// otherwise), the device-tree `riscv,vlenb` property and the vendor sysfs
+/// file some BSP kernels add
+fn measured_vlen() -> Option<(u32, Source)> {
+    if hwprobe_vector() && vector_allowed() {
+        if let Some(vlenb) = read_vlenb() {
+            return Some((vlenb * 8, Source::Csr("vlenb")));
         }
     }
+    if let Ok(bytes) = fs::read(DT_CPU0_VLENB) {
+        if let Some(cell) = bytes.get(..4) {
+            let vlenb = u32::from_be_bytes([cell[0], cell[1], cell[2], cell[3]]);
+            if vlenb > 0 {
+                return Some((vlenb * 8, Source::File(DT_CPU0_VLENB.to_string())));
+            }
+        }
+    }
+    let vlen = read_file(SYSFS_VLEN).ok()?.trim().parse().ok()?;
+    Some((vlen, Source::File(SYSFS_VLEN.to_string())))
+}
+
+/// Whether this process may execute vector instructions. hwprobe reports
+/// V for the hardware; with `riscv_v_default_allow` at 0 the first vector
+/// CSR access still raises SIGILL. `PR_RISCV_V_GET_CONTROL` (Linux 6.5+,
+/// like kernel V support itself) has the current state in its low bits.
+/// Always false off riscv64 Linux.
+#[cfg(all(target_arch = "riscv64", target_os = "linux"))]
+#[must_use]
+pub fn vector_allowed() -> bool {
+    const PR_RISCV_V_GET_CONTROL: libc::c_int = 70;
+    const PR_RISCV_V_VSTATE_CTRL_CUR_MASK: libc::c_int = 0x3;
+    const PR_RISCV_V_VSTATE_CTRL_ON: libc::c_int = 2;
+    // SAFETY: PR_RISCV_V_GET_CONTROL takes no arguments and only returns
+    // the control value
+    let control = unsafe { libc::prctl(PR_RISCV_V_GET_CONTROL) };
+    tracing::trace!(control, "prctl(PR_RISCV_V_GET_CONTROL)");
+    control >= 0 && control & PR_RISCV_V_VSTATE_CTRL_CUR_MASK == PR_RISCV_V_VSTATE_CTRL_ON
+}
+
+#[cfg(not(all(target_arch = "riscv64", target_os = "linux")))]
+#[must_use]
+pub fn vector_allowed() -> bool {
+    false
+}
+
+/// `csrr vlenb`; the caller checks that V is enabled for this process
+#[cfg(target_arch = "riscv64")]
+#[allow(clippy::cast_possible_truncation)]
+fn read_vlenb() -> Option<u32> {
+    let vlenb: u64;
+    // SAFETY: only reached when hwprobe reports V and prctl reports vector
+    // as on for this process, so reading the vlenb CSR (0xc22) does not trap
+    unsafe {
+        std::arch::asm!("csrr {0}, 0xc22", out(reg) vlenb, options(nomem, nostack));
+    }
+    tracing::trace!(vlenb, "csrr vlenb");
+    (vlenb > 0).then_some(vlenb as u32)
+}
 
-    result
+#[cfg(not(target_arch = "riscv64"))]
+fn read_vlenb() -> Option<u32> {
+    None
+}
+
+/// Largest element width: 64 with V or `zve64*`, 32 with only `zve32*`
+fn elen_from_isa(isa: &str) -> Option<u32> {
+    let isa = isa.to_lowercase();
+    let base = isa.split('_').next().unwrap_or(&isa);
+    if strip_rv_prefix(base).contains('v') || isa.split('_').any(|p| p.starts_with("zve64")) {
+        Some(64)
+    } else if isa.split('_').any(|p| p.starts_with("zve32")) {
+        Some(32)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elen_from_isa() {
+        assert_eq!(elen_from_isa("rv64imafdcv_zicsr"), Some(64));
+        assert_eq!(elen_from_isa("rv64imac_zve64x"), Some(64));
+        assert_eq!(elen_from_isa("rv32imc_zve32x_zvl128b"), Some(32));
+        // The v of rv64 is not the V extension
+        assert_eq!(elen_from_isa("rv64imafdc"), None);
+    }
 }
@@ -0,0 +1,59 @@
+//! `riscv_hwprobe` system call (Linux 6.4+)
+
+#[cfg(all(target_arch = "riscv64", target_os = "linux"))]
+mod sys {
+    /// `__NR_riscv_hwprobe` (not in the libc crate)
+    const SYS_RISCV_HWPROBE: libc::c_long = 258;
+
+    #[repr(C)]
+    struct Pair {
+        key: i64,
+        value: u64,
+    }
+
+    pub fn probe(key: i64) -> Option<u64> {
+        let mut pair = Pair { key, value: 0 };
+        // SAFETY: one valid pair; a null CPU set with size 0 means all CPUs
+        let ret = unsafe {
+            libc::syscall(
+                SYS_RISCV_HWPROBE,
+                &mut pair as *mut Pair,
+                1usize,
+                0usize,
+                std::ptr::null_mut::<libc::cpu_set_t>(),
+                0u32,
+            )
+        };
+        // The kernel sets the key to -1 when it does not know it
+        (ret == 0 && pair.key != -1).then_some(pair.value)
+    }
+}
+
+#[cfg(not(all(target_arch = "riscv64", target_os = "linux")))]
+mod sys {
+    pub fn probe(_key: i64) -> Option<u64> {
+        None
+    }
+}
+
+/// `RISCV_HWPROBE_KEY_IMA_EXT_0`
+const KEY_IMA_EXT_0: i64 = 4;
+/// `RISCV_HWPROBE_IMA_V` in `KEY_IMA_EXT_0`
+const IMA_V: u64 = 1 << 2;
+
+/// Value of one `riscv_hwprobe` key for all harts; None on other targets,
+/// on kernels without the syscall and for keys the kernel does not know
+#[must_use]
+pub fn riscv_hwprobe(key: i64) -> Option<u64> {
+    let value = sys::probe(key);
+    tracing::trace!(key, ?value, "riscv_hwprobe");
+    value
+}
+
+/// Whether the kernel reports RVV 1.0 as usable from user space. Kernels
+/// without hwprobe cannot enable V either, and boards with only the
+/// pre-ratification XTheadVector do not set the bit.
+#[must_use]
+pub fn hwprobe_vector() -> bool {
+    riscv_hwprobe(KEY_IMA_EXT_0).is_some_and(|value| value & IMA_V != 0)
+}
@@ -0,0 +1,197 @@
+//! Init system / service manager detection
+//!
+//! PID 1 is identified from its name, its executable and the runtime
+//! directories each service manager creates, since distro images often start
+//! a generic `/sbin/init` that is really systemd, OpenRC or BusyBox.
+
+use crate::provenance::{read_file, run_command, traced, Source};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Runtime directories that mark the running service manager
+const RUN_MARKERS: &[(&str, &str)] = &[
+    ("/run/systemd/system", "systemd"),
+    ("/run/openrc", "OpenRC"),
+    ("/run/runit", "runit"),
+    ("/etc/runit/runsvdir", "runit"),
+];
+
+/// The running init system
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InitSystem {
+    /// `systemd`, `OpenRC`, `runit`, `BusyBox` or the PID 1 name
+    pub name: String,
+    pub version: Option<String>,
+}
+
+/// Init system running as PID 1, if it can be identified
+#[must_use]
+pub fn get_init_system() -> Option<InitSystem> {
+    traced("init", init_system_with_source())
+}
+
+pub(crate) fn init_system_with_source() -> (Option<InitSystem>, Source) {
+    let comm = read_file("/proc/1/comm").unwrap_or_default();
+    let exe = init_executable();
+    let markers: Vec<&str> = RUN_MARKERS
+        .iter()
+        .map(|(path, _)| *path)
+        .filter(|path| Path::new(path).exists())
+        .collect();
+
+    let Some(name) = identify_init(comm.trim(), &exe, &markers) else {
+        return (
+            None,
+            Source::Missing {
+                tried: "/proc/1/comm, /proc/1/exe, /proc/1/cmdline, /run".to_string(),
+            },
+        );
+    };
+    let version = version_command(&name).and_then(|(program, args)| {
+        let output = run_command(program, args).ok()?;
+        parse_init_version(&name, &String::from_utf8_lossy(&output.stdout))
+    });
+    (
+        Some(InitSystem { name, version }),
+        Source::File("/proc/1/comm, /proc/1/exe, /proc/1/cmdline, /run".to_string()),
+    )
+}
+
+/// Path of PID 1's executable. Reading `/proc/1/exe` needs root, so other
+/// users get the program from `/proc/1/cmdline` with symlinks resolved
+/// (`/sbin/init` usually links to the real init).
+fn init_executable() -> String {
+    if let Ok(path) = fs::read_link("/proc/1/exe") {
+        return path.to_string_lossy().into_owned();
+    }
+    let cmdline = fs::read("/proc/1/cmdline").unwrap_or_default();
+    let Some(program) = cmdline_program(&cmdline) else {
+        return String::new();
+    };
+    fs::canonicalize(&program).map_or(program, |path| path.to_string_lossy().into_owned())
+}
+
+/// First argument of a NUL-separated `/proc/PID/cmdline`, if it is an
+/// absolute path
+fn cmdline_program(cmdline: &[u8]) -> Option<String> {
+    let program = cmdline.split(|&b| b == 0).next()?;
+    let program = String::from_utf8_lossy(program);
+    program.starts_with('/').then(|| program.into_owned())
+}
+
+/// Name the init system from PID 1's name, executable path and the
+/// `RUN_MARKERS` paths that exist
+fn identify_init(comm: &str, exe: &str, markers: &[&str]) -> Option<String> {
+    let exe_name = exe.rsplit('/').next().unwrap_or_default();
+    let name = match (comm, exe_name) {
+        ("systemd", _) | (_, "systemd") => "systemd",
+        ("openrc-init", _) | (_, "openrc-init") => "OpenRC",
+        ("runit", _) | (_, "runit" | "runit-init") => "runit",
+        (_, "busybox") => "BusyBox",
+        _ => {
+            if let Some((_, name)) = RUN_MARKERS.iter().find(|(p, _)| markers.contains(p)) {
+                name
+            } else if comm.is_empty() {
+                return None;
+            } else {
+                return Some(comm.to_string());
+            }
+        }
+    };
+    Some(name.to_string())
+}
+
+/// Command that prints the version of a known init system
+fn version_command(name: &str) -> Option<(&'static str, &'static [&'static str])> {
+    match name {
+        "systemd" => Some(("systemctl", &["--version"])),
+        "OpenRC" => Some(("openrc", &["--version"])),
+        "BusyBox" => Some(("busybox", &[])),
+        _ => None,
+    }
+}
+
+/// Version from the first line of `systemctl --version` (`systemd 255 (255.4-1)`),
+/// `openrc --version` (`openrc (OpenRC) 0.52.1`) or `busybox` (`BusyBox v1.36.1 ...`)
+fn parse_init_version(name: &str, output: &str) -> Option<String> {
+    let line = output.lines().next()?;
+    let version = match name {
+        "systemd" => line.strip_prefix("systemd ")?.split_whitespace().next()?,
+        "OpenRC" => line.split_whitespace().last()?,
+        "BusyBox" => line.strip_prefix("BusyBox v")?.split_whitespace().next()?,
+        _ => return None,
+    };
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identify_init_from_pid1() {
+        assert_eq!(
+            identify_init("systemd", "/usr/lib/systemd/systemd", &[]).as_deref(),
+            Some("systemd")
+        );
+        assert_eq!(
+            identify_init("init", "/bin/busybox", &[]).as_deref(),
+            Some("BusyBox")
+        );
+        assert_eq!(
+            identify_init("runit", "/sbin/runit", &[]).as_deref(),
+            Some("runit")
+        );
+        assert_eq!(
+            identify_init("openrc-init", "/sbin/openrc-init", &[]).as_deref(),
+            Some("OpenRC")
+        );
+    }
+
+    #[test]
+    fn test_identify_init_from_markers() {
+        // sysvinit starting OpenRC, as on Alpine and Gentoo images
+        assert_eq!(
+            identify_init("init", "/sbin/init", &["/run/openrc"]).as_deref(),
+            Some("OpenRC")
+        );
+        assert_eq!(
+            identify_init("init", "/sbin/init", &[]).as_deref(),
+            Some("init")
+        );
+        assert_eq!(identify_init("", "", &[]), None);
+    }
+
+    #[test]
+    fn test_cmdline_program() {
+        assert_eq!(
+            cmdline_program(b"/sbin/init\0splash\0").as_deref(),
+            Some("/sbin/init")
+        );
+        assert_eq!(cmdline_program(b"init\0"), None);
+        assert_eq!(cmdline_program(b""), None);
+    }
+
+    #[test]
+    fn test_parse_init_version() {
+        assert_eq!(
+            parse_init_version("systemd", "systemd 255 (255.4-1ubuntu8)\n+PAM +AUDIT\n").as_deref(),
+            Some("255")
+        );
+        assert_eq!(
+            parse_init_version("OpenRC", "openrc (OpenRC) 0.52.1\n").as_deref(),
+            Some("0.52.1")
+        );
+        assert_eq!(
+            parse_init_version(
+                "BusyBox",
+                "BusyBox v1.36.1 (2024-01-01 00:00:00 UTC) multi-call binary.\n"
+            )
+            .as_deref(),
+            Some("1.36.1")
+        );
+        assert_eq!(parse_init_version("runit", "anything"), None);
+        assert_eq!(parse_init_version("systemd", ""), None);
+    }
+}
@@ -0,0 +1,74 @@
+//! JEDEC JEP106 manufacturer names for the `mvendorid` CSR
+//!
+//! `mvendorid` holds a JEP106 manufacturer ID: bits 31:7 count the `0x7f`
+//! continuation codes (the bank number minus one) and bits 6:0 are the
+//! final ID byte without its parity bit, so SiFive (bank 10, `0x89`) reads
+//! `0x489`.
+
+/// Manufacturers known to ship RISC-V cores: (bank, ID without parity, name)
+const MANUFACTURERS: &[(u32, u8, &str)] = &[
+    (1, 0x45, "Western Digital"),
+    (7, 0x1e, "Andes Technology"),
+    (10, 0x09, "SiFive"),
+    (11, 0x36, "Nuclei System Technology"),
+    (12, 0x37, "T-Head"),
+    (13, 0x02, "OpenHW Group"),
+    (13, 0x12, "Espressif"),
+    (15, 0x10, "SpacemiT"),
+];
+
+/// Split an `mvendorid` value (`0x489`, `489` or decimal) into JEP106 bank and ID
+fn bank_and_id(mvendorid: &str) -> Option<(u32, u8)> {
+    let value = mvendorid.trim();
+    let value = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+        None => value.parse().ok()?,
+    };
+    if value == 0 {
+        return None;
+    }
+    let bank = u32::try_from(value >> 7).ok()?.checked_add(1)?;
+    #[allow(clippy::cast_possible_truncation)]
+    let id = (value & 0x7f) as u8;
+    Some((bank, id))
+}
+
+/// Manufacturer name for an `mvendorid` value such as `0x489`
+///
+/// Returns `None` for 0 (not implemented), unparsable values and
+/// manufacturers missing from the embedded table.
+#[must_use]
+pub fn decode_mvendorid(mvendorid: &str) -> Option<&'static str> {
+    let (bank, id) = bank_and_id(mvendorid)?;
+    MANUFACTURERS
+        .iter()
+        .find(|(b, i, _)| *b == bank && *i == id)
+        .map(|(_, _, name)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bank_and_id() {
+        assert_eq!(bank_and_id("0x489"), Some((10, 0x09)));
+        assert_eq!(bank_and_id("0x5b7"), Some((12, 0x37)));
+        assert_eq!(bank_and_id("69"), Some((1, 0x45)));
+        assert_eq!(bank_and_id("0x0"), None);
+        assert_eq!(bank_and_id("vendor"), None);
+    }
+
+    #[test]
+    fn test_decode_mvendorid() {
+        assert_eq!(decode_mvendorid("0x489"), Some("SiFive"));
+        assert_eq!(decode_mvendorid("0x5b7"), Some("T-Head"));
+        assert_eq!(decode_mvendorid("0X31E"), Some("Andes Technology"));
+        assert_eq!(decode_mvendorid("0x710"), Some("SpacemiT"));
+        assert_eq!(decode_mvendorid("0x7ff"), None);
+        assert_eq!(decode_mvendorid(""), None);
+    }
+}
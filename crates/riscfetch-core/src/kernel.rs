@@ -0,0 +1,241 @@
+//! Minimum kernel versions of RISC-V features
+//!
+//! Old vendor kernels are common on RISC-V boards, so a missing capability
+//! is often the kernel's doing rather than the hardware's. The table lets
+//! verbose output and `doctor` say "requires kernel ≥ 6.5 (you have 5.15)".
+//! The RISC-V options of the running kernel's configuration tell whether a
+//! missing feature was compiled out.
+
+use crate::parsing::isa_supports;
+use crate::provenance::{read_file, traced, Source};
+use crate::system::{get_kernel_info, parse_kernel_version};
+use std::fs;
+
+/// Kernel configuration installed next to the kernel image by distributions
+const BOOT_CONFIG_DIR: &str = "/boot";
+
+/// ISA properties of the first hart in the device tree
+const DT_CPU0_ISA: [&str; 2] = [
+    "/proc/device-tree/cpus/cpu@0/riscv,isa",
+    "/proc/device-tree/cpus/cpu@0/riscv,isa-extensions",
+];
+
+/// Option prefixes kept by `get_kernel_config()`: ISA support, errata
+/// workarounds and SoC platforms
+const KCONFIG_PREFIXES: [&str; 3] = ["CONFIG_RISCV_", "CONFIG_ERRATA_", "CONFIG_SOC_"];
+
+/// A kernel feature and the first release that has it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KernelFeature {
+    pub id: &'static str,
+    pub description: &'static str,
+    /// Extension the feature exposes, if any; the feature only matters
+    /// when the hardware has it
+    pub extension: Option<&'static str>,
+    /// First release with the feature, as (major, minor)
+    pub min_version: (u32, u32),
+}
+
+/// Known feature gates, oldest first
+pub const KERNEL_FEATURES: &[KernelFeature] = &[
+    KernelFeature {
+        id: "hwprobe",
+        description: "The riscv_hwprobe syscall",
+        extension: None,
+        min_version: (6, 4),
+    },
+    KernelFeature {
+        id: "vector",
+        description: "Vector support for user space",
+        extension: Some("v"),
+        min_version: (6, 5),
+    },
+    KernelFeature {
+        id: "zicboz",
+        description: "Zicboz (cbo.zero) in user space",
+        extension: Some("zicboz"),
+        min_version: (6, 7),
+    },
+];
+
+impl KernelFeature {
+    /// `requires kernel ≥ 6.5 (you have 5.15)` if `release` is older than
+    /// the feature; None if it is new enough or cannot be parsed
+    #[must_use]
+    pub fn requirement(&self, release: &str) -> Option<String> {
+        let version = parse_kernel_version(release)?;
+        let (major, minor) = self.min_version;
+        ((version.major, version.minor) < self.min_version)
+            .then(|| format!("requires kernel ≥ {major}.{minor} (you have {version})"))
+    }
+}
+
+/// Look up a feature by id
+#[must_use]
+pub fn kernel_feature(id: &str) -> Option<&'static KernelFeature> {
+    KERNEL_FEATURES.iter().find(|feature| feature.id == id)
+}
+
+/// Device-tree ISA properties of the first hart (`riscv,isa` and the
+/// `riscv,isa-extensions` list), joined by spaces; None without them
+#[must_use]
+pub fn get_dt_isa() -> Option<String> {
+    let parts: Vec<String> = DT_CPU0_ISA
+        .iter()
+        .filter_map(|path| fs::read(path).ok())
+        .flat_map(|bytes| {
+            String::from_utf8_lossy(&bytes)
+                .split('\0')
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// Whether device-tree ISA properties as returned by `get_dt_isa()` name
+/// `ext`, either in the ISA string or the extension list
+#[must_use]
+pub fn dt_isa_lists(dt_isa: &str, ext: &str) -> bool {
+    dt_isa
+        .split([' ', '_'])
+        .any(|part| part == ext || (part.starts_with("rv") && isa_supports(part, ext)))
+}
+
+/// One hint per feature that `release` is too old for: features of an
+/// extension only when the device tree lists it but `isa` lacks it, e.g.
+/// `Vector support for user space requires kernel ≥ 6.5 (you have 5.15)`
+#[must_use]
+pub fn kernel_hints(release: &str, isa: &str, dt_isa: Option<&str>) -> Vec<String> {
+    KERNEL_FEATURES
+        .iter()
+        .filter(|feature| {
+            feature.extension.is_none_or(|ext| {
+                dt_isa.is_some_and(|dt| dt_isa_lists(dt, ext)) && !isa_supports(isa, ext)
+            })
+        })
+        .filter_map(|feature| {
+            let requirement = feature.requirement(release)?;
+            Some(format!("{} {requirement}", feature.description))
+        })
+        .collect()
+}
+
+/// RISC-V options of the running kernel's configuration as (name, value)
+/// pairs in file order, `n` for options that are not set; empty when
+/// `/boot/config-<release>` is missing
+#[must_use]
+pub fn get_kernel_config() -> Vec<(String, String)> {
+    traced("kconfig", kernel_config_with_source())
+}
+
+pub(crate) fn kernel_config_with_source() -> (Vec<(String, String)>, Source) {
+    let path = format!("{BOOT_CONFIG_DIR}/config-{}", get_kernel_info());
+    match read_file(&path) {
+        Ok(content) => (parse_kernel_config(&content), Source::File(path)),
+        Err(_) => (Vec::new(), Source::Missing { tried: path }),
+    }
+}
+
+/// RISC-V options of a kernel `.config`, including `# CONFIG_X is not set`
+#[must_use]
+pub fn parse_kernel_config(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            match line.strip_prefix("# ") {
+                Some(comment) => Some((comment.strip_suffix(" is not set")?, "n")),
+                None => line.split_once('='),
+            }
+        })
+        .filter(|(name, _)| {
+            KCONFIG_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        })
+        .map(|(name, value)| (name.to_string(), value.trim_matches('"').to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requirement() {
+        let hwprobe = kernel_feature("hwprobe").unwrap();
+        assert_eq!(
+            hwprobe.requirement("5.15.0-starfive").as_deref(),
+            Some("requires kernel ≥ 6.4 (you have 5.15)")
+        );
+        assert_eq!(hwprobe.requirement("6.4.0"), None);
+        assert_eq!(hwprobe.requirement("Unknown"), None);
+        assert!(kernel_feature("nope").is_none());
+    }
+
+    #[test]
+    fn test_kernel_hints() {
+        let dt = Some("rv64imafdcv_zicboz i m a f d c v zicsr zicboz");
+        assert_eq!(
+            kernel_hints("6.1.80", "rv64imafdc_zicsr", dt),
+            vec![
+                "The riscv_hwprobe syscall requires kernel ≥ 6.4 (you have 6.1)",
+                "Vector support for user space requires kernel ≥ 6.5 (you have 6.1)",
+                "Zicboz (cbo.zero) in user space requires kernel ≥ 6.7 (you have 6.1)",
+            ]
+        );
+        // Extensions already visible need no hint
+        assert_eq!(
+            kernel_hints("6.6.20", "rv64imafdcv_zicboz", dt),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            kernel_hints("6.6.20", "rv64imafdcv", dt),
+            vec!["Zicboz (cbo.zero) in user space requires kernel ≥ 6.7 (you have 6.6)"]
+        );
+        // Hardware without V or Zicboz, or without a device tree, gets no
+        // extension hints
+        assert_eq!(
+            kernel_hints("6.1.80", "rv64imafdc", Some("rv64imafdc i m a f d c")),
+            vec!["The riscv_hwprobe syscall requires kernel ≥ 6.4 (you have 6.1)"]
+        );
+        assert_eq!(kernel_hints("6.1.80", "rv64imafdc", None).len(), 1);
+    }
+
+    #[test]
+    fn test_dt_isa_lists() {
+        assert!(dt_isa_lists("rv64imafdcv_zba", "v"));
+        assert!(dt_isa_lists("rv64imafdcv_zba", "zba"));
+        assert!(dt_isa_lists("i m a f d c zicboz", "zicboz"));
+        assert!(!dt_isa_lists("rv64imafdc i m a f d c", "v"));
+    }
+
+    #[test]
+    fn test_parse_kernel_config() {
+        let config = "\
+#
+# Platform type
+#
+CONFIG_SOC_STARFIVE=y
+CONFIG_ERRATA_SIFIVE=y
+CONFIG_RISCV_ISA_ZBB=y
+# CONFIG_RISCV_ISA_V is not set
+CONFIG_RISCV_SBI_V01=y
+CONFIG_CMDLINE=\"console=ttyS0\"
+CONFIG_SMP=y
+";
+        assert_eq!(
+            parse_kernel_config(config),
+            [
+                ("CONFIG_SOC_STARFIVE", "y"),
+                ("CONFIG_ERRATA_SIFIVE", "y"),
+                ("CONFIG_RISCV_ISA_ZBB", "y"),
+                ("CONFIG_RISCV_ISA_V", "n"),
+                ("CONFIG_RISCV_SBI_V01", "y"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+    }
+}
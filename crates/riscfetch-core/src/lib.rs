@@ -14,110 +14,200 @@
 //! }
 //! ```
 
+mod boards;
+mod cache;
+mod container;
+mod cores;
+mod cpufreq;
+mod cpuinfo;
+mod devicetree;
+mod diff;
+mod displays;
 mod extensions;
+mod firmware;
+mod gpu;
 mod hardware;
+mod hwprobe;
+mod init;
+mod jedec;
+mod kernel;
+mod matching;
+mod mmc;
 mod parsing;
+mod provenance;
+mod regulators;
+mod sensors;
 mod system;
+mod thermal;
 mod types;
+mod vendors;
 
 // Re-export types
-pub use types::{CacheInfo, ExtensionEntry, HardwareIds, RiscvInfo, SystemInfo, VectorInfo};
+pub use types::{
+    CacheInfo, ExtensionEntry, FieldWarning, HardwareIds, IsaDivergence, LoadAverage,
+    MemoryBreakdown, RiscvInfo, SystemInfo, VectorInfo,
+};
+
+// Re-export board catalog
+pub use boards::{boards, find_board, get_board_details, Board};
+
+// Re-export snapshot comparison
+pub use diff::{diff_system_info, Change};
+
+// Re-export field provenance
+pub use provenance::{collect_sources, collect_warnings, Source, FIELD_IDS};
 
 // Re-export extension definitions
 pub use extensions::{
-    STANDARD_EXTENSIONS, S_CATEGORY_NAMES, S_EXTENSIONS, Z_CATEGORY_NAMES, Z_EXTENSIONS,
+    EXTENSION_DOCS, PROFILES, STANDARD_EXTENSIONS, S_CATEGORY_NAMES, S_EXTENSIONS,
+    Z_CATEGORY_NAMES, Z_EXTENSIONS,
 };
 
 // Re-export parsing functions and types
 pub use parsing::{
-    get_all_s_extensions_with_status, get_all_standard_extensions_with_status,
-    get_all_z_extensions_with_status, get_s_category_name, get_z_category_name, group_by_category,
-    parse_extensions_compact, parse_extensions_explained, parse_s_extensions,
-    parse_s_extensions_explained, parse_s_extensions_with_category, parse_vector_from_isa,
-    parse_z_extensions, parse_z_extensions_explained, parse_z_extensions_with_category,
-    ExtensionInfo,
+    expand_isa, extension_doc, get_all_s_extensions_with_status,
+    get_all_standard_extensions_with_status, get_all_z_extensions_with_status, get_profile,
+    get_s_category_name, get_z_category_name, group_by_category, isa_difference, isa_intersection,
+    isa_supports, isa_union, missing_extensions, parse_cache_size, parse_extensions_compact,
+    parse_extensions_explained, parse_s_extensions, parse_s_extensions_explained,
+    parse_s_extensions_with_category, parse_vector_from_isa, parse_z_extensions,
+    parse_z_extensions_explained, parse_z_extensions_with_category, ExtensionInfo,
 };
 
+// Re-export the shared /proc/cpuinfo parse and its cache
+pub use cache::refresh;
+pub use cpuinfo::{get_cpuinfo, parse_cpuinfo, CpuInfo};
+
 // Re-export hardware functions
 pub use hardware::{
-    get_board_info, get_cache_info, get_hardware_ids, get_hart_count, get_hart_count_num,
-    get_isa_string, get_vector_detail,
+    get_board_info, get_cache_detail, get_cache_info, get_cache_sizes, get_compatible,
+    get_hardware_ids, get_hart_count, get_hart_count_num, get_isa_divergence, get_isa_string,
+    get_vector_detail, vector_allowed,
+};
+
+// Re-export the device-tree dump
+pub use devicetree::{get_dt_nodes, parse_dt_value, DtNode, DtValue};
+
+// Re-export CPU core identification
+pub use cores::{get_core, identify_core, Core, CORES};
+
+// Re-export the riscv_hwprobe system call
+pub use hwprobe::{hwprobe_vector, riscv_hwprobe};
+
+// Re-export JEDEC manufacturer decoding
+pub use jedec::decode_mvendorid;
+
+// Re-export vendor identification
+pub use vendors::{
+    detect_vendor, detection_keywords, find_vendor, suggest_vendor, vendor_keywords, Vendor,
+    VENDORS,
+};
+
+// Re-export name matching
+pub use matching::{closest_match, normalize_name};
+
+// Re-export kernel feature gates
+pub use kernel::{
+    dt_isa_lists, get_dt_isa, get_kernel_config, kernel_feature, kernel_hints, parse_kernel_config,
+    KernelFeature, KERNEL_FEATURES,
+};
+
+// Re-export boot firmware detection
+pub use firmware::{get_firmware_info, FirmwareInfo};
+
+// Re-export init system detection
+pub use init::{get_init_system, InitSystem};
+
+// Re-export container detection
+pub use container::get_container;
+
+// Re-export display detection
+pub use displays::{get_display_info, DisplayInfo};
+
+// Re-export GPU detection
+pub use gpu::{get_gpu_info, GpuInfo};
+
+// Re-export hwmon sensors
+pub use sensors::{
+    get_board_power, get_sensors, FanReading, PowerReading, Sensors, VoltageReading,
+};
+
+// Re-export frequency scaling and CPU time
+pub use cpufreq::{
+    cpu_utilization, get_cpu_times, get_cpufreq, parse_proc_stat, CpuTimes, HartFreq,
 };
 
+// Re-export thermal zones
+pub use thermal::{get_thermal_zones, ThermalZone};
+
+// Re-export power rails
+pub use regulators::{get_regulators, Regulator};
+
+// Re-export eMMC / SD card identification
+pub use mmc::{get_mmc_info, MmcInfo};
+
 // Re-export system functions
 pub use system::{
-    get_kernel_info, get_memory_bytes, get_memory_info, get_os_info, get_uptime, get_uptime_seconds,
+    format_uptime, get_disk_usage, get_kernel_info, get_kernel_version, get_load_average,
+    get_memory_breakdown, get_memory_bytes, get_memory_info, get_os_info, get_uptime,
+    get_uptime_seconds, parse_kernel_version, parse_loadavg, parse_meminfo, DiskUsage,
+    KernelVersion,
 };
 
-use std::fs;
-use std::process::Command;
-use sysinfo::System;
+use cache::cached;
 
 /// Check if the current system is RISC-V architecture
 #[must_use]
 pub fn is_riscv() -> bool {
-    if let Ok(output) = Command::new("uname").arg("-m").output() {
-        let arch = String::from_utf8_lossy(&output.stdout);
-        if arch.contains("riscv") {
-            return true;
-        }
-    }
-
-    if let Ok(content) = fs::read_to_string("/proc/cpuinfo") {
-        if content.contains("riscv") || content.contains("RISC-V") {
-            return true;
-        }
-    }
-
-    false
+    std::env::consts::ARCH.starts_with("riscv") || get_cpuinfo().is_riscv()
 }
 
 /// Get compact extension list (e.g., "I M A F D C V")
 #[must_use]
 pub fn get_extensions_compact() -> String {
-    parse_extensions_compact(&get_isa_string())
+    cached().extensions_compact().clone()
 }
 
 /// Get Z-extensions as compact string
 #[must_use]
 pub fn get_z_extensions() -> String {
-    parse_z_extensions(&get_isa_string())
+    cached().z_extensions().clone()
 }
 
 /// Get extensions with explanations
 #[must_use]
 pub fn get_extensions_explained() -> Vec<(String, String)> {
-    parse_extensions_explained(&get_isa_string())
+    cached().extensions_explained().clone()
 }
 
 /// Get Z-extensions with explanations
 #[must_use]
 pub fn get_z_extensions_explained() -> Vec<(String, String)> {
-    parse_z_extensions_explained(&get_isa_string())
+    cached().z_extensions_explained().clone()
 }
 
 /// Get S-extensions as compact string
 #[must_use]
 pub fn get_s_extensions() -> String {
-    parse_s_extensions(&get_isa_string())
+    cached().s_extensions().clone()
 }
 
 /// Get S-extensions with explanations
 #[must_use]
 pub fn get_s_extensions_explained() -> Vec<(String, String)> {
-    parse_s_extensions_explained(&get_isa_string())
+    cached().s_extensions_explained().clone()
 }
 
 /// Get Z-extensions with category info
 #[must_use]
 pub fn get_z_extensions_with_category() -> Vec<ExtensionInfo> {
-    parse_z_extensions_with_category(&get_isa_string())
+    cached().z_extensions_with_category().clone()
 }
 
 /// Get S-extensions with category info
 #[must_use]
 pub fn get_s_extensions_with_category() -> Vec<ExtensionInfo> {
-    parse_s_extensions_with_category(&get_isa_string())
+    cached().s_extensions_with_category().clone()
 }
 
 /// Collect RISC-V specific information only (excludes generic system info)
@@ -125,9 +215,6 @@ pub fn get_s_extensions_with_category() -> Vec<ExtensionInfo> {
 pub fn collect_riscv_info() -> RiscvInfo {
     use types::ExtensionEntry;
 
-    let mut sys = System::new();
-    sys.refresh_cpu_all();
-
     let isa = get_isa_string();
     let exts: Vec<ExtensionEntry> = get_extensions_explained()
         .into_iter()
@@ -139,21 +226,17 @@ pub fn collect_riscv_info() -> RiscvInfo {
         .collect();
 
     let hw_ids = get_hardware_ids();
-    let isa_lower = isa.to_lowercase();
-    let base = isa_lower.split('_').next().unwrap_or(&isa_lower);
 
     RiscvInfo {
         isa,
         extensions: exts,
         z_extensions: z_exts,
-        vector: VectorInfo {
-            enabled: base.contains('v') || isa_lower.contains("zve"),
-            vlen: None,
-            elen: None,
-        },
-        hart_count: sys.cpus().len(),
+        vector: get_vector_detail(),
+        hart_count: system::sysinfo_cpu_count(),
         hardware_ids: hw_ids,
-        cache: CacheInfo::default(),
+        cache: get_cache_detail(),
+        isa_divergence: get_isa_divergence(),
+        warnings: collect_warnings(&provenance::RISCV_FIELD_IDS),
     }
 }
 
@@ -162,9 +245,7 @@ pub fn collect_riscv_info() -> RiscvInfo {
 pub fn collect_all_info() -> SystemInfo {
     use types::ExtensionEntry;
 
-    let mut sys = System::new();
-    sys.refresh_memory();
-    sys.refresh_cpu_all();
+    let (memory_used_bytes, memory_total_bytes) = system::sysinfo_memory();
 
     let isa = get_isa_string();
     let exts: Vec<ExtensionEntry> = get_extensions_explained()
@@ -181,28 +262,28 @@ pub fn collect_all_info() -> SystemInfo {
         .collect();
 
     let hw_ids = get_hardware_ids();
-    let isa_lower = isa.to_lowercase();
-    let base = isa_lower.split('_').next().unwrap_or(&isa_lower);
 
     SystemInfo {
         isa,
         extensions: exts,
         z_extensions: z_exts,
         s_extensions: s_exts,
-        vector: VectorInfo {
-            enabled: base.contains('v') || isa_lower.contains("zve"),
-            vlen: None,
-            elen: None,
-        },
-        hart_count: sys.cpus().len(),
+        vector: get_vector_detail(),
+        hart_count: system::sysinfo_cpu_count(),
         hardware_ids: hw_ids,
-        cache: CacheInfo::default(),
+        cache: get_cache_detail(),
         board: get_board_info(),
-        memory_used_bytes: sys.used_memory(),
-        memory_total_bytes: sys.total_memory(),
+        memory_used_bytes,
+        memory_total_bytes,
         kernel: get_kernel_info(),
         os: get_os_info(),
-        uptime_seconds: System::uptime(),
+        uptime_seconds: get_uptime_seconds(),
+        load_average: get_load_average(),
+        memory_breakdown: get_memory_breakdown(),
+        sensors: get_sensors(),
+        container: get_container().map(str::to_string),
+        isa_divergence: get_isa_divergence(),
+        warnings: collect_warnings(&provenance::SYSTEM_FIELD_IDS),
     }
 }
 
@@ -218,6 +299,13 @@ mod tests {
         assert!(!uptime.is_empty());
     }
 
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(59), "0m");
+        assert_eq!(format_uptime(300), "5m");
+        assert_eq!(format_uptime(13_320), "3h 42m");
+    }
+
     #[test]
     fn test_get_uptime_seconds() {
         let secs = get_uptime_seconds();
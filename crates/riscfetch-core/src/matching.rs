@@ -0,0 +1,80 @@
+//! Normalized and fuzzy name matching
+//!
+//! Shared by vendor lookup, vendor detection and the CLI's `--logo`
+//! suggestions, so `Milk-V`, `milk v` and `MILKV` all name the same vendor.
+
+/// Lowercase `text` and drop dashes, underscores and whitespace
+#[must_use]
+pub fn normalize_name(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, '-' | '_') && !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Levenshtein distance between two strings, in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            let next = (row[j] + 1).min(row[j + 1] + 1).min(diagonal + cost);
+            diagonal = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+/// Candidate closest to `input` after normalization, if it is a likely typo
+///
+/// Allows one edit per three characters of `input` (at least one); ties go
+/// to the earlier candidate.
+#[must_use]
+pub fn closest_match<'a>(
+    input: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let input = normalize_name(input);
+    let limit = (input.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|c| (edit_distance(&input, &normalize_name(c)), c))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Milk-V"), "milkv");
+        assert_eq!(normalize_name(" milk v "), "milkv");
+        assert_eq!(normalize_name("T_Head"), "thead");
+        assert_eq!(normalize_name("RZ/Five"), "rz/five");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("starfive", "starfive"), 0);
+        assert_eq!(edit_distance("starfiv", "starfive"), 1);
+        assert_eq!(edit_distance("sifvie", "sifive"), 2);
+        assert_eq!(edit_distance("", "wch"), 3);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let names = ["sifive", "starfive", "spacemit", "wch"];
+        assert_eq!(closest_match("starfiv", names), Some("starfive"));
+        assert_eq!(closest_match("Star-Fife", names), Some("starfive"));
+        assert_eq!(closest_match("sifvie", names), Some("sifive"));
+        assert_eq!(closest_match("wcj", names), Some("wch"));
+        assert_eq!(closest_match("intel", names), None);
+    }
+}
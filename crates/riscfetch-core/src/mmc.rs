@@ -0,0 +1,247 @@
+//! eMMC and SD card identification
+//!
+//! The card registers the MMC core exposes under `/sys/block/mmcblk*/device`
+//! name the manufacturer and product, and the SD status register (`ssr`)
+//! carries the speed class. Slow cards are the most common cause of a
+//! sluggish SBC, so they are flagged.
+
+use crate::provenance::{read_file, traced, Source};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const BLOCK_DIR: &str = "/sys/block";
+
+/// SD card manufacturer IDs (`manfid`), as commonly reported
+const SD_MANUFACTURERS: &[(u32, &str)] = &[
+    (0x01, "Panasonic"),
+    (0x02, "Toshiba"),
+    (0x03, "SanDisk"),
+    (0x1b, "Samsung"),
+    (0x1d, "ADATA"),
+    (0x27, "Phison"),
+    (0x28, "Lexar"),
+    (0x31, "Silicon Power"),
+    (0x41, "Kingston"),
+    (0x74, "Transcend"),
+    (0x76, "Patriot"),
+    (0x82, "Sony"),
+];
+
+/// eMMC manufacturer IDs (`manfid`), from JEDEC JESD84
+const MMC_MANUFACTURERS: &[(u32, &str)] = &[
+    (0x11, "Toshiba"),
+    (0x13, "Micron"),
+    (0x15, "Samsung"),
+    (0x45, "SanDisk"),
+    (0x70, "Kingston"),
+    (0x88, "Foresee"),
+    (0x90, "SK Hynix"),
+    (0x9b, "YMTC"),
+    (0xd6, "Foresee"),
+    (0xfe, "Micron"),
+];
+
+/// One eMMC or SD card
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MmcInfo {
+    /// Block device, e.g. `mmcblk0`
+    pub device: String,
+    /// `MMC` (eMMC) or `SD`
+    pub kind: String,
+    /// Product name from the CID, e.g. `SN64G`
+    pub name: String,
+    pub manufacturer: Option<String>,
+    /// Raw card identification register, hex
+    pub cid: String,
+    pub size_bytes: u64,
+    /// SD speed class, e.g. `Class 10, U3, V30` (None for eMMC or without `ssr`)
+    pub speed_class: Option<String>,
+    /// Class 2/4/6 SD cards without a UHS grade: expect poor random I/O
+    pub slow: bool,
+}
+
+/// eMMC and SD cards, in `mmcblkN` order (SDIO devices are skipped)
+#[must_use]
+pub fn get_mmc_info() -> Vec<MmcInfo> {
+    traced("mmc", mmc_info_with_source())
+}
+
+pub(crate) fn mmc_info_with_source() -> (Vec<MmcInfo>, Source) {
+    let source = Source::File(format!("{BLOCK_DIR}/mmcblk*/device"));
+    let Ok(entries) = fs::read_dir(BLOCK_DIR) else {
+        return (Vec::new(), source);
+    };
+    let mut cards: Vec<(u32, MmcInfo)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let device = entry.file_name().to_string_lossy().into_owned();
+            // Boot and RPMB partitions (`mmcblk0boot0`) are not cards
+            let index = device.strip_prefix("mmcblk")?.parse().ok()?;
+            let path = entry.path();
+            let read = |file: &str| read_file(path.join("device").join(file)).ok();
+            let sectors: u64 = read_file(path.join("size"))
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(0);
+            let card = parse_mmc(
+                &device,
+                &read("type")?,
+                &read("name").unwrap_or_default(),
+                read("manfid").as_deref(),
+                &read("cid").unwrap_or_default(),
+                read("ssr").as_deref(),
+                sectors * 512,
+            )?;
+            Some((index, card))
+        })
+        .collect();
+    cards.sort_by_key(|(index, _)| *index);
+    // No card is a valid answer on boards booting from NVMe or USB
+    (cards.into_iter().map(|(_, card)| card).collect(), source)
+}
+
+/// Card from its sysfs attributes; None for SDIO and unknown types
+fn parse_mmc(
+    device: &str,
+    kind: &str,
+    name: &str,
+    manfid: Option<&str>,
+    cid: &str,
+    ssr: Option<&str>,
+    size_bytes: u64,
+) -> Option<MmcInfo> {
+    let kind = kind.trim();
+    let table = match kind {
+        "SD" => SD_MANUFACTURERS,
+        "MMC" => MMC_MANUFACTURERS,
+        _ => return None,
+    };
+    let manufacturer = manfid
+        .and_then(|id| u32::from_str_radix(id.trim().trim_start_matches("0x"), 16).ok())
+        .and_then(|id| table.iter().find(|(m, _)| *m == id))
+        .map(|(_, name)| (*name).to_string());
+    let speed = ssr.filter(|_| kind == "SD").and_then(parse_ssr_speed);
+    Some(MmcInfo {
+        device: device.to_string(),
+        kind: kind.to_string(),
+        name: name.trim().to_string(),
+        manufacturer,
+        cid: cid.trim().to_string(),
+        size_bytes,
+        speed_class: speed.map(|s| s.label()),
+        slow: speed.is_some_and(|s| s.is_slow()),
+    })
+}
+
+/// Speed ratings from the SD status register
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SdSpeed {
+    /// 0, 2, 4, 6 or 10
+    class: u8,
+    /// UHS speed grade: 0, 1 or 3
+    uhs: u8,
+    /// Video speed class: 0, 6, 10, 30, 60 or 90
+    video: u8,
+}
+
+impl SdSpeed {
+    fn label(self) -> String {
+        let mut parts = vec![format!("Class {}", self.class)];
+        if self.uhs > 0 {
+            parts.push(format!("U{}", self.uhs));
+        }
+        if self.video > 0 {
+            parts.push(format!("V{}", self.video));
+        }
+        parts.join(", ")
+    }
+
+    fn is_slow(self) -> bool {
+        matches!(self.class, 2 | 4 | 6) && self.uhs == 0 && self.video == 0
+    }
+}
+
+/// Speed class (bits 447:440), UHS grade (399:396) and video speed class
+/// (391:384) from the 512-bit SSR as printed by sysfs (hex, MSB first)
+fn parse_ssr_speed(ssr: &str) -> Option<SdSpeed> {
+    let ssr = ssr.trim();
+    let byte = |bit: usize| {
+        let index = (511 - bit) / 8 * 2;
+        u8::from_str_radix(ssr.get(index..index + 2)?, 16).ok()
+    };
+    let class = match byte(447)? {
+        0 => 0,
+        1 => 2,
+        2 => 4,
+        3 => 6,
+        4 => 10,
+        _ => return None,
+    };
+    Some(SdSpeed {
+        class,
+        uhs: byte(399)? >> 4,
+        video: byte(391)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SSR with the given speed class, UHS grade and video speed class codes
+    fn ssr(class: u8, uhs: u8, video: u8) -> String {
+        let mut bytes = [0u8; 64];
+        bytes[8] = class;
+        bytes[14] = uhs << 4;
+        bytes[15] = video;
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn test_parse_ssr_speed() {
+        assert_eq!(
+            parse_ssr_speed(&ssr(4, 3, 30)),
+            Some(SdSpeed {
+                class: 10,
+                uhs: 3,
+                video: 30
+            })
+        );
+        assert_eq!(
+            parse_ssr_speed(&ssr(4, 3, 30)).unwrap().label(),
+            "Class 10, U3, V30"
+        );
+        assert!(parse_ssr_speed(&ssr(2, 0, 0)).unwrap().is_slow());
+        assert!(!parse_ssr_speed(&ssr(4, 1, 0)).unwrap().is_slow());
+        // Class 0 cards predate speed classes rather than being slow ones
+        assert!(!parse_ssr_speed(&ssr(0, 0, 0)).unwrap().is_slow());
+        assert_eq!(parse_ssr_speed("00"), None);
+    }
+
+    #[test]
+    fn test_parse_mmc_sd() {
+        let card = parse_mmc(
+            "mmcblk1",
+            "SD\n",
+            "SN64G\n",
+            Some("0x000003\n"),
+            "035344534e3634478512345678016a00\n",
+            Some(&ssr(2, 0, 0)),
+            64_000_000_000,
+        )
+        .unwrap();
+        assert_eq!(card.manufacturer.as_deref(), Some("SanDisk"));
+        assert_eq!(card.name, "SN64G");
+        assert_eq!(card.speed_class.as_deref(), Some("Class 4"));
+        assert!(card.slow);
+    }
+
+    #[test]
+    fn test_parse_mmc_emmc_and_sdio() {
+        let emmc = parse_mmc("mmcblk0", "MMC", "DG4032", Some("0x000045"), "", None, 0).unwrap();
+        assert_eq!(emmc.manufacturer.as_deref(), Some("SanDisk"));
+        assert_eq!(emmc.speed_class, None);
+        assert!(!emmc.slow);
+        assert_eq!(parse_mmc("mmcblk2", "SDIO", "", None, "", None, 0), None);
+    }
+}
@@ -1,7 +1,8 @@
 //! ISA string parsing functions
 
 use crate::extensions::{
-    STANDARD_EXTENSIONS, S_CATEGORY_NAMES, S_EXTENSIONS, Z_CATEGORY_NAMES, Z_EXTENSIONS,
+    EXTENSION_DOCS, PROFILES, STANDARD_EXTENSIONS, S_CATEGORY_NAMES, S_EXTENSIONS,
+    Z_CATEGORY_NAMES, Z_EXTENSIONS,
 };
 
 /// Extension info with category and support status
@@ -28,72 +29,107 @@ fn isa_has_extension(isa: &str, pattern: &str) -> bool {
     isa.split('_').any(|part| part == pattern)
 }
 
-/// Parse extensions from ISA string (pure function for testing)
+/// Extensions each shorthand letter stands for: G for IMAFD plus Zicsr and
+/// Zifencei, B for Zba, Zbb and Zbs
+const SHORTHANDS: &[(char, &str, &[&str])] = &[
+    ('g', "imafd", &["zicsr", "zifencei"]),
+    ('b', "b", &["zba", "zbb", "zbs"]),
+];
+
+/// Lowercase the ISA string and spell out its shorthands, so every parser
+/// sees the same extensions: `rv64gc_zba` becomes
+/// `rv64imafdc_zicsr_zifencei_zba`, and `rv64imacb` gains `_zba_zbb_zbs`.
+/// Strings without an `rv` prefix are only lowercased.
 #[must_use]
-pub fn parse_extensions_compact(isa: &str) -> String {
-    let isa = isa.to_lowercase();
-    let mut exts = Vec::new();
-
-    // Get the base part before any underscore
-    let base = isa.split('_').next().unwrap_or(&isa);
-    let ext_part = strip_rv_prefix(base);
-
-    // G is shorthand for IMAFD (per RISC-V spec)
-    let has_g = ext_part.contains('g');
-
-    // Standard extensions in canonical order
-    // Note: E and I are mutually exclusive
-    let standard = [
-        ('i', "I", false), // (char, name, implied_by_g)
-        ('e', "E", false), // E = embedded (16 registers)
-        ('m', "M", true),
-        ('a', "A", true),
-        ('f', "F", true),
-        ('d', "D", true),
-        ('q', "Q", false),
-        ('c', "C", false),
-        ('b', "B", false),
-        ('v', "V", false),
-        ('h', "H", false),
-    ];
-
-    for (ch, name, implied_by_g) in standard {
-        if ext_part.contains(ch) || (has_g && implied_by_g) {
-            exts.push(name);
+pub fn expand_isa(isa: &str) -> String {
+    let isa = isa.trim().to_lowercase();
+    let mut parts = isa.split('_');
+    let base = parts.next().unwrap_or_default();
+    if !base.starts_with("rv") {
+        return isa;
+    }
+    let letters = strip_rv_prefix(base);
+    let mut expanded = base[..base.len() - letters.len()].to_string();
+    let rest: Vec<&str> = parts.filter(|part| !part.is_empty()).collect();
+
+    let mut single = String::new();
+    let mut implied: Vec<&str> = Vec::new();
+    for ch in letters.chars() {
+        let (spelled, extensions) = match SHORTHANDS.iter().find(|(letter, _, _)| *letter == ch) {
+            Some((_, spelled, extensions)) => (spelled.to_string(), *extensions),
+            None => (ch.to_string(), &[][..]),
+        };
+        for letter in spelled.chars() {
+            if !single.contains(letter) {
+                single.push(letter);
+            }
+        }
+        for ext in extensions {
+            if !rest.contains(ext) && !implied.contains(ext) {
+                implied.push(ext);
+            }
         }
     }
-
-    // If G is present but I wasn't explicitly added, add I (G implies IMAFD)
-    if has_g && !exts.contains(&"I") && !exts.contains(&"E") {
-        exts.insert(0, "I");
+    expanded.push_str(&single);
+    for part in implied.into_iter().chain(rest) {
+        expanded.push('_');
+        expanded.push_str(part);
     }
+    expanded
+}
+
+/// Single-letter extensions in the ISA manual's canonical order; a Z
+/// extension is ordered by the letter after its `z`
+const CANONICAL_LETTERS: &str = "iemafdqlcbkjtpvnh";
+
+/// Sort key for the spec's canonical extension order: single letters, then
+/// Z extensions by category letter and name, then S extensions by name,
+/// then anything else (vendor X extensions) by name
+fn canonical_key(name: &str) -> (u8, usize, String) {
+    let name = name.to_lowercase();
+    let letter_rank = |ch: Option<char>| {
+        ch.and_then(|ch| CANONICAL_LETTERS.find(ch))
+            .unwrap_or(CANONICAL_LETTERS.len())
+    };
+    let mut chars = name.chars();
+    let (group, rank) = match (chars.next(), chars.next()) {
+        (first, None) => (0, letter_rank(first)),
+        (Some('z'), second) => (1, letter_rank(second)),
+        (Some('s'), _) => (2, 0),
+        _ => (3, 0),
+    };
+    (group, rank, name)
+}
 
-    exts.join(" ")
+/// Sort extensions into canonical order and drop repeated names
+fn canonicalize<T>(items: &mut Vec<T>, name: impl Fn(&T) -> &str) {
+    items.sort_by_cached_key(|item| canonical_key(name(item)));
+    items.dedup_by(|a, b| name(a).eq_ignore_ascii_case(name(b)));
+}
+
+/// Parse extensions from ISA string (pure function for testing)
+#[must_use]
+pub fn parse_extensions_compact(isa: &str) -> String {
+    parse_extensions_explained(isa)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 /// Parse Z-extensions from ISA string (pure function for testing)
 #[must_use]
 pub fn parse_z_extensions(isa: &str) -> String {
-    let isa = isa.to_lowercase();
+    let isa = expand_isa(isa);
     let mut z_exts = Vec::new();
 
-    // Check if G is present (G implies Zicsr_Zifencei per RISC-V spec)
-    let base = isa.split('_').next().unwrap_or(&isa);
-    let ext_part = strip_rv_prefix(base);
-    let has_g = ext_part.contains('g');
-
-    // Add implied Z-extensions from G
-    if has_g {
-        z_exts.push("zicsr".to_string());
-        z_exts.push("zifencei".to_string());
-    }
-
-    // Add explicit Z-extensions (z prefix only)
+    // Z-extensions (z prefix only), including those implied by G and B
     for part in isa.split('_') {
-        if part.starts_with('z') && !z_exts.contains(&part.to_string()) {
-            z_exts.push(part.to_string());
+        if part.starts_with('z') {
+            z_exts.push(part);
         }
     }
+    canonicalize(&mut z_exts, |ext| ext);
 
     z_exts.join(" ")
 }
@@ -101,15 +137,16 @@ pub fn parse_z_extensions(isa: &str) -> String {
 /// Parse S-extensions from ISA string (pure function for testing)
 #[must_use]
 pub fn parse_s_extensions(isa: &str) -> String {
-    let isa = isa.to_lowercase();
+    let isa = expand_isa(isa);
     let mut s_exts = Vec::new();
 
     // Add explicit S-extensions (s prefix only)
     for part in isa.split('_') {
-        if part.starts_with('s') && !s_exts.contains(&part.to_string()) {
-            s_exts.push(part.to_string());
+        if part.starts_with('s') {
+            s_exts.push(part);
         }
     }
+    canonicalize(&mut s_exts, |ext| ext);
 
     s_exts.join(" ")
 }
@@ -117,7 +154,7 @@ pub fn parse_s_extensions(isa: &str) -> String {
 /// Parse extensions with explanations (pure function for testing)
 #[must_use]
 pub fn parse_extensions_explained(isa: &str) -> Vec<(String, String)> {
-    let isa = isa.to_lowercase();
+    let isa = expand_isa(isa);
     let base = isa.split('_').next().unwrap_or(&isa);
     let ext_part = strip_rv_prefix(base);
     let mut exts = Vec::new();
@@ -134,7 +171,7 @@ pub fn parse_extensions_explained(isa: &str) -> Vec<(String, String)> {
 /// Parse Z-extensions with explanations (pure function for testing)
 #[must_use]
 pub fn parse_z_extensions_explained(isa: &str) -> Vec<(String, String)> {
-    let isa = isa.to_lowercase();
+    let isa = expand_isa(isa);
     let mut z_exts = Vec::new();
 
     for &(pattern, name, desc, _category) in Z_EXTENSIONS {
@@ -142,6 +179,7 @@ pub fn parse_z_extensions_explained(isa: &str) -> Vec<(String, String)> {
             z_exts.push((name.to_string(), desc.to_string()));
         }
     }
+    canonicalize(&mut z_exts, |(name, _)| name);
 
     z_exts
 }
@@ -149,7 +187,7 @@ pub fn parse_z_extensions_explained(isa: &str) -> Vec<(String, String)> {
 /// Parse S-extensions with explanations (pure function for testing)
 #[must_use]
 pub fn parse_s_extensions_explained(isa: &str) -> Vec<(String, String)> {
-    let isa = isa.to_lowercase();
+    let isa = expand_isa(isa);
     let mut s_exts = Vec::new();
 
     for &(pattern, name, desc, _category) in S_EXTENSIONS {
@@ -157,6 +195,7 @@ pub fn parse_s_extensions_explained(isa: &str) -> Vec<(String, String)> {
             s_exts.push((name.to_string(), desc.to_string()));
         }
     }
+    canonicalize(&mut s_exts, |(name, _)| name);
 
     s_exts
 }
@@ -164,43 +203,20 @@ pub fn parse_s_extensions_explained(isa: &str) -> Vec<(String, String)> {
 /// Parse Z-extensions with category info
 #[must_use]
 pub fn parse_z_extensions_with_category(isa: &str) -> Vec<ExtensionInfo> {
-    let isa = isa.to_lowercase();
+    let isa = expand_isa(isa);
     let mut z_exts = Vec::new();
 
-    // Check if G is present (G implies Zicsr_Zifencei per RISC-V spec)
-    let base = isa.split('_').next().unwrap_or(&isa);
-    let ext_part = strip_rv_prefix(base);
-    let has_g = ext_part.contains('g');
-
-    // Add implied Z-extensions from G
-    if has_g {
-        z_exts.push(ExtensionInfo {
-            name: "Zicsr".to_string(),
-            description: "CSR Instructions".to_string(),
-            category: "base".to_string(),
-            supported: true,
-        });
-        z_exts.push(ExtensionInfo {
-            name: "Zifencei".to_string(),
-            description: "Instruction-Fetch Fence".to_string(),
-            category: "base".to_string(),
-            supported: true,
-        });
-    }
-
     for &(pattern, name, desc, category) in Z_EXTENSIONS {
         if isa_has_extension(&isa, pattern) {
-            // Skip if already added (implied by G)
-            if !z_exts.iter().any(|e| e.name.eq_ignore_ascii_case(name)) {
-                z_exts.push(ExtensionInfo {
-                    name: name.to_string(),
-                    description: desc.to_string(),
-                    category: category.to_string(),
-                    supported: true,
-                });
-            }
+            z_exts.push(ExtensionInfo {
+                name: name.to_string(),
+                description: desc.to_string(),
+                category: category.to_string(),
+                supported: true,
+            });
         }
     }
+    canonicalize(&mut z_exts, |ext| &ext.name);
 
     z_exts
 }
@@ -208,7 +224,7 @@ pub fn parse_z_extensions_with_category(isa: &str) -> Vec<ExtensionInfo> {
 /// Parse S-extensions with category info
 #[must_use]
 pub fn parse_s_extensions_with_category(isa: &str) -> Vec<ExtensionInfo> {
-    let isa = isa.to_lowercase();
+    let isa = expand_isa(isa);
     let mut s_exts = Vec::new();
 
     for &(pattern, name, desc, category) in S_EXTENSIONS {
@@ -221,6 +237,7 @@ pub fn parse_s_extensions_with_category(isa: &str) -> Vec<ExtensionInfo> {
             });
         }
     }
+    canonicalize(&mut s_exts, |ext| &ext.name);
 
     s_exts
 }
@@ -259,16 +276,12 @@ pub fn group_by_category(extensions: &[ExtensionInfo]) -> Vec<(String, Vec<&Exte
 /// Get ALL Z-extensions with support status based on ISA string
 #[must_use]
 pub fn get_all_z_extensions_with_status(isa: &str) -> Vec<ExtensionInfo> {
-    let isa = isa.to_lowercase();
-    let base = isa.split('_').next().unwrap_or(&isa);
-    let ext_part = strip_rv_prefix(base);
-    let has_g = ext_part.contains('g');
+    let isa = expand_isa(isa);
 
-    Z_EXTENSIONS
+    let mut exts: Vec<ExtensionInfo> = Z_EXTENSIONS
         .iter()
         .map(|&(pattern, name, desc, category)| {
-            let supported = isa_has_extension(&isa, pattern)
-                || (has_g && (pattern == "zicsr" || pattern == "zifencei"));
+            let supported = isa_has_extension(&isa, pattern);
             ExtensionInfo {
                 name: name.to_string(),
                 description: desc.to_string(),
@@ -276,15 +289,17 @@ pub fn get_all_z_extensions_with_status(isa: &str) -> Vec<ExtensionInfo> {
                 supported,
             }
         })
-        .collect()
+        .collect();
+    canonicalize(&mut exts, |ext| &ext.name);
+    exts
 }
 
 /// Get ALL S-extensions with support status based on ISA string
 #[must_use]
 pub fn get_all_s_extensions_with_status(isa: &str) -> Vec<ExtensionInfo> {
-    let isa = isa.to_lowercase();
+    let isa = expand_isa(isa);
 
-    S_EXTENSIONS
+    let mut exts: Vec<ExtensionInfo> = S_EXTENSIONS
         .iter()
         .map(|&(pattern, name, desc, category)| {
             let supported = isa_has_extension(&isa, pattern);
@@ -295,22 +310,22 @@ pub fn get_all_s_extensions_with_status(isa: &str) -> Vec<ExtensionInfo> {
                 supported,
             }
         })
-        .collect()
+        .collect();
+    canonicalize(&mut exts, |ext| &ext.name);
+    exts
 }
 
 /// Get ALL standard extensions with support status
 #[must_use]
 pub fn get_all_standard_extensions_with_status(isa: &str) -> Vec<(String, String, bool)> {
-    let isa = isa.to_lowercase();
+    let isa = expand_isa(isa);
     let base = isa.split('_').next().unwrap_or(&isa);
     let ext_part = strip_rv_prefix(base);
-    let has_g = ext_part.contains('g');
 
     STANDARD_EXTENSIONS
         .iter()
         .map(|&(char, name, desc)| {
-            let supported =
-                ext_part.contains(char) || (has_g && matches!(char, 'i' | 'm' | 'a' | 'f' | 'd'));
+            let supported = ext_part.contains(char);
             (name.to_string(), desc.to_string(), supported)
         })
         .collect()
@@ -320,7 +335,7 @@ pub fn get_all_standard_extensions_with_status(isa: &str) -> Vec<(String, String
 /// Returns None if no vector extension, Some(details) otherwise
 #[must_use]
 pub fn parse_vector_from_isa(isa: &str) -> Option<String> {
-    let isa = isa.to_lowercase();
+    let isa = expand_isa(isa);
     let base = isa.split('_').next().unwrap_or(&isa);
     let ext_part = strip_rv_prefix(base);
 
@@ -364,6 +379,153 @@ pub fn parse_vector_from_isa(isa: &str) -> Option<String> {
     Some(details.join(", "))
 }
 
+/// Check whether the ISA string includes one extension (case-insensitive).
+/// Single letters are looked up in the base part; G implies IMAFD, Zicsr
+/// and Zifencei, and B implies Zba, Zbb and Zbs.
+#[must_use]
+pub fn isa_supports(isa: &str, name: &str) -> bool {
+    let isa = expand_isa(isa);
+    let name = name.trim().to_lowercase();
+    let base = isa.split('_').next().unwrap_or(&isa);
+    let ext_part = strip_rv_prefix(base);
+
+    let mut letters = name.chars();
+    match (letters.next(), letters.next()) {
+        (Some(ch), None) => ext_part.contains(ch),
+        _ => isa_has_extension(&isa, &name),
+    }
+}
+
+/// Split an ISA string into its `rv32`/`rv64` prefix and lowercase
+/// extension names (shorthands expanded): single letters first, then the
+/// multi-letter extensions
+fn isa_tokens(isa: &str) -> (String, Vec<String>) {
+    let isa = expand_isa(isa);
+    let mut parts = isa.split('_');
+    let base = parts.next().unwrap_or_default();
+    let letters = strip_rv_prefix(base);
+    let prefix = base[..base.len() - letters.len()].to_string();
+
+    let mut tokens: Vec<String> = letters.chars().map(String::from).collect();
+    for part in parts {
+        if !tokens.iter().any(|token| token == part) {
+            tokens.push(part.to_string());
+        }
+    }
+    (prefix, tokens)
+}
+
+/// Reassemble `isa_tokens()` output into an ISA string
+fn join_isa(prefix: &str, tokens: &[String]) -> String {
+    let (letters, multi): (Vec<&String>, Vec<&String>) =
+        tokens.iter().partition(|token| token.len() == 1);
+    let mut isa = prefix.to_string();
+    letters.iter().for_each(|letter| isa.push_str(letter));
+    for ext in multi {
+        isa.push('_');
+        isa.push_str(ext);
+    }
+    isa
+}
+
+/// ISA string with the extensions every hart has, i.e. what is safe to use
+/// on any hart. Identical strings are returned unchanged.
+#[must_use]
+pub fn isa_intersection(isas: &[&str]) -> Option<String> {
+    let (first, rest) = isas.split_first()?;
+    if rest.iter().all(|isa| isa.trim() == first.trim()) {
+        return Some(first.trim().to_string());
+    }
+    let others: Vec<Vec<String>> = rest.iter().map(|isa| isa_tokens(isa).1).collect();
+    let (prefix, tokens) = isa_tokens(first);
+    let common: Vec<String> = tokens
+        .into_iter()
+        .filter(|token| others.iter().all(|other| other.contains(token)))
+        .collect();
+    Some(join_isa(&prefix, &common))
+}
+
+/// ISA string with the extensions any hart has. Identical strings are
+/// returned unchanged.
+#[must_use]
+pub fn isa_union(isas: &[&str]) -> Option<String> {
+    let (first, rest) = isas.split_first()?;
+    if rest.iter().all(|isa| isa.trim() == first.trim()) {
+        return Some(first.trim().to_string());
+    }
+    let (prefix, mut tokens) = isa_tokens(first);
+    for isa in rest {
+        for token in isa_tokens(isa).1 {
+            if !tokens.contains(&token) {
+                tokens.push(token);
+            }
+        }
+    }
+    Some(join_isa(&prefix, &tokens))
+}
+
+/// Extensions in `union` but not in `intersection` (as returned by
+/// `isa_union()` / `isa_intersection()`), i.e. the ones only some harts have
+#[must_use]
+pub fn isa_difference(union: &str, intersection: &str) -> Vec<String> {
+    let common = isa_tokens(intersection).1;
+    isa_tokens(union)
+        .1
+        .into_iter()
+        .filter(|token| !common.contains(token))
+        .collect()
+}
+
+/// Look up a profile by name or alias (e.g. "RVA23U64" or "rva23").
+/// Returns (name, required extensions) or None if unknown.
+#[must_use]
+pub fn get_profile(name: &str) -> Option<(&'static str, &'static [&'static str])> {
+    let name = name.trim().to_lowercase();
+    PROFILES
+        .iter()
+        .find(|(id, aliases, _)| id.to_lowercase() == name || aliases.contains(&name.as_str()))
+        .map(|(id, _, required)| (*id, *required))
+}
+
+/// Longer documentation of an extension (`V`, `zba`, ...), paragraphs
+/// separated by blank lines; None for extensions without one
+#[must_use]
+pub fn extension_doc(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_lowercase();
+    EXTENSION_DOCS
+        .iter()
+        .find(|(id, _)| *id == name)
+        .map(|(_, doc)| *doc)
+}
+
+/// Extensions from `required` that the ISA string lacks, in input order
+#[must_use]
+pub fn missing_extensions<'a>(isa: &str, required: &[&'a str]) -> Vec<&'a str> {
+    required
+        .iter()
+        .copied()
+        .filter(|name| !isa_supports(isa, name))
+        .collect()
+}
+
+/// Parse a sysfs cache size (`32K`, `2048K`, `4M`) into bytes
+#[must_use]
+pub fn parse_cache_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (digits, unit) = match size.char_indices().find(|(_, ch)| !ch.is_ascii_digit()) {
+        Some((index, _)) => size.split_at(index),
+        None => (size, ""),
+    };
+    let multiplier = match unit.trim().to_ascii_uppercase().as_str() {
+        "" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return None,
+    };
+    digits.parse::<u64>().ok().map(|n| n * multiplier)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -691,4 +853,180 @@ mod tests {
         assert!(detail.contains("Enabled"));
         assert!(!detail.contains("VLEN"));
     }
+
+    #[test]
+    fn test_isa_supports() {
+        assert!(isa_supports(ISA_VISIONFIVE2, "zba"));
+        assert!(isa_supports(ISA_VISIONFIVE2, "Zbb"));
+        assert!(!isa_supports(ISA_VISIONFIVE2, "zbs"));
+        assert!(isa_supports(ISA_VISIONFIVE2, "c"));
+        // The "v" of the rv64 prefix is not the V extension
+        assert!(!isa_supports(ISA_VISIONFIVE2, "v"));
+        // G implies IMAFD, Zicsr and Zifencei
+        assert!(isa_supports("rv64gc", "d"));
+        assert!(isa_supports("rv64gc", "zifencei"));
+    }
+
+    #[test]
+    fn test_get_profile() {
+        let (name, required) = get_profile("rva23").unwrap();
+        assert_eq!(name, "RVA23U64");
+        assert!(required.contains(&"zvbb"));
+        assert_eq!(get_profile("RVA22U64").map(|(n, _)| n), Some("RVA22U64"));
+        assert!(get_profile("rvb23").is_none());
+    }
+
+    #[test]
+    fn test_extension_doc() {
+        assert!(extension_doc("V").unwrap().contains("vsetvli"));
+        assert!(extension_doc(" zicond ").is_some());
+        assert!(extension_doc("zbs").is_none());
+        // Every documented extension exists in the tables
+        for (name, doc) in EXTENSION_DOCS {
+            let known = STANDARD_EXTENSIONS
+                .iter()
+                .any(|(c, _, _)| c.to_string() == *name)
+                || Z_EXTENSIONS.iter().any(|(id, _, _, _)| id == name)
+                || S_EXTENSIONS.iter().any(|(id, _, _, _)| id == name);
+            assert!(known, "{name}");
+            assert!(doc.contains("\n\n"), "{name} has a single paragraph");
+        }
+    }
+
+    #[test]
+    fn test_missing_extensions() {
+        let (_, rva20) = get_profile("rva20").unwrap();
+        assert!(missing_extensions(ISA_VISIONFIVE2, rva20).is_empty());
+        let (_, rva22) = get_profile("rva22").unwrap();
+        let missing = missing_extensions(ISA_VISIONFIVE2, rva22);
+        assert!(missing.contains(&"zbs"));
+        assert!(!missing.contains(&"zba"));
+    }
+
+    #[test]
+    fn test_profiles_are_cumulative() {
+        for pair in PROFILES.windows(2) {
+            let (_, _, older) = pair[0];
+            let (_, _, newer) = pair[1];
+            assert!(older.iter().all(|e| newer.contains(e)));
+        }
+    }
+
+    #[test]
+    fn test_parse_cache_size() {
+        assert_eq!(parse_cache_size("32K"), Some(32 * 1024));
+        assert_eq!(parse_cache_size("2048K\n"), Some(2 * 1024 * 1024));
+        assert_eq!(parse_cache_size("4M"), Some(4 * 1024 * 1024));
+        assert_eq!(parse_cache_size("512"), Some(512));
+        assert_eq!(parse_cache_size("K"), None);
+        assert_eq!(parse_cache_size("32X"), None);
+    }
+
+    // === shorthand expansion tests ===
+
+    #[test]
+    fn test_expand_isa() {
+        assert_eq!(expand_isa("rv64gc"), "rv64imafdc_zicsr_zifencei");
+        assert_eq!(
+            expand_isa("RV64GC_Zicsr_Zba"),
+            "rv64imafdc_zifencei_zicsr_zba"
+        );
+        assert_eq!(expand_isa("rv64imacb"), "rv64imacb_zba_zbb_zbs");
+        assert_eq!(expand_isa(ISA_VISIONFIVE2), ISA_VISIONFIVE2);
+        assert_eq!(expand_isa("unknown"), "unknown");
+    }
+
+    #[test]
+    fn test_shorthands_consistent_across_parsers() {
+        let names = |exts: Vec<(String, String)>| -> Vec<String> {
+            exts.into_iter().map(|(name, _)| name).collect()
+        };
+        assert_eq!(
+            names(parse_extensions_explained("rv64gc")),
+            ["I", "M", "A", "F", "D", "C"]
+        );
+        assert_eq!(parse_extensions_compact("rv64gc"), "I M A F D C");
+        assert_eq!(
+            names(parse_z_extensions_explained("rv64gc")),
+            ["Zicsr", "Zifencei"]
+        );
+
+        assert_eq!(parse_z_extensions("rv64imacb"), "zba zbb zbs");
+        let category: Vec<String> = parse_z_extensions_with_category("rv64imacb")
+            .into_iter()
+            .map(|ext| ext.name)
+            .collect();
+        assert_eq!(category, ["Zba", "Zbb", "Zbs"]);
+        assert!(get_all_z_extensions_with_status("rv64imacb")
+            .iter()
+            .any(|ext| ext.name == "Zbs" && ext.supported));
+        assert!(isa_supports("rv64imacb", "zbs"));
+    }
+
+    // === canonical ordering tests ===
+
+    #[test]
+    fn test_canonical_order_and_dedup() {
+        // Kernel order with a repeated extension
+        let isa = "rv64imafdc_zba_zicsr_zfh_zicsr_zbb_svpbmt_sstc_svpbmt";
+        assert_eq!(parse_z_extensions(isa), "zicsr zfh zba zbb");
+        assert_eq!(parse_s_extensions(isa), "sstc svpbmt");
+
+        let explained: Vec<String> = parse_z_extensions_explained(isa)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(explained, ["Zicsr", "Zfh", "Zba", "Zbb"]);
+        let category: Vec<String> = parse_z_extensions_with_category(isa)
+            .into_iter()
+            .map(|ext| ext.name)
+            .collect();
+        assert_eq!(category, explained);
+
+        let all = get_all_z_extensions_with_status(isa);
+        assert_eq!(all[0].name, "Zic64b");
+        assert_eq!(all.len(), Z_EXTENSIONS.len());
+        let supported: Vec<&str> = all
+            .iter()
+            .filter(|ext| ext.supported)
+            .map(|ext| ext.name.as_str())
+            .collect();
+        assert_eq!(supported, explained);
+    }
+
+    // === per-hart ISA combination tests ===
+
+    #[test]
+    fn test_isa_intersection_and_union() {
+        // Big/little cores where only the big ones have V and Zfh
+        let big = "rv64imafdcv_zicsr_zfh_zba";
+        let little = "rv64imafdc_zicsr_zba_zbb";
+        assert_eq!(
+            isa_intersection(&[big, little, big]).as_deref(),
+            Some("rv64imafdc_zicsr_zba")
+        );
+        assert_eq!(
+            isa_union(&[big, little]).as_deref(),
+            Some("rv64imafdcv_zicsr_zfh_zba_zbb")
+        );
+        assert_eq!(
+            isa_difference("rv64imafdcv_zicsr_zfh_zba_zbb", "rv64imafdc_zicsr_zba"),
+            ["v", "zfh", "zbb"]
+        );
+    }
+
+    #[test]
+    fn test_isa_intersection_identical_and_empty() {
+        assert_eq!(
+            isa_intersection(&[ISA_VISIONFIVE2, ISA_VISIONFIVE2]).as_deref(),
+            Some(ISA_VISIONFIVE2)
+        );
+        assert_eq!(isa_union(&["rv64gc"]).as_deref(), Some("rv64gc"));
+        assert_eq!(isa_intersection(&[]), None);
+        // G is expanded before comparing
+        assert_eq!(
+            isa_intersection(&["rv64gc", "rv64imafdc_zicsr"]).as_deref(),
+            Some("rv64imafdc_zicsr")
+        );
+    }
 }
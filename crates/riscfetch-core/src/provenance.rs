@@ -0,0 +1,300 @@
+//! Where each collected value came from
+//!
+//! Collectors report a `Source` next to the value so wrong or missing fields
+//! on new boards can be traced back to the file, command or fallback used.
+//! File reads, commands and fallbacks are also logged with `tracing`
+//! (`riscfetch --debug`).
+
+use crate::container::container_with_source;
+use crate::cores::core_with_source;
+use crate::cpufreq::cpufreq_with_source;
+use crate::displays::display_info_with_source;
+use crate::firmware::firmware_info_with_source;
+use crate::gpu::gpu_info_with_source;
+use crate::hardware::{
+    board_info_with_source, cache_info_with_source, hardware_ids_with_source,
+    hart_count_with_source, isa_string_with_source, vector_detail_with_source,
+};
+use crate::init::init_system_with_source;
+use crate::kernel::kernel_config_with_source;
+use crate::mmc::mmc_info_with_source;
+use crate::regulators::regulators_with_source;
+use crate::sensors::sensors_with_source;
+use crate::system::{kernel_info_with_source, os_info_with_source};
+use crate::thermal::thermal_zones_with_source;
+use crate::types::FieldWarning;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Origin of one field's value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// Read from a file (procfs, sysfs, device tree, /etc)
+    File(String),
+    /// Output of an external command
+    Command(&'static str),
+    /// Returned by a system call
+    Syscall(&'static str),
+    /// Read from a CPU control and status register
+    Csr(&'static str),
+    /// Provided by a library (which reads the given files itself)
+    Library(&'static str),
+    /// Computed from another value
+    Derived(&'static str),
+    /// Nothing found; a default value is shown instead
+    Fallback { value: &'static str, tried: String },
+    /// Nothing found and nothing shown
+    Missing { tried: String },
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{path}"),
+            Self::Command(command) => write!(f, "`{command}`"),
+            Self::Syscall(call) => write!(f, "{call}"),
+            Self::Csr(csr) => write!(f, "the {csr} CSR"),
+            Self::Library(library) => write!(f, "{library}"),
+            Self::Derived(from) => write!(f, "derived from {from}"),
+            Self::Fallback { value, tried } => {
+                write!(f, "fallback \"{value}\" (not found in {tried})")
+            }
+            Self::Missing { tried } => write!(f, "missing (not found in {tried})"),
+        }
+    }
+}
+
+impl Source {
+    /// Why the field is empty or shows a fallback; None when a value was found
+    #[must_use]
+    pub fn warning(&self) -> Option<String> {
+        match self {
+            Self::Fallback { value, tried } => {
+                Some(format!("not found in {tried}; \"{value}\" shown instead"))
+            }
+            Self::Missing { tried } => Some(format!("not found in {tried}")),
+            _ => None,
+        }
+    }
+}
+
+/// Log where a getter's value came from, or the fallback it took, and
+/// return the value
+pub(crate) fn traced<T>(field: &'static str, (value, source): (T, Source)) -> T {
+    match source.warning() {
+        Some(reason) => tracing::debug!(field, %reason, "fallback taken"),
+        None => tracing::debug!(field, %source, "collected"),
+    }
+    value
+}
+
+/// `fs::read_to_string`, logging the path and the outcome
+pub(crate) fn read_file(path: impl AsRef<Path>) -> io::Result<String> {
+    let path = path.as_ref();
+    let result = fs::read_to_string(path);
+    match &result {
+        Ok(content) => tracing::trace!(path = %path.display(), bytes = content.len(), "read"),
+        Err(e) => tracing::trace!(path = %path.display(), error = %e, "read failed"),
+    }
+    result
+}
+
+/// Run a command and capture its output, logging the outcome
+pub(crate) fn run_command(program: &str, args: &[&str]) -> io::Result<Output> {
+    let result = Command::new(program).args(args).output();
+    match &result {
+        Ok(output) => tracing::trace!(program, ?args, status = %output.status, "ran"),
+        Err(e) => tracing::trace!(program, ?args, error = %e, "could not run"),
+    }
+    result
+}
+
+/// Ids of every field riscfetch collects, in display order
+pub const FIELD_IDS: [&str; 24] = [
+    "isa",
+    "vector",
+    "harts",
+    "hwids",
+    "cache",
+    "board",
+    "os",
+    "kernel",
+    "init",
+    "display",
+    "gpu",
+    "memory",
+    "disk",
+    "mmc",
+    "uptime",
+    "load",
+    "sensors",
+    "rails",
+    "firmware",
+    "kconfig",
+    "core",
+    "cpufreq",
+    "thermal",
+    "container",
+];
+
+/// Fields of `RiscvInfo` (and the RISC-V part of `SystemInfo`)
+pub(crate) const RISCV_FIELD_IDS: [&str; 5] = ["isa", "vector", "harts", "hwids", "cache"];
+
+/// Fields of `SystemInfo`
+pub(crate) const SYSTEM_FIELD_IDS: [&str; 12] = [
+    "isa", "vector", "harts", "hwids", "cache", "board", "os", "kernel", "memory", "uptime",
+    "load", "sensors",
+];
+
+/// Source of one field, by id; unknown ids are Missing
+fn source_of(id: &str) -> Source {
+    match id {
+        "isa" => isa_string_with_source().1,
+        "vector" => vector_detail_with_source().1,
+        "harts" => hart_count_with_source().1,
+        "hwids" => hardware_ids_with_source().1,
+        "cache" => cache_info_with_source().1,
+        "board" => board_info_with_source().1,
+        "os" => os_info_with_source().1,
+        "kernel" => kernel_info_with_source().1,
+        "init" => init_system_with_source().1,
+        "display" => display_info_with_source().1,
+        "gpu" => gpu_info_with_source().1,
+        "memory" => Source::Library("sysinfo (/proc/meminfo), /proc/device-tree/reserved-memory"),
+        "disk" => Source::Library("sysinfo (/proc/mounts, statvfs)"),
+        "mmc" => mmc_info_with_source().1,
+        "uptime" => Source::Library("sysinfo (/proc/uptime)"),
+        "load" => Source::File("/proc/loadavg".to_string()),
+        "sensors" => sensors_with_source().1,
+        "rails" => regulators_with_source().1,
+        "firmware" => firmware_info_with_source().1,
+        "kconfig" => kernel_config_with_source().1,
+        "core" => core_with_source().1,
+        "cpufreq" => cpufreq_with_source().1,
+        "thermal" => thermal_zones_with_source().1,
+        "container" => container_with_source().1,
+        _ => Source::Missing {
+            tried: "no collector".to_string(),
+        },
+    }
+}
+
+/// Source of every field riscfetch collects, keyed by field id
+/// (`isa`, `vector`, `harts`, `hwids`, `cache`, `board`, `os`, `kernel`,
+/// `init`, `display`, `gpu`, `memory`, `disk`, `mmc`, `uptime`, `load`,
+/// `sensors`, `rails`, `firmware`, `kconfig`, `core`, `cpufreq`, `thermal`,
+/// `container`)
+#[must_use]
+pub fn collect_sources() -> Vec<(&'static str, Source)> {
+    FIELD_IDS.iter().map(|id| (*id, source_of(id))).collect()
+}
+
+/// A warning for each of the given fields that could not be determined
+#[must_use]
+pub fn collect_warnings(ids: &[&str]) -> Vec<FieldWarning> {
+    ids.iter()
+        .filter_map(|id| {
+            source_of(id).warning().map(|reason| FieldWarning {
+                field: (*id).to_string(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_display() {
+        assert_eq!(
+            Source::File("/proc/device-tree/model".to_string()).to_string(),
+            "/proc/device-tree/model"
+        );
+        assert_eq!(Source::Command("glxinfo -B").to_string(), "`glxinfo -B`");
+        assert_eq!(Source::Syscall("uname(2)").to_string(), "uname(2)");
+        assert_eq!(
+            Source::Fallback {
+                value: "Linux",
+                tried: "/etc/os-release".to_string()
+            }
+            .to_string(),
+            "fallback \"Linux\" (not found in /etc/os-release)"
+        );
+    }
+
+    #[test]
+    fn test_source_warning() {
+        assert_eq!(Source::Syscall("uname(2)").warning(), None);
+        assert_eq!(
+            Source::Missing {
+                tried: "/sys/devices/system/cpu/cpu0/cache/index*/size".to_string()
+            }
+            .warning()
+            .as_deref(),
+            Some("not found in /sys/devices/system/cpu/cpu0/cache/index*/size")
+        );
+        assert_eq!(
+            Source::Fallback {
+                value: "Linux",
+                tried: "/etc/os-release".to_string()
+            }
+            .warning()
+            .as_deref(),
+            Some("not found in /etc/os-release; \"Linux\" shown instead")
+        );
+    }
+
+    #[test]
+    fn test_collect_sources_covers_fields() {
+        let ids: Vec<&str> = collect_sources().iter().map(|(id, _)| *id).collect();
+        assert_eq!(
+            ids,
+            [
+                "isa",
+                "vector",
+                "harts",
+                "hwids",
+                "cache",
+                "board",
+                "os",
+                "kernel",
+                "init",
+                "display",
+                "gpu",
+                "memory",
+                "disk",
+                "mmc",
+                "uptime",
+                "load",
+                "sensors",
+                "rails",
+                "firmware",
+                "kconfig",
+                "core",
+                "cpufreq",
+                "thermal",
+                "container"
+            ]
+        );
+        assert!(SYSTEM_FIELD_IDS.iter().all(|id| ids.contains(id)));
+        assert!(RISCV_FIELD_IDS
+            .iter()
+            .all(|id| SYSTEM_FIELD_IDS.contains(id)));
+    }
+
+    #[test]
+    fn test_collect_warnings_only_for_missing() {
+        let sources = collect_sources();
+        let warnings = collect_warnings(&SYSTEM_FIELD_IDS);
+        for warning in &warnings {
+            let (_, source) = sources.iter().find(|(id, _)| *id == warning.field).unwrap();
+            assert_eq!(source.warning().as_ref(), Some(&warning.reason));
+        }
+        assert!(!warnings.iter().any(|warning| warning.field == "load"));
+    }
+}
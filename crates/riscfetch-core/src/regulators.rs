@@ -0,0 +1,111 @@
+//! Voltage regulators (power rails) from sysfs
+//!
+//! `/sys/class/regulator/regulator.N` shows each rail's name, whether it is
+//! enabled and its voltage, which is where undervolting or overclocking the
+//! CPU cluster shows up (e.g. `dvdd_cpu` on the Lichee Pi 4A).
+
+use crate::provenance::{read_file, traced, Source};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const REGULATOR_DIR: &str = "/sys/class/regulator";
+
+/// Name fragments of rails that feed the CPU cluster
+const CPU_RAIL_KEYWORDS: [&str; 3] = ["cpu", "core", "cluster"];
+
+/// One regulator
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Regulator {
+    pub name: String,
+    /// `state` is `enabled`; None when the driver reports `unknown`
+    pub enabled: Option<bool>,
+    /// Output voltage, for voltage regulators
+    pub microvolts: Option<u64>,
+}
+
+impl Regulator {
+    /// Whether the name suggests the rail powers the CPU cluster
+    #[must_use]
+    pub fn is_cpu_rail(&self) -> bool {
+        let name = self.name.to_lowercase();
+        CPU_RAIL_KEYWORDS
+            .iter()
+            .any(|keyword| name.contains(keyword))
+    }
+}
+
+/// Every regulator in sysfs, in `regulator.N` order
+#[must_use]
+pub fn get_regulators() -> Vec<Regulator> {
+    traced("rails", regulators_with_source())
+}
+
+pub(crate) fn regulators_with_source() -> (Vec<Regulator>, Source) {
+    let source = Source::File(format!("{REGULATOR_DIR}/regulator.*"));
+    let Ok(entries) = fs::read_dir(REGULATOR_DIR) else {
+        return (Vec::new(), source);
+    };
+    let mut regulators: Vec<(u32, Regulator)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.file_name().to_string_lossy().into_owned();
+            let index = dir.strip_prefix("regulator.")?.parse().ok()?;
+            let path = entry.path();
+            let read = |file: &str| read_file(path.join(file)).ok();
+            let regulator = parse_regulator(
+                &read("name")?,
+                read("state").as_deref(),
+                read("microvolts").as_deref(),
+            );
+            Some((index, regulator))
+        })
+        .collect();
+    regulators.sort_by_key(|(index, _)| *index);
+    // Boards without regulators in sysfs are not missing anything
+    (regulators.into_iter().map(|(_, r)| r).collect(), source)
+}
+
+/// Regulator from its sysfs `name`, `state` and `microvolts` files
+fn parse_regulator(name: &str, state: Option<&str>, microvolts: Option<&str>) -> Regulator {
+    Regulator {
+        name: name.trim().to_string(),
+        enabled: match state.map(str::trim) {
+            Some("enabled") => Some(true),
+            Some("disabled") => Some(false),
+            _ => None,
+        },
+        microvolts: microvolts.and_then(|uv| uv.trim().parse().ok()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_regulator() {
+        assert_eq!(
+            parse_regulator("dvdd_cpu\n", Some("enabled\n"), Some("800000\n")),
+            Regulator {
+                name: "dvdd_cpu".to_string(),
+                enabled: Some(true),
+                microvolts: Some(800_000),
+            }
+        );
+        let fixed = parse_regulator("regulator-dummy", Some("unknown"), None);
+        assert_eq!(fixed.enabled, None);
+        assert_eq!(fixed.microvolts, None);
+        assert_eq!(
+            parse_regulator("vdd_gpu", Some("disabled"), Some("")).enabled,
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_is_cpu_rail() {
+        let rail = |name: &str| parse_regulator(name, None, None);
+        assert!(rail("dvdd_cpu").is_cpu_rail());
+        assert!(rail("VDD_CORE").is_cpu_rail());
+        assert!(!rail("vcc_3v3").is_cpu_rail());
+    }
+}
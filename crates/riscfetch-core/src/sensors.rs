@@ -0,0 +1,286 @@
+//! hwmon fan, voltage and power sensors
+//!
+//! Fan controllers, PMICs and current monitors register with the hwmon
+//! class, so `/sys/class/hwmon/hwmon*/fan*_input` (RPM), `in*_input`
+//! (millivolts) and `power*_input` (microwatts) cover them without
+//! board-specific code. Monitors without power channels (INA3221) report
+//! each channel's bus voltage and current, whose product is the power.
+
+use crate::provenance::{read_file, traced, Source};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const HWMON_DIR: &str = "/sys/class/hwmon";
+
+/// One hwmon fan
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FanReading {
+    /// hwmon chip name, e.g. `pwmfan`
+    pub chip: String,
+    /// `fanN_label`, or `fanN` without one
+    pub label: String,
+    pub rpm: u32,
+}
+
+/// One hwmon voltage rail
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VoltageReading {
+    /// hwmon chip name, e.g. `da9063`
+    pub chip: String,
+    /// `inN_label`, or `inN` without one
+    pub label: String,
+    pub millivolts: i64,
+}
+
+/// One measured power draw (INA219, INA226, INA3221 and the like)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PowerReading {
+    /// hwmon chip name, e.g. `ina3221`
+    pub chip: String,
+    /// `powerN_label` / `currN_label` / `inN_label`, or the channel name
+    pub label: String,
+    pub microwatts: i64,
+}
+
+/// Every fan, voltage and power sensor
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sensors {
+    pub fans: Vec<FanReading>,
+    pub voltages: Vec<VoltageReading>,
+    #[serde(default)]
+    pub powers: Vec<PowerReading>,
+}
+
+impl Sensors {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fans.is_empty() && self.voltages.is_empty() && self.powers.is_empty()
+    }
+
+    /// Best estimate of the whole board's draw: the largest reading, since
+    /// a monitor on the supply input also covers the rails measured behind it
+    #[must_use]
+    pub fn board_microwatts(&self) -> Option<i64> {
+        self.powers.iter().map(|p| p.microwatts).max()
+    }
+}
+
+/// Fan speeds, voltage rails and power draw (empty without hwmon sensors)
+#[must_use]
+pub fn get_sensors() -> Sensors {
+    traced("sensors", sensors_with_source())
+}
+
+/// Current board power in microwatts (see [`Sensors::board_microwatts`]),
+/// without logging, for sampling in a loop
+#[must_use]
+pub fn get_board_power() -> Option<i64> {
+    sensors_with_source().0.board_microwatts()
+}
+
+pub(crate) fn sensors_with_source() -> (Sensors, Source) {
+    let mut sensors = Sensors::default();
+    let Ok(entries) = fs::read_dir(HWMON_DIR) else {
+        return (sensors, Source::File(format!("{HWMON_DIR}/hwmon*")));
+    };
+    let mut chips: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+    chips.sort_by_key(|path| hwmon_index(&path.to_string_lossy()));
+    for path in chips {
+        let chip = read_file(path.join("name")).unwrap_or_default();
+        let files: Vec<(String, String)> = fs::read_dir(&path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let wanted = ["fan", "in", "curr", "power"]
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix))
+                    && (name.ends_with("_input") || name.ends_with("_label"));
+                if !wanted {
+                    return None;
+                }
+                Some((name, read_file(entry.path()).ok()?))
+            })
+            .collect();
+        let chip_sensors = parse_hwmon_chip(chip.trim(), &files);
+        sensors.fans.extend(chip_sensors.fans);
+        sensors.voltages.extend(chip_sensors.voltages);
+        sensors.powers.extend(chip_sensors.powers);
+    }
+    // No sensor is a valid answer on most boards, not a missing value
+    (sensors, Source::File(format!("{HWMON_DIR}/hwmon*")))
+}
+
+/// Trailing number of `hwmon12` or `fan3`, for numeric ordering
+fn hwmon_index(name: &str) -> u32 {
+    let start = name
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    name[start..].parse().unwrap_or(u32::MAX)
+}
+
+/// Sensors of one hwmon chip from its `(file name, contents)` pairs
+fn parse_hwmon_chip(chip: &str, files: &[(String, String)]) -> Sensors {
+    let label = |channel: &str| {
+        files
+            .iter()
+            .find(|(name, _)| name.strip_suffix("_label") == Some(channel))
+            .map_or_else(|| channel.to_string(), |(_, text)| text.trim().to_string())
+    };
+    let mut inputs: Vec<(&str, &str)> = files
+        .iter()
+        .filter_map(|(name, text)| Some((name.strip_suffix("_input")?, text.trim())))
+        .collect();
+    inputs.sort_by_key(|(channel, _)| (channel.starts_with("in"), hwmon_index(channel)));
+    let input = |channel: &str| {
+        inputs
+            .iter()
+            .find(|(name, _)| *name == channel)
+            .and_then(|(_, text)| text.parse::<i64>().ok())
+    };
+
+    let mut sensors = Sensors::default();
+    for &(channel, text) in &inputs {
+        if channel.starts_with("curr") {
+            continue;
+        }
+        if channel.starts_with("power") {
+            if let Ok(microwatts) = text.parse() {
+                sensors.powers.push(PowerReading {
+                    chip: chip.to_string(),
+                    label: label(channel),
+                    microwatts,
+                });
+            }
+        } else if channel.starts_with("fan") {
+            if let Ok(rpm) = text.parse() {
+                sensors.fans.push(FanReading {
+                    chip: chip.to_string(),
+                    label: label(channel),
+                    rpm,
+                });
+            }
+        } else if let Ok(millivolts) = text.parse() {
+            sensors.voltages.push(VoltageReading {
+                chip: chip.to_string(),
+                label: label(channel),
+                millivolts,
+            });
+        }
+    }
+    // Without power channels: bus millivolts times milliamps of the same
+    // channel number is microwatts
+    if sensors.powers.is_empty() {
+        for &(channel, _) in inputs.iter().filter(|(c, _)| c.starts_with("curr")) {
+            let bus = format!("in{}", &channel[4..]);
+            if let (Some(milliamps), Some(millivolts)) = (input(channel), input(&bus)) {
+                let labelled = files
+                    .iter()
+                    .any(|(name, _)| name.strip_suffix("_label") == Some(channel));
+                sensors.powers.push(PowerReading {
+                    chip: chip.to_string(),
+                    label: if labelled {
+                        label(channel)
+                    } else {
+                        label(&bus)
+                    },
+                    microwatts: millivolts * milliamps,
+                });
+            }
+        }
+    }
+    sensors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(list: &[(&str, &str)]) -> Vec<(String, String)> {
+        list.iter()
+            .map(|(name, text)| ((*name).to_string(), (*text).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_hwmon_fan() {
+        let sensors = parse_hwmon_chip("pwmfan", &files(&[("fan1_input", "3200\n")]));
+        assert_eq!(
+            sensors.fans,
+            vec![FanReading {
+                chip: "pwmfan".to_string(),
+                label: "fan1".to_string(),
+                rpm: 3200,
+            }]
+        );
+        assert!(sensors.voltages.is_empty());
+    }
+
+    #[test]
+    fn test_parse_hwmon_voltages() {
+        let sensors = parse_hwmon_chip(
+            "da9063",
+            &files(&[
+                ("in10_input", "1800\n"),
+                ("in2_input", "900\n"),
+                ("in2_label", "vdd_cpu\n"),
+                ("in10_label", "vcc_1v8\n"),
+                ("in3_input", "garbage\n"),
+            ]),
+        );
+        let rails: Vec<(&str, i64)> = sensors
+            .voltages
+            .iter()
+            .map(|v| (v.label.as_str(), v.millivolts))
+            .collect();
+        assert_eq!(rails, [("vdd_cpu", 900), ("vcc_1v8", 1800)]);
+        assert!(!sensors.is_empty());
+        assert!(Sensors::default().is_empty());
+    }
+
+    #[test]
+    fn test_parse_hwmon_power() {
+        // INA219: power channel in microwatts
+        let sensors = parse_hwmon_chip(
+            "ina219",
+            &files(&[
+                ("in1_input", "5080\n"),
+                ("curr1_input", "950\n"),
+                ("power1_input", "4826000\n"),
+            ]),
+        );
+        assert_eq!(sensors.powers.len(), 1);
+        assert_eq!(sensors.powers[0].label, "power1");
+        assert_eq!(sensors.board_microwatts(), Some(4_826_000));
+
+        // INA3221: bus voltage times current per channel
+        let sensors = parse_hwmon_chip(
+            "ina3221",
+            &files(&[
+                ("in1_input", "5000\n"),
+                ("in1_label", "vdd_in\n"),
+                ("curr1_input", "800\n"),
+                ("in2_input", "800\n"),
+                ("in2_label", "vdd_cpu\n"),
+                ("curr2_input", "1250\n"),
+                ("in4_input", "12\n"),
+            ]),
+        );
+        let powers: Vec<(&str, i64)> = sensors
+            .powers
+            .iter()
+            .map(|p| (p.label.as_str(), p.microwatts))
+            .collect();
+        assert_eq!(powers, [("vdd_in", 4_000_000), ("vdd_cpu", 1_000_000)]);
+        assert_eq!(sensors.voltages.len(), 3);
+        assert_eq!(sensors.board_microwatts(), Some(4_000_000));
+    }
+
+    #[test]
+    fn test_hwmon_index() {
+        assert_eq!(hwmon_index("/sys/class/hwmon/hwmon12"), 12);
+        assert_eq!(hwmon_index("fan3"), 3);
+        assert_eq!(hwmon_index("name"), u32::MAX);
+    }
+}
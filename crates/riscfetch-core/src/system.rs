@@ -1,18 +1,101 @@
-//! General system information (memory, uptime, kernel, OS)
+//! General system information (memory, disks, uptime, kernel, OS)
 
+use crate::provenance::{read_file, traced, Source};
+use crate::types::{LoadAverage, MemoryBreakdown};
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs;
-use std::process::Command;
-use sysinfo::System;
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::{Duration, Instant};
+use sysinfo::{CpuRefreshKind, Disks, System};
+
+/// Mount points reported by `get_disk_usage()`, in display order
+const DISK_MOUNT_POINTS: [&str; 2] = ["/", "/boot"];
+
+/// Device-tree node whose children are firmware / driver carve-outs
+const DT_RESERVED_MEMORY: &str = "/proc/device-tree/reserved-memory";
+
+/// How long a memory reading is reused before sysinfo reads it again, so
+/// the collectors of one render share a single refresh
+const MEMORY_MAX_AGE: Duration = Duration::from_millis(250);
+
+/// The one sysinfo handle; the CPU list is loaded on first use and memory
+/// whenever the last reading is older than `MEMORY_MAX_AGE`
+struct SharedSystem {
+    sys: System,
+    cpus_loaded: bool,
+    memory_read_at: Option<Instant>,
+}
+
+fn shared_system() -> MutexGuard<'static, SharedSystem> {
+    static SYSTEM: OnceLock<Mutex<SharedSystem>> = OnceLock::new();
+    SYSTEM
+        .get_or_init(|| {
+            Mutex::new(SharedSystem {
+                sys: System::new(),
+                cpus_loaded: false,
+                memory_read_at: None,
+            })
+        })
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Number of CPUs sysinfo sees
+pub(crate) fn sysinfo_cpu_count() -> usize {
+    let mut shared = shared_system();
+    if !shared.cpus_loaded {
+        shared.sys.refresh_cpu_list(CpuRefreshKind::new());
+        shared.cpus_loaded = true;
+    }
+    shared.sys.cpus().len()
+}
+
+/// Used and total memory in bytes from the shared sysinfo handle
+pub(crate) fn sysinfo_memory() -> (u64, u64) {
+    let mut shared = shared_system();
+    let stale = shared
+        .memory_read_at
+        .is_none_or(|read_at| read_at.elapsed() > MEMORY_MAX_AGE);
+    if stale {
+        shared.sys.refresh_memory();
+        shared.memory_read_at = Some(Instant::now());
+    }
+    (shared.sys.used_memory(), shared.sys.total_memory())
+}
+
+/// Space on one mounted filesystem
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub mount_point: String,
+    /// Filesystem type, e.g. `ext4`
+    pub file_system: String,
+    /// Total minus available, so blocks reserved for root count as used
+    pub used_bytes: u64,
+    pub total_bytes: u64,
+}
+
+/// Numeric part of a kernel release such as `6.6.20-starfive`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct KernelVersion {
+    pub major: u32,
+    pub minor: u32,
+    /// 0 when the release has no patch level (`6.8-rc1`)
+    pub patch: u32,
+}
+
+/// `major.minor`, the granularity feature gates are given in
+impl fmt::Display for KernelVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
 
 /// Get memory usage as formatted string
 #[must_use]
 #[allow(clippy::cast_precision_loss)]
 pub fn get_memory_info() -> String {
-    let mut sys = System::new();
-    sys.refresh_memory();
-
-    let total_mem = sys.total_memory();
-    let used_mem = sys.used_memory();
+    let (used_mem, total_mem) = sysinfo_memory();
 
     let total_gb = total_mem as f64 / 1_073_741_824.0;
     let used_gb = used_mem as f64 / 1_073_741_824.0;
@@ -23,43 +106,249 @@ pub fn get_memory_info() -> String {
 /// Get memory information as bytes
 #[must_use]
 pub fn get_memory_bytes() -> (u64, u64) {
-    let mut sys = System::new();
-    sys.refresh_memory();
-    (sys.used_memory(), sys.total_memory())
+    sysinfo_memory()
+}
+
+/// Available, buffers/cache and firmware-reserved memory (None where
+/// /proc/meminfo is unavailable)
+#[must_use]
+pub fn get_memory_breakdown() -> Option<MemoryBreakdown> {
+    let mut breakdown = parse_meminfo(&read_file("/proc/meminfo").ok()?)?;
+    breakdown.reserved_bytes = reserved_memory_bytes();
+    Some(breakdown)
+}
+
+/// Parse /proc/meminfo (`MemAvailable:  6123456 kB`); `reserved_bytes` is
+/// left None
+#[must_use]
+pub fn parse_meminfo(content: &str) -> Option<MemoryBreakdown> {
+    let kib = |key: &str| {
+        content.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.strip_prefix(':')?;
+            value.split_whitespace().next()?.parse::<u64>().ok()
+        })
+    };
+    let buffers_cache =
+        kib("Buffers").unwrap_or(0) + kib("Cached").unwrap_or(0) + kib("SReclaimable").unwrap_or(0);
+    Some(MemoryBreakdown {
+        available_bytes: kib("MemAvailable")? * 1024,
+        buffers_cache_bytes: buffers_cache * 1024,
+        reserved_bytes: None,
+    })
+}
+
+/// Total size of the device-tree `reserved-memory` children that are
+/// carved out of RAM; CMA pools and disabled nodes are left out
+fn reserved_memory_bytes() -> Option<u64> {
+    let root = std::path::Path::new(DT_RESERVED_MEMORY);
+    let cells = |name: &str| {
+        let bytes = fs::read(root.join(name)).ok()?;
+        Some(u32::from_be_bytes(bytes.get(..4)?.try_into().ok()?))
+    };
+    let address_cells = cells("#address-cells").unwrap_or(2);
+    let size_cells = cells("#size-cells").unwrap_or(2);
+    let total = fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter(|entry| {
+            let path = entry.path();
+            is_carve_out(
+                path.join("reusable").exists(),
+                fs::read(path.join("status")).ok().as_deref(),
+            )
+        })
+        .map(|entry| {
+            let path = entry.path();
+            // Statically placed regions have `reg`, dynamically placed ones only `size`
+            if let Ok(reg) = fs::read(path.join("reg")) {
+                reserved_reg_size(&reg, address_cells, size_cells)
+            } else if let Ok(size) = fs::read(path.join("size")) {
+                reserved_reg_size(&size, 0, size_cells)
+            } else {
+                0
+            }
+        })
+        .sum();
+    Some(total)
+}
+
+/// Whether a `reserved-memory` node takes its memory away from the kernel:
+/// `reusable` regions (CMA) are still counted in MemTotal, and nodes with a
+/// `status` other than `okay` are not reserved at all
+fn is_carve_out(reusable: bool, status: Option<&[u8]>) -> bool {
+    let okay = status.is_none_or(|status| {
+        matches!(
+            status.strip_suffix(b"\0").unwrap_or(status),
+            b"okay" | b"ok"
+        )
+    });
+    !reusable && okay
+}
+
+/// Sum of the sizes in a device-tree `reg` property: big-endian
+/// (address, size) pairs of `address_cells` and `size_cells` 32-bit cells
+fn reserved_reg_size(reg: &[u8], address_cells: u32, size_cells: u32) -> u64 {
+    if size_cells == 0 {
+        return 0;
+    }
+    let address_bytes = address_cells as usize * 4;
+    reg.chunks_exact(address_bytes + size_cells as usize * 4)
+        .map(|chunk| {
+            chunk[address_bytes..]
+                .chunks_exact(4)
+                .fold(0u64, |size, cell| {
+                    (size << 32)
+                        | u64::from(u32::from_be_bytes([cell[0], cell[1], cell[2], cell[3]]))
+                })
+        })
+        .sum()
+}
+
+/// Usage of the root filesystem, and of /boot when it is a separate mount
+#[must_use]
+pub fn get_disk_usage() -> Vec<DiskUsage> {
+    let disks = Disks::new_with_refreshed_list();
+    select_disks(
+        disks
+            .list()
+            .iter()
+            .map(|disk| DiskUsage {
+                mount_point: disk.mount_point().to_string_lossy().into_owned(),
+                file_system: disk.file_system().to_string_lossy().into_owned(),
+                used_bytes: disk.total_space().saturating_sub(disk.available_space()),
+                total_bytes: disk.total_space(),
+            })
+            .collect(),
+    )
+}
+
+/// Keep the mounts in `DISK_MOUNT_POINTS`, one each, in that order
+fn select_disks(disks: Vec<DiskUsage>) -> Vec<DiskUsage> {
+    DISK_MOUNT_POINTS
+        .iter()
+        .filter_map(|mount| disks.iter().find(|d| d.mount_point == *mount).cloned())
+        .collect()
 }
 
 /// Get kernel version
 #[must_use]
 pub fn get_kernel_info() -> String {
-    if let Ok(output) = Command::new("uname").arg("-r").output() {
-        let kernel = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !kernel.is_empty() {
-            return kernel;
+    traced("kernel", kernel_info_with_source())
+}
+
+pub(crate) fn kernel_info_with_source() -> (String, Source) {
+    if let Some(kernel) = uname_release().filter(|kernel| !kernel.is_empty()) {
+        return (kernel, Source::Syscall("uname(2)"));
+    }
+    (
+        "Unknown".to_string(),
+        Source::Fallback {
+            value: "Unknown",
+            tried: "uname(2)".to_string(),
+        },
+    )
+}
+
+/// `release` field of uname(2), called directly so minimal containers
+/// without coreutils still report the kernel
+#[cfg(unix)]
+fn uname_release() -> Option<String> {
+    // SAFETY: utsname is plain data; uname fills every field with a
+    // NUL-terminated string
+    unsafe {
+        let mut uts: libc::utsname = std::mem::zeroed();
+        if libc::uname(&mut uts) != 0 {
+            tracing::trace!(error = %std::io::Error::last_os_error(), "uname(2) failed");
+            return None;
         }
+        let release = std::ffi::CStr::from_ptr(uts.release.as_ptr());
+        let release = release.to_string_lossy().trim().to_string();
+        tracing::trace!(%release, "uname(2)");
+        Some(release)
     }
-    "Unknown".to_string()
+}
+
+#[cfg(not(unix))]
+fn uname_release() -> Option<String> {
+    None
+}
+
+/// Structured kernel version, if uname(2) returned one
+#[must_use]
+pub fn get_kernel_version() -> Option<KernelVersion> {
+    parse_kernel_version(&get_kernel_info())
+}
+
+/// Parse a release such as `6.6.20-starfive` or `5.15.0-1040-generic`
+#[must_use]
+pub fn parse_kernel_version(release: &str) -> Option<KernelVersion> {
+    let mut parts = release.trim().split(|c: char| !c.is_ascii_digit());
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+    Some(KernelVersion {
+        major,
+        minor,
+        patch,
+    })
 }
 
 /// Get OS name from /etc/os-release
 #[must_use]
 pub fn get_os_info() -> String {
-    if let Ok(content) = fs::read_to_string("/etc/os-release") {
+    traced("os", os_info_with_source())
+}
+
+pub(crate) fn os_info_with_source() -> (String, Source) {
+    if let Ok(content) = read_file("/etc/os-release") {
         for line in content.lines() {
             if line.starts_with("PRETTY_NAME=") {
                 if let Some(name) = line.split('=').nth(1) {
-                    return name.trim_matches('"').to_string();
+                    return (
+                        name.trim_matches('"').to_string(),
+                        Source::File("/etc/os-release (PRETTY_NAME)".to_string()),
+                    );
                 }
             }
         }
     }
 
-    "Linux".to_string()
+    (
+        "Linux".to_string(),
+        Source::Fallback {
+            value: "Linux",
+            tried: "/etc/os-release".to_string(),
+        },
+    )
+}
+
+/// Load averages from /proc/loadavg (None where procfs is unavailable)
+#[must_use]
+pub fn get_load_average() -> Option<LoadAverage> {
+    parse_loadavg(&read_file("/proc/loadavg").ok()?)
+}
+
+/// Parse the first three fields of /proc/loadavg (`0.52 0.41 0.30 1/123 4567`)
+#[must_use]
+pub fn parse_loadavg(content: &str) -> Option<LoadAverage> {
+    let mut values = content.split_whitespace().map(str::parse::<f64>);
+    Some(LoadAverage {
+        one: values.next()?.ok()?,
+        five: values.next()?.ok()?,
+        fifteen: values.next()?.ok()?,
+    })
 }
 
 /// Get uptime as formatted string
 #[must_use]
 pub fn get_uptime() -> String {
-    let uptime_secs = System::uptime();
+    format_uptime(System::uptime())
+}
+
+/// Format a duration in seconds as uptime (e.g. "3h 42m", "5m")
+#[must_use]
+pub fn format_uptime(uptime_secs: u64) -> String {
     let hours = uptime_secs / 3600;
     let minutes = (uptime_secs % 3600) / 60;
 
@@ -75,3 +364,96 @@ pub fn get_uptime() -> String {
 pub fn get_uptime_seconds() -> u64 {
     System::uptime()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disk(mount_point: &str) -> DiskUsage {
+        DiskUsage {
+            mount_point: mount_point.to_string(),
+            file_system: "ext4".to_string(),
+            used_bytes: 1,
+            total_bytes: 2,
+        }
+    }
+
+    #[test]
+    fn test_parse_kernel_version() {
+        let version = parse_kernel_version("6.6.20-starfive").unwrap();
+        assert_eq!((version.major, version.minor, version.patch), (6, 6, 20));
+        assert_eq!(version.to_string(), "6.6");
+        assert_eq!(parse_kernel_version("6.8-rc1").unwrap().patch, 0);
+        assert!(parse_kernel_version("5.15.0-1040-generic") < parse_kernel_version("6.1.0"));
+        assert_eq!(parse_kernel_version("Unknown"), None);
+        assert_eq!(parse_kernel_version("6"), None);
+    }
+
+    #[test]
+    fn test_parse_meminfo() {
+        let meminfo = "\
+MemTotal:        7999004 kB
+MemFree:         1000000 kB
+MemAvailable:    6000000 kB
+Buffers:          100000 kB
+Cached:          2000000 kB
+SwapCached:            0 kB
+SReclaimable:     150000 kB
+";
+        assert_eq!(
+            parse_meminfo(meminfo),
+            Some(MemoryBreakdown {
+                available_bytes: 6_000_000 * 1024,
+                buffers_cache_bytes: 2_250_000 * 1024,
+                reserved_bytes: None,
+            })
+        );
+        // Kernels before 3.14 have no MemAvailable
+        assert_eq!(parse_meminfo("MemTotal: 1000 kB\n"), None);
+    }
+
+    #[test]
+    fn test_reserved_reg_size() {
+        // Two regions with 2 address and 2 size cells: 0x1000_0000 and 0x80_0000
+        let reg = [
+            0u8, 0, 0, 0, 0x40, 0, 0, 0, 0, 0, 0, 0, 0x10, 0, 0, 0, //
+            0, 0, 0, 0, 0x50, 0, 0, 0, 0, 0, 0, 0, 0, 0x80, 0, 0,
+        ];
+        assert_eq!(reserved_reg_size(&reg, 2, 2), 0x1000_0000 + 0x80_0000);
+        // A dynamically placed region: `size` only, 1 cell
+        assert_eq!(reserved_reg_size(&[0, 0x40, 0, 0], 0, 1), 0x40_0000);
+        assert_eq!(reserved_reg_size(&[], 2, 2), 0);
+    }
+
+    #[test]
+    fn test_is_carve_out() {
+        assert!(is_carve_out(false, None));
+        assert!(is_carve_out(false, Some(b"okay\0")));
+        // A CMA pool stays usable by the kernel
+        assert!(!is_carve_out(true, None));
+        assert!(!is_carve_out(false, Some(b"disabled\0")));
+    }
+
+    #[test]
+    fn test_parse_loadavg() {
+        let load = parse_loadavg("0.52 0.41 0.30 1/123 4567\n").unwrap();
+        assert_eq!(
+            load,
+            LoadAverage {
+                one: 0.52,
+                five: 0.41,
+                fifteen: 0.30
+            }
+        );
+        assert!(parse_loadavg("0.52 0.41").is_none());
+        assert!(parse_loadavg("").is_none());
+    }
+
+    #[test]
+    fn test_select_disks() {
+        let selected = select_disks(vec![disk("/boot"), disk("/home"), disk("/"), disk("/")]);
+        let mounts: Vec<&str> = selected.iter().map(|d| d.mount_point.as_str()).collect();
+        assert_eq!(mounts, ["/", "/boot"]);
+        assert!(select_disks(vec![disk("/data")]).is_empty());
+    }
+}
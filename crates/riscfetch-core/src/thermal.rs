@@ -0,0 +1,154 @@
+//! Thermal zones and the harts they cover
+//!
+//! Each `/sys/class/thermal/thermal_zoneN` has a name (`type`) and a
+//! temperature in millidegrees Celsius. A zone covers the harts of the
+//! cpufreq policies bound to it as cooling devices (`thermal-cpufreq-K` or
+//! `cpufreq-cpuK`, K being the policy's first hart); without such a binding
+//! a zone named `cpuN-thermal` covers hart N.
+
+use crate::cpufreq::CPU_DIR;
+use crate::provenance::{read_file, traced, Source};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const THERMAL_DIR: &str = "/sys/class/thermal";
+
+/// One thermal zone with its current temperature
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ThermalZone {
+    /// sysfs directory name, e.g. `thermal_zone0`
+    pub zone: String,
+    /// Zone type, e.g. `cpu-thermal`
+    pub name: String,
+    pub millicelsius: i64,
+    /// Harts whose frequency this zone throttles, ascending
+    pub harts: Vec<usize>,
+}
+
+/// Every thermal zone with a readable temperature
+#[must_use]
+pub fn get_thermal_zones() -> Vec<ThermalZone> {
+    traced("thermal", thermal_zones_with_source())
+}
+
+pub(crate) fn thermal_zones_with_source() -> (Vec<ThermalZone>, Source) {
+    let mut dirs: Vec<_> = fs::read_dir(THERMAL_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("thermal_zone"))
+        })
+        .collect();
+    dirs.sort_by_key(|path| {
+        path.to_string_lossy()
+            .trim_start_matches(|c: char| !c.is_ascii_digit())
+            .parse::<u32>()
+            .unwrap_or(u32::MAX)
+    });
+    let zones: Vec<ThermalZone> = dirs.iter().filter_map(|dir| read_zone(dir)).collect();
+    let source = if zones.is_empty() {
+        Source::Missing {
+            tried: format!("{THERMAL_DIR}/thermal_zone*/temp"),
+        }
+    } else {
+        Source::File(format!("{THERMAL_DIR}/thermal_zone*"))
+    };
+    (zones, source)
+}
+
+fn read_zone(dir: &Path) -> Option<ThermalZone> {
+    let millicelsius = read_file(dir.join("temp")).ok()?.trim().parse().ok()?;
+    let name = read_file(dir.join("type"))
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    let cooling: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.starts_with("cdev") && name[4..].chars().all(|c| c.is_ascii_digit())
+        })
+        .filter_map(|entry| read_file(entry.path().join("type")).ok())
+        .collect();
+    let mut harts: Vec<usize> = cooling
+        .iter()
+        .filter_map(|cdev| cooling_policy_hart(cdev.trim()))
+        .flat_map(|first| {
+            let related = read_file(format!("{CPU_DIR}/cpu{first}/cpufreq/related_cpus"))
+                .map(|list| parse_hart_list(&list))
+                .unwrap_or_default();
+            if related.is_empty() {
+                vec![first]
+            } else {
+                related
+            }
+        })
+        .collect();
+    if harts.is_empty() {
+        harts.extend(zone_name_hart(&name));
+    }
+    harts.sort_unstable();
+    harts.dedup();
+    Some(ThermalZone {
+        zone: dir.file_name()?.to_string_lossy().into_owned(),
+        name,
+        millicelsius,
+        harts,
+    })
+}
+
+/// First hart of the cpufreq policy behind a cooling device type
+/// (`thermal-cpufreq-0`, `cpufreq-cpu4`)
+fn cooling_policy_hart(cdev_type: &str) -> Option<usize> {
+    cdev_type
+        .strip_prefix("thermal-cpufreq-")
+        .or_else(|| cdev_type.strip_prefix("cpufreq-cpu"))?
+        .parse()
+        .ok()
+}
+
+/// Hart named by a per-core zone (`cpu2-thermal`, `cpu2_thermal`)
+fn zone_name_hart(name: &str) -> Option<usize> {
+    let rest = name.strip_prefix("cpu")?;
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    let suffix = &rest[digits.len()..];
+    if suffix == "-thermal" || suffix == "_thermal" {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Space-separated hart numbers, as in `related_cpus` (`0 1 2 3`)
+fn parse_hart_list(list: &str) -> Vec<usize> {
+    list.split_whitespace()
+        .filter_map(|n| n.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooling_policy_hart() {
+        assert_eq!(cooling_policy_hart("thermal-cpufreq-0"), Some(0));
+        assert_eq!(cooling_policy_hart("cpufreq-cpu4"), Some(4));
+        assert_eq!(cooling_policy_hart("pwm-fan"), None);
+    }
+
+    #[test]
+    fn test_zone_name_hart() {
+        assert_eq!(zone_name_hart("cpu2-thermal"), Some(2));
+        assert_eq!(zone_name_hart("cpu0_thermal"), Some(0));
+        assert_eq!(zone_name_hart("cpu-thermal"), None);
+        assert_eq!(zone_name_hart("cpu1-top"), None);
+        assert_eq!(parse_hart_list("0 1 2 3\n"), [0, 1, 2, 3]);
+    }
+}
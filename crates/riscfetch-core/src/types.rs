@@ -1,16 +1,17 @@
 //! Data types for RISC-V system information
 
-use serde::Serialize;
+use crate::sensors::Sensors;
+use serde::{Deserialize, Serialize};
 
 /// Extension entry with name and description
-#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct ExtensionEntry {
     pub name: String,
     pub description: String,
 }
 
 /// Hardware IDs from RISC-V CSRs
-#[derive(Default, Serialize, Clone, Debug)]
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct HardwareIds {
     pub mvendorid: String,
     pub marchid: String,
@@ -18,7 +19,7 @@ pub struct HardwareIds {
 }
 
 /// Vector extension information
-#[derive(Serialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug)]
 pub struct VectorInfo {
     pub enabled: bool,
     pub vlen: Option<u32>,
@@ -26,7 +27,7 @@ pub struct VectorInfo {
 }
 
 /// Cache information
-#[derive(Serialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug)]
 pub struct CacheInfo {
     pub l1d: Option<String>,
     pub l1i: Option<String>,
@@ -34,8 +35,50 @@ pub struct CacheInfo {
     pub l3: Option<String>,
 }
 
+/// Load averages over 1, 5 and 15 minutes, from /proc/loadavg
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+/// Memory beyond used/total, from /proc/meminfo and the device tree
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct MemoryBreakdown {
+    /// `MemAvailable`: what programs can still get without swapping
+    pub available_bytes: u64,
+    /// `Buffers` + `Cached` + `SReclaimable`, reclaimable on demand
+    pub buffers_cache_bytes: u64,
+    /// Device-tree `reserved-memory` carve-outs (GPU, NPU, firmware),
+    /// which never show up in `MemTotal`; None without a device tree
+    pub reserved_bytes: Option<u64>,
+}
+
+/// Harts whose /proc/cpuinfo `isa` lines differ (big/little or partly
+/// disabled cores); `isa` then holds only the extensions all harts share
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IsaDivergence {
+    /// `isa` line of each hart, in `processor` order
+    pub hart_isas: Vec<String>,
+    /// ISA string with the extensions any hart has
+    pub union: String,
+    /// Extensions only some harts have, lowercase (`v`, `zfh`)
+    pub partial_extensions: Vec<String>,
+}
+
+/// A field whose value could not be determined, so JSON consumers can tell
+/// "not there" (no warning) from "could not be read"
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FieldWarning {
+    /// Field id, as in `collect_sources()` (`cache`, `board`, ...)
+    pub field: String,
+    /// What was tried, and the fallback shown if any
+    pub reason: String,
+}
+
 /// RISC-V specific information only (excludes generic system info)
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct RiscvInfo {
     pub isa: String,
     pub extensions: Vec<ExtensionEntry>,
@@ -44,10 +87,16 @@ pub struct RiscvInfo {
     pub hart_count: usize,
     pub hardware_ids: HardwareIds,
     pub cache: CacheInfo,
+    /// Missing in snapshots saved by older versions
+    #[serde(default)]
+    pub isa_divergence: Option<IsaDivergence>,
+    /// Fields that could not be determined; missing in older snapshots
+    #[serde(default)]
+    pub warnings: Vec<FieldWarning>,
 }
 
 /// Complete system information for JSON serialization
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct SystemInfo {
     pub isa: String,
     pub extensions: Vec<ExtensionEntry>,
@@ -63,4 +112,22 @@ pub struct SystemInfo {
     pub kernel: String,
     pub os: String,
     pub uptime_seconds: u64,
+    /// Missing in snapshots saved by older versions
+    #[serde(default)]
+    pub load_average: Option<LoadAverage>,
+    /// Missing in snapshots saved by older versions
+    #[serde(default)]
+    pub memory_breakdown: Option<MemoryBreakdown>,
+    /// Missing in snapshots saved by older versions
+    #[serde(default)]
+    pub sensors: Sensors,
+    /// Container runtime riscfetch ran in; missing in older snapshots
+    #[serde(default)]
+    pub container: Option<String>,
+    /// Missing in snapshots saved by older versions
+    #[serde(default)]
+    pub isa_divergence: Option<IsaDivergence>,
+    /// Fields that could not be determined; missing in older snapshots
+    #[serde(default)]
+    pub warnings: Vec<FieldWarning>,
 }
@@ -0,0 +1,501 @@
+//! RISC-V vendor identification
+//!
+//! Vendors are recognized from keywords in the device-tree model and
+//! `compatible` strings, or from the `mvendorid` CSR when there is no device
+//! tree. To add a new vendor, add an entry to `VENDORS` and its keywords to
+//! `VENDOR_KEYWORDS`.
+
+use crate::jedec::decode_mvendorid;
+use crate::matching::{closest_match, normalize_name};
+use crate::types::HardwareIds;
+use serde::Serialize;
+
+/// Vendor definitions
+/// Format: (aliases, display_name, subtitle)
+///
+/// - aliases: lowercase strings used to match CLI input (first one is primary)
+/// - display_name: name shown in logo
+/// - subtitle: text shown below logo
+pub const VENDORS: &[(&[&str], &str, &str)] = &[
+    // Default
+    (
+        &["default", "riscv", "risc-v"],
+        "RISC-V",
+        "Architecture Info",
+    ),
+    // Major IP/SoC Providers
+    (&["sifive"], "SiFive", "RISC-V by SiFive"),
+    (&["starfive"], "StarFive", "RISC-V by StarFive"),
+    (
+        &["thead", "t-head", "alibaba"],
+        "T-Head",
+        "RISC-V by T-Head",
+    ),
+    (&["nuclei", "ux600", "nx900"], "Nuclei", "RISC-V by Nuclei"),
+    // Board Manufacturers
+    (&["milkv", "milk-v"], "Milk-V", "RISC-V by Milk-V"),
+    (&["sipeed"], "Sipeed", "RISC-V by Sipeed"),
+    (&["pine64", "pine"], "Pine64", "RISC-V by Pine64"),
+    (
+        &["deepcomputing", "dc-roma"],
+        "DeepComputing",
+        "RISC-V by DeepComputing",
+    ),
+    (&["framework"], "Framework", "RISC-V by Framework"),
+    // SoC Vendors
+    (&["eswin"], "ESWIN", "RISC-V by ESWIN"),
+    (&["ultrarisc"], "UltraRISC", "RISC-V by UltraRISC"),
+    (&["kendryte", "canaan"], "Kendryte", "RISC-V by Kendryte"),
+    (&["allwinner"], "Allwinner", "RISC-V by Allwinner"),
+    (&["espressif", "esp"], "Espressif", "RISC-V by Espressif"),
+    (&["spacemit"], "SpacemiT", "RISC-V by SpacemiT"),
+    (&["sophgo"], "Sophgo", "RISC-V by Sophgo"),
+    (
+        &["bouffalo", "bl808", "bl616"],
+        "Bouffalo",
+        "RISC-V by Bouffalo",
+    ),
+    (
+        &["renesas", "rzfive", "rz/five"],
+        "Renesas",
+        "RISC-V by Renesas",
+    ),
+    // MCU Vendors
+    (&["wch", "winchiphead"], "WCH", "RISC-V by WCH"),
+    (
+        &["gigadevice", "gd32v"],
+        "GigaDevice",
+        "RISC-V by GigaDevice",
+    ),
+    // Server / Datacenter
+    (&["ventana", "veyron"], "Ventana", "RISC-V by Ventana"),
+    (
+        &["tenstorrent", "ascalon"],
+        "Tenstorrent",
+        "RISC-V by Tenstorrent",
+    ),
+];
+
+/// A vendor identified by [`detect_vendor`] or [`find_vendor`]
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Vendor {
+    /// Primary alias, e.g. `starfive`
+    pub id: &'static str,
+    /// Display name, e.g. `StarFive`
+    pub name: &'static str,
+}
+
+/// Detection keywords for auto-detecting vendor from board/compatible strings.
+/// Broader than CLI aliases — includes board names and SoC identifiers.
+/// Format: (keyword, vendor_primary_alias)
+///
+/// **Order matters**: more specific keywords (SoC IDs, board names) must come
+/// before generic vendor names. The first match wins, so e.g. "eic7700" (ESWIN)
+/// must precede "sifive" to correctly identify HiFive Premier P550.
+const VENDOR_KEYWORDS: &[(&str, &str)] = &[
+    // SoC / board-specific (most specific first)
+    ("fml13v01", "framework"),
+    ("framework laptop", "framework"),
+    ("dc-roma", "deepcomputing"),
+    ("eic7700", "eswin"),
+    ("eic7702", "eswin"),
+    ("ebc77", "eswin"),
+    ("hifive premier", "eswin"),
+    ("starpro64", "eswin"),
+    ("megrez", "eswin"),
+    ("ur-dp1000", "ultrarisc"),
+    ("urdp1000", "ultrarisc"),
+    ("milkv titan", "ultrarisc"),
+    ("milk-v titan", "ultrarisc"),
+    ("visionfive", "starfive"),
+    ("jh7110", "starfive"),
+    ("xuantie", "thead"),
+    ("lichee", "sipeed"),
+    ("maix", "sipeed"),
+    ("star64", "pine64"),
+    ("nezha", "allwinner"),
+    ("esp32", "espressif"),
+    ("cv1800", "sophgo"),
+    ("sg2000", "sophgo"),
+    ("ch32v", "wch"),
+    ("ky,x1", "spacemit"),
+    ("ux600", "nuclei"),
+    ("ux900", "nuclei"),
+    ("nx900", "nuclei"),
+    ("bl808", "bouffalo"),
+    ("bl616", "bouffalo"),
+    ("bl618", "bouffalo"),
+    ("bflb,", "bouffalo"),
+    ("ox64", "bouffalo"),
+    ("r9a07g043f", "renesas"),
+    ("rzfive", "renesas"),
+    ("rz/five", "renesas"),
+    ("gd32v", "gigadevice"),
+    ("veyron", "ventana"),
+    ("ascalon", "tenstorrent"),
+    // Vendor names (generic, checked after specific keywords)
+    ("eswin", "eswin"),
+    ("ultrarisc", "ultrarisc"),
+    ("hifive", "sifive"),
+    ("sifive", "sifive"),
+    ("starfive", "starfive"),
+    ("thead", "thead"),
+    ("t-head", "thead"),
+    ("milkv", "milkv"),
+    ("milk-v", "milkv"),
+    ("sipeed", "sipeed"),
+    ("pine64", "pine64"),
+    ("kendryte", "kendryte"),
+    ("canaan", "kendryte"),
+    ("allwinner", "allwinner"),
+    ("espressif", "espressif"),
+    ("spacemit", "spacemit"),
+    ("sophgo", "sophgo"),
+    ("wch", "wch"),
+    ("winchiphead", "wch"),
+    ("nuclei", "nuclei"),
+    ("bouffalo", "bouffalo"),
+    ("renesas", "renesas"),
+    ("gigadevice", "gigadevice"),
+    ("ventana", "ventana"),
+    ("tenstorrent", "tenstorrent"),
+    ("deepcomputing", "deepcomputing"),
+    ("framework", "framework"),
+];
+
+/// Core-IP manufacturers (as decoded from `mvendorid`) mapped to vendors.
+/// Manufacturers without a vendor entry of their own, such as Andes, are omitted.
+const CORE_IP_VENDORS: &[(&str, &str)] = &[
+    ("SiFive", "sifive"),
+    ("T-Head", "thead"),
+    ("Nuclei System Technology", "nuclei"),
+    ("Espressif", "espressif"),
+    ("SpacemiT", "spacemit"),
+];
+
+/// Look up a vendor by any of its aliases, ignoring case, dashes and spaces
+#[must_use]
+pub fn find_vendor(alias: &str) -> Option<Vendor> {
+    let alias = normalize_name(alias);
+    VENDORS
+        .iter()
+        .find(|(aliases, _, _)| aliases.iter().any(|a| normalize_name(a) == alias))
+        .map(|(aliases, name, _)| Vendor {
+            id: aliases[0],
+            name,
+        })
+}
+
+/// Identify the vendor from board information and hardware IDs
+///
+/// `board` is the device-tree model and/or `compatible` strings, in any
+/// combination. When it is empty (containers, QEMU, ACPI systems) the
+/// `mvendorid` CSR decides instead; it names the core designer, so a SiFive
+/// core in a StarFive SoC reads as SiFive, which is why it is only a fallback.
+/// `marchid` is vendor-relative and does not change the answer.
+#[must_use]
+pub fn detect_vendor(board: &str, hw: &HardwareIds) -> Option<Vendor> {
+    let board = board.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    let id = if board.is_empty() {
+        let manufacturer = decode_mvendorid(&hw.mvendorid)?;
+        CORE_IP_VENDORS
+            .iter()
+            .find(|(name, _)| *name == manufacturer)
+            .map(|(_, vendor)| *vendor)?
+    } else {
+        VENDOR_KEYWORDS
+            .iter()
+            .find(|(keyword, _)| contains_keyword(board, keyword))
+            .map(|(_, vendor)| *vendor)?
+    };
+    find_vendor(id)
+}
+
+/// Whether `keyword` appears in `text` starting at a word: case, dashes and
+/// spaces are ignored from there on (`Milk V` matches `milkv`), but a match
+/// cannot begin inside a word, so `New Chip` does not contain `wch`
+fn contains_keyword(text: &str, keyword: &str) -> bool {
+    let keyword = normalize_name(keyword);
+    let mut previous = None;
+    text.char_indices().any(|(i, c)| {
+        let word_start = previous.is_none_or(|p: char| !p.is_alphanumeric());
+        previous = Some(c);
+        word_start && normalize_name(&text[i..]).starts_with(&keyword)
+    })
+}
+
+/// Vendor alias closest to a misspelled `input`, for "did you mean" hints
+#[must_use]
+pub fn suggest_vendor(input: &str) -> Option<&'static str> {
+    closest_match(
+        input,
+        VENDORS
+            .iter()
+            .flat_map(|(aliases, _, _)| aliases.iter().copied()),
+    )
+}
+
+/// The keyword table used by [`detect_vendor`]: (keyword, vendor id), in
+/// matching order
+#[must_use]
+pub fn vendor_keywords() -> &'static [(&'static str, &'static str)] {
+    VENDOR_KEYWORDS
+}
+
+/// Detection keywords that resolve to the vendor with primary alias `vendor`
+#[must_use]
+pub fn detection_keywords(vendor: &str) -> Vec<&'static str> {
+    VENDOR_KEYWORDS
+        .iter()
+        .filter(|(_, alias)| *alias == vendor)
+        .map(|(keyword, _)| *keyword)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Vendor id detected from a device-tree model and `compatible` string
+    fn detect(model: &str, compatible: &str) -> Option<&'static str> {
+        detect_vendor(&format!("{model} {compatible}"), &HardwareIds::default()).map(|v| v.id)
+    }
+
+    #[test]
+    fn test_find_vendor() {
+        let vendor = find_vendor("Canaan").unwrap();
+        assert_eq!((vendor.id, vendor.name), ("kendryte", "Kendryte"));
+        assert!(find_vendor("unknown_vendor").is_none());
+        assert_eq!(find_vendor("Milk V").unwrap().id, "milkv");
+        assert_eq!(find_vendor("T_HEAD").unwrap().id, "thead");
+    }
+
+    #[test]
+    fn test_suggest_vendor() {
+        assert_eq!(suggest_vendor("starfiv"), Some("starfive"));
+        assert_eq!(suggest_vendor("spacmit"), Some("spacemit"));
+        assert_eq!(suggest_vendor("qualcomm"), None);
+    }
+
+    #[test]
+    fn test_detect_vendor_ignores_separators() {
+        assert_eq!(detect("Milk V Duo", ""), Some("milkv"));
+        assert_eq!(detect("StarFive Vision-Five 2", ""), Some("starfive"));
+        assert_eq!(vendor_keywords().first(), VENDOR_KEYWORDS.first());
+    }
+
+    #[test]
+    fn test_detect_vendor_matches_words() {
+        // Keywords spanning word boundaries are not matches
+        assert_eq!(detect("New Chip Devboard", ""), None);
+        assert_eq!(
+            detect("Lab Thead-less Board", "lab,thead-less"),
+            Some("thead")
+        );
+        assert_eq!(detect("Preview Chassis", ""), None);
+    }
+
+    #[test]
+    fn test_detect_vendor_from_model() {
+        assert_eq!(detect("StarFive VisionFive 2", ""), Some("starfive"));
+        assert_eq!(detect("Milk-V Mars", ""), Some("milkv"));
+        assert_eq!(detect("", ""), None);
+    }
+
+    #[test]
+    fn test_detect_vendor_from_compatible() {
+        assert_eq!(detect("", "sipeed,licheerv-nano"), Some("sipeed"));
+        assert_eq!(detect("", "starfive,visionfive-2-jh7110"), Some("starfive"));
+    }
+
+    #[test]
+    fn test_detect_eswin_boards() {
+        assert_eq!(detect("", "eswin,eic7700x"), Some("eswin"));
+        assert_eq!(detect("Pine64 StarPro64", ""), Some("eswin"));
+        assert_eq!(detect("Milk-V Megrez", ""), Some("eswin"));
+        assert_eq!(detect("", "eswin,eic7702x"), Some("eswin"));
+        assert_eq!(detect("ESWIN EBC7702", ""), Some("eswin"));
+    }
+
+    #[test]
+    fn test_detect_ultrarisc_boards() {
+        assert_eq!(detect("Milk-V Titan", ""), Some("ultrarisc"));
+        assert_eq!(detect("", "ultrarisc,ur-dp1000"), Some("ultrarisc"));
+    }
+
+    #[test]
+    fn test_hifive_unmatched_still_sifive() {
+        // HiFive Unmatched uses SiFive FU740, not ESWIN
+        assert_eq!(detect("HiFive Unmatched", ""), Some("sifive"));
+        assert_eq!(detect("HiFive Unleashed", ""), Some("sifive"));
+    }
+
+    #[test]
+    fn test_detect_vendor_case_insensitive() {
+        assert_eq!(detect("SIFIVE HIFIVE UNMATCHED", ""), Some("sifive"));
+        // HiFive Premier P550 uses ESWIN EIC7700X, not SiFive silicon
+        assert_eq!(detect("SiFive HiFive Premier P550", ""), Some("eswin"));
+    }
+
+    #[test]
+    fn test_detect_vendor_unknown_board() {
+        assert_eq!(detect("Some Unknown Board", "unknown,board"), None);
+    }
+
+    #[test]
+    fn test_detect_orangepi_rv2_as_spacemit() {
+        assert_eq!(
+            detect("ky x1 orangepi-rv2 board", "ky,orangepi-rv2 ky,x1"),
+            Some("spacemit")
+        );
+    }
+
+    #[test]
+    fn test_ky_orangepi_alone_does_not_match() {
+        // We deliberately key on "ky,x1" only. A bare "ky,orangepi-rv2" without
+        // "ky,x1" is rejected so future Ky-derived boards on non-SpacemiT SoCs
+        // are not silently miscategorized.
+        assert_eq!(detect("", "ky,orangepi-rv2"), None);
+    }
+
+    #[test]
+    fn test_nuclei_vendor() {
+        assert_eq!(find_vendor("nuclei").unwrap().name, "Nuclei");
+        assert_eq!(find_vendor("UX600").unwrap().name, "Nuclei");
+        assert_eq!(find_vendor("nx900").unwrap().name, "Nuclei");
+        assert_eq!(detect("", "nuclei,evalsoc"), Some("nuclei"));
+        assert_eq!(detect("Nuclei UX600 Evaluation SoC", ""), Some("nuclei"));
+        assert_eq!(detect("NX900 FPGA board", ""), Some("nuclei"));
+    }
+
+    #[test]
+    fn test_bouffalo_vendor() {
+        assert_eq!(find_vendor("bl808").unwrap().name, "Bouffalo");
+        assert_eq!(find_vendor("bl616").unwrap().name, "Bouffalo");
+        // BL808 boards are sold by Pine64 and Sipeed; the SoC decides the logo
+        assert_eq!(
+            detect("Pine64 Ox64 (D0)", "pine64,ox64-d0 bflb,bl808"),
+            Some("bouffalo")
+        );
+        assert_eq!(
+            detect("Sipeed M1s Dock", "sipeed,m1s-dock bflb,bl808"),
+            Some("bouffalo")
+        );
+        assert_eq!(detect("Pine64 Star64", ""), Some("pine64"));
+    }
+
+    #[test]
+    fn test_renesas_vendor() {
+        assert_eq!(find_vendor("rzfive").unwrap().name, "Renesas");
+        assert_eq!(find_vendor("RZ/Five").unwrap().name, "Renesas");
+        assert_eq!(
+            detect(
+                "Renesas SMARC EVK based on r9a07g043f01",
+                "renesas,smarc-evk renesas,r9a07g043f01 renesas,r9a07g043"
+            ),
+            Some("renesas")
+        );
+    }
+
+    #[test]
+    fn test_gigadevice_vendor() {
+        assert_eq!(find_vendor("gd32v").unwrap().name, "GigaDevice");
+        assert_eq!(detect("Longan Nano", "gd32vf103"), Some("gigadevice"));
+        assert_eq!(detect("", "gigadevice,gd32vf103"), Some("gigadevice"));
+    }
+
+    #[test]
+    fn test_ventana_vendor() {
+        assert_eq!(find_vendor("veyron").unwrap().name, "Ventana");
+        assert_eq!(
+            detect("Veyron V1 Development Platform", ""),
+            Some("ventana")
+        );
+        assert_eq!(detect("", "ventana,veyron-v1"), Some("ventana"));
+    }
+
+    #[test]
+    fn test_tenstorrent_vendor() {
+        assert_eq!(find_vendor("ascalon").unwrap().name, "Tenstorrent");
+        assert_eq!(detect("", "tenstorrent,ascalon"), Some("tenstorrent"));
+        assert_eq!(detect("Tenstorrent Ascalon X", ""), Some("tenstorrent"));
+    }
+
+    #[test]
+    fn test_deepcomputing_vendor() {
+        assert_eq!(find_vendor("dc-roma").unwrap().name, "DeepComputing");
+        // The laptop brand wins over the SoC vendor (ESWIN / StarFive)
+        assert_eq!(
+            detect("DeepComputing DC-ROMA II", "eswin,eic7702x"),
+            Some("deepcomputing")
+        );
+        assert_eq!(
+            detect("", "deepcomputing,dc-roma starfive,jh7110"),
+            Some("deepcomputing")
+        );
+    }
+
+    #[test]
+    fn test_framework_vendor() {
+        assert_eq!(find_vendor("framework").unwrap().name, "Framework");
+        // Mainboard made by DeepComputing on a StarFive JH7110
+        assert_eq!(
+            detect(
+                "DeepComputing FML13V01",
+                "deepcomputing,fml13v01 starfive,jh7110"
+            ),
+            Some("framework")
+        );
+        assert_eq!(detect("Framework Laptop 13", ""), Some("framework"));
+    }
+
+    #[test]
+    fn test_detect_vendor_from_hw_ids() {
+        let ids = |mvendorid: &str| HardwareIds {
+            mvendorid: mvendorid.into(),
+            marchid: "0x8000000000000007".into(),
+            mimpid: String::new(),
+        };
+        assert_eq!(
+            detect_vendor("", &ids("0x489")).map(|v| v.id),
+            Some("sifive")
+        );
+        assert_eq!(
+            detect_vendor("", &ids("0x5b7")).map(|v| v.id),
+            Some("thead")
+        );
+        assert_eq!(
+            detect_vendor("", &ids("0x710")).map(|v| v.id),
+            Some("spacemit")
+        );
+        // Andes is decoded but has no vendor entry; 0 means not implemented (QEMU)
+        assert_eq!(detect_vendor("", &ids("0x31e")).map(|v| v.id), None);
+        assert_eq!(detect_vendor("", &ids("0x0")).map(|v| v.id), None);
+        // Board information wins over the core designer
+        let vendor = detect_vendor("StarFive VisionFive 2", &ids("0x489")).unwrap();
+        assert_eq!(vendor.id, "starfive");
+        assert_eq!(detect_vendor("QEMU riscv64 virt", &ids("0x489")), None);
+        for (_, vendor) in CORE_IP_VENDORS {
+            assert!(find_vendor(vendor).is_some(), "{vendor} is not a vendor");
+        }
+    }
+
+    #[test]
+    fn test_detection_keywords() {
+        let keywords = detection_keywords("starfive");
+        assert!(keywords.contains(&"visionfive"));
+        assert!(keywords.contains(&"jh7110"));
+        assert!(!keywords.contains(&"sifive"));
+        assert!(detection_keywords("default").is_empty());
+    }
+
+    #[test]
+    fn test_every_keyword_targets_a_vendor() {
+        for (keyword, vendor) in VENDOR_KEYWORDS {
+            assert!(
+                VENDORS.iter().any(|(aliases, _, _)| aliases[0] == *vendor),
+                "{keyword} -> {vendor} is not a primary alias"
+            );
+        }
+    }
+}